@@ -0,0 +1,79 @@
+//! HTTP API over the SQLite index written by `predictory-indexer`.
+
+use std::{
+    net::SocketAddr,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use clap::Parser;
+use predictory_indexer::Store;
+use rusqlite::Connection;
+use tower_http::cors::CorsLayer;
+
+use crate::error::ApiError;
+
+mod error;
+mod page;
+mod routes;
+mod types;
+
+#[derive(Parser)]
+#[command(
+    name = "predictory-api",
+    version,
+    about = "Serve the Predictory index over HTTP"
+)]
+struct Cli {
+    /// SQLite database file written by the indexer
+    #[arg(long, env = "PREDICTORY_DB", default_value = "predictory.db")]
+    db: PathBuf,
+
+    /// Address to listen on
+    #[arg(long, env = "PREDICTORY_API_ADDR", default_value = "127.0.0.1:8080")]
+    listen: SocketAddr,
+}
+
+#[derive(Clone)]
+pub struct AppState {
+    store: Arc<Mutex<Store>>,
+}
+
+impl AppState {
+    /// Runs a query on a blocking thread
+    pub async fn query<T, F>(&self, query: F) -> Result<T, ApiError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Connection) -> Result<T, ApiError> + Send + 'static,
+    {
+        let store = self.store.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let store = store
+                .lock()
+                .map_err(|error| ApiError::Internal(error.to_string()))?;
+
+            query(store.connection())
+        })
+        .await
+        .map_err(|error| ApiError::Internal(error.to_string()))?
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let state = AppState {
+        store: Arc::new(Mutex::new(Store::open(&cli.db)?)),
+    };
+
+    let app = routes::router()
+        .layer(CorsLayer::permissive())
+        .with_state(state);
+    let listener = tokio::net::TcpListener::bind(cli.listen).await?;
+
+    println!("Listening on http://{}", cli.listen);
+    axum::serve(listener, app).await?;
+
+    Ok(())
+}
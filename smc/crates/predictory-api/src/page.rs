@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_LIMIT: u32 = 50;
+const MAX_LIMIT: u32 = 500;
+
+/// `offset` and `limit` query parameters of list endpoints
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct Pagination {
+    #[serde(default)]
+    pub offset: u32,
+    pub limit: Option<u32>,
+}
+
+impl Pagination {
+    pub fn limit(&self) -> u32 {
+        self.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT)
+    }
+
+    /// Builds the page holding `items`, out of `total` matching rows
+    pub fn page<T>(&self, items: Vec<T>, total: u64) -> Page<T> {
+        Page {
+            items,
+            offset: self.offset,
+            limit: self.limit(),
+            total,
+        }
+    }
+
+    /// Builds the page of an already loaded list
+    pub fn slice<T>(&self, items: Vec<T>) -> Page<T> {
+        let total = items.len() as u64;
+        let items = items
+            .into_iter()
+            .skip(self.offset as usize)
+            .take(self.limit() as usize)
+            .collect();
+
+        self.page(items, total)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub offset: u32,
+    pub limit: u32,
+    pub total: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pagination(offset: u32, limit: Option<u32>) -> Pagination {
+        Pagination { offset, limit }
+    }
+
+    #[test]
+    fn limit_is_clamped() {
+        assert_eq!(pagination(0, None).limit(), DEFAULT_LIMIT);
+        assert_eq!(pagination(0, Some(0)).limit(), 1);
+        assert_eq!(pagination(0, Some(20)).limit(), 20);
+        assert_eq!(pagination(0, Some(u32::MAX)).limit(), MAX_LIMIT);
+    }
+
+    #[test]
+    fn slice_bounds() {
+        let page = pagination(3, Some(4)).slice((0..10).collect());
+        assert_eq!(page.items, vec![3, 4, 5, 6]);
+        assert_eq!((page.offset, page.limit, page.total), (3, 4, 10));
+
+        let page = pagination(8, Some(4)).slice((0..10).collect());
+        assert_eq!(page.items, vec![8, 9]);
+
+        let page = pagination(10, None).slice((0..10).collect::<Vec<u32>>());
+        assert!(page.items.is_empty());
+        assert_eq!(page.total, 10);
+
+        let page = pagination(u32::MAX, None).slice(Vec::<u32>::new());
+        assert!(page.items.is_empty());
+        assert_eq!(page.total, 0);
+    }
+}
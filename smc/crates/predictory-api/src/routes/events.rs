@@ -0,0 +1,289 @@
+use std::{
+    collections::BTreeMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use predictory_client::{
+    predictory::{
+        context::{Odds, OptionOdds, Settlement},
+        state::{legacy::Versioned, option::EventOption},
+    },
+    views::{EventOptionView, OddsView},
+};
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension, ToSql};
+use serde::Deserialize;
+
+use super::event_id;
+use crate::{
+    error::{ApiError, ApiResult},
+    page::{Page, Pagination},
+    types::{EventDetails, EventSummary, OddsPoint, ParticipationSummary},
+    AppState,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct EventFilter {
+    /// Stored status, or `open` for published events still running
+    status: Option<String>,
+    authority: Option<String>,
+    /// Case-insensitive search in the event name
+    q: Option<String>,
+    /// `volume` (default), `newest` or `ending`
+    sort: Option<String>,
+}
+
+pub async fn list(
+    State(state): State<AppState>,
+    Query(filter): Query<EventFilter>,
+    Query(pagination): Query<Pagination>,
+) -> ApiResult<Page<EventSummary>> {
+    let order = match filter.sort.as_deref() {
+        None | Some("volume") => "total_amount DESC",
+        Some("newest") => "created_slot DESC",
+        Some("ending") => "end_date ASC",
+        Some(sort) => return Err(ApiError::BadRequest(format!("unknown sort {sort}"))),
+    };
+
+    // Hidden and private events are only listed for their authority
+    let mut conditions = vec!["status != 'deleted'".to_string()];
+    let mut values: Vec<Box<dyn ToSql + Send>> = vec![];
+
+    match &filter.authority {
+        Some(authority) => {
+            values.push(Box::new(authority.clone()));
+            conditions.push(format!("authority = ?{}", values.len()));
+        }
+        None => conditions.push("hidden = 0 AND is_private = 0".to_string()),
+    }
+
+    match filter.status.as_deref() {
+        None => {}
+        Some("open") => {
+            values.push(Box::new(now()));
+            conditions.push(format!(
+                "status = 'published' AND frozen = 0 AND end_date > ?{}",
+                values.len()
+            ));
+        }
+        Some(status) => {
+            values.push(Box::new(status.to_string()));
+            conditions.push(format!("status = ?{}", values.len()));
+        }
+    }
+
+    if let Some(q) = &filter.q {
+        values.push(Box::new(format!("%{q}%")));
+        conditions.push(format!("name LIKE ?{}", values.len()));
+    }
+
+    let clause = conditions.join(" AND ");
+
+    state
+        .query(move |connection| {
+            let total: u64 = connection.query_row(
+                &format!("SELECT COUNT(*) FROM events WHERE {clause}"),
+                params_from_iter(values.iter()),
+                |row| row.get(0),
+            )?;
+
+            let mut statement = connection.prepare(&format!(
+                "SELECT {} FROM events LEFT JOIN event_volume USING (event_id)
+                 WHERE {clause} ORDER BY {order}, events.event_id LIMIT {} OFFSET {}",
+                EventSummary::COLUMNS,
+                pagination.limit(),
+                pagination.offset
+            ))?;
+            let items = statement
+                .query_map(params_from_iter(values.iter()), EventSummary::from_row)?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+
+            Ok(pagination.page(items, total))
+        })
+        .await
+        .map(Json)
+}
+
+pub async fn details(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ApiResult<EventDetails> {
+    let event_id = event_id(&id)?;
+
+    state
+        .query(move |connection| {
+            Ok(EventDetails {
+                event: load_event(connection, &event_id)?,
+                options: load_options(connection, &event_id)?,
+            })
+        })
+        .await
+        .map(Json)
+}
+
+pub async fn options(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ApiResult<Vec<EventOptionView>> {
+    let event_id = event_id(&id)?;
+
+    state
+        .query(move |connection| {
+            load_event(connection, &event_id)?;
+            load_options(connection, &event_id)
+        })
+        .await
+        .map(Json)
+}
+
+pub async fn participations(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(pagination): Query<Pagination>,
+) -> ApiResult<Page<ParticipationSummary>> {
+    let event_id = event_id(&id)?;
+
+    state
+        .query(move |connection| {
+            load_event(connection, &event_id)?;
+            participation_page(connection, "event_id", &event_id, pagination)
+        })
+        .await
+        .map(Json)
+}
+
+/// Odds of the event after each vote, oldest first
+pub async fn odds_timeline(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(pagination): Query<Pagination>,
+) -> ApiResult<Page<OddsPoint>> {
+    let event_id = event_id(&id)?;
+
+    state
+        .query(move |connection| {
+            let event = load_event(connection, &event_id)?;
+
+            // Options start empty and are updated with the totals recorded by each vote
+            let mut pools: BTreeMap<u8, (u64, u64)> = load_options(connection, &event_id)?
+                .iter()
+                .map(|option| (option.index, (0, 0)))
+                .collect();
+
+            let mut statement = connection.prepare(
+                "SELECT signature, slot, block_time, option, option_votes, option_vault_balance,
+                    total_amount
+                 FROM votes WHERE event_id = ?1 ORDER BY slot, signature, seq",
+            )?;
+            let mut rows = statement.query([&event_id])?;
+            let mut points = vec![];
+
+            while let Some(row) = rows.next()? {
+                let total_amount: u64 = row.get(6)?;
+                pools.insert(row.get(3)?, (row.get(4)?, row.get(5)?));
+
+                let settlement =
+                    Settlement::new(total_amount, event.platform_fee, event.org_reward);
+                let odds = Odds {
+                    total_amount,
+                    available_for_winners: settlement.available_for_winners,
+                    options: pools
+                        .iter()
+                        .map(|(&index, &(votes, vault_balance))| OptionOdds {
+                            index,
+                            votes,
+                            vault_balance,
+                            payout_per_sol: settlement
+                                .winner_payout(LAMPORTS_PER_SOL, vault_balance),
+                        })
+                        .collect(),
+                };
+
+                points.push(OddsPoint {
+                    signature: row.get(0)?,
+                    slot: row.get(1)?,
+                    block_time: row.get(2)?,
+                    odds: OddsView::from(&odds),
+                });
+            }
+
+            Ok(pagination.slice(points))
+        })
+        .await
+        .map(Json)
+}
+
+fn load_event(connection: &Connection, event_id: &str) -> Result<EventSummary, ApiError> {
+    connection
+        .query_row(
+            &format!(
+                "SELECT {} FROM events LEFT JOIN event_volume USING (event_id)
+                 WHERE events.event_id = ?1",
+                EventSummary::COLUMNS
+            ),
+            [event_id],
+            EventSummary::from_row,
+        )
+        .optional()?
+        .ok_or_else(|| ApiError::NotFound(format!("event {event_id}")))
+}
+
+fn load_options(connection: &Connection, event_id: &str) -> Result<Vec<EventOptionView>, ApiError> {
+    let mut statement = connection.prepare(
+        "SELECT idx, description, votes, vault_balance FROM options
+         WHERE event_id = ?1 ORDER BY idx",
+    )?;
+    let options = statement
+        .query_map(params![event_id], |row| {
+            Ok(EventOptionView {
+                version: EventOption::CURRENT_VERSION,
+                event_id: event_id.to_string(),
+                index: row.get(0)?,
+                description: Some(row.get(1)?),
+                votes: row.get(2)?,
+                vault_balance: row.get(3)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    Ok(options)
+}
+
+/// Loads the participations matching `column = value`, largest deposits first
+pub(crate) fn participation_page(
+    connection: &Connection,
+    column: &str,
+    value: &str,
+    pagination: Pagination,
+) -> Result<Page<ParticipationSummary>, ApiError> {
+    let total: u64 = connection.query_row(
+        &format!("SELECT COUNT(*) FROM participations WHERE {column} = ?1"),
+        [value],
+        |row| row.get(0),
+    )?;
+
+    let mut statement = connection.prepare(&format!(
+        "SELECT {} FROM participations WHERE {column} = ?1
+         ORDER BY amount DESC, event_id, user LIMIT ?2 OFFSET ?3",
+        ParticipationSummary::COLUMNS
+    ))?;
+    let items = statement
+        .query_map(
+            params![value, pagination.limit(), pagination.offset],
+            ParticipationSummary::from_row,
+        )?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    Ok(pagination.page(items, total))
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or_default()
+}
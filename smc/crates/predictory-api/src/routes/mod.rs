@@ -0,0 +1,148 @@
+use axum::{routing::get, Router};
+use predictory_client::event_id::{format_event_id, parse_event_id};
+
+use crate::{error::ApiError, AppState};
+
+mod events;
+mod stats;
+mod users;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/events", get(events::list))
+        .route("/events/:id", get(events::details))
+        .route("/events/:id/options", get(events::options))
+        .route("/events/:id/participations", get(events::participations))
+        .route("/events/:id/odds", get(events::odds_timeline))
+        .route("/users", get(users::list))
+        .route("/users/:address", get(users::details))
+        .route("/users/:address/participations", get(users::participations))
+        .route("/users/:address/history", get(users::history))
+        .route("/leaderboard", get(users::leaderboard))
+        .route("/stats", get(stats::protocol))
+}
+
+/// Normalizes an event id path segment to the form stored in the index
+fn event_id(id: &str) -> Result<String, ApiError> {
+    parse_event_id(id)
+        .map(format_event_id)
+        .map_err(|error| ApiError::BadRequest(error.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use axum::{
+        body::{to_bytes, Body},
+        http::{Request, StatusCode},
+    };
+    use predictory_client::event_id::new_event_id;
+    use predictory_indexer::Store;
+    use serde_json::Value;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    /// Router over an in-memory index holding `count` public events
+    fn app(count: u64) -> Router {
+        let store = Store::open(":memory:").unwrap();
+
+        for amount in 0..count {
+            store
+                .connection()
+                .execute(
+                    "INSERT INTO events (event_id, authority, status, total_amount, updated_slot)
+                     VALUES (?1, 'authority', 'published', ?2, 1)",
+                    rusqlite::params![format_event_id(new_event_id()), amount],
+                )
+                .unwrap();
+        }
+
+        router().with_state(AppState {
+            store: Arc::new(Mutex::new(store)),
+        })
+    }
+
+    async fn get(app: &Router, uri: &str) -> (StatusCode, Value) {
+        let response = app
+            .clone()
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn unknown_event_is_not_found() {
+        let app = app(1);
+        let id = format_event_id(new_event_id());
+
+        for suffix in ["", "/options", "/participations", "/odds"] {
+            let (status, body) = get(&app, &format!("/events/{id}{suffix}")).await;
+            assert_eq!(status, StatusCode::NOT_FOUND, "{suffix}");
+            assert_eq!(body["error"], format!("event {id} not found"));
+        }
+    }
+
+    #[tokio::test]
+    async fn invalid_event_id_is_bad_request() {
+        let (status, _) = get(&app(0), "/events/not-an-id").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn events_are_paginated() {
+        let app = app(5);
+
+        let (status, page) = get(&app, "/events?offset=1&limit=2").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            (&page["offset"], &page["limit"], &page["total"]),
+            (&1.into(), &2.into(), &5.into())
+        );
+        let amounts: Vec<_> = page["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|event| event["total_amount"].as_u64().unwrap())
+            .collect();
+        assert_eq!(amounts, [3, 2]);
+
+        let (_, page) = get(&app, "/events?offset=4&limit=2").await;
+        assert_eq!(page["items"].as_array().unwrap().len(), 1);
+
+        let (_, page) = get(&app, "/events?offset=5").await;
+        assert!(page["items"].as_array().unwrap().is_empty());
+        assert_eq!(page["total"], 5);
+
+        let (_, page) = get(&app, "/events?limit=0").await;
+        assert_eq!(page["limit"], 1);
+        assert_eq!(page["items"].as_array().unwrap().len(), 1);
+
+        let (_, page) = get(&app, "/events?limit=100000").await;
+        assert_eq!(page["limit"], 500);
+        assert_eq!(page["items"].as_array().unwrap().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn invalid_pagination_is_rejected() {
+        let app = app(1);
+
+        for query in ["offset=-1", "limit=abc", "offset=4294967296"] {
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::get(format!("/events?{query}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{query}");
+        }
+    }
+}
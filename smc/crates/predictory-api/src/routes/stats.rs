@@ -0,0 +1,47 @@
+use std::collections::BTreeMap;
+
+use axum::{extract::State, Json};
+use rusqlite::Connection;
+
+use crate::{error::ApiResult, types::ProtocolStats, AppState};
+
+pub async fn protocol(State(state): State<AppState>) -> ApiResult<ProtocolStats> {
+    state
+        .query(|connection| {
+            let count = |sql: &str| connection.query_row(sql, [], |row| row.get::<_, u64>(0));
+
+            Ok(ProtocolStats {
+                users: count("SELECT COUNT(*) FROM users")?,
+                events: totals(
+                    connection,
+                    "SELECT status, COUNT(*) FROM events GROUP BY status",
+                )?,
+                votes: count("SELECT COUNT(*) FROM votes")?,
+                participants: count("SELECT COUNT(DISTINCT user) FROM votes")?,
+                volume: count("SELECT COALESCE(SUM(amount), 0) FROM votes")?,
+                paid_out: count(
+                    "SELECT COALESCE(SUM(amount), 0) FROM claims
+                     WHERE kind IN ('claim', 'recharge')",
+                )?,
+                fee_flows: totals(
+                    connection,
+                    "SELECT kind, SUM(amount) FROM fee_flows GROUP BY kind",
+                )?,
+                last_slot: connection.query_row(
+                    "SELECT MAX(slot) FROM transactions",
+                    [],
+                    |row| row.get(0),
+                )?,
+            })
+        })
+        .await
+        .map(Json)
+}
+
+/// Collects `key, value` rows into a map
+fn totals(connection: &Connection, sql: &str) -> rusqlite::Result<BTreeMap<String, u64>> {
+    let mut statement = connection.prepare(sql)?;
+    let rows = statement.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+
+    rows.collect()
+}
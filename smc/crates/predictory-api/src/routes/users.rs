@@ -0,0 +1,174 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Deserialize;
+
+use super::events::participation_page;
+use crate::{
+    error::{ApiError, ApiResult},
+    page::{Page, Pagination},
+    types::{HistoryEntry, LeaderboardEntry, ParticipationSummary, UserSummary},
+    AppState,
+};
+
+/// Votes, claims and appeals of a user, as rows of [`HistoryEntry`]
+const HISTORY: &str = "
+    SELECT signature, seq, slot, block_time, 'vote' AS kind, event_id, option, amount
+        FROM votes WHERE user = ?1
+    UNION ALL
+    SELECT signature, seq, slot, block_time, kind, event_id, option, amount
+        FROM claims WHERE user = ?1
+    UNION ALL
+    SELECT signature, seq, slot, block_time, 'appeal', event_id, NULL, NULL
+        FROM appeals WHERE user = ?1";
+
+#[derive(Debug, Deserialize)]
+pub struct LeaderboardQuery {
+    /// `pnl` (default), `volume` or `trust`
+    by: Option<String>,
+}
+
+pub async fn list(
+    State(state): State<AppState>,
+    Query(pagination): Query<Pagination>,
+) -> ApiResult<Page<UserSummary>> {
+    state
+        .query(move |connection| user_page(connection, "address", pagination))
+        .await
+        .map(Json)
+}
+
+pub async fn leaderboard(
+    State(state): State<AppState>,
+    Query(query): Query<LeaderboardQuery>,
+    Query(pagination): Query<Pagination>,
+) -> ApiResult<Page<LeaderboardEntry>> {
+    let order = match query.by.as_deref() {
+        None | Some("pnl") => "pnl DESC",
+        Some("volume") => "staked DESC",
+        Some("trust") => "trust_lvl DESC",
+        Some(by) => return Err(ApiError::BadRequest(format!("unknown ranking {by}"))),
+    };
+
+    let page = state
+        .query(move |connection| user_page(connection, order, pagination))
+        .await?;
+    let first_rank = page.offset as u64 + 1;
+
+    Ok(Json(Page {
+        items: page
+            .items
+            .into_iter()
+            .zip(first_rank..)
+            .map(|(user, rank)| LeaderboardEntry { rank, user })
+            .collect(),
+        offset: page.offset,
+        limit: page.limit,
+        total: page.total,
+    }))
+}
+
+pub async fn details(
+    State(state): State<AppState>,
+    Path(address): Path<String>,
+) -> ApiResult<UserSummary> {
+    state
+        .query(move |connection| load_user(connection, &address))
+        .await
+        .map(Json)
+}
+
+pub async fn participations(
+    State(state): State<AppState>,
+    Path(address): Path<String>,
+    Query(pagination): Query<Pagination>,
+) -> ApiResult<Page<ParticipationSummary>> {
+    state
+        .query(move |connection| {
+            load_user(connection, &address)?;
+            participation_page(connection, "user", &address, pagination)
+        })
+        .await
+        .map(Json)
+}
+
+/// Votes, claims and appeals of a user, newest first
+pub async fn history(
+    State(state): State<AppState>,
+    Path(address): Path<String>,
+    Query(pagination): Query<Pagination>,
+) -> ApiResult<Page<HistoryEntry>> {
+    state
+        .query(move |connection| {
+            load_user(connection, &address)?;
+
+            let total: u64 = connection.query_row(
+                &format!("SELECT COUNT(*) FROM ({HISTORY})"),
+                [&address],
+                |row| row.get(0),
+            )?;
+
+            let mut statement = connection.prepare(&format!(
+                "{HISTORY} ORDER BY slot DESC, signature, seq DESC LIMIT ?2 OFFSET ?3"
+            ))?;
+            let items = statement
+                .query_map(
+                    params![address, pagination.limit(), pagination.offset],
+                    |row| {
+                        Ok(HistoryEntry {
+                            signature: row.get(0)?,
+                            slot: row.get(2)?,
+                            block_time: row.get(3)?,
+                            kind: row.get(4)?,
+                            event_id: row.get(5)?,
+                            option: row.get(6)?,
+                            amount: row.get(7)?,
+                        })
+                    },
+                )?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+
+            Ok(pagination.page(items, total))
+        })
+        .await
+        .map(Json)
+}
+
+fn user_page(
+    connection: &Connection,
+    order: &str,
+    pagination: Pagination,
+) -> Result<Page<UserSummary>, ApiError> {
+    let total: u64 = connection.query_row("SELECT COUNT(*) FROM users", [], |row| row.get(0))?;
+
+    let mut statement = connection.prepare(&format!(
+        "SELECT {} FROM users JOIN user_pnl ON user_pnl.user = users.address
+         ORDER BY {order}, address LIMIT ?1 OFFSET ?2",
+        UserSummary::COLUMNS
+    ))?;
+    let items = statement
+        .query_map(
+            params![pagination.limit(), pagination.offset],
+            UserSummary::from_row,
+        )?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    Ok(pagination.page(items, total))
+}
+
+fn load_user(connection: &Connection, address: &str) -> Result<UserSummary, ApiError> {
+    connection
+        .query_row(
+            &format!(
+                "SELECT {} FROM users JOIN user_pnl ON user_pnl.user = users.address
+                 WHERE address = ?1",
+                UserSummary::COLUMNS
+            ),
+            [address],
+            UserSummary::from_row,
+        )
+        .optional()?
+        .ok_or_else(|| ApiError::NotFound(format!("user {address}")))
+}
@@ -0,0 +1,178 @@
+//! Response types of the API.
+//!
+//! Indexed rows use the field names of the account views in
+//! [`predictory_client::views`]; options and odds are returned as those views
+//! directly, so clients can decode API and RPC data with the same types.
+
+use std::collections::BTreeMap;
+
+use predictory_client::views::{EventOptionView, OddsView};
+use rusqlite::Row;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct EventSummary {
+    pub id: String,
+    pub authority: String,
+    pub name: String,
+    pub description: String,
+    pub is_private: bool,
+    pub status: String,
+    pub start_date: Option<i64>,
+    pub end_date: Option<i64>,
+    pub participation_deadline: Option<i64>,
+    pub stake: u64,
+    pub platform_fee: u64,
+    pub org_reward: u64,
+    pub result: Option<u8>,
+    pub total_amount: u64,
+    pub frozen: bool,
+    pub hidden: bool,
+    pub vote_count: u64,
+    pub participation_count: u64,
+    pub created_slot: Option<u64>,
+}
+
+impl EventSummary {
+    /// Columns expected by [`EventSummary::from_row`], `events` joined with `event_volume`
+    pub const COLUMNS: &'static str = "events.event_id, authority, name, description, is_private,
+        status, start_date, end_date, participation_deadline, stake, platform_fee, org_reward,
+        result, total_amount, frozen, hidden, COALESCE(vote_count, 0),
+        COALESCE(participant_count, 0), created_slot";
+
+    pub fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(EventSummary {
+            id: row.get(0)?,
+            authority: row.get(1)?,
+            name: row.get(2)?,
+            description: row.get(3)?,
+            is_private: row.get(4)?,
+            status: row.get(5)?,
+            start_date: row.get(6)?,
+            end_date: row.get(7)?,
+            participation_deadline: row.get(8)?,
+            stake: row.get(9)?,
+            platform_fee: row.get(10)?,
+            org_reward: row.get(11)?,
+            result: row.get(12)?,
+            total_amount: row.get(13)?,
+            frozen: row.get(14)?,
+            hidden: row.get(15)?,
+            vote_count: row.get(16)?,
+            participation_count: row.get(17)?,
+            created_slot: row.get(18)?,
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct EventDetails {
+    #[serde(flatten)]
+    pub event: EventSummary,
+    pub options: Vec<EventOptionView>,
+}
+
+/// Odds of every option right after a vote
+#[derive(Debug, Serialize)]
+pub struct OddsPoint {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    #[serde(flatten)]
+    pub odds: OddsView,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ParticipationSummary {
+    pub event_id: String,
+    pub participant: String,
+    pub option: u8,
+    pub deposited_amount: u64,
+    pub is_claimed: bool,
+    pub appealed: bool,
+    pub created_slot: u64,
+}
+
+impl ParticipationSummary {
+    pub const COLUMNS: &'static str =
+        "event_id, user, option, amount, claimed, appealed, created_slot";
+
+    pub fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(ParticipationSummary {
+            event_id: row.get(0)?,
+            participant: row.get(1)?,
+            option: row.get(2)?,
+            deposited_amount: row.get(3)?,
+            is_claimed: row.get(4)?,
+            appealed: row.get(5)?,
+            created_slot: row.get(6)?,
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct UserSummary {
+    pub owner: String,
+    pub name: String,
+    pub stake: u64,
+    pub trust_lvl: u64,
+    /// Total amount voted
+    pub volume: u64,
+    /// Total amount claimed back, rewards and recharges
+    pub returned: u64,
+    /// Realized profit, `returned - volume`
+    pub pnl: i64,
+}
+
+impl UserSummary {
+    /// Columns expected by [`UserSummary::from_row`], `users` joined with `user_pnl`
+    pub const COLUMNS: &'static str = "address, name, stake, trust_lvl, staked, returned, pnl";
+
+    pub fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(UserSummary {
+            owner: row.get(0)?,
+            name: row.get(1)?,
+            stake: row.get(2)?,
+            trust_lvl: row.get(3)?,
+            volume: row.get(4)?,
+            returned: row.get(5)?,
+            pnl: row.get(6)?,
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct LeaderboardEntry {
+    pub rank: u64,
+    #[serde(flatten)]
+    pub user: UserSummary,
+}
+
+/// Vote, claim or appeal of a user
+#[derive(Debug, Serialize)]
+pub struct HistoryEntry {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    /// `vote`, `claim`, `recharge`, `burn_trust` or `appeal`
+    pub kind: String,
+    pub event_id: String,
+    pub option: Option<u8>,
+    pub amount: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProtocolStats {
+    pub users: u64,
+    /// Event count per status
+    pub events: BTreeMap<String, u64>,
+    pub votes: u64,
+    pub participants: u64,
+    pub volume: u64,
+    /// Amount claimed back by participants
+    pub paid_out: u64,
+    /// Total amount per fee flow kind
+    pub fee_flows: BTreeMap<String, u64>,
+    /// Slot of the last indexed transaction
+    pub last_slot: Option<u64>,
+}
@@ -0,0 +1,139 @@
+//! Reader for Predictory `ResolutionAttestation` accounts.
+//!
+//! An attestation lives at `["attestation", event_id]` and holds the outcome
+//! of one event. Its layout only ever grows by appending fields, so this crate
+//! reads any version from 1 up. It depends on `solana-program` alone, for
+//! programs that don't want to pull in Predictory or Anchor.
+//!
+//! Only final attestations should be trusted: a pending one can still be
+//! appealed and disputed.
+
+use solana_program::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey, pubkey::Pubkey,
+};
+use thiserror::Error;
+
+pub const PROGRAM_ID: Pubkey = pubkey!("EV4SZ8mid5j4AVTBsi9tE4faCVHuu2XEPk857KWvrnL4");
+
+/// Anchor discriminator of the account, `sha256("account:ResolutionAttestation")[..8]`
+pub const DISCRIMINATOR: [u8; 8] = [159, 48, 18, 144, 218, 165, 146, 93];
+
+/// Size of the version 1 layout, discriminator included
+pub const LEN_V1: usize = 67;
+
+pub fn attestation_address(event_id: u128) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"attestation", &event_id.to_le_bytes()], &PROGRAM_ID)
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum AttestationError {
+    #[error("account is not owned by the Predictory program")]
+    WrongOwner,
+
+    #[error("account is not the attestation of event {0:#x}")]
+    WrongAddress(u128),
+
+    #[error("account is not a resolution attestation")]
+    InvalidData,
+
+    #[error("unknown resolution status {0}")]
+    UnknownStatus(u8),
+}
+
+impl From<AttestationError> for ProgramError {
+    fn from(error: AttestationError) -> Self {
+        match error {
+            AttestationError::WrongOwner => ProgramError::IllegalOwner,
+            AttestationError::WrongAddress(_) => ProgramError::InvalidSeeds,
+            AttestationError::InvalidData | AttestationError::UnknownStatus(_) => {
+                ProgramError::InvalidAccountData
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// Result set, still open to appeals and disputes
+    Pending,
+    /// Result final
+    Resolved,
+    /// Event canceled, no result
+    Canceled,
+}
+
+impl TryFrom<u8> for Status {
+    type Error = AttestationError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Status::Pending),
+            1 => Ok(Status::Resolved),
+            2 => Ok(Status::Canceled),
+            other => Err(AttestationError::UnknownStatus(other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Attestation {
+    pub version: u8,
+    pub event_id: u128,
+    pub status: Status,
+    /// Index of the outcome option, meaningful once resolved
+    pub result: u8,
+    /// Slot the status became final at, zero while pending
+    pub finalized_slot: u64,
+    /// Signer that set the result or canceled the event; the event authority
+    /// for events resolved before attestations existed
+    pub resolver: Pubkey,
+}
+
+impl Attestation {
+    /// Decodes the account data, discriminator included
+    pub fn decode(data: &[u8]) -> Result<Self, AttestationError> {
+        if data.len() < LEN_V1 || data[..8] != DISCRIMINATOR {
+            return Err(AttestationError::InvalidData);
+        }
+
+        let version = data[8];
+        if version == 0 {
+            return Err(AttestationError::InvalidData);
+        }
+
+        // Fixed offsets of the version 1 fields, later versions append after them
+        Ok(Attestation {
+            version,
+            event_id: u128::from_le_bytes(data[9..25].try_into().unwrap()),
+            status: data[25].try_into()?,
+            result: data[26],
+            finalized_slot: u64::from_le_bytes(data[27..35].try_into().unwrap()),
+            resolver: Pubkey::new_from_array(data[35..67].try_into().unwrap()),
+        })
+    }
+
+    pub fn is_final(&self) -> bool {
+        self.status != Status::Pending
+    }
+
+    /// Final winning option, `None` while pending or for canceled events
+    pub fn outcome(&self) -> Option<u8> {
+        (self.status == Status::Resolved).then_some(self.result)
+    }
+}
+
+/// Reads the attestation of an event, checking the account owner and address
+pub fn read(account: &AccountInfo, event_id: u128) -> Result<Attestation, AttestationError> {
+    if *account.owner != PROGRAM_ID {
+        return Err(AttestationError::WrongOwner);
+    }
+    if *account.key != attestation_address(event_id).0 {
+        return Err(AttestationError::WrongAddress(event_id));
+    }
+
+    let data = account
+        .try_borrow_data()
+        .map_err(|_| AttestationError::InvalidData)?;
+
+    Attestation::decode(&data)
+}
@@ -0,0 +1,360 @@
+use anyhow::anyhow;
+use clap::Subcommand;
+use predictory_client::{
+    accounts::{self, ProgramAccount},
+    instructions as ix, pda,
+    predictory::state::{
+        appeal::Appellation,
+        contract_state::State,
+        event::{Event, EventMeta},
+        legacy::Versioned,
+        option::EventOption,
+        participation::Participation,
+        treasury::TreasuryRecipient,
+        user::User,
+    },
+};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+use crate::{
+    args::{self, ParamKindArg, RoleArg},
+    Context,
+};
+
+#[derive(Subcommand)]
+pub enum StateCommand {
+    /// Initialize the contract state, signed by the program upgrade authority
+    Init {
+        /// Contract authority, defaults to the signer
+        #[arg(long, value_parser = args::pubkey)]
+        authority: Option<Pubkey>,
+        #[arg(long)]
+        multiplier: u64,
+        /// Organizer stake locked on event publication, in lamports
+        #[arg(long)]
+        event_price: u64,
+        /// Platform fee, in lamports
+        #[arg(long)]
+        platform_fee: u64,
+        /// Organizer reward, in percent
+        #[arg(long)]
+        org_reward: u64,
+    },
+    /// Propose a new contract authority
+    ProposeAuthority {
+        #[arg(value_parser = args::pubkey)]
+        authority: Pubkey,
+    },
+    /// Accept a pending authority transfer
+    AcceptAuthority,
+    /// Cancel a pending authority transfer
+    CancelAuthorityTransfer,
+    /// Set the authority transfer timelock, in seconds
+    SetTimelock { timelock: i64 },
+    /// Set pause flags, as the contract authority or a guardian
+    SetPaused {
+        flags: u8,
+        /// Pause as a guardian
+        #[arg(long)]
+        as_guardian: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ParamCommand {
+    /// Queue a parameter change, as a fee manager
+    Queue { kind: ParamKindArg, value: u64 },
+    /// Apply a queued change once its timelock elapsed
+    Apply { kind: ParamKindArg },
+    /// Cancel a queued change, as a fee manager
+    Cancel { kind: ParamKindArg },
+}
+
+#[derive(Subcommand)]
+pub enum RoleCommand {
+    /// Grant a role, as the contract authority
+    Grant {
+        role: RoleArg,
+        #[arg(value_parser = args::pubkey)]
+        member: Pubkey,
+    },
+    /// Revoke a role, as the contract authority
+    Revoke {
+        role: RoleArg,
+        #[arg(value_parser = args::pubkey)]
+        member: Pubkey,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ProposalCommand {
+    /// Propose a parameter change
+    Create { kind: ParamKindArg, value: u64 },
+    /// Vote on a proposal with the signer's trust level
+    Vote {
+        proposal_id: u64,
+        /// Vote against the proposal
+        #[arg(long)]
+        against: bool,
+    },
+    /// Execute an accepted proposal
+    Execute { proposal_id: u64 },
+}
+
+#[derive(Subcommand)]
+pub enum TreasuryCommand {
+    /// Create the treasury of a deployment initialized before it existed, as the contract authority
+    Init,
+    /// Replace the treasury split, as a fee manager
+    SetSplit {
+        /// Recipients as `<wallet>:<share_bps>`
+        #[arg(required = true, value_parser = args::recipient)]
+        recipients: Vec<TreasuryRecipient>,
+    },
+    /// Distribute the treasury balance to the configured recipients
+    Distribute,
+}
+
+#[derive(Subcommand)]
+pub enum MigrateCommand {
+    /// Migrate the contract state
+    State,
+    /// Migrate a user account
+    User {
+        #[arg(value_parser = args::pubkey)]
+        owner: Pubkey,
+    },
+    /// Migrate the event and its metadata, options and appellation
+    Event {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+    },
+    /// Migrate a participation
+    Participation {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+        #[arg(value_parser = args::pubkey)]
+        participant: Pubkey,
+    },
+}
+
+pub fn run_state(context: &mut Context, command: StateCommand) -> anyhow::Result<()> {
+    let instruction = state_instruction(context, command)?;
+
+    context.send(vec![instruction])
+}
+
+fn state_instruction(context: &mut Context, command: StateCommand) -> anyhow::Result<Instruction> {
+    let payer = context.payer()?;
+
+    let instruction = match command {
+        StateCommand::Init {
+            authority,
+            multiplier,
+            event_price,
+            platform_fee,
+            org_reward,
+        } => ix::initialize_contract_state(
+            &payer,
+            authority.unwrap_or(payer),
+            multiplier,
+            event_price,
+            platform_fee,
+            org_reward,
+        ),
+        StateCommand::ProposeAuthority { authority } => ix::propose_authority(&payer, authority),
+        StateCommand::AcceptAuthority => ix::accept_authority(&payer),
+        StateCommand::CancelAuthorityTransfer => ix::cancel_authority_transfer(&payer),
+        StateCommand::SetTimelock { timelock } => ix::set_authority_timelock(&payer, timelock),
+        StateCommand::SetPaused { flags, as_guardian } => {
+            ix::set_paused(&payer, flags, as_guardian)
+        }
+    };
+
+    Ok(instruction)
+}
+
+pub fn run_param(context: &mut Context, command: ParamCommand) -> anyhow::Result<()> {
+    let payer = context.payer()?;
+
+    let instruction = match command {
+        ParamCommand::Queue { kind, value } => ix::queue_param_change(&payer, kind.into(), value),
+        ParamCommand::Apply { kind } => ix::apply_param_change(&payer, kind.into()),
+        ParamCommand::Cancel { kind } => ix::cancel_param_change(&payer, kind.into()),
+    };
+
+    context.send(vec![instruction])
+}
+
+pub fn run_role(context: &mut Context, command: RoleCommand) -> anyhow::Result<()> {
+    let payer = context.payer()?;
+
+    let instruction = match command {
+        RoleCommand::Grant { role, member } => ix::grant_role(&payer, role.into(), member),
+        RoleCommand::Revoke { role, member } => ix::revoke_role(&payer, role.into(), member),
+    };
+
+    context.send(vec![instruction])
+}
+
+pub fn run_proposal(context: &mut Context, command: ProposalCommand) -> anyhow::Result<()> {
+    let payer = context.payer()?;
+
+    let instruction = match command {
+        ProposalCommand::Create { kind, value } => {
+            let proposal_id = accounts::fetch_state(&context.rpc)?.proposal_count;
+
+            if !context.json {
+                println!("Proposal id: {proposal_id}");
+            }
+
+            ix::create_proposal(&payer, proposal_id, kind.into(), value)
+        }
+        ProposalCommand::Vote {
+            proposal_id,
+            against,
+        } => ix::vote_proposal(&payer, proposal_id, !against),
+        ProposalCommand::Execute { proposal_id } => ix::execute_proposal(&payer, proposal_id),
+    };
+
+    context.send(vec![instruction])
+}
+
+pub fn run_treasury(context: &mut Context, command: TreasuryCommand) -> anyhow::Result<()> {
+    let payer = context.payer()?;
+
+    let instruction = match command {
+        TreasuryCommand::Init => ix::initialize_treasury(&payer),
+        TreasuryCommand::SetSplit { recipients } => ix::set_treasury_split(&payer, recipients),
+        TreasuryCommand::Distribute => {
+            let wallets: Vec<Pubkey> = accounts::fetch_treasury(&context.rpc)?
+                .recipients
+                .iter()
+                .map(|recipient| recipient.wallet)
+                .collect();
+
+            ix::distribute_treasury(&payer, &wallets)
+        }
+    };
+
+    context.send(vec![instruction])
+}
+
+pub fn run_migrate(context: &mut Context, command: MigrateCommand) -> anyhow::Result<()> {
+    let payer = context.payer()?;
+
+    let instructions = match command {
+        MigrateCommand::State => vec![ix::migrate_state(&payer)],
+        MigrateCommand::User { owner } => vec![ix::migrate_user(&payer, owner)],
+        MigrateCommand::Event { event_id } => {
+            let event = accounts::fetch_event(&context.rpc, event_id)?;
+            let mut instructions = vec![
+                ix::migrate_event(&payer, event_id),
+                ix::migrate_event_meta(&payer, event_id),
+            ];
+
+            instructions.extend(
+                (0..event.option_count)
+                    .map(|index| ix::migrate_event_option(&payer, event_id, index)),
+            );
+
+            if context
+                .rpc
+                .get_account(&pda::appeal_address(event_id))?
+                .is_some()
+            {
+                instructions.push(ix::migrate_appellation(&payer, event_id));
+            }
+
+            instructions
+        }
+        MigrateCommand::Participation {
+            event_id,
+            participant,
+        } => vec![ix::migrate_participation(&payer, event_id, participant)],
+    };
+
+    // Migrating an up-to-date account fails, so each account gets its own transaction
+    // and accounts that already use the current layout are skipped
+    for instruction in instructions {
+        // The migrated account always precedes the system program
+        let target = instruction.accounts[instruction.accounts.len() - 2].pubkey;
+
+        if is_current(context, &target)? {
+            if !context.json {
+                println!("Account {target} is up to date");
+            }
+            continue;
+        }
+
+        context.send(vec![instruction])?;
+    }
+
+    Ok(())
+}
+
+fn is_current(context: &Context, address: &Pubkey) -> anyhow::Result<bool> {
+    let account = context
+        .rpc
+        .get_account(address)?
+        .ok_or_else(|| anyhow!("account {address} not found"))?;
+    let version = accounts::account_version(&account.data);
+
+    let current = match ProgramAccount::decode(&account.data)? {
+        ProgramAccount::State(_) => State::CURRENT_VERSION,
+        ProgramAccount::User(_) => User::CURRENT_VERSION,
+        ProgramAccount::Event(_) => Event::CURRENT_VERSION,
+        ProgramAccount::EventMeta(_) => EventMeta::CURRENT_VERSION,
+        ProgramAccount::EventOption(_) => EventOption::CURRENT_VERSION,
+        ProgramAccount::Participation(_) => Participation::CURRENT_VERSION,
+        ProgramAccount::Appellation(_) => Appellation::CURRENT_VERSION,
+        other => return Err(anyhow!("{} accounts are not versioned", other.name())),
+    };
+
+    Ok(version == Some(current))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, Command};
+
+    fn instruction(args: &[&str]) -> (Pubkey, Instruction) {
+        let Ok(Command::State(command)) = parse(&[&["state"], args].concat()) else {
+            panic!("not a state command");
+        };
+        let mut context = Context::offline();
+        let payer = context.payer().unwrap();
+
+        (payer, state_instruction(&mut context, command).unwrap())
+    }
+
+    #[test]
+    fn set_paused_arguments() {
+        let (payer, ix) = instruction(&["set-paused", "5"]);
+        assert_eq!(ix, ix::set_paused(&payer, 5, false));
+
+        let (payer, ix) = instruction(&["set-paused", "5", "--as-guardian"]);
+        assert_eq!(ix, ix::set_paused(&payer, 5, true));
+    }
+
+    #[test]
+    fn init_defaults_authority_to_signer() {
+        let (payer, ix) = instruction(&[
+            "init",
+            "--multiplier",
+            "2",
+            "--event-price",
+            "1000",
+            "--platform-fee",
+            "3",
+            "--org-reward",
+            "4",
+        ]);
+
+        assert_eq!(
+            ix,
+            ix::initialize_contract_state(&payer, payer, 2, 1000, 3, 4)
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use std::{
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use clap::ValueEnum;
+use predictory_client::{
+    event_id::parse_event_id,
+    predictory::state::{param::ParamKind, role::Role, treasury::TreasuryRecipient},
+};
+use solana_sdk::pubkey::Pubkey;
+
+pub fn pubkey(value: &str) -> Result<Pubkey, String> {
+    Pubkey::from_str(value).map_err(|error| error.to_string())
+}
+
+pub fn event_id(value: &str) -> Result<u128, String> {
+    parse_event_id(value).map_err(|error| error.to_string())
+}
+
+/// Parses a `<wallet>:<share_bps>` treasury recipient
+pub fn recipient(value: &str) -> Result<TreasuryRecipient, String> {
+    let (wallet, share_bps) = value
+        .split_once(':')
+        .ok_or_else(|| "expected <wallet>:<share_bps>".to_string())?;
+
+    Ok(TreasuryRecipient {
+        wallet: pubkey(wallet)?,
+        share_bps: share_bps.parse().map_err(|_| "invalid share".to_string())?,
+    })
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ParamKindArg {
+    Multiplier,
+    EventPrice,
+    PlatformFee,
+    OrgReward,
+}
+
+impl From<ParamKindArg> for ParamKind {
+    fn from(kind: ParamKindArg) -> Self {
+        match kind {
+            ParamKindArg::Multiplier => ParamKind::Multiplier,
+            ParamKindArg::EventPrice => ParamKind::EventPrice,
+            ParamKindArg::PlatformFee => ParamKind::PlatformFee,
+            ParamKindArg::OrgReward => ParamKind::OrgReward,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum RoleArg {
+    FeeManager,
+    Moderator,
+    Resolver,
+    Guardian,
+}
+
+impl From<RoleArg> for Role {
+    fn from(role: RoleArg) -> Self {
+        match role {
+            RoleArg::FeeManager => Role::FeeManager,
+            RoleArg::Moderator => Role::Moderator,
+            RoleArg::Resolver => Role::Resolver,
+            RoleArg::Guardian => Role::Guardian,
+        }
+    }
+}
+
+/// Parses a unix timestamp, or an offset in seconds from now written as `+<seconds>`
+pub fn timestamp(value: &str) -> Result<i64, String> {
+    match value.strip_prefix('+') {
+        Some(offset) => {
+            let offset: i64 = offset.parse().map_err(|_| "invalid offset".to_string())?;
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_err(|error| error.to_string())?
+                .as_secs() as i64;
+
+            Ok(now + offset)
+        }
+        None => value.parse().map_err(|_| "invalid timestamp".to_string()),
+    }
+}
@@ -0,0 +1,587 @@
+use clap::{Args, Subcommand};
+use predictory_client::{
+    accounts,
+    event_id::{format_event_id, new_event_id},
+    instructions as ix,
+    predictory::context::CreateEventArgs,
+    text::to_fixed,
+};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+use crate::{args, Context};
+
+/// Signer acting as a co-organizer instead of the event authority
+#[derive(Args)]
+pub struct Delegation {
+    /// Act as a co-organizer of the event
+    #[arg(long)]
+    as_organizer: bool,
+}
+
+/// Accounts forwarded to the callback program of the event
+#[derive(Args)]
+pub struct CallbackAccounts {
+    /// Writable account passed to the callback program, in order
+    #[arg(long = "callback-account", value_parser = args::pubkey)]
+    accounts: Vec<Pubkey>,
+}
+
+#[derive(Subcommand)]
+pub enum EventCommand {
+    /// Create a draft event
+    Create {
+        #[arg(long)]
+        name: String,
+        #[arg(long, default_value = "")]
+        description: String,
+        /// Start date, as a unix timestamp or `+<seconds>`
+        #[arg(long, value_parser = args::timestamp)]
+        start: i64,
+        /// End date, as a unix timestamp or `+<seconds>`
+        #[arg(long, value_parser = args::timestamp)]
+        end: i64,
+        /// Participation deadline, as a unix timestamp or `+<seconds>`
+        #[arg(long, value_parser = args::timestamp)]
+        deadline: Option<i64>,
+        #[arg(long)]
+        private: bool,
+        /// Event id, a random UUID v4 by default
+        #[arg(long, value_parser = args::event_id)]
+        id: Option<u128>,
+    },
+    /// Publish a draft event, locking the organizer stake
+    Publish {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+    },
+    /// Delete a draft event with its options
+    Delete {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+    },
+    /// Rename an event
+    UpdateName {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+        name: String,
+        #[command(flatten)]
+        delegation: Delegation,
+    },
+    /// Replace the event description
+    UpdateDescription {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+        description: String,
+        #[command(flatten)]
+        delegation: Delegation,
+    },
+    /// Move the event end date
+    UpdateEndDate {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+        #[arg(value_parser = args::timestamp)]
+        end_date: i64,
+        #[command(flatten)]
+        delegation: Delegation,
+    },
+    /// Set or clear the participation deadline
+    UpdateDeadline {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+        #[arg(value_parser = args::timestamp)]
+        deadline: Option<i64>,
+        #[command(flatten)]
+        delegation: Delegation,
+    },
+    /// Propose a new event authority, or clear the proposal when omitted
+    ProposeAuthority {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+        #[arg(value_parser = args::pubkey)]
+        authority: Option<Pubkey>,
+    },
+    /// Accept a proposed event authority transfer
+    AcceptAuthority {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+    },
+    /// Cancel an event
+    Cancel {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+        /// Cancel as a moderator
+        #[arg(long)]
+        as_moderator: bool,
+    },
+    /// Notify a program of the event result, before the event is published
+    SetCallback {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+        #[arg(value_parser = args::pubkey)]
+        program: Pubkey,
+    },
+    /// Stop notifying a program of the event result
+    RemoveCallback {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+    },
+    /// Complete an event with the winning option
+    Complete {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+        result: u8,
+        #[command(flatten)]
+        delegation: Delegation,
+    },
+    /// Freeze or unfreeze an event, as a guardian
+    Freeze {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+        /// Unfreeze the event
+        #[arg(long)]
+        unfreeze: bool,
+    },
+    /// Hide or show an event, as a moderator
+    Moderate {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+        /// Show the event again
+        #[arg(long)]
+        show: bool,
+    },
+    /// Seal the event outcome in its resolution attestation, once appeals are over
+    Finalize {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+    },
+    /// Settle a disputed result, as a resolver
+    ResolveDispute {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+        result: u8,
+    },
+    /// Keep the original result of a dispute no resolver settled in time
+    ExpireDispute {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+    },
+    /// Pass the event result to its callback program, once it can no longer be disputed
+    NotifyCallback {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+        #[command(flatten)]
+        callback: CallbackAccounts,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum OptionCommand {
+    /// Keep the option pools in a single options table, before adding any option
+    CreateTable {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+        #[command(flatten)]
+        delegation: Delegation,
+    },
+    /// Append options to an event
+    Create {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+        #[arg(required = true)]
+        descriptions: Vec<String>,
+        #[command(flatten)]
+        delegation: Delegation,
+    },
+    /// Replace an option description
+    Update {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+        index: u8,
+        description: String,
+        #[command(flatten)]
+        delegation: Delegation,
+    },
+    /// Remove an option, moving the last option into its slot
+    Remove {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+        index: u8,
+        #[command(flatten)]
+        delegation: Delegation,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum OrganizerCommand {
+    /// Add a co-organizer
+    Add {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+        #[arg(value_parser = args::pubkey)]
+        member: Pubkey,
+        /// Permission bit flags
+        #[arg(long)]
+        permissions: u8,
+        /// Share of the organizer reward, in percent
+        #[arg(long, default_value_t = 0)]
+        reward_share: u8,
+    },
+    /// Change co-organizer permissions and reward share
+    Update {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+        #[arg(value_parser = args::pubkey)]
+        member: Pubkey,
+        #[arg(long)]
+        permissions: u8,
+        #[arg(long, default_value_t = 0)]
+        reward_share: u8,
+    },
+    /// Remove a co-organizer
+    Remove {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+        #[arg(value_parser = args::pubkey)]
+        member: Pubkey,
+    },
+    /// Claim the signer's share of the organizer reward
+    ClaimReward {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+    },
+}
+
+pub fn run_event(context: &mut Context, command: EventCommand) -> anyhow::Result<()> {
+    let instruction = event_instruction(context, command)?;
+
+    context.send(vec![instruction])
+}
+
+fn event_instruction(context: &mut Context, command: EventCommand) -> anyhow::Result<Instruction> {
+    let payer = context.payer()?;
+
+    let instruction = match command {
+        EventCommand::Create {
+            name,
+            description,
+            start,
+            end,
+            deadline,
+            private,
+            id,
+        } => {
+            let event_id = id.unwrap_or_else(new_event_id);
+
+            if !context.json {
+                println!("Event id: {}", format_event_id(event_id));
+            }
+
+            ix::create_event(
+                &payer,
+                event_id,
+                CreateEventArgs {
+                    name: to_fixed(&name)?,
+                    is_private: private,
+                    description: to_fixed(&description)?,
+                    start_date: start,
+                    end_date: end,
+                    participation_deadline: deadline,
+                },
+            )
+        }
+        EventCommand::Publish { event_id } => ix::publish_event(&payer, event_id),
+        EventCommand::Delete { event_id } => {
+            let event = accounts::fetch_event(&context.rpc, event_id)?;
+            let organizers: Vec<Pubkey> = accounts::fetch_event_organizers(&context.rpc, event_id)?
+                .iter()
+                .map(|organizer| organizer.member)
+                .collect();
+
+            ix::delete_event(
+                &payer,
+                event_id,
+                event.option_count,
+                event.options_table != 0,
+                &organizers,
+            )
+        }
+        EventCommand::UpdateName {
+            event_id,
+            name,
+            delegation,
+        } => ix::update_event_name(&payer, event_id, delegation.as_organizer, to_fixed(&name)?),
+        EventCommand::UpdateDescription {
+            event_id,
+            description,
+            delegation,
+        } => ix::update_event_description(
+            &payer,
+            event_id,
+            delegation.as_organizer,
+            to_fixed(&description)?,
+        ),
+        EventCommand::UpdateEndDate {
+            event_id,
+            end_date,
+            delegation,
+        } => ix::update_event_end_date(&payer, event_id, delegation.as_organizer, end_date),
+        EventCommand::UpdateDeadline {
+            event_id,
+            deadline,
+            delegation,
+        } => ix::update_event_participation_deadline(
+            &payer,
+            event_id,
+            delegation.as_organizer,
+            deadline,
+        ),
+        EventCommand::ProposeAuthority {
+            event_id,
+            authority,
+        } => ix::propose_event_authority(&payer, event_id, authority),
+        EventCommand::AcceptAuthority { event_id } => {
+            let event = accounts::fetch_event(&context.rpc, event_id)?;
+
+            ix::accept_event_authority(&payer, event_id, &event.authority)
+        }
+        EventCommand::Cancel {
+            event_id,
+            as_moderator,
+        } => {
+            let event = accounts::fetch_event(&context.rpc, event_id)?;
+
+            ix::cancel_event(&payer, event_id, &event.authority, as_moderator)
+        }
+        EventCommand::SetCallback { event_id, program } => {
+            ix::set_event_callback(&payer, event_id, program)
+        }
+        EventCommand::RemoveCallback { event_id } => ix::remove_event_callback(&payer, event_id),
+        EventCommand::Complete {
+            event_id,
+            result,
+            delegation,
+        } => ix::complete_event(&payer, event_id, delegation.as_organizer, result),
+        EventCommand::Freeze { event_id, unfreeze } => {
+            ix::set_event_frozen(&payer, event_id, !unfreeze)
+        }
+        EventCommand::Moderate { event_id, show } => ix::moderate_event(&payer, event_id, !show),
+        EventCommand::Finalize { event_id } => ix::finalize_event(&payer, event_id),
+        EventCommand::ResolveDispute { event_id, result } => {
+            ix::resolve_dispute(&payer, event_id, result)
+        }
+        EventCommand::ExpireDispute { event_id } => ix::expire_dispute(&payer, event_id),
+        EventCommand::NotifyCallback { event_id, callback } => {
+            let program = accounts::fetch_event_callback(&context.rpc, event_id)?.program;
+
+            ix::notify_callback(
+                event_id,
+                &program,
+                callback
+                    .accounts
+                    .into_iter()
+                    .map(|account| AccountMeta::new(account, false))
+                    .collect(),
+            )
+        }
+    };
+
+    Ok(instruction)
+}
+
+pub fn run_option(context: &mut Context, command: OptionCommand) -> anyhow::Result<()> {
+    let payer = context.payer()?;
+
+    let instruction = match command {
+        OptionCommand::CreateTable {
+            event_id,
+            delegation,
+        } => ix::create_options_table(&payer, event_id, delegation.as_organizer),
+        OptionCommand::Create {
+            event_id,
+            descriptions,
+            delegation,
+        } => {
+            let event = accounts::fetch_event(&context.rpc, event_id)?;
+
+            ix::create_event_options(
+                &payer,
+                event_id,
+                delegation.as_organizer,
+                event.option_count,
+                event.options_table != 0,
+                descriptions,
+            )
+        }
+        OptionCommand::Update {
+            event_id,
+            index,
+            description,
+            delegation,
+        } => ix::update_event_option(
+            &payer,
+            event_id,
+            delegation.as_organizer,
+            index,
+            to_fixed(&description)?,
+        ),
+        OptionCommand::Remove {
+            event_id,
+            index,
+            delegation,
+        } => {
+            let event = accounts::fetch_event(&context.rpc, event_id)?;
+
+            ix::remove_event_option(
+                &payer,
+                event_id,
+                delegation.as_organizer,
+                index,
+                event.option_count,
+                event.options_table != 0,
+            )
+        }
+    };
+
+    context.send(vec![instruction])
+}
+
+pub fn run_organizer(context: &mut Context, command: OrganizerCommand) -> anyhow::Result<()> {
+    let payer = context.payer()?;
+
+    let instruction = match command {
+        OrganizerCommand::Add {
+            event_id,
+            member,
+            permissions,
+            reward_share,
+        } => ix::add_event_organizer(&payer, event_id, member, permissions, reward_share),
+        OrganizerCommand::Update {
+            event_id,
+            member,
+            permissions,
+            reward_share,
+        } => ix::update_event_organizer(&payer, event_id, member, permissions, reward_share),
+        OrganizerCommand::Remove { event_id, member } => {
+            ix::remove_event_organizer(&payer, event_id, member)
+        }
+        OrganizerCommand::ClaimReward { event_id } => ix::claim_organizer_reward(&payer, event_id),
+    };
+
+    context.send(vec![instruction])
+}
+
+#[cfg(test)]
+mod tests {
+    use predictory_client::{
+        event_id::{parse_event_id, validate_event_id, EVENT_ID_VERSION},
+        events::instruction_name,
+        predictory::context::UUID_VERSION,
+    };
+
+    use super::*;
+    use crate::{parse, Command};
+
+    const EVENT_ID: &str = "0f8f1f3a-6b5c-4d2e-9a7b-3c4d5e6f7a8b";
+
+    /// Builds the instruction of an event command, with the signer it is built for
+    fn instruction(args: &[&str]) -> (Pubkey, Instruction) {
+        let Ok(Command::Event(command)) = parse(&[&["event"], args].concat()) else {
+            panic!("not an event command");
+        };
+        let mut context = Context::offline();
+        let payer = context.payer().unwrap();
+
+        (payer, event_instruction(&mut context, command).unwrap())
+    }
+
+    #[test]
+    fn generated_event_ids_are_uuid_v4() {
+        assert_eq!(EVENT_ID_VERSION, UUID_VERSION);
+
+        for _ in 0..16 {
+            let (_, ix) = instruction(&["create", "--name", "Final", "--start", "1", "--end", "2"]);
+            assert_eq!(instruction_name(&ix.data), Some("create_event"));
+
+            // The event id follows the discriminator
+            let event_id = u128::from_le_bytes(ix.data[8..24].try_into().unwrap());
+
+            // Version nibble of the UUID, checked by `CreateEvent::validate`
+            assert_eq!((event_id >> 76) & 0xf, UUID_VERSION as u128);
+            assert!(validate_event_id(event_id).is_ok());
+        }
+    }
+
+    #[test]
+    fn create_event_arguments() {
+        let event_id = parse_event_id(EVENT_ID).unwrap();
+        let (payer, ix) = instruction(&[
+            "create",
+            "--name",
+            "Final",
+            "--description",
+            "Who wins",
+            "--start",
+            "100",
+            "--end",
+            "200",
+            "--deadline",
+            "150",
+            "--private",
+            "--id",
+            EVENT_ID,
+        ]);
+
+        assert_eq!(
+            ix,
+            ix::create_event(
+                &payer,
+                event_id,
+                CreateEventArgs {
+                    name: to_fixed("Final").unwrap(),
+                    is_private: true,
+                    description: to_fixed("Who wins").unwrap(),
+                    start_date: 100,
+                    end_date: 200,
+                    participation_deadline: Some(150),
+                },
+            )
+        );
+    }
+
+    #[test]
+    fn event_id_must_be_uuid_v4() {
+        // Version 1 UUID
+        assert!(parse(&["event", "publish", "6fa459ea-ee8a-11ed-a05b-0242ac120003"]).is_err());
+        assert!(parse(&["event", "publish", "not-a-uuid"]).is_err());
+        assert!(parse(&["event", "publish", EVENT_ID]).is_ok());
+    }
+
+    #[test]
+    fn flags_map_to_instruction_arguments() {
+        let event_id = parse_event_id(EVENT_ID).unwrap();
+
+        let (payer, ix) = instruction(&["complete", EVENT_ID, "2", "--as-organizer"]);
+        assert_eq!(ix, ix::complete_event(&payer, event_id, true, 2));
+
+        let (payer, ix) = instruction(&["complete", EVENT_ID, "2"]);
+        assert_eq!(ix, ix::complete_event(&payer, event_id, false, 2));
+
+        let (payer, ix) = instruction(&["freeze", EVENT_ID]);
+        assert_eq!(ix, ix::set_event_frozen(&payer, event_id, true));
+
+        let (payer, ix) = instruction(&["freeze", EVENT_ID, "--unfreeze"]);
+        assert_eq!(ix, ix::set_event_frozen(&payer, event_id, false));
+
+        let (payer, ix) = instruction(&["moderate", EVENT_ID, "--show"]);
+        assert_eq!(ix, ix::moderate_event(&payer, event_id, false));
+
+        let (payer, ix) = instruction(&["update-deadline", EVENT_ID]);
+        assert_eq!(
+            ix,
+            ix::update_event_participation_deadline(&payer, event_id, false, None)
+        );
+    }
+}
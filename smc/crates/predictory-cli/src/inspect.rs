@@ -0,0 +1,162 @@
+use clap::Subcommand;
+use predictory_client::{
+    accounts, pda,
+    predictory::state::event::Event,
+    views::{
+        AccountView, EventMetaView, EventOptionView, EventView, ParticipationView, PortfolioView,
+        ProposalView, StateView, TreasuryView, UserView,
+    },
+};
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{args, output, Context};
+
+#[derive(Subcommand)]
+pub enum InspectCommand {
+    /// Decode any program account by address
+    Account {
+        #[arg(value_parser = args::pubkey)]
+        address: Pubkey,
+    },
+    /// Contract state
+    State,
+    /// Treasury split and totals
+    Treasury,
+    /// User account of an owner, the signer by default
+    User {
+        #[arg(value_parser = args::pubkey)]
+        owner: Option<Pubkey>,
+    },
+    /// Event with its metadata and options
+    Event {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+    },
+    /// Every event of the program
+    Events,
+    /// Participation of a user, the signer by default
+    Participation {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+        #[arg(long, value_parser = args::pubkey)]
+        user: Option<Pubkey>,
+    },
+    /// Portfolio page of an owner, the signer by default
+    Portfolio {
+        #[arg(default_value_t = 0)]
+        page: u16,
+        #[arg(long, value_parser = args::pubkey)]
+        owner: Option<Pubkey>,
+    },
+    /// Governance proposal
+    Proposal { proposal_id: u64 },
+}
+
+#[derive(Serialize)]
+struct AddressedAccount {
+    address: String,
+    #[serde(flatten)]
+    account: AccountView,
+}
+
+#[derive(Serialize)]
+struct EventDetails {
+    address: String,
+    event: EventView,
+    meta: EventMetaView,
+    options: Vec<EventOptionView>,
+}
+
+pub fn run(context: &mut Context, command: InspectCommand) -> anyhow::Result<()> {
+    let rpc = &context.rpc;
+    let json = context.json;
+
+    match command {
+        InspectCommand::Account { address } => {
+            let account = accounts::fetch_any(rpc, &address)?;
+
+            output::print(
+                &AddressedAccount {
+                    address: address.to_string(),
+                    account: AccountView::from(&account),
+                },
+                json,
+            )
+        }
+        InspectCommand::State => {
+            output::print(&StateView::from(&accounts::fetch_state(rpc)?), json)
+        }
+        InspectCommand::Treasury => {
+            output::print(&TreasuryView::from(&accounts::fetch_treasury(rpc)?), json)
+        }
+        InspectCommand::User { owner } => {
+            let owner = owner_or_signer(context, owner)?;
+            let user = accounts::fetch_user(&context.rpc, &owner)?;
+
+            output::print(&UserView::from(&user), json)
+        }
+        InspectCommand::Event { event_id } => {
+            let event = accounts::fetch_event(rpc, event_id)?;
+            let meta = accounts::fetch_event_meta(rpc, event_id)?;
+            let table = (event.options_table != 0)
+                .then(|| accounts::fetch_options_table(rpc, event_id))
+                .transpose()?;
+            let options = (0..event.option_count)
+                .map(|index| {
+                    let meta = accounts::fetch_event_option_meta(rpc, event_id, index)?;
+
+                    Ok(match &table {
+                        Some(table) => EventOptionView::from_table(table, &meta),
+                        None => EventOptionView::with_meta(
+                            &accounts::fetch_event_option(rpc, event_id, index)?,
+                            &meta,
+                        ),
+                    })
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+
+            output::print(
+                &EventDetails {
+                    address: pda::event_address(event_id).to_string(),
+                    event: EventView::from(&event),
+                    meta: EventMetaView::from(&meta),
+                    options,
+                },
+                json,
+            )
+        }
+        InspectCommand::Events => {
+            let events: Vec<EventView> = accounts::fetch_all_versioned::<Event>(rpc)?
+                .iter()
+                .map(|(_, event)| EventView::from(event))
+                .collect();
+
+            output::print(&events, json)
+        }
+        InspectCommand::Participation { event_id, user } => {
+            let user = owner_or_signer(context, user)?;
+            let participation = accounts::fetch_participation(&context.rpc, event_id, &user)?;
+
+            output::print(&ParticipationView::from(&participation), json)
+        }
+        InspectCommand::Portfolio { page, owner } => {
+            let owner = owner_or_signer(context, owner)?;
+            let portfolio = accounts::fetch_portfolio(&context.rpc, &owner, page)?;
+
+            output::print(&PortfolioView::from(&portfolio), json)
+        }
+        InspectCommand::Proposal { proposal_id } => {
+            let proposal = accounts::fetch_proposal(rpc, proposal_id)?;
+
+            output::print(&ProposalView::from(&proposal), json)
+        }
+    }
+}
+
+fn owner_or_signer(context: &mut Context, owner: Option<Pubkey>) -> anyhow::Result<Pubkey> {
+    match owner {
+        Some(owner) => Ok(owner),
+        None => context.payer(),
+    }
+}
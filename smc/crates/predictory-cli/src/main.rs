@@ -0,0 +1,204 @@
+//! Command-line tool for operating the Predictory program.
+
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use clap::{Parser, Subcommand};
+use predictory_client::rpc::{RpcClient, LOCALNET_URL};
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signer},
+};
+
+mod admin;
+mod args;
+mod event;
+mod inspect;
+mod output;
+mod user;
+
+#[derive(Parser)]
+#[command(name = "predictory", version, about = "Operate the Predictory program")]
+struct Cli {
+    /// RPC endpoint
+    #[arg(long, global = true, env = "ANCHOR_PROVIDER_URL", default_value = LOCALNET_URL)]
+    url: String,
+
+    /// Keypair file used to sign and pay for transactions
+    #[arg(long, global = true, env = "ANCHOR_WALLET")]
+    keypair: Option<PathBuf>,
+
+    /// Print results as JSON
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Contract state, authority and pause flags
+    #[command(subcommand)]
+    State(admin::StateCommand),
+
+    /// Timelocked protocol parameter changes
+    #[command(subcommand)]
+    Param(admin::ParamCommand),
+
+    /// Role registry
+    #[command(subcommand)]
+    Role(admin::RoleCommand),
+
+    /// Governance proposals
+    #[command(subcommand)]
+    Proposal(admin::ProposalCommand),
+
+    /// Protocol treasury
+    #[command(subcommand)]
+    Treasury(admin::TreasuryCommand),
+
+    /// Account layout migrations
+    #[command(subcommand)]
+    Migrate(admin::MigrateCommand),
+
+    /// User account, stake and portfolio pages
+    #[command(subcommand)]
+    User(user::UserCommand),
+
+    /// Event lifecycle
+    #[command(subcommand)]
+    Event(event::EventCommand),
+
+    /// Event options
+    #[command(subcommand)]
+    Option(event::OptionCommand),
+
+    /// Event co-organizers
+    #[command(subcommand)]
+    Organizer(event::OrganizerCommand),
+
+    /// Participation: vote, claim, recharge, appeal and burn trust
+    #[command(flatten)]
+    Participation(user::ParticipationCommand),
+
+    /// Read-only quotes and odds
+    #[command(subcommand)]
+    Quote(user::QuoteCommand),
+
+    /// Decode program accounts
+    #[command(subcommand)]
+    Inspect(inspect::InspectCommand),
+
+    /// Request an airdrop from a local validator
+    Airdrop {
+        /// Amount in lamports
+        lamports: u64,
+
+        /// Recipient, defaults to the signer
+        #[arg(long, value_parser = args::pubkey)]
+        to: Option<Pubkey>,
+    },
+}
+
+pub struct Context {
+    pub rpc: RpcClient,
+    pub json: bool,
+    keypair_path: Option<PathBuf>,
+    keypair: Option<Keypair>,
+}
+
+impl Context {
+    /// Loads the signer keypair on first use, so read-only commands work without one
+    pub fn signer(&mut self) -> anyhow::Result<&Keypair> {
+        if self.keypair.is_none() {
+            let path = match &self.keypair_path {
+                Some(path) => path.clone(),
+                None => default_keypair_path()?,
+            };
+            let keypair = read_keypair_file(&path)
+                .map_err(|error| anyhow!("failed to read keypair {}: {error}", path.display()))?;
+
+            self.keypair = Some(keypair);
+        }
+
+        Ok(self.keypair.as_ref().expect("keypair is loaded"))
+    }
+
+    pub fn payer(&mut self) -> anyhow::Result<Pubkey> {
+        Ok(self.signer()?.pubkey())
+    }
+
+    /// Signs the instructions with the signer keypair, sends them and prints the signature
+    pub fn send(&mut self, instructions: Vec<Instruction>) -> anyhow::Result<()> {
+        let json = self.json;
+        let keypair = self.signer()?.insecure_clone();
+        let signature = self.rpc.send_instructions(&instructions, &[&keypair])?;
+
+        output::print_signature(&signature, json)
+    }
+}
+
+#[cfg(test)]
+impl Context {
+    /// Context signing with a new keypair, for commands that don't read accounts
+    fn offline() -> Self {
+        Context {
+            rpc: RpcClient::new(LOCALNET_URL),
+            json: true,
+            keypair_path: None,
+            keypair: Some(Keypair::new()),
+        }
+    }
+}
+
+/// Parses a command line, without the program name
+#[cfg(test)]
+fn parse(args: &[&str]) -> Result<Command, clap::Error> {
+    Cli::try_parse_from(std::iter::once("predictory").chain(args.iter().copied()))
+        .map(|cli| cli.command)
+}
+
+fn default_keypair_path() -> anyhow::Result<PathBuf> {
+    let home = std::env::var_os("HOME").ok_or_else(|| anyhow!("HOME is not set"))?;
+
+    Ok(PathBuf::from(home).join(".config/solana/id.json"))
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    let mut context = Context {
+        rpc: RpcClient::new(cli.url),
+        json: cli.json,
+        keypair_path: cli.keypair,
+        keypair: None,
+    };
+
+    match cli.command {
+        Command::State(command) => admin::run_state(&mut context, command),
+        Command::Param(command) => admin::run_param(&mut context, command),
+        Command::Role(command) => admin::run_role(&mut context, command),
+        Command::Proposal(command) => admin::run_proposal(&mut context, command),
+        Command::Treasury(command) => admin::run_treasury(&mut context, command),
+        Command::Migrate(command) => admin::run_migrate(&mut context, command),
+        Command::User(command) => user::run_user(&mut context, command),
+        Command::Event(command) => event::run_event(&mut context, command),
+        Command::Option(command) => event::run_option(&mut context, command),
+        Command::Organizer(command) => event::run_organizer(&mut context, command),
+        Command::Participation(command) => user::run_participation(&mut context, command),
+        Command::Quote(command) => user::run_quote(&mut context, command),
+        Command::Inspect(command) => inspect::run(&mut context, command),
+        Command::Airdrop { lamports, to } => {
+            let recipient = match to {
+                Some(recipient) => recipient,
+                None => context.payer()?,
+            };
+            let signature = context.rpc.request_airdrop(&recipient, lamports)?;
+            context.rpc.confirm_transaction(&signature)?;
+
+            output::print_signature(&signature, context.json)
+        }
+    }
+}
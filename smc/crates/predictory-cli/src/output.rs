@@ -0,0 +1,69 @@
+use serde::Serialize;
+use serde_json::Value;
+use solana_sdk::signature::Signature;
+
+/// Prints a value as pretty JSON or as an aligned `field: value` listing
+pub fn print(value: &impl Serialize, json: bool) -> anyhow::Result<()> {
+    let value = serde_json::to_value(value)?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&value)?);
+    } else {
+        print_value(&value, 0);
+    }
+
+    Ok(())
+}
+
+pub fn print_signature(signature: &Signature, json: bool) -> anyhow::Result<()> {
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({ "signature": signature.to_string() })
+        );
+    } else {
+        println!("Signature: {signature}");
+    }
+
+    Ok(())
+}
+
+fn print_value(value: &Value, indent: usize) {
+    let padding = " ".repeat(indent);
+
+    match value {
+        Value::Object(fields) => {
+            let width = fields.keys().map(String::len).max().unwrap_or_default();
+
+            for (field, value) in fields {
+                match value {
+                    Value::Object(_) | Value::Array(_) => {
+                        println!("{padding}{field}:");
+                        print_value(value, indent + 2);
+                    }
+                    _ => println!("{padding}{field:width$}  {}", scalar(value)),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                match item {
+                    Value::Object(_) | Value::Array(_) => {
+                        println!("{padding}[{index}]");
+                        print_value(item, indent + 2);
+                    }
+                    _ => println!("{padding}[{index}] {}", scalar(item)),
+                }
+            }
+        }
+        _ => println!("{padding}{}", scalar(value)),
+    }
+}
+
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::Null => "-".to_string(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
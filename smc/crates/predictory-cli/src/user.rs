@@ -0,0 +1,372 @@
+use anyhow::{anyhow, bail};
+use clap::Subcommand;
+use predictory_client::{
+    accounts,
+    instructions::{self as ix, ClaimPosition, SettlementPosition},
+    pda,
+    predictory::{
+        context::{ClaimQuote, ClaimResult, Odds, VoteQuote},
+        state::{participation::Participation, portfolio::Portfolio},
+    },
+    text::to_fixed,
+    views::{ClaimQuoteView, ClaimResultView, OddsView, VoteQuoteView},
+};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+use crate::{args, output, Context};
+
+#[derive(Subcommand)]
+pub enum UserCommand {
+    /// Create the signer's user account
+    Create { name: String },
+    /// Deposit stake, in lamports
+    Deposit { lamports: u64 },
+    /// Withdraw stake, in lamports; withdraws the whole free stake when omitted
+    Withdraw { lamports: Option<u64> },
+    /// Create a portfolio page
+    CreatePage { page: u16 },
+    /// Close an empty portfolio page
+    ClosePage { page: u16 },
+    /// Refresh the position statuses of a portfolio page, the signer's by default
+    RefreshPage {
+        page: u16,
+        #[arg(long, value_parser = args::pubkey)]
+        owner: Option<Pubkey>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ParticipationCommand {
+    /// Vote for an event option
+    Vote {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+        option: u8,
+        /// Amount in lamports
+        amount: u64,
+        /// Portfolio page to record the position on; the first page with
+        /// free space is used, or created, when omitted
+        #[arg(long)]
+        page: Option<u16>,
+    },
+    /// Claim the reward of a completed event
+    Claim {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+    },
+    /// Claim every resolved position of the signer
+    ClaimAll {
+        /// Positions claimed per transaction
+        #[arg(long, default_value_t = 6)]
+        page_size: usize,
+    },
+    /// Settle the unclaimed positions of a completed event for their owners
+    Settle {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+        /// Positions settled per transaction
+        #[arg(long, default_value_t = 7)]
+        page_size: usize,
+    },
+    /// Get the deposit back from a canceled event
+    Recharge {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+    },
+    /// Appeal the result of a completed event
+    Appeal {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+    },
+    /// Burn trust gained on a losing position
+    BurnTrust {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum QuoteCommand {
+    /// Quote the payout of a vote
+    Vote {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+        option: u8,
+        amount: u64,
+    },
+    /// Quote the claim of a participant, the signer by default
+    Claim {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+        #[arg(long, value_parser = args::pubkey)]
+        user: Option<Pubkey>,
+    },
+    /// Show the current odds of every option
+    Odds {
+        #[arg(value_parser = args::event_id)]
+        event_id: u128,
+    },
+}
+
+pub fn run_user(context: &mut Context, command: UserCommand) -> anyhow::Result<()> {
+    let payer = context.payer()?;
+
+    let instruction = match command {
+        UserCommand::Create { name } => ix::create_user(&payer, to_fixed(&name)?),
+        UserCommand::Deposit { lamports } => ix::transfer_stake(&payer, lamports),
+        UserCommand::Withdraw { lamports } => ix::withdraw_stake(&payer, lamports),
+        UserCommand::CreatePage { page } => ix::create_portfolio_page(&payer, page),
+        UserCommand::ClosePage { page } => ix::close_portfolio_page(&payer, page),
+        UserCommand::RefreshPage { page, owner } => {
+            let owner = owner.unwrap_or(payer);
+            let event_ids: Vec<u128> = accounts::fetch_portfolio(&context.rpc, &owner, page)?
+                .entries
+                .iter()
+                .map(|entry| entry.event_id)
+                .collect();
+
+            if event_ids.is_empty() {
+                bail!("portfolio page has no positions");
+            }
+
+            ix::refresh_portfolio(&payer, owner, page, &event_ids)
+        }
+    };
+
+    context.send(vec![instruction])
+}
+
+pub fn run_participation(
+    context: &mut Context,
+    command: ParticipationCommand,
+) -> anyhow::Result<()> {
+    let payer = context.payer()?;
+
+    let instructions = match command {
+        ParticipationCommand::Vote {
+            event_id,
+            option,
+            amount,
+            page,
+        } => {
+            let (page, mut instructions) = match page {
+                Some(page) => (page, vec![]),
+                None => portfolio_page_with_space(context, &payer)?,
+            };
+
+            let event = accounts::fetch_event(&context.rpc, event_id)?;
+
+            instructions.push(ix::vote(
+                &payer,
+                event_id,
+                option,
+                event.options_table != 0,
+                amount,
+                page,
+            ));
+            instructions
+        }
+        ParticipationCommand::Claim { event_id } => {
+            let event = accounts::fetch_event(&context.rpc, event_id)?;
+            let participation = accounts::fetch_participation(&context.rpc, event_id, &payer)?;
+
+            vec![ix::claim_event_reward(
+                &payer,
+                event_id,
+                &event.authority,
+                participation.option,
+                event.options_table != 0,
+                participation.portfolio_page,
+            )]
+        }
+        ParticipationCommand::ClaimAll { page_size } => {
+            return claim_all(context, &payer, page_size)
+        }
+        ParticipationCommand::Settle {
+            event_id,
+            page_size,
+        } => return settle(context, &payer, event_id, page_size),
+        ParticipationCommand::Recharge { event_id } => {
+            let participation = accounts::fetch_participation(&context.rpc, event_id, &payer)?;
+
+            vec![ix::recharge(&payer, event_id, participation.portfolio_page)]
+        }
+        ParticipationCommand::Appeal { event_id } => {
+            let event = accounts::fetch_event(&context.rpc, event_id)?;
+            let participation = accounts::fetch_participation(&context.rpc, event_id, &payer)?;
+
+            vec![ix::appeal(
+                &payer,
+                event_id,
+                &event.authority,
+                participation.option,
+                event.options_table != 0,
+                participation.portfolio_page,
+            )]
+        }
+        ParticipationCommand::BurnTrust { event_id } => {
+            let participation = accounts::fetch_participation(&context.rpc, event_id, &payer)?;
+
+            vec![ix::burn_trust(
+                &payer,
+                event_id,
+                participation.portfolio_page,
+            )]
+        }
+    };
+
+    context.send(instructions)
+}
+
+/// Sends `claim_many` transactions over the signer's unclaimed positions in resolved events,
+/// printing the per-event results returned by the simulation of each batch
+fn claim_all(context: &mut Context, payer: &Pubkey, page_size: usize) -> anyhow::Result<()> {
+    let mut positions = vec![];
+
+    for (_, participation) in accounts::fetch_all_versioned::<Participation>(&context.rpc)? {
+        if participation.payer != *payer || participation.is_claimed != 0 {
+            continue;
+        }
+
+        let event = accounts::fetch_event(&context.rpc, participation.event_id())?;
+
+        if event.result().is_some() {
+            positions.push(ClaimPosition {
+                event_id: participation.event_id(),
+                option: participation.option,
+                options_table: event.options_table != 0,
+                portfolio_page: participation.portfolio_page,
+            });
+        }
+    }
+
+    if positions.is_empty() {
+        return Err(anyhow!("no position left to claim"));
+    }
+
+    for page in positions.chunks(page_size.max(1)) {
+        let instruction = ix::claim_many(payer, page);
+        let results: Vec<ClaimResult> = context.rpc.view(instruction.clone(), payer)?;
+
+        context.send(vec![instruction])?;
+
+        let views: Vec<ClaimResultView> = results.iter().map(ClaimResultView::from).collect();
+        output::print(&views, context.json)?;
+    }
+
+    Ok(())
+}
+
+/// Sends `settle_batch` transactions until every settleable position of the event is paid out
+fn settle(
+    context: &mut Context,
+    payer: &Pubkey,
+    event_id: u128,
+    page_size: usize,
+) -> anyhow::Result<()> {
+    let event = accounts::fetch_event(&context.rpc, event_id)?;
+    let result = event
+        .result()
+        .ok_or_else(|| anyhow!("event has no result yet"))?;
+    if event.disputed != 0 {
+        bail!("event result is disputed");
+    }
+
+    let positions: Vec<SettlementPosition> =
+        accounts::fetch_all_versioned::<Participation>(&context.rpc)?
+            .into_iter()
+            .map(|(_, participation)| participation)
+            .filter(|participation| {
+                participation.event_id() == event_id && participation.is_claimed == 0
+            })
+            .map(|participation| SettlementPosition {
+                participant: participation.payer,
+                portfolio_page: participation.portfolio_page,
+            })
+            .collect();
+
+    if positions.is_empty() {
+        return Err(anyhow!("no position left to settle"));
+    }
+
+    for page in positions.chunks(page_size.max(1)) {
+        context.send(vec![ix::settle_batch(
+            payer,
+            event_id,
+            &event.authority,
+            result,
+            event.options_table != 0,
+            page,
+        )])?;
+    }
+
+    Ok(())
+}
+
+/// Returns the first portfolio page with free space, creating a new page if all are full
+fn portfolio_page_with_space(
+    context: &Context,
+    owner: &Pubkey,
+) -> anyhow::Result<(u16, Vec<Instruction>)> {
+    for page in 0..=u16::MAX {
+        let Some(account) = context
+            .rpc
+            .get_account(&pda::portfolio_address(owner, page))?
+        else {
+            return Ok((page, vec![ix::create_portfolio_page(owner, page)]));
+        };
+
+        let portfolio: Portfolio = accounts::decode(&account.data)?;
+
+        if !portfolio.is_full() {
+            return Ok((page, vec![]));
+        }
+    }
+
+    Err(anyhow!("all portfolio pages are full"))
+}
+
+pub fn run_quote(context: &mut Context, command: QuoteCommand) -> anyhow::Result<()> {
+    let payer = context.payer()?;
+
+    match command {
+        QuoteCommand::Vote {
+            event_id,
+            option,
+            amount,
+        } => {
+            let event = accounts::fetch_event(&context.rpc, event_id)?;
+            let quote: VoteQuote = context.rpc.view(
+                ix::quote_vote(event_id, option, event.options_table != 0, amount),
+                &payer,
+            )?;
+
+            output::print(&VoteQuoteView::from(&quote), context.json)
+        }
+        QuoteCommand::Claim { event_id, user } => {
+            let user = user.unwrap_or(payer);
+            let event = accounts::fetch_event(&context.rpc, event_id)?;
+            let participation = accounts::fetch_participation(&context.rpc, event_id, &user)?;
+            let quote: ClaimQuote = context.rpc.view(
+                ix::quote_claim(
+                    event_id,
+                    user,
+                    participation.option,
+                    event.options_table != 0,
+                ),
+                &payer,
+            )?;
+
+            output::print(&ClaimQuoteView::from(&quote), context.json)
+        }
+        QuoteCommand::Odds { event_id } => {
+            let event = accounts::fetch_event(&context.rpc, event_id)?;
+            let odds: Odds = context.rpc.view(
+                ix::event_odds(event_id, event.option_count, event.options_table != 0),
+                &payer,
+            )?;
+
+            output::print(&OddsView::from(&odds), context.json)
+        }
+    }
+}
@@ -0,0 +1,357 @@
+//! Account decoding and fetching.
+//!
+//! Versioned accounts are decoded through `Versioned::from_legacy`, so
+//! accounts that have not been migrated yet read the same as current ones.
+
+use anchor_lang::{prelude::Pubkey, AccountDeserialize, Discriminator};
+use predictory::state::{
+    appeal::Appellation,
+    attestation::ResolutionAttestation,
+    callback::EventCallback,
+    contract_state::State,
+    event::{Event, EventMeta},
+    governance::{Proposal, ProposalVote},
+    legacy::Versioned,
+    option::{EventOption, EventOptionMeta, OptionsTable},
+    organizer::EventOrganizer,
+    param::ParamChange,
+    participation::Participation,
+    portfolio::Portfolio,
+    role::RoleMember,
+    treasury::Treasury,
+    user::User,
+    DISCRIMINATOR_LENGTH,
+};
+
+use crate::{pda, rpc::RpcClient, ClientError, Result, PROGRAM_ID};
+
+/// Decodes an account with a single layout
+pub fn decode<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    Ok(T::try_deserialize(&mut &data[..])?)
+}
+
+/// Decodes a versioned account, upgrading legacy layouts in memory
+pub fn decode_versioned<T: Versioned>(data: &[u8]) -> Result<T> {
+    if data.len() <= DISCRIMINATOR_LENGTH || !data.starts_with(T::DISCRIMINATOR) {
+        return Err(ClientError::Decode("discriminator mismatch".to_string()));
+    }
+
+    match data[DISCRIMINATOR_LENGTH] {
+        version if version == T::CURRENT_VERSION => decode(data),
+        version => Ok(T::from_legacy(version, &mut &data[DISCRIMINATOR_LENGTH..])?),
+    }
+}
+
+/// Returns the layout version stored in a versioned account
+pub fn account_version(data: &[u8]) -> Option<u8> {
+    data.get(DISCRIMINATOR_LENGTH).copied()
+}
+
+fn fetch_data(rpc: &RpcClient, address: &Pubkey) -> Result<Vec<u8>> {
+    let account = rpc
+        .get_account(address)?
+        .ok_or_else(|| ClientError::AccountNotFound(address.to_string()))?;
+
+    if account.owner != PROGRAM_ID {
+        return Err(ClientError::Decode(format!(
+            "account {address} is owned by {}",
+            account.owner
+        )));
+    }
+
+    Ok(account.data)
+}
+
+pub fn fetch<T: AccountDeserialize>(rpc: &RpcClient, address: &Pubkey) -> Result<T> {
+    decode(&fetch_data(rpc, address)?)
+}
+
+pub fn fetch_versioned<T: Versioned>(rpc: &RpcClient, address: &Pubkey) -> Result<T> {
+    decode_versioned(&fetch_data(rpc, address)?)
+}
+
+/// Fetches every program account of type `T`
+pub fn fetch_all<T: AccountDeserialize + Discriminator>(
+    rpc: &RpcClient,
+) -> Result<Vec<(Pubkey, T)>> {
+    rpc.get_program_accounts(&PROGRAM_ID, T::DISCRIMINATOR)?
+        .into_iter()
+        .map(|(address, account)| Ok((address, decode(&account.data)?)))
+        .collect()
+}
+
+/// Fetches every program account of versioned type `T`, whatever its layout
+pub fn fetch_all_versioned<T: Versioned>(rpc: &RpcClient) -> Result<Vec<(Pubkey, T)>> {
+    rpc.get_program_accounts(&PROGRAM_ID, T::DISCRIMINATOR)?
+        .into_iter()
+        .map(|(address, account)| Ok((address, decode_versioned(&account.data)?)))
+        .collect()
+}
+
+pub fn fetch_state(rpc: &RpcClient) -> Result<State> {
+    fetch_versioned(rpc, &pda::state_address())
+}
+
+pub fn fetch_treasury(rpc: &RpcClient) -> Result<Treasury> {
+    fetch(rpc, &pda::treasury_address())
+}
+
+pub fn fetch_user(rpc: &RpcClient, owner: &Pubkey) -> Result<User> {
+    fetch_versioned(rpc, &pda::user_address(owner))
+}
+
+pub fn fetch_event(rpc: &RpcClient, event_id: u128) -> Result<Event> {
+    fetch_versioned(rpc, &pda::event_address(event_id))
+}
+
+pub fn fetch_event_meta(rpc: &RpcClient, event_id: u128) -> Result<EventMeta> {
+    fetch_versioned(rpc, &pda::event_meta_address(event_id))
+}
+
+pub fn fetch_event_option(rpc: &RpcClient, event_id: u128, index: u8) -> Result<EventOption> {
+    fetch_versioned(rpc, &pda::option_address(event_id, index))
+}
+
+pub fn fetch_event_option_meta(
+    rpc: &RpcClient,
+    event_id: u128,
+    index: u8,
+) -> Result<EventOptionMeta> {
+    fetch(rpc, &pda::option_meta_address(event_id, index))
+}
+
+pub fn fetch_options_table(rpc: &RpcClient, event_id: u128) -> Result<OptionsTable> {
+    fetch(rpc, &pda::options_table_address(event_id))
+}
+
+pub fn fetch_attestation(rpc: &RpcClient, event_id: u128) -> Result<ResolutionAttestation> {
+    fetch(rpc, &pda::attestation_address(event_id))
+}
+
+pub fn fetch_event_callback(rpc: &RpcClient, event_id: u128) -> Result<EventCallback> {
+    fetch(rpc, &pda::callback_address(event_id))
+}
+
+pub fn fetch_participation(
+    rpc: &RpcClient,
+    event_id: u128,
+    participant: &Pubkey,
+) -> Result<Participation> {
+    fetch_versioned(rpc, &pda::participation_address(event_id, participant))
+}
+
+pub fn fetch_appellation(rpc: &RpcClient, event_id: u128) -> Result<Appellation> {
+    fetch_versioned(rpc, &pda::appeal_address(event_id))
+}
+
+pub fn fetch_portfolio(rpc: &RpcClient, owner: &Pubkey, page: u16) -> Result<Portfolio> {
+    fetch(rpc, &pda::portfolio_address(owner, page))
+}
+
+pub fn fetch_organizer(rpc: &RpcClient, event_id: u128, member: &Pubkey) -> Result<EventOrganizer> {
+    fetch(rpc, &pda::organizer_address(event_id, member))
+}
+
+/// Co-organizers of an event, found by scanning every organizer account
+pub fn fetch_event_organizers(rpc: &RpcClient, event_id: u128) -> Result<Vec<EventOrganizer>> {
+    Ok(fetch_all::<EventOrganizer>(rpc)?
+        .into_iter()
+        .map(|(_, organizer)| organizer)
+        .filter(|organizer| organizer.event_id == event_id)
+        .collect())
+}
+
+pub fn fetch_proposal(rpc: &RpcClient, proposal_id: u64) -> Result<Proposal> {
+    fetch(rpc, &pda::proposal_address(proposal_id))
+}
+
+/// Any account owned by the program
+pub enum ProgramAccount {
+    State(State),
+    Treasury(Treasury),
+    User(User),
+    Event(Event),
+    EventMeta(EventMeta),
+    EventOption(EventOption),
+    EventOptionMeta(EventOptionMeta),
+    OptionsTable(OptionsTable),
+    EventCallback(EventCallback),
+    ResolutionAttestation(ResolutionAttestation),
+    EventOrganizer(EventOrganizer),
+    Participation(Participation),
+    Appellation(Appellation),
+    Portfolio(Portfolio),
+    RoleMember(RoleMember),
+    ParamChange(ParamChange),
+    Proposal(Proposal),
+    ProposalVote(ProposalVote),
+}
+
+impl ProgramAccount {
+    /// Decodes an account by its discriminator
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let discriminator = data
+            .get(..DISCRIMINATOR_LENGTH)
+            .ok_or_else(|| ClientError::Decode("account data too short".to_string()))?;
+
+        let account = match discriminator {
+            d if d == State::DISCRIMINATOR => Self::State(decode_versioned(data)?),
+            d if d == Treasury::DISCRIMINATOR => Self::Treasury(decode(data)?),
+            d if d == User::DISCRIMINATOR => Self::User(decode_versioned(data)?),
+            d if d == Event::DISCRIMINATOR => Self::Event(decode_versioned(data)?),
+            d if d == EventMeta::DISCRIMINATOR => Self::EventMeta(decode_versioned(data)?),
+            d if d == EventOption::DISCRIMINATOR => Self::EventOption(decode_versioned(data)?),
+            d if d == EventOptionMeta::DISCRIMINATOR => Self::EventOptionMeta(decode(data)?),
+            d if d == OptionsTable::DISCRIMINATOR => Self::OptionsTable(decode(data)?),
+            d if d == EventCallback::DISCRIMINATOR => Self::EventCallback(decode(data)?),
+            d if d == ResolutionAttestation::DISCRIMINATOR => {
+                Self::ResolutionAttestation(decode(data)?)
+            }
+            d if d == EventOrganizer::DISCRIMINATOR => Self::EventOrganizer(decode(data)?),
+            d if d == Participation::DISCRIMINATOR => Self::Participation(decode_versioned(data)?),
+            d if d == Appellation::DISCRIMINATOR => Self::Appellation(decode_versioned(data)?),
+            d if d == Portfolio::DISCRIMINATOR => Self::Portfolio(decode(data)?),
+            d if d == RoleMember::DISCRIMINATOR => Self::RoleMember(decode(data)?),
+            d if d == ParamChange::DISCRIMINATOR => Self::ParamChange(decode(data)?),
+            d if d == Proposal::DISCRIMINATOR => Self::Proposal(decode(data)?),
+            d if d == ProposalVote::DISCRIMINATOR => Self::ProposalVote(decode(data)?),
+            _ => {
+                return Err(ClientError::Decode(
+                    "unknown account discriminator".to_string(),
+                ))
+            }
+        };
+
+        Ok(account)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::State(_) => "State",
+            Self::Treasury(_) => "Treasury",
+            Self::User(_) => "User",
+            Self::Event(_) => "Event",
+            Self::EventMeta(_) => "EventMeta",
+            Self::EventOption(_) => "EventOption",
+            Self::EventOptionMeta(_) => "EventOptionMeta",
+            Self::OptionsTable(_) => "OptionsTable",
+            Self::EventCallback(_) => "EventCallback",
+            Self::ResolutionAttestation(_) => "ResolutionAttestation",
+            Self::EventOrganizer(_) => "EventOrganizer",
+            Self::Participation(_) => "Participation",
+            Self::Appellation(_) => "Appellation",
+            Self::Portfolio(_) => "Portfolio",
+            Self::RoleMember(_) => "RoleMember",
+            Self::ParamChange(_) => "ParamChange",
+            Self::Proposal(_) => "Proposal",
+            Self::ProposalVote(_) => "ProposalVote",
+        }
+    }
+}
+
+/// Fetches and decodes any program account
+pub fn fetch_any(rpc: &RpcClient, address: &Pubkey) -> Result<ProgramAccount> {
+    ProgramAccount::decode(&fetch_data(rpc, address)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::AccountSerialize;
+
+    use super::*;
+
+    const EVENT_ID: u128 = 0x0123_4567_89ab_4cde_8f01_2345_6789_abcd;
+
+    fn encode<T: AccountSerialize>(account: &T) -> Vec<u8> {
+        let mut data = vec![];
+        account.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    /// Zero-copy account with every field zeroed, as on creation
+    fn zeroed<T: Versioned>() -> T {
+        let mut data = T::DISCRIMINATOR.to_vec();
+        data.resize(T::SPACE, 0);
+        decode(&data).unwrap()
+    }
+
+    #[test]
+    fn event_round_trip() {
+        let authority = Pubkey::new_unique();
+        let mut event: Event = zeroed();
+        event.version = Event::VERSION;
+        event.id = EVENT_ID.to_le_bytes();
+        event.authority = authority;
+        event.end_date = 1_700_000_000;
+        event.total_amount = 9_000;
+        event.options_table = 1;
+        event.set_result(2);
+
+        let data = encode(&event);
+        assert_eq!(data.len(), Event::LEN);
+
+        let decoded: Event = decode_versioned(&data).unwrap();
+        assert_eq!(decoded.id(), EVENT_ID);
+        assert_eq!(decoded.authority, authority);
+        assert_eq!(decoded.end_date, 1_700_000_000);
+        assert_eq!(decoded.total_amount, 9_000);
+        assert_eq!(decoded.options_table, 1);
+        assert_eq!(decoded.result(), Some(2));
+        assert_eq!(account_version(&data), Some(Event::VERSION));
+    }
+
+    #[test]
+    fn participation_round_trip() {
+        let payer = Pubkey::new_unique();
+        let mut participation: Participation = zeroed();
+        participation.version = Participation::VERSION;
+        participation.event_id = EVENT_ID.to_le_bytes();
+        participation.payer = payer;
+        participation.option = 1;
+        participation.deposited_amount = 800;
+        participation.is_claimed = 1;
+        participation.portfolio_page = 5;
+
+        let decoded: Participation = decode_versioned(&encode(&participation)).unwrap();
+        assert_eq!(decoded.event_id(), EVENT_ID);
+        assert_eq!(decoded.payer, payer);
+        assert_eq!(decoded.option, 1);
+        assert_eq!(decoded.deposited_amount, 800);
+        assert_eq!(decoded.is_claimed, 1);
+        assert_eq!(decoded.appealed, 0);
+        assert_eq!(decoded.portfolio_page, 5);
+    }
+
+    #[test]
+    fn legacy_state_is_upgraded() {
+        let authority = Pubkey::new_unique();
+        let mut data = State::DISCRIMINATOR.to_vec();
+        data.push(1);
+        data.extend_from_slice(authority.as_ref());
+        [2u64, 1_000, 33, 44]
+            .iter()
+            .for_each(|value| data.extend_from_slice(&value.to_le_bytes()));
+
+        let state: State = decode_versioned(&data).unwrap();
+        assert_eq!(state.version, State::VERSION);
+        assert_eq!(state.authority, authority);
+        assert_eq!(state.multiplier, 2);
+        assert_eq!(state.event_price, 1_000);
+        assert_eq!(state.platform_fee, 33);
+        assert_eq!(state.org_reward, 44);
+        assert_eq!(state.pending_authority, None);
+        assert_eq!(account_version(&data), Some(1));
+    }
+
+    #[test]
+    fn discriminator_mismatch() {
+        let participation: Participation = zeroed();
+        let data = encode(&participation);
+
+        assert!(matches!(
+            decode_versioned::<Event>(&data),
+            Err(ClientError::Decode(_))
+        ));
+        assert!(decode::<Event>(&data).is_err());
+    }
+}
@@ -0,0 +1,40 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ClientError {
+    #[error("rpc transport error: {0}")]
+    Transport(String),
+
+    #[error("rpc error {code}: {message}")]
+    Rpc { code: i64, message: String },
+
+    #[error("account {0} not found")]
+    AccountNotFound(String),
+
+    #[error("failed to decode account: {0}")]
+    Decode(String),
+
+    #[error("invalid event id: {0}")]
+    InvalidEventId(String),
+
+    #[error("transaction failed: {0}")]
+    Transaction(String),
+}
+
+impl From<ureq::Error> for ClientError {
+    fn from(error: ureq::Error) -> Self {
+        ClientError::Transport(error.to_string())
+    }
+}
+
+impl From<std::io::Error> for ClientError {
+    fn from(error: std::io::Error) -> Self {
+        ClientError::Transport(error.to_string())
+    }
+}
+
+impl From<anchor_lang::error::Error> for ClientError {
+    fn from(error: anchor_lang::error::Error) -> Self {
+        ClientError::Decode(error.to_string())
+    }
+}
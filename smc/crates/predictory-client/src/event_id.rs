@@ -0,0 +1,37 @@
+use crate::{ClientError, Result};
+
+/// UUID version accepted by `CreateEvent::validate`
+pub const EVENT_ID_VERSION: usize = 4;
+
+/// Generates a random event id accepted by `create_event`
+pub fn new_event_id() -> u128 {
+    uuid::Uuid::new_v4().as_u128()
+}
+
+/// Parses a textual UUID and checks it is a valid event id
+pub fn parse_event_id(value: &str) -> Result<u128> {
+    let id = uuid::Uuid::parse_str(value)
+        .map_err(|error| ClientError::InvalidEventId(error.to_string()))?;
+
+    validate_event_id(id.as_u128())?;
+
+    Ok(id.as_u128())
+}
+
+/// Checks the event id the same way the program does on creation
+pub fn validate_event_id(event_id: u128) -> Result<()> {
+    let version = uuid::Uuid::from_u128(event_id).get_version_num();
+
+    if version != EVENT_ID_VERSION {
+        return Err(ClientError::InvalidEventId(format!(
+            "expected UUID v{EVENT_ID_VERSION}, got v{version}"
+        )));
+    }
+
+    Ok(())
+}
+
+/// Formats the event id as a hyphenated UUID
+pub fn format_event_id(event_id: u128) -> String {
+    uuid::Uuid::from_u128(event_id).to_string()
+}
@@ -0,0 +1,296 @@
+//! Decoding of program instructions and of the events emitted through
+//! `emit!`, as found in transaction logs.
+
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use predictory::{events, instruction};
+
+use crate::PROGRAM_ID;
+
+macro_rules! program_events {
+    ($($name:ident,)*) => {
+        /// Any event emitted by the program
+        pub enum ProgramEvent {
+            $($name(events::$name),)*
+        }
+
+        impl ProgramEvent {
+            /// Decodes event data: discriminator followed by the serialized event
+            pub fn decode(data: &[u8]) -> Option<Self> {
+                $(
+                    if data.starts_with(events::$name::DISCRIMINATOR) {
+                        let mut payload = &data[events::$name::DISCRIMINATOR.len()..];
+                        return events::$name::deserialize(&mut payload).ok().map(Self::$name);
+                    }
+                )*
+
+                None
+            }
+
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$name(_) => stringify!($name),)*
+                }
+            }
+        }
+    };
+}
+
+macro_rules! instruction_names {
+    ($($name:ident => $label:literal,)*) => {
+        /// Returns the name of a program instruction from its data
+        pub fn instruction_name(data: &[u8]) -> Option<&'static str> {
+            $(
+                if data.starts_with(instruction::$name::DISCRIMINATOR) {
+                    return Some($label);
+                }
+            )*
+
+            None
+        }
+    };
+}
+
+program_events! {
+    ContractInitialized,
+    AuthorityTransferProposed,
+    AuthorityTransferred,
+    AuthorityTimelockSet,
+    PauseFlagsSet,
+    RoleGranted,
+    RoleRevoked,
+    ParamChangeQueued,
+    ParamChangeApplied,
+    ParamChangeCancelled,
+    ProposalCreated,
+    ProposalVoted,
+    ProposalExecuted,
+    TreasuryCollected,
+    TreasurySplitUpdated,
+    TreasuryDistributed,
+    AccountMigrated,
+    UserCreated,
+    StakeDeposited,
+    StakeWithdrawn,
+    PortfolioPageCreated,
+    PortfolioPageClosed,
+    EventCreated,
+    EventPublished,
+    EventMetaUpdated,
+    EventDatesUpdated,
+    EventDeleted,
+    EventCanceled,
+    EventCompleted,
+    DisputeResolved,
+    DisputeExpired,
+    EventAuthorityProposed,
+    EventAuthorityTransferred,
+    EventCallbackSet,
+    CallbackNotified,
+    EventFinalized,
+    EventFrozen,
+    EventModerated,
+    OptionsTableCreated,
+    OptionCreated,
+    OptionUpdated,
+    OptionRemoved,
+    OrganizerSet,
+    OrganizerRemoved,
+    OrganizerRewardClaimed,
+    VoteCast,
+    OrganizerStakeReleased,
+    RewardClaimed,
+    Recharged,
+    AppealFiled,
+    TrustChanged,
+    TrustBurned,
+}
+
+instruction_names! {
+    InitializeContractState => "initialize_contract_state",
+    ProposeAuthority => "propose_authority",
+    AcceptAuthority => "accept_authority",
+    CancelAuthorityTransfer => "cancel_authority_transfer",
+    SetAuthorityTimelock => "set_authority_timelock",
+    QueueParamChange => "queue_param_change",
+    ApplyParamChange => "apply_param_change",
+    CancelParamChange => "cancel_param_change",
+    CreateProposal => "create_proposal",
+    VoteProposal => "vote_proposal",
+    ExecuteProposal => "execute_proposal",
+    InitializeTreasury => "initialize_treasury",
+    SetTreasurySplit => "set_treasury_split",
+    DistributeTreasury => "distribute_treasury",
+    MigrateState => "migrate_state",
+    MigrateUser => "migrate_user",
+    MigrateEvent => "migrate_event",
+    MigrateEventMeta => "migrate_event_meta",
+    MigrateEventOption => "migrate_event_option",
+    MigrateParticipation => "migrate_participation",
+    MigrateAppellation => "migrate_appellation",
+    GrantRole => "grant_role",
+    RevokeRole => "revoke_role",
+    SetPaused => "set_paused",
+    SetEventFrozen => "set_event_frozen",
+    CreateUser => "create_user",
+    TransferStake => "transfer_stake",
+    WithdrawStake => "withdraw_stake",
+    CreatePortfolioPage => "create_portfolio_page",
+    ClosePortfolioPage => "close_portfolio_page",
+    RefreshPortfolio => "refresh_portfolio",
+    CreateEvent => "create_event",
+    PublishEvent => "publish_event",
+    DeleteEvent => "delete_event",
+    UpdateEventName => "update_event_name",
+    UpdateEventDescription => "update_event_description",
+    UpdateEventEndDate => "update_event_end_date",
+    UpdateEventParticipationDeadline => "update_event_participation_deadline",
+    ProposeEventAuthority => "propose_event_authority",
+    AcceptEventAuthority => "accept_event_authority",
+    SetEventCallback => "set_event_callback",
+    RemoveEventCallback => "remove_event_callback",
+    NotifyCallback => "notify_callback",
+    CreateOptionsTable => "create_options_table",
+    CreateEventOption => "create_event_option",
+    CreateEventOptions => "create_event_options",
+    RemoveEventOption => "remove_event_option",
+    UpdateEventOption => "update_event_option",
+    AddEventOrganizer => "add_event_organizer",
+    UpdateEventOrganizer => "update_event_organizer",
+    RemoveEventOrganizer => "remove_event_organizer",
+    ClaimOrganizerReward => "claim_organizer_reward",
+    CancelEvent => "cancel_event",
+    CompleteEvent => "complete_event",
+    ModerateEvent => "moderate_event",
+    ResolveDispute => "resolve_dispute",
+    ExpireDispute => "expire_dispute",
+    FinalizeEvent => "finalize_event",
+    QuoteVote => "quote_vote",
+    QuoteClaim => "quote_claim",
+    EventOdds => "event_odds",
+    Vote => "vote",
+    ClaimEventReward => "claim_event_reward",
+    SettleBatch => "settle_batch",
+    ClaimMany => "claim_many",
+    Recharge => "recharge",
+    Appeal => "appeal",
+    BurnTrust => "burn_trust",
+}
+
+/// Program event with its position among the events of a transaction
+pub struct LoggedEvent {
+    pub seq: u32,
+    pub event: ProgramEvent,
+    /// Raw event data, discriminator included
+    pub data: Vec<u8>,
+}
+
+/// Decodes the events emitted by the program from transaction logs.
+///
+/// The invocation stack is tracked so data logged by other programs,
+/// including programs invoked by this one, is ignored.
+pub fn decode_logs(logs: &[String]) -> Vec<LoggedEvent> {
+    let program_id = PROGRAM_ID.to_string();
+    let mut stack: Vec<bool> = vec![];
+    let mut events = vec![];
+
+    for line in logs {
+        let Some(message) = line.strip_prefix("Program ") else {
+            continue;
+        };
+
+        if let Some(data) = message.strip_prefix("data: ") {
+            if stack.last() != Some(&true) {
+                continue;
+            }
+
+            let Ok(data) = STANDARD.decode(data) else {
+                continue;
+            };
+
+            if let Some(event) = ProgramEvent::decode(&data) {
+                events.push(LoggedEvent {
+                    seq: events.len() as u32,
+                    event,
+                    data,
+                });
+            }
+        } else if let Some((program, rest)) = message.split_once(' ') {
+            if rest.starts_with("invoke [") {
+                stack.push(program == program_id);
+            } else if rest == "success" || rest.starts_with("failed") {
+                stack.pop();
+            }
+        }
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::AnchorSerialize;
+
+    use super::*;
+    use crate::instructions as ix;
+
+    fn event_data<T: AnchorSerialize + Discriminator>(event: &T) -> Vec<u8> {
+        let mut data = T::DISCRIMINATOR.to_vec();
+        event.serialize(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn instruction_names_match_builders() {
+        let sender = anchor_lang::prelude::Pubkey::new_unique();
+
+        assert_eq!(
+            instruction_name(&ix::vote(&sender, 7, 0, false, 10, 0).data),
+            Some("vote")
+        );
+        assert_eq!(
+            instruction_name(&ix::expire_dispute(&sender, 7).data),
+            Some("expire_dispute")
+        );
+        assert_eq!(instruction_name(&[0; 8]), None);
+    }
+
+    #[test]
+    fn event_round_trip() {
+        let data = event_data(&events::DisputeExpired {
+            event_id: 7,
+            result: 1,
+        });
+
+        let Some(ProgramEvent::DisputeExpired(event)) = ProgramEvent::decode(&data) else {
+            panic!("event not decoded");
+        };
+        assert_eq!(event.event_id, 7);
+        assert_eq!(event.result, 1);
+        assert!(ProgramEvent::decode(&data[..data.len() - 1]).is_none());
+    }
+
+    #[test]
+    fn logs_of_other_programs_are_ignored() {
+        let data = STANDARD.encode(event_data(&events::DisputeExpired {
+            event_id: 7,
+            result: 1,
+        }));
+        let other = "11111111111111111111111111111111";
+        let logs = [
+            format!("Program {PROGRAM_ID} invoke [1]"),
+            format!("Program {other} invoke [2]"),
+            format!("Program data: {data}"),
+            format!("Program {other} success"),
+            format!("Program data: {data}"),
+            format!("Program {PROGRAM_ID} success"),
+            format!("Program data: {data}"),
+        ];
+
+        let events = decode_logs(&logs);
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].seq, 0);
+        assert_eq!(events[0].event.name(), "DisputeExpired");
+    }
+}
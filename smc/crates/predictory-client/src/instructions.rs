@@ -0,0 +1,1436 @@
+//! Instruction builders, one per program entrypoint.
+//!
+//! Every builder derives the PDAs the instruction expects, so callers only
+//! pass signers, ids and the few values the program reads from other
+//! accounts (e.g. the portfolio page a participation was recorded on).
+//! Builders touching option pools take the event's `options_table` flag.
+
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        system_program, sysvar,
+    },
+    InstructionData, ToAccountMetas,
+};
+use predictory::{
+    accounts,
+    context::CreateEventArgs,
+    instruction,
+    state::{param::ParamKind, role::Role, treasury::TreasuryRecipient},
+};
+
+use crate::{pda, PROGRAM_ID};
+
+fn build(
+    accounts: impl ToAccountMetas,
+    data: impl InstructionData,
+    remaining: Vec<AccountMeta>,
+) -> Instruction {
+    let mut metas = accounts.to_account_metas(None);
+    metas.extend(remaining);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: metas,
+        data: data.data(),
+    }
+}
+
+/// Organizer account, if the signer acts as a co-organizer of the event
+fn organizer(event_id: u128, authority: &Pubkey, co_organizer: bool) -> Option<Pubkey> {
+    co_organizer.then(|| pda::organizer_address(event_id, authority))
+}
+
+/// Account holding the option pool: the option itself or the event's options table
+fn option_pool_address(event_id: u128, index: u8, options_table: bool) -> Pubkey {
+    if options_table {
+        pda::options_table_address(event_id)
+    } else {
+        pda::option_address(event_id, index)
+    }
+}
+
+/// Option account and options table, only the one holding the event's option pools is passed
+fn option_pool(event_id: u128, index: u8, options_table: bool) -> (Option<Pubkey>, Option<Pubkey>) {
+    let address = Some(option_pool_address(event_id, index, options_table));
+
+    if options_table {
+        (None, address)
+    } else {
+        (address, None)
+    }
+}
+
+// -------------------------- Contract state ------------------------- //
+
+pub fn initialize_contract_state(
+    payer: &Pubkey,
+    authority: Pubkey,
+    multiplier: u64,
+    event_price: u64,
+    platform_fee: u64,
+    org_reward: u64,
+) -> Instruction {
+    build(
+        accounts::InitializeContractState {
+            authority: *payer,
+            state: pda::state_address(),
+            treasury: pda::treasury_address(),
+            program_account: PROGRAM_ID,
+            program_data: pda::program_data_address(),
+            system_program: system_program::ID,
+        },
+        instruction::InitializeContractState {
+            authority,
+            multiplier,
+            event_price,
+            platform_fee,
+            org_reward,
+        },
+        vec![],
+    )
+}
+
+fn update_contract_state(authority: &Pubkey) -> accounts::UpdateContractState {
+    accounts::UpdateContractState {
+        authority: *authority,
+        state: pda::state_address(),
+    }
+}
+
+pub fn propose_authority(authority: &Pubkey, new_authority: Pubkey) -> Instruction {
+    build(
+        update_contract_state(authority),
+        instruction::ProposeAuthority {
+            authority: new_authority,
+        },
+        vec![],
+    )
+}
+
+pub fn accept_authority(sender: &Pubkey) -> Instruction {
+    build(
+        accounts::AcceptAuthority {
+            sender: *sender,
+            state: pda::state_address(),
+        },
+        instruction::AcceptAuthority {},
+        vec![],
+    )
+}
+
+pub fn cancel_authority_transfer(authority: &Pubkey) -> Instruction {
+    build(
+        update_contract_state(authority),
+        instruction::CancelAuthorityTransfer {},
+        vec![],
+    )
+}
+
+pub fn set_authority_timelock(authority: &Pubkey, timelock: i64) -> Instruction {
+    build(
+        update_contract_state(authority),
+        instruction::SetAuthorityTimelock { timelock },
+        vec![],
+    )
+}
+
+/// `as_guardian` passes the guardian role of the sender, the contract authority signs without it
+pub fn set_paused(sender: &Pubkey, flags: u8, as_guardian: bool) -> Instruction {
+    build(
+        accounts::SetPaused {
+            sender: *sender,
+            role: as_guardian.then(|| pda::role_address(Role::Guardian, sender)),
+            state: pda::state_address(),
+        },
+        instruction::SetPaused { flags },
+        vec![],
+    )
+}
+
+// ---------------------------- Parameters --------------------------- //
+
+pub fn queue_param_change(fee_manager: &Pubkey, kind: ParamKind, value: u64) -> Instruction {
+    build(
+        accounts::QueueParamChange {
+            authority: *fee_manager,
+            role: pda::role_address(Role::FeeManager, fee_manager),
+            param_change: pda::param_change_address(kind),
+            system_program: system_program::ID,
+        },
+        instruction::QueueParamChange { kind, value },
+        vec![],
+    )
+}
+
+pub fn apply_param_change(sender: &Pubkey, kind: ParamKind) -> Instruction {
+    build(
+        accounts::ApplyParamChange {
+            sender: *sender,
+            state: pda::state_address(),
+            param_change: pda::param_change_address(kind),
+        },
+        instruction::ApplyParamChange { kind },
+        vec![],
+    )
+}
+
+pub fn cancel_param_change(fee_manager: &Pubkey, kind: ParamKind) -> Instruction {
+    build(
+        accounts::CancelParamChange {
+            authority: *fee_manager,
+            role: pda::role_address(Role::FeeManager, fee_manager),
+            param_change: pda::param_change_address(kind),
+        },
+        instruction::CancelParamChange { kind },
+        vec![],
+    )
+}
+
+// ---------------------------- Governance --------------------------- //
+
+/// `proposal_id` must equal the current `State::proposal_count`
+pub fn create_proposal(
+    sender: &Pubkey,
+    proposal_id: u64,
+    kind: ParamKind,
+    value: u64,
+) -> Instruction {
+    build(
+        accounts::CreateProposal {
+            sender: *sender,
+            state: pda::state_address(),
+            user: pda::user_address(sender),
+            proposal: pda::proposal_address(proposal_id),
+            system_program: system_program::ID,
+        },
+        instruction::CreateProposal { kind, value },
+        vec![],
+    )
+}
+
+pub fn vote_proposal(sender: &Pubkey, proposal_id: u64, support: bool) -> Instruction {
+    build(
+        accounts::VoteProposal {
+            sender: *sender,
+            user: pda::user_address(sender),
+            proposal: pda::proposal_address(proposal_id),
+            proposal_vote: pda::proposal_vote_address(proposal_id, sender),
+            system_program: system_program::ID,
+        },
+        instruction::VoteProposal {
+            proposal_id,
+            support,
+        },
+        vec![],
+    )
+}
+
+pub fn execute_proposal(sender: &Pubkey, proposal_id: u64) -> Instruction {
+    build(
+        accounts::ExecuteProposal {
+            sender: *sender,
+            state: pda::state_address(),
+            proposal: pda::proposal_address(proposal_id),
+        },
+        instruction::ExecuteProposal { proposal_id },
+        vec![],
+    )
+}
+
+// ----------------------------- Treasury ---------------------------- //
+
+pub fn initialize_treasury(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializeTreasury {
+            authority: *authority,
+            state: pda::state_address(),
+            treasury: pda::treasury_address(),
+            system_program: system_program::ID,
+        },
+        instruction::InitializeTreasury {},
+        vec![],
+    )
+}
+
+pub fn set_treasury_split(fee_manager: &Pubkey, recipients: Vec<TreasuryRecipient>) -> Instruction {
+    build(
+        accounts::SetTreasurySplit {
+            authority: *fee_manager,
+            role: pda::role_address(Role::FeeManager, fee_manager),
+            treasury: pda::treasury_address(),
+        },
+        instruction::SetTreasurySplit { recipients },
+        vec![],
+    )
+}
+
+/// `wallets` must follow the order of the configured treasury recipients
+pub fn distribute_treasury(sender: &Pubkey, wallets: &[Pubkey]) -> Instruction {
+    build(
+        accounts::DistributeTreasury {
+            sender: *sender,
+            treasury: pda::treasury_address(),
+        },
+        instruction::DistributeTreasury {},
+        wallets
+            .iter()
+            .map(|wallet| AccountMeta::new(*wallet, false))
+            .collect(),
+    )
+}
+
+// ---------------------------- Migrations --------------------------- //
+
+pub fn migrate_state(payer: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateState {
+            payer: *payer,
+            state: pda::state_address(),
+            system_program: system_program::ID,
+        },
+        instruction::MigrateState {},
+        vec![],
+    )
+}
+
+pub fn migrate_user(payer: &Pubkey, owner: Pubkey) -> Instruction {
+    build(
+        accounts::MigrateUser {
+            payer: *payer,
+            user: pda::user_address(&owner),
+            system_program: system_program::ID,
+        },
+        instruction::MigrateUser { owner },
+        vec![],
+    )
+}
+
+pub fn migrate_event(payer: &Pubkey, event_id: u128) -> Instruction {
+    build(
+        accounts::MigrateEvent {
+            payer: *payer,
+            state: pda::state_address(),
+            event: pda::event_address(event_id),
+            system_program: system_program::ID,
+        },
+        instruction::MigrateEvent { event_id },
+        vec![],
+    )
+}
+
+pub fn migrate_event_meta(payer: &Pubkey, event_id: u128) -> Instruction {
+    build(
+        accounts::MigrateEventMeta {
+            payer: *payer,
+            event_meta: pda::event_meta_address(event_id),
+            system_program: system_program::ID,
+        },
+        instruction::MigrateEventMeta { event_id },
+        vec![],
+    )
+}
+
+pub fn migrate_event_option(payer: &Pubkey, event_id: u128, index: u8) -> Instruction {
+    build(
+        accounts::MigrateEventOption {
+            payer: *payer,
+            option_meta: pda::option_meta_address(event_id, index),
+            option: pda::option_address(event_id, index),
+            system_program: system_program::ID,
+        },
+        instruction::MigrateEventOption { event_id, index },
+        vec![],
+    )
+}
+
+pub fn migrate_participation(payer: &Pubkey, event_id: u128, participant: Pubkey) -> Instruction {
+    build(
+        accounts::MigrateParticipation {
+            payer: *payer,
+            participation: pda::participation_address(event_id, &participant),
+            portfolio: pda::portfolio_address(&participant, 0),
+            system_program: system_program::ID,
+        },
+        instruction::MigrateParticipation {
+            event_id,
+            participant,
+        },
+        vec![],
+    )
+}
+
+pub fn migrate_appellation(payer: &Pubkey, event_id: u128) -> Instruction {
+    build(
+        accounts::MigrateAppellation {
+            payer: *payer,
+            appellation: pda::appeal_address(event_id),
+            system_program: system_program::ID,
+        },
+        instruction::MigrateAppellation { event_id },
+        vec![],
+    )
+}
+
+// ------------------------------- Roles ----------------------------- //
+
+pub fn grant_role(authority: &Pubkey, role: Role, member: Pubkey) -> Instruction {
+    build(
+        accounts::GrantRole {
+            authority: *authority,
+            state: pda::state_address(),
+            role_member: pda::role_address(role, &member),
+            system_program: system_program::ID,
+        },
+        instruction::GrantRole { role, member },
+        vec![],
+    )
+}
+
+pub fn revoke_role(authority: &Pubkey, role: Role, member: Pubkey) -> Instruction {
+    build(
+        accounts::RevokeRole {
+            authority: *authority,
+            state: pda::state_address(),
+            role_member: pda::role_address(role, &member),
+        },
+        instruction::RevokeRole { role, member },
+        vec![],
+    )
+}
+
+// ------------------------------- Users ----------------------------- //
+
+pub fn create_user(sender: &Pubkey, name: [u8; 32]) -> Instruction {
+    build(
+        accounts::CreateUser {
+            sender: *sender,
+            user: pda::user_address(sender),
+            system_program: system_program::ID,
+        },
+        instruction::CreateUser { name },
+        vec![],
+    )
+}
+
+pub fn transfer_stake(sender: &Pubkey, stake: u64) -> Instruction {
+    build(
+        accounts::TransferStake {
+            sender: *sender,
+            user: pda::user_address(sender),
+            system_program: system_program::ID,
+        },
+        instruction::TransferStake { stake },
+        vec![],
+    )
+}
+
+pub fn withdraw_stake(sender: &Pubkey, amount: Option<u64>) -> Instruction {
+    build(
+        accounts::WithdrawStake {
+            sender: *sender,
+            state: pda::state_address(),
+            user: pda::user_address(sender),
+            system_program: system_program::ID,
+        },
+        instruction::WithdrawStake { amount },
+        vec![],
+    )
+}
+
+pub fn create_portfolio_page(sender: &Pubkey, page: u16) -> Instruction {
+    build(
+        accounts::CreatePortfolioPage {
+            sender: *sender,
+            portfolio: pda::portfolio_address(sender, page),
+            system_program: system_program::ID,
+        },
+        instruction::CreatePortfolioPage { page },
+        vec![],
+    )
+}
+
+pub fn close_portfolio_page(sender: &Pubkey, page: u16) -> Instruction {
+    build(
+        accounts::ClosePortfolioPage {
+            sender: *sender,
+            portfolio: pda::portfolio_address(sender, page),
+        },
+        instruction::ClosePortfolioPage { page },
+        vec![],
+    )
+}
+
+/// Refreshes the status of the positions in `event_ids` on a portfolio page
+pub fn refresh_portfolio(
+    sender: &Pubkey,
+    owner: Pubkey,
+    page: u16,
+    event_ids: &[u128],
+) -> Instruction {
+    build(
+        accounts::RefreshPortfolio {
+            sender: *sender,
+            portfolio: pda::portfolio_address(&owner, page),
+        },
+        instruction::RefreshPortfolio { owner, page },
+        event_ids
+            .iter()
+            .map(|event_id| AccountMeta::new_readonly(pda::event_address(*event_id), false))
+            .collect(),
+    )
+}
+
+// ------------------------------ Events ----------------------------- //
+
+pub fn create_event(authority: &Pubkey, event_id: u128, args: CreateEventArgs) -> Instruction {
+    build(
+        accounts::CreateEvent {
+            authority: *authority,
+            user: pda::user_address(authority),
+            state: pda::state_address(),
+            event: pda::event_address(event_id),
+            event_meta: pda::event_meta_address(event_id),
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        instruction::CreateEvent { event_id, args },
+        vec![],
+    )
+}
+
+pub fn publish_event(authority: &Pubkey, event_id: u128) -> Instruction {
+    build(
+        accounts::PublishEvent {
+            authority: *authority,
+            user: pda::user_address(authority),
+            state: pda::state_address(),
+            event: pda::event_address(event_id),
+        },
+        instruction::PublishEvent { event_id },
+        vec![],
+    )
+}
+
+/// `option_count` must match the event's current number of options
+/// `organizers` are the members of every co-organizer account of the event, closed along with it
+pub fn delete_event(
+    authority: &Pubkey,
+    event_id: u128,
+    option_count: u8,
+    options_table: bool,
+    organizers: &[Pubkey],
+) -> Instruction {
+    build(
+        accounts::DeleteEvent {
+            authority: *authority,
+            event: pda::event_address(event_id),
+            event_meta: pda::event_meta_address(event_id),
+            options_table: options_table.then(|| pda::options_table_address(event_id)),
+        },
+        instruction::DeleteEvent { event_id },
+        (0..option_count)
+            .flat_map(|index| option_metas(event_id, index, options_table))
+            .chain(
+                organizers.iter().map(|member| {
+                    AccountMeta::new(pda::organizer_address(event_id, member), false)
+                }),
+            )
+            .collect(),
+    )
+}
+
+fn update_event(authority: &Pubkey, event_id: u128, co_organizer: bool) -> accounts::UpdateEvent {
+    accounts::UpdateEvent {
+        authority: *authority,
+        event: pda::event_address(event_id),
+        organizer: organizer(event_id, authority, co_organizer),
+        event_meta: pda::event_meta_address(event_id),
+    }
+}
+
+pub fn update_event_name(
+    authority: &Pubkey,
+    event_id: u128,
+    co_organizer: bool,
+    name: [u8; 32],
+) -> Instruction {
+    build(
+        update_event(authority, event_id, co_organizer),
+        instruction::UpdateEventName { event_id, name },
+        vec![],
+    )
+}
+
+pub fn update_event_description(
+    authority: &Pubkey,
+    event_id: u128,
+    co_organizer: bool,
+    description: [u8; 256],
+) -> Instruction {
+    build(
+        update_event(authority, event_id, co_organizer),
+        instruction::UpdateEventDescription {
+            event_id,
+            description,
+        },
+        vec![],
+    )
+}
+
+pub fn update_event_end_date(
+    authority: &Pubkey,
+    event_id: u128,
+    co_organizer: bool,
+    end_date: i64,
+) -> Instruction {
+    build(
+        update_event(authority, event_id, co_organizer),
+        instruction::UpdateEventEndDate { event_id, end_date },
+        vec![],
+    )
+}
+
+pub fn update_event_participation_deadline(
+    authority: &Pubkey,
+    event_id: u128,
+    co_organizer: bool,
+    deadline: Option<i64>,
+) -> Instruction {
+    build(
+        update_event(authority, event_id, co_organizer),
+        instruction::UpdateEventParticipationDeadline { event_id, deadline },
+        vec![],
+    )
+}
+
+pub fn propose_event_authority(
+    authority: &Pubkey,
+    event_id: u128,
+    new_authority: Option<Pubkey>,
+) -> Instruction {
+    build(
+        accounts::ProposeEventAuthority {
+            authority: *authority,
+            event: pda::event_address(event_id),
+        },
+        instruction::ProposeEventAuthority {
+            event_id,
+            new_authority,
+        },
+        vec![],
+    )
+}
+
+/// `previous_authority` is the event authority being replaced
+pub fn accept_event_authority(
+    sender: &Pubkey,
+    event_id: u128,
+    previous_authority: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::AcceptEventAuthority {
+            sender: *sender,
+            event: pda::event_address(event_id),
+            previous_user: pda::user_address(previous_authority),
+            user: pda::user_address(sender),
+        },
+        instruction::AcceptEventAuthority { event_id },
+        vec![],
+    )
+}
+
+/// `event_authority` is the organizer whose stake is slashed or released
+pub fn cancel_event(
+    sender: &Pubkey,
+    event_id: u128,
+    event_authority: &Pubkey,
+    as_moderator: bool,
+) -> Instruction {
+    build(
+        accounts::CancelEvent {
+            sender: *sender,
+            treasury: pda::treasury_address(),
+            moderator: as_moderator.then(|| pda::role_address(Role::Moderator, sender)),
+            user: pda::user_address(event_authority),
+            event: pda::event_address(event_id),
+            attestation: pda::attestation_address(event_id),
+            system_program: system_program::ID,
+        },
+        instruction::CancelEvent { event_id },
+        vec![],
+    )
+}
+
+pub fn set_event_callback(authority: &Pubkey, event_id: u128, program: Pubkey) -> Instruction {
+    build(
+        accounts::SetEventCallback {
+            authority: *authority,
+            event: pda::event_address(event_id),
+            callback: pda::callback_address(event_id),
+            system_program: system_program::ID,
+        },
+        instruction::SetEventCallback { event_id, program },
+        vec![],
+    )
+}
+
+pub fn remove_event_callback(authority: &Pubkey, event_id: u128) -> Instruction {
+    build(
+        accounts::RemoveEventCallback {
+            authority: *authority,
+            event: pda::event_address(event_id),
+            callback: pda::callback_address(event_id),
+        },
+        instruction::RemoveEventCallback { event_id },
+        vec![],
+    )
+}
+
+/// `accounts` are forwarded to the callback program after the callback authority and the event
+pub fn notify_callback(
+    event_id: u128,
+    program: &Pubkey,
+    accounts: Vec<AccountMeta>,
+) -> Instruction {
+    build(
+        accounts::NotifyCallback {
+            event: pda::event_address(event_id),
+            callback: pda::callback_address(event_id),
+            callback_program: *program,
+            callback_authority: pda::callback_authority_address(event_id),
+        },
+        instruction::NotifyCallback { event_id },
+        accounts,
+    )
+}
+
+pub fn complete_event(
+    authority: &Pubkey,
+    event_id: u128,
+    co_organizer: bool,
+    result: u8,
+) -> Instruction {
+    build(
+        accounts::CompleteEvent {
+            authority: *authority,
+            state: pda::state_address(),
+            event: pda::event_address(event_id),
+            organizer: organizer(event_id, authority, co_organizer),
+            attestation: pda::attestation_address(event_id),
+            system_program: system_program::ID,
+        },
+        instruction::CompleteEvent { event_id, result },
+        vec![],
+    )
+}
+
+pub fn set_event_frozen(guardian: &Pubkey, event_id: u128, frozen: bool) -> Instruction {
+    build(
+        accounts::FreezeEvent {
+            sender: *guardian,
+            role: pda::role_address(Role::Guardian, guardian),
+            event: pda::event_address(event_id),
+        },
+        instruction::SetEventFrozen { event_id, frozen },
+        vec![],
+    )
+}
+
+pub fn moderate_event(moderator: &Pubkey, event_id: u128, hidden: bool) -> Instruction {
+    build(
+        accounts::ModerateEvent {
+            sender: *moderator,
+            role: pda::role_address(Role::Moderator, moderator),
+            event_meta: pda::event_meta_address(event_id),
+        },
+        instruction::ModerateEvent { event_id, hidden },
+        vec![],
+    )
+}
+
+pub fn resolve_dispute(resolver: &Pubkey, event_id: u128, result: u8) -> Instruction {
+    build(
+        accounts::ResolveDispute {
+            sender: *resolver,
+            role: pda::role_address(Role::Resolver, resolver),
+            event: pda::event_address(event_id),
+            attestation: pda::attestation_address(event_id),
+            system_program: system_program::ID,
+        },
+        instruction::ResolveDispute { event_id, result },
+        vec![],
+    )
+}
+
+/// Keeps the original result of a dispute no resolver settled in time
+pub fn expire_dispute(sender: &Pubkey, event_id: u128) -> Instruction {
+    build(
+        accounts::ExpireDispute {
+            sender: *sender,
+            event: pda::event_address(event_id),
+        },
+        instruction::ExpireDispute { event_id },
+        vec![],
+    )
+}
+
+/// Seals the outcome in the resolution attestation once the appellation window is over
+pub fn finalize_event(sender: &Pubkey, event_id: u128) -> Instruction {
+    build(
+        accounts::FinalizeEvent {
+            sender: *sender,
+            event: pda::event_address(event_id),
+            attestation: pda::attestation_address(event_id),
+            system_program: system_program::ID,
+        },
+        instruction::FinalizeEvent { event_id },
+        vec![],
+    )
+}
+
+// ------------------------------ Options ---------------------------- //
+
+/// Must be sent before the event gets its first option
+pub fn create_options_table(authority: &Pubkey, event_id: u128, co_organizer: bool) -> Instruction {
+    build(
+        accounts::CreateOptionsTable {
+            authority: *authority,
+            options_table: pda::options_table_address(event_id),
+            event: pda::event_address(event_id),
+            organizer: organizer(event_id, authority, co_organizer),
+            system_program: system_program::ID,
+        },
+        instruction::CreateOptionsTable { event_id },
+        vec![],
+    )
+}
+
+pub fn create_event_option(
+    authority: &Pubkey,
+    event_id: u128,
+    co_organizer: bool,
+    index: u8,
+    options_table: bool,
+    description: [u8; 256],
+) -> Instruction {
+    build(
+        accounts::CreateEventOption {
+            authority: *authority,
+            option: (!options_table).then(|| pda::option_address(event_id, index)),
+            option_meta: pda::option_meta_address(event_id, index),
+            event: pda::event_address(event_id),
+            organizer: organizer(event_id, authority, co_organizer),
+            system_program: system_program::ID,
+        },
+        instruction::CreateEventOption {
+            event_id,
+            index,
+            description,
+        },
+        vec![],
+    )
+}
+
+/// `first_index` must match the event's current number of options
+pub fn create_event_options(
+    authority: &Pubkey,
+    event_id: u128,
+    co_organizer: bool,
+    first_index: u8,
+    options_table: bool,
+    descriptions: Vec<String>,
+) -> Instruction {
+    let remaining = (0..descriptions.len() as u8)
+        .flat_map(|offset| option_metas(event_id, first_index + offset, options_table))
+        .collect();
+
+    build(
+        accounts::CreateEventOptions {
+            authority: *authority,
+            event: pda::event_address(event_id),
+            organizer: organizer(event_id, authority, co_organizer),
+            system_program: system_program::ID,
+        },
+        instruction::CreateEventOptions {
+            event_id,
+            descriptions,
+        },
+        remaining,
+    )
+}
+
+pub fn update_event_option(
+    authority: &Pubkey,
+    event_id: u128,
+    co_organizer: bool,
+    index: u8,
+    description: [u8; 256],
+) -> Instruction {
+    build(
+        accounts::UpdateEventOption {
+            authority: *authority,
+            event: pda::event_address(event_id),
+            organizer: organizer(event_id, authority, co_organizer),
+            option_meta: pda::option_meta_address(event_id, index),
+        },
+        instruction::UpdateEventOption {
+            event_id,
+            index,
+            description,
+        },
+        vec![],
+    )
+}
+
+/// `option_count` is the event's current number of options; the last one
+/// is moved into the removed slot unless it is the one being removed
+pub fn remove_event_option(
+    authority: &Pubkey,
+    event_id: u128,
+    co_organizer: bool,
+    index: u8,
+    option_count: u8,
+    options_table: bool,
+) -> Instruction {
+    let last_index = option_count.saturating_sub(1);
+
+    build(
+        accounts::RemoveEventOption {
+            authority: *authority,
+            event: pda::event_address(event_id),
+            organizer: organizer(event_id, authority, co_organizer),
+            option: (!options_table).then(|| pda::option_address(event_id, index)),
+            option_meta: pda::option_meta_address(event_id, index),
+            last_option: (index != last_index && !options_table)
+                .then(|| pda::option_address(event_id, last_index)),
+            last_option_meta: (index != last_index)
+                .then(|| pda::option_meta_address(event_id, last_index)),
+        },
+        instruction::RemoveEventOption { event_id, index },
+        vec![],
+    )
+}
+
+/// Option and option meta accounts, in the order `create_event_options` and `delete_event` take them;
+/// events with an options table only pass the option meta
+fn option_metas(event_id: u128, index: u8, options_table: bool) -> Vec<AccountMeta> {
+    let option =
+        (!options_table).then(|| AccountMeta::new(pda::option_address(event_id, index), false));
+
+    option
+        .into_iter()
+        .chain([AccountMeta::new(
+            pda::option_meta_address(event_id, index),
+            false,
+        )])
+        .collect()
+}
+
+// ---------------------------- Organizers --------------------------- //
+
+pub fn add_event_organizer(
+    authority: &Pubkey,
+    event_id: u128,
+    member: Pubkey,
+    permissions: u8,
+    reward_share: u8,
+) -> Instruction {
+    build(
+        accounts::AddEventOrganizer {
+            authority: *authority,
+            event: pda::event_address(event_id),
+            organizer: pda::organizer_address(event_id, &member),
+            system_program: system_program::ID,
+        },
+        instruction::AddEventOrganizer {
+            event_id,
+            member,
+            permissions,
+            reward_share,
+        },
+        vec![],
+    )
+}
+
+pub fn update_event_organizer(
+    authority: &Pubkey,
+    event_id: u128,
+    member: Pubkey,
+    permissions: u8,
+    reward_share: u8,
+) -> Instruction {
+    build(
+        accounts::UpdateEventOrganizer {
+            authority: *authority,
+            event: pda::event_address(event_id),
+            organizer: pda::organizer_address(event_id, &member),
+        },
+        instruction::UpdateEventOrganizer {
+            event_id,
+            member,
+            permissions,
+            reward_share,
+        },
+        vec![],
+    )
+}
+
+pub fn remove_event_organizer(authority: &Pubkey, event_id: u128, member: Pubkey) -> Instruction {
+    build(
+        accounts::RemoveEventOrganizer {
+            authority: *authority,
+            event: pda::event_address(event_id),
+            organizer: pda::organizer_address(event_id, &member),
+        },
+        instruction::RemoveEventOrganizer { event_id, member },
+        vec![],
+    )
+}
+
+pub fn claim_organizer_reward(sender: &Pubkey, event_id: u128) -> Instruction {
+    build(
+        accounts::ClaimOrganizerReward {
+            sender: *sender,
+            state: pda::state_address(),
+            user: pda::user_address(sender),
+            event: pda::event_address(event_id),
+            organizer: pda::organizer_address(event_id, sender),
+        },
+        instruction::ClaimOrganizerReward { event_id },
+        vec![],
+    )
+}
+
+// --------------------------- Participation ------------------------- //
+
+pub fn vote(
+    sender: &Pubkey,
+    event_id: u128,
+    option_ix: u8,
+    options_table: bool,
+    amount: u64,
+    portfolio_page: u16,
+) -> Instruction {
+    let (option, options_table) = option_pool(event_id, option_ix, options_table);
+
+    build(
+        accounts::Vote {
+            sender: *sender,
+            user: pda::user_address(sender),
+            state: pda::state_address(),
+            event: pda::event_address(event_id),
+            option,
+            options_table,
+            participation: pda::participation_address(event_id, sender),
+            portfolio: pda::portfolio_address(sender, portfolio_page),
+            system_program: system_program::ID,
+        },
+        instruction::Vote {
+            event_id,
+            option_ix,
+            amount,
+        },
+        vec![],
+    )
+}
+
+/// `option_ix` and `portfolio_page` are read from the sender's participation
+pub fn claim_event_reward(
+    sender: &Pubkey,
+    event_id: u128,
+    event_authority: &Pubkey,
+    option_ix: u8,
+    options_table: bool,
+    portfolio_page: u16,
+) -> Instruction {
+    let (option, options_table) = option_pool(event_id, option_ix, options_table);
+
+    build(
+        accounts::ClaimEventReward {
+            sender: *sender,
+            treasury: pda::treasury_address(),
+            user: pda::user_address(sender),
+            event_admin: pda::user_address(event_authority),
+            state: pda::state_address(),
+            event: pda::event_address(event_id),
+            option,
+            options_table,
+            participation: pda::participation_address(event_id, sender),
+            portfolio: pda::portfolio_address(sender, portfolio_page),
+            system_program: system_program::ID,
+        },
+        instruction::ClaimEventReward { event_id },
+        vec![],
+    )
+}
+
+/// Position settled by `settle_batch`
+#[derive(Debug, Clone, Copy)]
+pub struct SettlementPosition {
+    pub participant: Pubkey,
+    pub portfolio_page: u16,
+}
+
+/// Settles the positions of a completed event on behalf of their owners
+pub fn settle_batch(
+    sender: &Pubkey,
+    event_id: u128,
+    event_authority: &Pubkey,
+    result: u8,
+    options_table: bool,
+    positions: &[SettlementPosition],
+) -> Instruction {
+    let (option, options_table) = option_pool(event_id, result, options_table);
+
+    build(
+        accounts::SettleBatch {
+            sender: *sender,
+            treasury: pda::treasury_address(),
+            event_admin: pda::user_address(event_authority),
+            state: pda::state_address(),
+            event: pda::event_address(event_id),
+            option,
+            options_table,
+        },
+        instruction::SettleBatch { event_id },
+        positions
+            .iter()
+            .flat_map(|position| {
+                [
+                    AccountMeta::new(
+                        pda::participation_address(event_id, &position.participant),
+                        false,
+                    ),
+                    AccountMeta::new(pda::user_address(&position.participant), false),
+                    AccountMeta::new(
+                        pda::portfolio_address(&position.participant, position.portfolio_page),
+                        false,
+                    ),
+                ]
+            })
+            .collect(),
+    )
+}
+
+/// Position claimed by `claim_many`, read from the sender's participation
+#[derive(Debug, Clone, Copy)]
+pub struct ClaimPosition {
+    pub event_id: u128,
+    pub option: u8,
+    /// Whether the event keeps its option pools in the options table
+    pub options_table: bool,
+    pub portfolio_page: u16,
+}
+
+/// Claims the sender's positions in several completed events
+pub fn claim_many(sender: &Pubkey, positions: &[ClaimPosition]) -> Instruction {
+    let mut pages: Vec<u16> = positions
+        .iter()
+        .map(|position| position.portfolio_page)
+        .collect();
+    pages.sort_unstable();
+    pages.dedup();
+
+    build(
+        accounts::ClaimMany {
+            sender: *sender,
+            treasury: pda::treasury_address(),
+            user: pda::user_address(sender),
+            state: pda::state_address(),
+        },
+        instruction::ClaimMany {
+            claim_count: positions.len() as u8,
+        },
+        positions
+            .iter()
+            .flat_map(|position| {
+                [
+                    AccountMeta::new(pda::event_address(position.event_id), false),
+                    AccountMeta::new_readonly(
+                        option_pool_address(
+                            position.event_id,
+                            position.option,
+                            position.options_table,
+                        ),
+                        false,
+                    ),
+                    AccountMeta::new(pda::participation_address(position.event_id, sender), false),
+                ]
+            })
+            .chain(
+                pages
+                    .into_iter()
+                    .map(|page| AccountMeta::new(pda::portfolio_address(sender, page), false)),
+            )
+            .collect(),
+    )
+}
+
+pub fn recharge(sender: &Pubkey, event_id: u128, portfolio_page: u16) -> Instruction {
+    build(
+        accounts::Recharge {
+            sender: *sender,
+            state: pda::state_address(),
+            event: pda::event_address(event_id),
+            user: pda::user_address(sender),
+            participant: pda::participation_address(event_id, sender),
+            portfolio: pda::portfolio_address(sender, portfolio_page),
+            system_program: system_program::ID,
+        },
+        instruction::Recharge { event_id },
+        vec![],
+    )
+}
+
+/// `option_ix` and `portfolio_page` are read from the sender's participation
+pub fn appeal(
+    sender: &Pubkey,
+    event_id: u128,
+    event_authority: &Pubkey,
+    option_ix: u8,
+    options_table: bool,
+    portfolio_page: u16,
+) -> Instruction {
+    let (option, options_table) = option_pool(event_id, option_ix, options_table);
+
+    build(
+        accounts::AppealResult {
+            sender: *sender,
+            treasury: pda::treasury_address(),
+            state: pda::state_address(),
+            appellation: pda::appeal_address(event_id),
+            event_admin: pda::user_address(event_authority),
+            event: pda::event_address(event_id),
+            option,
+            options_table,
+            user: pda::user_address(sender),
+            participation: pda::participation_address(event_id, sender),
+            portfolio: pda::portfolio_address(sender, portfolio_page),
+            system_program: system_program::ID,
+        },
+        instruction::Appeal { event_id },
+        vec![],
+    )
+}
+
+pub fn burn_trust(sender: &Pubkey, event_id: u128, portfolio_page: u16) -> Instruction {
+    build(
+        accounts::BurnTrust {
+            sender: *sender,
+            state: pda::state_address(),
+            event: pda::event_address(event_id),
+            user: pda::user_address(sender),
+            participation: pda::participation_address(event_id, sender),
+            portfolio: pda::portfolio_address(sender, portfolio_page),
+            system_program: system_program::ID,
+        },
+        instruction::BurnTrust { event_id },
+        vec![],
+    )
+}
+
+// ------------------------------- Views ----------------------------- //
+
+pub fn quote_vote(event_id: u128, option_ix: u8, options_table: bool, amount: u64) -> Instruction {
+    let (option, options_table) = option_pool(event_id, option_ix, options_table);
+
+    build(
+        accounts::QuoteVote {
+            state: pda::state_address(),
+            event: pda::event_address(event_id),
+            option,
+            options_table,
+        },
+        instruction::QuoteVote {
+            event_id,
+            option_ix,
+            amount,
+        },
+        vec![],
+    )
+}
+
+/// `option_ix` is read from the user's participation
+pub fn quote_claim(
+    event_id: u128,
+    user: Pubkey,
+    option_ix: u8,
+    options_table: bool,
+) -> Instruction {
+    let (option, options_table) = option_pool(event_id, option_ix, options_table);
+
+    build(
+        accounts::QuoteClaim {
+            state: pda::state_address(),
+            event: pda::event_address(event_id),
+            participation: pda::participation_address(event_id, &user),
+            option,
+            options_table,
+        },
+        instruction::QuoteClaim { event_id, user },
+        vec![],
+    )
+}
+
+pub fn event_odds(event_id: u128, option_count: u8, options_table: bool) -> Instruction {
+    let pools = if options_table {
+        vec![pda::options_table_address(event_id)]
+    } else {
+        (0..option_count)
+            .map(|index| pda::option_address(event_id, index))
+            .collect()
+    };
+
+    build(
+        accounts::EventOdds {
+            event: pda::event_address(event_id),
+        },
+        instruction::EventOdds { event_id },
+        pools
+            .into_iter()
+            .map(|pool| AccountMeta::new_readonly(pool, false))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::{AnchorDeserialize, Discriminator};
+
+    use super::*;
+
+    const EVENT_ID: u128 = 0x0123_4567_89ab_4cde_8f01_2345_6789_abcd;
+
+    /// Decodes the arguments of an instruction, checking its discriminator
+    fn args<T: AnchorDeserialize + Discriminator>(ix: &Instruction) -> T {
+        assert!(ix.data.starts_with(T::DISCRIMINATOR));
+
+        T::deserialize(&mut &ix.data[T::DISCRIMINATOR.len()..]).unwrap()
+    }
+
+    fn keys(ix: &Instruction) -> Vec<Pubkey> {
+        ix.accounts.iter().map(|meta| meta.pubkey).collect()
+    }
+
+    #[test]
+    fn vote_encoding() {
+        let sender = Pubkey::new_unique();
+        let ix = vote(&sender, EVENT_ID, 2, false, 500, 3);
+
+        assert_eq!(ix.program_id, PROGRAM_ID);
+
+        let data: instruction::Vote = args(&ix);
+        assert_eq!(data.event_id, EVENT_ID);
+        assert_eq!(data.option_ix, 2);
+        assert_eq!(data.amount, 500);
+
+        // Absent optional accounts are passed as the program id
+        assert_eq!(
+            keys(&ix),
+            [
+                sender,
+                pda::user_address(&sender),
+                pda::state_address(),
+                pda::event_address(EVENT_ID),
+                pda::option_address(EVENT_ID, 2),
+                PROGRAM_ID,
+                pda::participation_address(EVENT_ID, &sender),
+                pda::portfolio_address(&sender, 3),
+                system_program::ID,
+            ]
+        );
+        assert!(ix.accounts[0].is_signer && ix.accounts[0].is_writable);
+        assert!(ix.accounts[1..].iter().all(|meta| !meta.is_signer));
+    }
+
+    #[test]
+    fn vote_with_options_table() {
+        let sender = Pubkey::new_unique();
+        let ix = vote(&sender, EVENT_ID, 2, true, 500, 0);
+
+        assert_eq!(ix.accounts[4].pubkey, PROGRAM_ID);
+        assert_eq!(ix.accounts[5].pubkey, pda::options_table_address(EVENT_ID));
+    }
+
+    #[test]
+    fn set_paused_role() {
+        let sender = Pubkey::new_unique();
+
+        let ix = set_paused(&sender, 3, false);
+        assert_eq!(args::<instruction::SetPaused>(&ix).flags, 3);
+        assert_eq!(ix.accounts[1].pubkey, PROGRAM_ID);
+
+        let ix = set_paused(&sender, 3, true);
+        assert_eq!(
+            ix.accounts[1].pubkey,
+            pda::role_address(Role::Guardian, &sender)
+        );
+    }
+
+    #[test]
+    fn settle_batch_remaining_accounts() {
+        let sender = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let positions = [
+            SettlementPosition {
+                participant: Pubkey::new_unique(),
+                portfolio_page: 0,
+            },
+            SettlementPosition {
+                participant: Pubkey::new_unique(),
+                portfolio_page: 4,
+            },
+        ];
+
+        let ix = settle_batch(&sender, EVENT_ID, &authority, 1, false, &positions);
+
+        assert_eq!(args::<instruction::SettleBatch>(&ix).event_id, EVENT_ID);
+
+        let remaining = &ix.accounts[7..];
+        assert_eq!(remaining.len(), 3 * positions.len());
+        assert!(remaining
+            .iter()
+            .all(|meta| meta.is_writable && !meta.is_signer));
+
+        for (accounts, position) in remaining.chunks(3).zip(&positions) {
+            assert_eq!(
+                accounts[0].pubkey,
+                pda::participation_address(EVENT_ID, &position.participant)
+            );
+            assert_eq!(accounts[1].pubkey, pda::user_address(&position.participant));
+            assert_eq!(
+                accounts[2].pubkey,
+                pda::portfolio_address(&position.participant, position.portfolio_page)
+            );
+        }
+    }
+
+    #[test]
+    fn claim_many_pages_are_deduplicated() {
+        let sender = Pubkey::new_unique();
+        let position = |event_id, portfolio_page| ClaimPosition {
+            event_id,
+            option: 0,
+            options_table: false,
+            portfolio_page,
+        };
+        let positions = [
+            position(EVENT_ID, 2),
+            position(EVENT_ID + 1, 0),
+            position(EVENT_ID + 2, 2),
+        ];
+
+        let ix = claim_many(&sender, &positions);
+
+        assert_eq!(args::<instruction::ClaimMany>(&ix).claim_count, 3);
+        assert_eq!(
+            keys(&ix)[4 + 3 * positions.len()..],
+            [
+                pda::portfolio_address(&sender, 0),
+                pda::portfolio_address(&sender, 2)
+            ]
+        );
+    }
+}
@@ -0,0 +1,17 @@
+//! Rust client for the Predictory program: PDA derivation, instruction
+//! builders, account decoding and a minimal JSON-RPC transport.
+
+pub mod accounts;
+pub mod error;
+pub mod event_id;
+pub mod events;
+pub mod instructions;
+pub mod pda;
+pub mod rpc;
+pub mod text;
+pub mod views;
+
+pub use error::ClientError;
+pub use predictory::{self, ID as PROGRAM_ID};
+
+pub type Result<T> = std::result::Result<T, ClientError>;
@@ -0,0 +1,186 @@
+use anchor_lang::{prelude::Pubkey, solana_program::bpf_loader_upgradeable};
+use predictory::{
+    context::CALLBACK_AUTHORITY_SEED,
+    state::{param::ParamKind, role::Role},
+};
+
+use crate::PROGRAM_ID;
+
+fn find(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &PROGRAM_ID).0
+}
+
+pub fn state_address() -> Pubkey {
+    find(&[b"state"])
+}
+
+pub fn treasury_address() -> Pubkey {
+    find(&[b"treasury"])
+}
+
+pub fn program_data_address() -> Pubkey {
+    Pubkey::find_program_address(&[PROGRAM_ID.as_ref()], &bpf_loader_upgradeable::ID).0
+}
+
+pub fn user_address(owner: &Pubkey) -> Pubkey {
+    find(&[b"user", owner.as_ref()])
+}
+
+pub fn event_address(event_id: u128) -> Pubkey {
+    find(&[b"event", &event_id.to_le_bytes()])
+}
+
+pub fn event_meta_address(event_id: u128) -> Pubkey {
+    find(&[b"event_meta", &event_id.to_le_bytes()])
+}
+
+pub fn option_address(event_id: u128, index: u8) -> Pubkey {
+    find(&[b"option", &event_id.to_le_bytes(), &[index]])
+}
+
+pub fn option_meta_address(event_id: u128, index: u8) -> Pubkey {
+    find(&[b"option_meta", &event_id.to_le_bytes(), &[index]])
+}
+
+pub fn options_table_address(event_id: u128) -> Pubkey {
+    find(&[b"options_table", &event_id.to_le_bytes()])
+}
+
+pub fn attestation_address(event_id: u128) -> Pubkey {
+    find(&[b"attestation", &event_id.to_le_bytes()])
+}
+
+pub fn callback_address(event_id: u128) -> Pubkey {
+    find(&[b"callback", &event_id.to_le_bytes()])
+}
+
+/// Signer of the resolution callback of an event, callback programs check it to trust the call
+pub fn callback_authority_address(event_id: u128) -> Pubkey {
+    find(&[CALLBACK_AUTHORITY_SEED, &event_id.to_le_bytes()])
+}
+
+pub fn participation_address(event_id: u128, participant: &Pubkey) -> Pubkey {
+    find(&[
+        b"participation",
+        &event_id.to_le_bytes(),
+        participant.as_ref(),
+    ])
+}
+
+pub fn appeal_address(event_id: u128) -> Pubkey {
+    find(&[b"appeal", &event_id.to_le_bytes()])
+}
+
+pub fn portfolio_address(owner: &Pubkey, page: u16) -> Pubkey {
+    find(&[b"portfolio", owner.as_ref(), &page.to_le_bytes()])
+}
+
+pub fn organizer_address(event_id: u128, member: &Pubkey) -> Pubkey {
+    find(&[b"organizer", &event_id.to_le_bytes(), member.as_ref()])
+}
+
+pub fn role_address(role: Role, member: &Pubkey) -> Pubkey {
+    find(&[b"role", &[role as u8], member.as_ref()])
+}
+
+pub fn param_change_address(kind: ParamKind) -> Pubkey {
+    find(&[b"param_change", &[kind as u8]])
+}
+
+pub fn proposal_address(proposal_id: u64) -> Pubkey {
+    find(&[b"proposal", &proposal_id.to_le_bytes()])
+}
+
+pub fn proposal_vote_address(proposal_id: u64, voter: &Pubkey) -> Pubkey {
+    find(&[b"proposal_vote", &proposal_id.to_le_bytes(), voter.as_ref()])
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    const EVENT_ID: u128 = 0x0123_4567_89ab_4cde_8f01_2345_6789_abcd;
+
+    #[test]
+    fn event_id_seeds_are_little_endian() {
+        let (address, bump) =
+            Pubkey::find_program_address(&[b"event", &EVENT_ID.to_le_bytes()], &predictory::ID);
+
+        assert_eq!(event_address(EVENT_ID), address);
+        assert_ne!(event_address(EVENT_ID.swap_bytes()), address);
+        // The program checks stored bumps without searching
+        assert_eq!(
+            Pubkey::create_program_address(
+                &[b"event", &EVENT_ID.to_le_bytes(), &[bump]],
+                &predictory::ID
+            ),
+            Ok(address)
+        );
+    }
+
+    #[test]
+    fn page_seeds_are_little_endian() {
+        let owner = Pubkey::new_unique();
+
+        assert_eq!(
+            portfolio_address(&owner, 1),
+            find(&[b"portfolio", owner.as_ref(), &[1, 0]])
+        );
+        assert_eq!(
+            proposal_address(258),
+            find(&[b"proposal", &[2, 1, 0, 0, 0, 0, 0, 0]])
+        );
+    }
+
+    #[test]
+    fn event_accounts_are_distinct() {
+        let member = Pubkey::new_unique();
+        let addresses = [
+            event_address(EVENT_ID),
+            event_meta_address(EVENT_ID),
+            option_address(EVENT_ID, 0),
+            option_address(EVENT_ID, 1),
+            option_meta_address(EVENT_ID, 0),
+            options_table_address(EVENT_ID),
+            attestation_address(EVENT_ID),
+            callback_address(EVENT_ID),
+            callback_authority_address(EVENT_ID),
+            participation_address(EVENT_ID, &member),
+            appeal_address(EVENT_ID),
+            organizer_address(EVENT_ID, &member),
+        ];
+
+        assert_eq!(
+            addresses.iter().collect::<HashSet<_>>().len(),
+            addresses.len()
+        );
+    }
+
+    #[test]
+    fn callback_authority_is_per_event() {
+        assert_eq!(
+            callback_authority_address(EVENT_ID),
+            find(&[CALLBACK_AUTHORITY_SEED, &EVENT_ID.to_le_bytes()])
+        );
+        assert_ne!(
+            callback_authority_address(EVENT_ID),
+            callback_authority_address(EVENT_ID + 1)
+        );
+    }
+
+    #[test]
+    fn role_accounts_are_per_role() {
+        let member = Pubkey::new_unique();
+
+        assert_ne!(
+            role_address(Role::Guardian, &member),
+            role_address(Role::Moderator, &member)
+        );
+        assert_eq!(
+            role_address(Role::Guardian, &member),
+            find(&[b"role", &[Role::Guardian as u8], member.as_ref()])
+        );
+    }
+}
@@ -0,0 +1,513 @@
+//! Minimal blocking JSON-RPC transport covering the calls the client,
+//! CLI, indexer and keeper need.
+
+use std::{str::FromStr, thread, time::Duration};
+
+use anchor_lang::AnchorDeserialize;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use solana_sdk::{
+    hash::Hash,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Signature, Signer},
+    transaction::Transaction,
+};
+
+use crate::{ClientError, Result};
+
+pub const DEFAULT_COMMITMENT: &str = "confirmed";
+pub const LOCALNET_URL: &str = "http://127.0.0.1:8899";
+
+/// Interval between signature status polls
+const CONFIRM_POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// Number of signature status polls before giving up
+const CONFIRM_POLL_ATTEMPTS: usize = 60;
+
+#[derive(Debug, Clone)]
+pub struct RpcAccount {
+    pub lamports: u64,
+    pub owner: Pubkey,
+    pub data: Vec<u8>,
+    pub executable: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Simulation {
+    pub err: Option<Value>,
+    pub logs: Vec<String>,
+    pub units_consumed: Option<u64>,
+    pub return_data: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignatureInfo {
+    pub signature: String,
+    pub slot: u64,
+    pub err: Option<Value>,
+    pub block_time: Option<i64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CompiledInstruction {
+    pub program_id_index: u8,
+    pub accounts: Vec<u8>,
+    pub data: Vec<u8>,
+}
+
+/// Transaction as returned by `getTransaction` with `json` encoding
+#[derive(Debug, Clone)]
+pub struct ConfirmedTransaction {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub failed: bool,
+    /// Static keys followed by writable and readonly loaded addresses
+    pub account_keys: Vec<Pubkey>,
+    pub instructions: Vec<CompiledInstruction>,
+    /// Inner instructions, keyed by the index of their outer instruction
+    pub inner_instructions: Vec<(u8, Vec<CompiledInstruction>)>,
+    pub logs: Vec<String>,
+}
+
+pub struct RpcClient {
+    url: String,
+    commitment: String,
+    agent: ureq::Agent,
+}
+
+impl RpcClient {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self::new_with_commitment(url, DEFAULT_COMMITMENT)
+    }
+
+    pub fn new_with_commitment(url: impl Into<String>, commitment: impl Into<String>) -> Self {
+        RpcClient {
+            url: url.into(),
+            commitment: commitment.into(),
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Performs a raw JSON-RPC call and returns its `result` field
+    pub fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let response: Value = self
+            .agent
+            .post(&self.url)
+            .send_json(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": method,
+                "params": params,
+            }))?
+            .into_json()?;
+
+        if let Some(error) = response.get("error") {
+            return Err(ClientError::Rpc {
+                code: error["code"].as_i64().unwrap_or_default(),
+                message: error["message"].as_str().unwrap_or_default().to_string(),
+            });
+        }
+
+        Ok(response["result"].clone())
+    }
+
+    pub fn get_account(&self, address: &Pubkey) -> Result<Option<RpcAccount>> {
+        let result = self.call(
+            "getAccountInfo",
+            json!([address.to_string(), {
+                "encoding": "base64",
+                "commitment": self.commitment,
+            }]),
+        )?;
+
+        match &result["value"] {
+            Value::Null => Ok(None),
+            value => parse_account(value).map(Some),
+        }
+    }
+
+    pub fn get_multiple_accounts(&self, addresses: &[Pubkey]) -> Result<Vec<Option<RpcAccount>>> {
+        let keys: Vec<String> = addresses.iter().map(Pubkey::to_string).collect();
+        let result = self.call(
+            "getMultipleAccounts",
+            json!([keys, {
+                "encoding": "base64",
+                "commitment": self.commitment,
+            }]),
+        )?;
+
+        as_array(&result["value"])?
+            .iter()
+            .map(|value| match value {
+                Value::Null => Ok(None),
+                value => parse_account(value).map(Some),
+            })
+            .collect()
+    }
+
+    /// Returns program accounts whose data starts with `discriminator`
+    pub fn get_program_accounts(
+        &self,
+        program_id: &Pubkey,
+        discriminator: &[u8],
+    ) -> Result<Vec<(Pubkey, RpcAccount)>> {
+        let result = self.call(
+            "getProgramAccounts",
+            json!([program_id.to_string(), {
+                "encoding": "base64",
+                "commitment": self.commitment,
+                "filters": [{
+                    "memcmp": {
+                        "offset": 0,
+                        "bytes": STANDARD.encode(discriminator),
+                        "encoding": "base64",
+                    }
+                }],
+            }]),
+        )?;
+
+        as_array(&result)?
+            .iter()
+            .map(|entry| {
+                Ok((
+                    parse_pubkey(&entry["pubkey"])?,
+                    parse_account(&entry["account"])?,
+                ))
+            })
+            .collect()
+    }
+
+    pub fn get_balance(&self, address: &Pubkey) -> Result<u64> {
+        let result = self.call(
+            "getBalance",
+            json!([address.to_string(), { "commitment": self.commitment }]),
+        )?;
+
+        as_u64(&result["value"])
+    }
+
+    pub fn get_slot(&self) -> Result<u64> {
+        as_u64(&self.call("getSlot", json!([{ "commitment": self.commitment }]))?)
+    }
+
+    pub fn get_latest_blockhash(&self) -> Result<Hash> {
+        let result = self.call(
+            "getLatestBlockhash",
+            json!([{ "commitment": self.commitment }]),
+        )?;
+
+        Hash::from_str(as_str(&result["value"]["blockhash"])?)
+            .map_err(|error| ClientError::Decode(error.to_string()))
+    }
+
+    pub fn request_airdrop(&self, address: &Pubkey, lamports: u64) -> Result<Signature> {
+        let result = self.call(
+            "requestAirdrop",
+            json!([address.to_string(), lamports, { "commitment": self.commitment }]),
+        )?;
+
+        parse_signature(&result)
+    }
+
+    pub fn send_transaction(&self, transaction: &Transaction) -> Result<Signature> {
+        let result = self.call(
+            "sendTransaction",
+            json!([encode_transaction(transaction)?, {
+                "encoding": "base64",
+                "preflightCommitment": self.commitment,
+            }]),
+        )?;
+
+        parse_signature(&result)
+    }
+
+    pub fn simulate_transaction(&self, transaction: &Transaction) -> Result<Simulation> {
+        let result = self.call(
+            "simulateTransaction",
+            json!([encode_transaction(transaction)?, {
+                "encoding": "base64",
+                "commitment": self.commitment,
+                "sigVerify": false,
+                "replaceRecentBlockhash": true,
+            }]),
+        )?;
+        let value = &result["value"];
+
+        let return_data = match value["returnData"]["data"][0].as_str() {
+            Some(data) => Some(
+                STANDARD
+                    .decode(data)
+                    .map_err(|error| ClientError::Decode(error.to_string()))?,
+            ),
+            None => None,
+        };
+
+        Ok(Simulation {
+            err: Some(value["err"].clone()).filter(|err| !err.is_null()),
+            logs: string_list(&value["logs"]),
+            units_consumed: value["unitsConsumed"].as_u64(),
+            return_data,
+        })
+    }
+
+    /// Simulates a view instruction and decodes its return data
+    pub fn view<T: AnchorDeserialize>(
+        &self,
+        instruction: Instruction,
+        payer: &Pubkey,
+    ) -> Result<T> {
+        let mut transaction = Transaction::new_with_payer(&[instruction], Some(payer));
+        transaction.message.recent_blockhash = self.get_latest_blockhash()?;
+
+        let simulation = self.simulate_transaction(&transaction)?;
+
+        if let Some(err) = simulation.err {
+            return Err(ClientError::Transaction(format!(
+                "{err}: {}",
+                simulation.logs.join("\n")
+            )));
+        }
+
+        let data = simulation
+            .return_data
+            .ok_or_else(|| ClientError::Decode("instruction returned no data".to_string()))?;
+
+        T::try_from_slice(&data).map_err(|error| ClientError::Decode(error.to_string()))
+    }
+
+    /// Polls the signature status until the configured commitment is reached
+    pub fn confirm_transaction(&self, signature: &Signature) -> Result<()> {
+        for _ in 0..CONFIRM_POLL_ATTEMPTS {
+            let result = self.call(
+                "getSignatureStatuses",
+                json!([[signature.to_string()], { "searchTransactionHistory": false }]),
+            )?;
+            let status = &result["value"][0];
+
+            if !status.is_null() {
+                if !status["err"].is_null() {
+                    return Err(ClientError::Transaction(status["err"].to_string()));
+                }
+
+                let reached = match status["confirmationStatus"].as_str() {
+                    Some("finalized") => true,
+                    Some("confirmed") => self.commitment != "finalized",
+                    Some(_) => self.commitment == "processed",
+                    None => false,
+                };
+
+                if reached {
+                    return Ok(());
+                }
+            }
+
+            thread::sleep(CONFIRM_POLL_INTERVAL);
+        }
+
+        Err(ClientError::Transaction(format!(
+            "transaction {signature} was not confirmed in time"
+        )))
+    }
+
+    pub fn send_and_confirm(&self, transaction: &Transaction) -> Result<Signature> {
+        let signature = self.send_transaction(transaction)?;
+        self.confirm_transaction(&signature)?;
+
+        Ok(signature)
+    }
+
+    /// Builds, signs, sends and confirms a transaction paid by the first signer
+    pub fn send_instructions(
+        &self,
+        instructions: &[Instruction],
+        signers: &[&dyn Signer],
+    ) -> Result<Signature> {
+        let transaction = self.sign_instructions(instructions, signers)?;
+
+        self.send_and_confirm(&transaction)
+    }
+
+    /// Builds and signs a transaction paid by the first signer
+    pub fn sign_instructions(
+        &self,
+        instructions: &[Instruction],
+        signers: &[&dyn Signer],
+    ) -> Result<Transaction> {
+        let payer = signers
+            .first()
+            .ok_or_else(|| ClientError::Transaction("missing fee payer".to_string()))?;
+
+        Ok(Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            signers,
+            self.get_latest_blockhash()?,
+        ))
+    }
+
+    /// Returns signatures for `address`, newest first
+    pub fn get_signatures_for_address(
+        &self,
+        address: &Pubkey,
+        before: Option<&str>,
+        until: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<SignatureInfo>> {
+        let result = self.call(
+            "getSignaturesForAddress",
+            json!([address.to_string(), {
+                "commitment": self.commitment,
+                "before": before,
+                "until": until,
+                "limit": limit,
+            }]),
+        )?;
+
+        serde_json::from_value(result).map_err(|error| ClientError::Decode(error.to_string()))
+    }
+
+    pub fn get_transaction(&self, signature: &str) -> Result<Option<ConfirmedTransaction>> {
+        let result = self.call(
+            "getTransaction",
+            json!([signature, {
+                "encoding": "json",
+                "commitment": self.commitment,
+                "maxSupportedTransactionVersion": 0,
+            }]),
+        )?;
+
+        if result.is_null() {
+            return Ok(None);
+        }
+
+        ConfirmedTransaction::from_json(&result).map(Some)
+    }
+}
+
+impl ConfirmedTransaction {
+    /// Parses a `getTransaction` result requested with `json` encoding
+    pub fn from_json(result: &Value) -> Result<Self> {
+        let message = &result["transaction"]["message"];
+        let meta = &result["meta"];
+
+        let mut account_keys = as_array(&message["accountKeys"])?
+            .iter()
+            .map(parse_pubkey)
+            .collect::<Result<Vec<_>>>()?;
+
+        for kind in ["writable", "readonly"] {
+            if let Some(loaded) = meta["loadedAddresses"][kind].as_array() {
+                for key in loaded {
+                    account_keys.push(parse_pubkey(key)?);
+                }
+            }
+        }
+
+        let inner_instructions = match meta["innerInstructions"].as_array() {
+            Some(inner) => inner
+                .iter()
+                .map(|entry| {
+                    Ok((
+                        as_u64(&entry["index"])? as u8,
+                        parse_instructions(&entry["instructions"])?,
+                    ))
+                })
+                .collect::<Result<Vec<_>>>()?,
+            None => vec![],
+        };
+
+        Ok(ConfirmedTransaction {
+            signature: as_str(&result["transaction"]["signatures"][0])?.to_string(),
+            slot: as_u64(&result["slot"])?,
+            block_time: result["blockTime"].as_i64(),
+            failed: !meta["err"].is_null(),
+            account_keys,
+            instructions: parse_instructions(&message["instructions"])?,
+            inner_instructions,
+            logs: string_list(&meta["logMessages"]),
+        })
+    }
+}
+
+fn encode_transaction(transaction: &Transaction) -> Result<String> {
+    let bytes =
+        bincode::serialize(transaction).map_err(|error| ClientError::Decode(error.to_string()))?;
+
+    Ok(STANDARD.encode(bytes))
+}
+
+fn parse_account(value: &Value) -> Result<RpcAccount> {
+    Ok(RpcAccount {
+        lamports: as_u64(&value["lamports"])?,
+        owner: parse_pubkey(&value["owner"])?,
+        data: STANDARD
+            .decode(as_str(&value["data"][0])?)
+            .map_err(|error| ClientError::Decode(error.to_string()))?,
+        executable: value["executable"].as_bool().unwrap_or_default(),
+    })
+}
+
+fn parse_instructions(value: &Value) -> Result<Vec<CompiledInstruction>> {
+    as_array(value)?
+        .iter()
+        .map(|instruction| {
+            Ok(CompiledInstruction {
+                program_id_index: as_u64(&instruction["programIdIndex"])? as u8,
+                accounts: as_array(&instruction["accounts"])?
+                    .iter()
+                    .map(|index| as_u64(index).map(|index| index as u8))
+                    .collect::<Result<Vec<_>>>()?,
+                data: bs58::decode(as_str(&instruction["data"])?)
+                    .into_vec()
+                    .map_err(|error| ClientError::Decode(error.to_string()))?,
+            })
+        })
+        .collect()
+}
+
+fn parse_pubkey(value: &Value) -> Result<Pubkey> {
+    Pubkey::from_str(as_str(value)?).map_err(|error| ClientError::Decode(error.to_string()))
+}
+
+fn parse_signature(value: &Value) -> Result<Signature> {
+    Signature::from_str(as_str(value)?).map_err(|error| ClientError::Decode(error.to_string()))
+}
+
+fn as_str(value: &Value) -> Result<&str> {
+    value
+        .as_str()
+        .ok_or_else(|| ClientError::Decode(format!("expected string, got {value}")))
+}
+
+fn as_u64(value: &Value) -> Result<u64> {
+    value
+        .as_u64()
+        .ok_or_else(|| ClientError::Decode(format!("expected integer, got {value}")))
+}
+
+fn as_array(value: &Value) -> Result<&Vec<Value>> {
+    value
+        .as_array()
+        .ok_or_else(|| ClientError::Decode(format!("expected array, got {value}")))
+}
+
+fn string_list(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|values| {
+            values
+                .iter()
+                .filter_map(|value| value.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
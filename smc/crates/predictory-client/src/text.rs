@@ -0,0 +1,28 @@
+use crate::{ClientError, Result};
+
+/// Encodes a string into the zero-padded byte array the program stores
+pub fn to_fixed<const N: usize>(value: &str) -> Result<[u8; N]> {
+    let bytes = value.as_bytes();
+
+    if bytes.len() > N {
+        return Err(ClientError::Decode(format!(
+            "text is {} bytes long, at most {N} fit",
+            bytes.len()
+        )));
+    }
+
+    let mut buffer = [0u8; N];
+    buffer[..bytes.len()].copy_from_slice(bytes);
+
+    Ok(buffer)
+}
+
+/// Decodes a zero-padded byte array, dropping the padding
+pub fn from_fixed(bytes: &[u8]) -> String {
+    let end = bytes
+        .iter()
+        .rposition(|byte| *byte != 0)
+        .map_or(0, |position| position + 1);
+
+    String::from_utf8_lossy(&bytes[..end]).into_owned()
+}
@@ -0,0 +1,724 @@
+//! Serializable views of program accounts and view-instruction results.
+//!
+//! Keys are rendered as base58 strings, event ids as UUIDs and fixed-size
+//! text fields as trimmed UTF-8, so the same types serve CLI output and
+//! API responses.
+
+use anchor_lang::prelude::Pubkey;
+use predictory::{
+    context::{ClaimQuote, ClaimResult, ClaimStatus, Odds, OptionOdds, VoteQuote},
+    state::{
+        appeal::Appellation,
+        attestation::{ResolutionAttestation, ResolutionStatus},
+        callback::EventCallback,
+        contract_state::State,
+        event::{Event, EventMeta},
+        governance::{Proposal, ProposalVote},
+        option::{EventOption, EventOptionMeta, OptionsTable},
+        organizer::EventOrganizer,
+        param::{ParamChange, ParamKind},
+        participation::Participation,
+        portfolio::{Portfolio, PortfolioEntry, PositionStatus},
+        role::{Role, RoleMember},
+        treasury::{Treasury, TreasuryRecipient},
+        user::User,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{accounts::ProgramAccount, event_id::format_event_id, text::from_fixed};
+
+fn key(value: &Pubkey) -> String {
+    value.to_string()
+}
+
+pub fn param_kind_name(kind: ParamKind) -> &'static str {
+    match kind {
+        ParamKind::Multiplier => "multiplier",
+        ParamKind::EventPrice => "event_price",
+        ParamKind::PlatformFee => "platform_fee",
+        ParamKind::OrgReward => "org_reward",
+    }
+}
+
+pub fn role_name(role: Role) -> &'static str {
+    match role {
+        Role::FeeManager => "fee_manager",
+        Role::Moderator => "moderator",
+        Role::Resolver => "resolver",
+        Role::Guardian => "guardian",
+    }
+}
+
+pub fn resolution_status_name(status: ResolutionStatus) -> &'static str {
+    match status {
+        ResolutionStatus::Pending => "pending",
+        ResolutionStatus::Resolved => "resolved",
+        ResolutionStatus::Canceled => "canceled",
+    }
+}
+
+pub fn position_status_name(status: PositionStatus) -> &'static str {
+    match status {
+        PositionStatus::Open => "open",
+        PositionStatus::Appealed => "appealed",
+        PositionStatus::Won => "won",
+        PositionStatus::Lost => "lost",
+        PositionStatus::Refundable => "refundable",
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateView {
+    pub version: u8,
+    pub authority: String,
+    pub multiplier: u64,
+    pub event_price: u64,
+    pub platform_fee: u64,
+    pub org_reward: u64,
+    pub paused: u8,
+    pub pending_authority: Option<String>,
+    pub authority_transfer_eta: i64,
+    pub authority_timelock: i64,
+    pub proposal_count: u64,
+}
+
+impl From<&State> for StateView {
+    fn from(state: &State) -> Self {
+        StateView {
+            version: state.version,
+            authority: key(&state.authority),
+            multiplier: state.multiplier,
+            event_price: state.event_price,
+            platform_fee: state.platform_fee,
+            org_reward: state.org_reward,
+            paused: state.paused,
+            pending_authority: state.pending_authority.as_ref().map(key),
+            authority_transfer_eta: state.authority_transfer_eta,
+            authority_timelock: state.authority_timelock,
+            proposal_count: state.proposal_count,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TreasuryRecipientView {
+    pub wallet: String,
+    pub share_bps: u16,
+}
+
+impl From<&TreasuryRecipient> for TreasuryRecipientView {
+    fn from(recipient: &TreasuryRecipient) -> Self {
+        TreasuryRecipientView {
+            wallet: key(&recipient.wallet),
+            share_bps: recipient.share_bps,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TreasuryView {
+    pub version: u8,
+    pub recipients: Vec<TreasuryRecipientView>,
+    pub total_collected: u64,
+    pub total_distributed: u64,
+}
+
+impl From<&Treasury> for TreasuryView {
+    fn from(treasury: &Treasury) -> Self {
+        TreasuryView {
+            version: treasury.version,
+            recipients: treasury.recipients.iter().map(Into::into).collect(),
+            total_collected: treasury.total_collected,
+            total_distributed: treasury.total_distributed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserView {
+    pub version: u8,
+    pub owner: String,
+    pub name: String,
+    pub stake: u64,
+    pub locked_stake: u64,
+    pub trust_lvl: u64,
+    pub trust_raised_at: Option<i64>,
+}
+
+impl From<&User> for UserView {
+    fn from(user: &User) -> Self {
+        UserView {
+            version: user.version,
+            owner: key(&user.payer),
+            name: from_fixed(&user.name),
+            stake: user.stake,
+            locked_stake: user.locked_stake,
+            trust_lvl: user.trust_lvl,
+            trust_raised_at: Some(user.trust_raised_at).filter(|raised_at| *raised_at != 0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventView {
+    pub version: u8,
+    pub id: String,
+    pub authority: String,
+    pub stake: u64,
+    pub start_date: i64,
+    pub end_date: i64,
+    pub participation_deadline: Option<i64>,
+    pub option_count: u8,
+    pub participation_count: u64,
+    pub total_amount: u64,
+    pub total_trust: u64,
+    pub canceled: bool,
+    pub result: Option<u8>,
+    pub organizer_shares: u8,
+    pub org_reward_amount: u64,
+    pub pending_authority: Option<String>,
+    pub disputed: bool,
+    pub published: bool,
+    pub frozen: bool,
+    pub platform_fee: u64,
+    pub org_reward: u64,
+    pub options_table: bool,
+    pub callback: bool,
+}
+
+impl From<&Event> for EventView {
+    fn from(event: &Event) -> Self {
+        EventView {
+            version: event.version,
+            id: format_event_id(event.id()),
+            authority: key(&event.authority),
+            stake: event.stake,
+            start_date: event.start_date,
+            end_date: event.end_date,
+            participation_deadline: event.participation_deadline(),
+            option_count: event.option_count,
+            participation_count: event.participation_count,
+            total_amount: event.total_amount,
+            total_trust: event.total_trust,
+            canceled: event.canceled != 0,
+            result: event.result(),
+            organizer_shares: event.organizer_shares,
+            org_reward_amount: event.org_reward_amount,
+            pending_authority: event.pending_authority().as_ref().map(key),
+            disputed: event.disputed != 0,
+            published: event.published != 0,
+            frozen: event.frozen != 0,
+            platform_fee: event.platform_fee,
+            org_reward: event.org_reward,
+            options_table: event.options_table != 0,
+            callback: event.callback != 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventMetaView {
+    pub version: u8,
+    pub event_id: String,
+    pub is_private: bool,
+    pub hidden: bool,
+    pub name: String,
+    pub description: String,
+}
+
+impl From<&EventMeta> for EventMetaView {
+    fn from(meta: &EventMeta) -> Self {
+        EventMetaView {
+            version: meta.version,
+            event_id: format_event_id(meta.event_id),
+            is_private: meta.is_private,
+            hidden: meta.hidden,
+            name: from_fixed(&meta.name),
+            description: from_fixed(&meta.description),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventOptionView {
+    pub version: u8,
+    pub event_id: String,
+    pub index: u8,
+    /// Kept in the option meta account, absent when only the option pool is read
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub votes: u64,
+    pub vault_balance: u64,
+}
+
+impl EventOptionView {
+    pub fn with_meta(option: &EventOption, meta: &EventOptionMeta) -> Self {
+        EventOptionView {
+            description: Some(from_fixed(&meta.description)),
+            ..option.into()
+        }
+    }
+
+    /// Option of an event keeping its pools in the options table
+    pub fn from_table(table: &OptionsTable, meta: &EventOptionMeta) -> Self {
+        let index = meta.index as usize;
+
+        EventOptionView {
+            version: table.version,
+            event_id: format_event_id(meta.event_id),
+            index: meta.index,
+            description: Some(from_fixed(&meta.description)),
+            votes: table.votes[index],
+            vault_balance: table.vault_balances[index],
+        }
+    }
+}
+
+impl From<&EventOption> for EventOptionView {
+    fn from(option: &EventOption) -> Self {
+        EventOptionView {
+            version: option.version,
+            event_id: format_event_id(option.event_id()),
+            index: option.index,
+            description: None,
+            votes: option.votes,
+            vault_balance: option.vault_balance,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventOptionMetaView {
+    pub version: u8,
+    pub event_id: String,
+    pub index: u8,
+    pub description: String,
+}
+
+impl From<&EventOptionMeta> for EventOptionMetaView {
+    fn from(meta: &EventOptionMeta) -> Self {
+        EventOptionMetaView {
+            version: meta.version,
+            event_id: format_event_id(meta.event_id),
+            index: meta.index,
+            description: from_fixed(&meta.description),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptionsTableView {
+    pub version: u8,
+    pub event_id: String,
+    /// Votes per option slot, unused slots included
+    pub votes: Vec<u64>,
+    /// Vault balance per option slot, unused slots included
+    pub vault_balances: Vec<u64>,
+}
+
+impl From<&OptionsTable> for OptionsTableView {
+    fn from(table: &OptionsTable) -> Self {
+        OptionsTableView {
+            version: table.version,
+            event_id: format_event_id(table.event_id()),
+            votes: table.votes.to_vec(),
+            vault_balances: table.vault_balances.to_vec(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventCallbackView {
+    pub version: u8,
+    pub event_id: String,
+    pub program: String,
+    /// Last result the program accepted
+    pub notified_result: Option<u8>,
+}
+
+impl From<&EventCallback> for EventCallbackView {
+    fn from(callback: &EventCallback) -> Self {
+        EventCallbackView {
+            version: callback.version,
+            event_id: format_event_id(callback.event_id),
+            program: key(&callback.program),
+            notified_result: callback.notified.then_some(callback.notified_result),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolutionAttestationView {
+    pub version: u8,
+    pub event_id: String,
+    pub status: String,
+    pub result: Option<u8>,
+    pub finalized_slot: Option<u64>,
+    pub resolver: String,
+}
+
+impl From<&ResolutionAttestation> for ResolutionAttestationView {
+    fn from(attestation: &ResolutionAttestation) -> Self {
+        ResolutionAttestationView {
+            version: attestation.version,
+            event_id: format_event_id(attestation.event_id),
+            status: resolution_status_name(attestation.status).to_string(),
+            result: (attestation.status != ResolutionStatus::Canceled)
+                .then_some(attestation.result),
+            finalized_slot: Some(attestation.finalized_slot).filter(|slot| *slot != 0),
+            resolver: key(&attestation.resolver),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventOrganizerView {
+    pub version: u8,
+    pub event_id: String,
+    pub member: String,
+    pub permissions: u8,
+    pub reward_share: u8,
+    pub reward_claimed: bool,
+}
+
+impl From<&EventOrganizer> for EventOrganizerView {
+    fn from(organizer: &EventOrganizer) -> Self {
+        EventOrganizerView {
+            version: organizer.version,
+            event_id: format_event_id(organizer.event_id),
+            member: key(&organizer.member),
+            permissions: organizer.permissions,
+            reward_share: organizer.reward_share,
+            reward_claimed: organizer.reward_claimed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParticipationView {
+    pub version: u8,
+    pub event_id: String,
+    pub participant: String,
+    pub option: u8,
+    pub deposited_amount: u64,
+    pub is_claimed: bool,
+    pub appealed: bool,
+    pub portfolio_page: u16,
+}
+
+impl From<&Participation> for ParticipationView {
+    fn from(participation: &Participation) -> Self {
+        ParticipationView {
+            version: participation.version,
+            event_id: format_event_id(participation.event_id()),
+            participant: key(&participation.payer),
+            option: participation.option,
+            deposited_amount: participation.deposited_amount,
+            is_claimed: participation.is_claimed != 0,
+            appealed: participation.appealed != 0,
+            portfolio_page: participation.portfolio_page,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppellationView {
+    pub version: u8,
+    pub event_id: String,
+    pub disagree_count: u64,
+    pub disagree_trust_lvl: u64,
+    pub disagree_volume: u64,
+}
+
+impl From<&Appellation> for AppellationView {
+    fn from(appellation: &Appellation) -> Self {
+        AppellationView {
+            version: appellation.version,
+            event_id: format_event_id(appellation.event_id),
+            disagree_count: appellation.disagree_count,
+            disagree_trust_lvl: appellation.disagree_trust_lvl,
+            disagree_volume: appellation.disagree_volume,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortfolioEntryView {
+    pub event_id: String,
+    pub option: u8,
+    pub amount: u64,
+    pub status: String,
+}
+
+impl From<&PortfolioEntry> for PortfolioEntryView {
+    fn from(entry: &PortfolioEntry) -> Self {
+        PortfolioEntryView {
+            event_id: format_event_id(entry.event_id),
+            option: entry.option,
+            amount: entry.amount,
+            status: position_status_name(entry.status).to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortfolioView {
+    pub version: u8,
+    pub owner: String,
+    pub page: u16,
+    pub entries: Vec<PortfolioEntryView>,
+}
+
+impl From<&Portfolio> for PortfolioView {
+    fn from(portfolio: &Portfolio) -> Self {
+        PortfolioView {
+            version: portfolio.version,
+            owner: key(&portfolio.owner),
+            page: portfolio.page,
+            entries: portfolio.entries.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoleMemberView {
+    pub version: u8,
+    pub role: String,
+    pub member: String,
+}
+
+impl From<&RoleMember> for RoleMemberView {
+    fn from(role_member: &RoleMember) -> Self {
+        RoleMemberView {
+            version: role_member.version,
+            role: role_name(role_member.role).to_string(),
+            member: key(&role_member.member),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParamChangeView {
+    pub version: u8,
+    pub kind: String,
+    pub value: u64,
+    pub effective_at: i64,
+    pub pending: bool,
+}
+
+impl From<&ParamChange> for ParamChangeView {
+    fn from(change: &ParamChange) -> Self {
+        ParamChangeView {
+            version: change.version,
+            kind: param_kind_name(change.kind).to_string(),
+            value: change.value,
+            effective_at: change.effective_at,
+            pending: change.pending,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposalView {
+    pub version: u8,
+    pub id: u64,
+    pub proposer: String,
+    pub kind: String,
+    pub value: u64,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub voting_end_date: i64,
+    pub executed: bool,
+}
+
+impl From<&Proposal> for ProposalView {
+    fn from(proposal: &Proposal) -> Self {
+        ProposalView {
+            version: proposal.version,
+            id: proposal.id,
+            proposer: key(&proposal.proposer),
+            kind: param_kind_name(proposal.kind).to_string(),
+            value: proposal.value,
+            votes_for: proposal.votes_for,
+            votes_against: proposal.votes_against,
+            voting_end_date: proposal.voting_end_date,
+            executed: proposal.executed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposalVoteView {
+    pub version: u8,
+    pub proposal_id: u64,
+    pub voter: String,
+    pub weight: u64,
+    pub support: bool,
+}
+
+impl From<&ProposalVote> for ProposalVoteView {
+    fn from(vote: &ProposalVote) -> Self {
+        ProposalVoteView {
+            version: vote.version,
+            proposal_id: vote.proposal_id,
+            voter: key(&vote.voter),
+            weight: vote.weight,
+            support: vote.support,
+        }
+    }
+}
+
+/// Any program account, tagged with its type name
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data")]
+pub enum AccountView {
+    State(StateView),
+    Treasury(TreasuryView),
+    User(UserView),
+    Event(EventView),
+    EventMeta(EventMetaView),
+    EventOption(EventOptionView),
+    EventOptionMeta(EventOptionMetaView),
+    OptionsTable(OptionsTableView),
+    EventCallback(EventCallbackView),
+    ResolutionAttestation(ResolutionAttestationView),
+    EventOrganizer(EventOrganizerView),
+    Participation(ParticipationView),
+    Appellation(AppellationView),
+    Portfolio(PortfolioView),
+    RoleMember(RoleMemberView),
+    ParamChange(ParamChangeView),
+    Proposal(ProposalView),
+    ProposalVote(ProposalVoteView),
+}
+
+impl From<&ProgramAccount> for AccountView {
+    fn from(account: &ProgramAccount) -> Self {
+        match account {
+            ProgramAccount::State(state) => Self::State(state.into()),
+            ProgramAccount::Treasury(treasury) => Self::Treasury(treasury.into()),
+            ProgramAccount::User(user) => Self::User(user.into()),
+            ProgramAccount::Event(event) => Self::Event(event.into()),
+            ProgramAccount::EventMeta(meta) => Self::EventMeta(meta.into()),
+            ProgramAccount::EventOption(option) => Self::EventOption(option.into()),
+            ProgramAccount::EventOptionMeta(meta) => Self::EventOptionMeta(meta.into()),
+            ProgramAccount::OptionsTable(table) => Self::OptionsTable(table.into()),
+            ProgramAccount::EventCallback(callback) => Self::EventCallback(callback.into()),
+            ProgramAccount::ResolutionAttestation(attestation) => {
+                Self::ResolutionAttestation(attestation.into())
+            }
+            ProgramAccount::EventOrganizer(organizer) => Self::EventOrganizer(organizer.into()),
+            ProgramAccount::Participation(participation) => {
+                Self::Participation(participation.into())
+            }
+            ProgramAccount::Appellation(appellation) => Self::Appellation(appellation.into()),
+            ProgramAccount::Portfolio(portfolio) => Self::Portfolio(portfolio.into()),
+            ProgramAccount::RoleMember(role_member) => Self::RoleMember(role_member.into()),
+            ProgramAccount::ParamChange(change) => Self::ParamChange(change.into()),
+            ProgramAccount::Proposal(proposal) => Self::Proposal(proposal.into()),
+            ProgramAccount::ProposalVote(vote) => Self::ProposalVote(vote.into()),
+        }
+    }
+}
+
+// -------------------------- Return data -------------------------- //
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoteQuoteView {
+    pub payout: u64,
+    pub trust_reward_win: u64,
+    pub trust_reward_loss: u64,
+}
+
+impl From<&VoteQuote> for VoteQuoteView {
+    fn from(quote: &VoteQuote) -> Self {
+        VoteQuoteView {
+            payout: quote.payout,
+            trust_reward_win: quote.trust_reward_win,
+            trust_reward_loss: quote.trust_reward_loss,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaimQuoteView {
+    pub resolved: bool,
+    pub won: bool,
+    pub claimed: bool,
+    pub payout: u64,
+    pub trust_reward: u64,
+}
+
+impl From<&ClaimQuote> for ClaimQuoteView {
+    fn from(quote: &ClaimQuote) -> Self {
+        ClaimQuoteView {
+            resolved: quote.resolved,
+            won: quote.won,
+            claimed: quote.claimed,
+            payout: quote.payout,
+            trust_reward: quote.trust_reward,
+        }
+    }
+}
+
+pub fn claim_status_name(status: ClaimStatus) -> &'static str {
+    match status {
+        ClaimStatus::Claimed => "claimed",
+        ClaimStatus::AlreadyClaimed => "already_claimed",
+        ClaimStatus::NotClaimable => "not_claimable",
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaimResultView {
+    pub event_id: String,
+    pub status: String,
+    pub amount: u64,
+}
+
+impl From<&ClaimResult> for ClaimResultView {
+    fn from(result: &ClaimResult) -> Self {
+        ClaimResultView {
+            event_id: format_event_id(result.event_id),
+            status: claim_status_name(result.status).to_string(),
+            amount: result.amount,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptionOddsView {
+    pub index: u8,
+    pub votes: u64,
+    pub vault_balance: u64,
+    pub payout_per_sol: u64,
+}
+
+impl From<&OptionOdds> for OptionOddsView {
+    fn from(odds: &OptionOdds) -> Self {
+        OptionOddsView {
+            index: odds.index,
+            votes: odds.votes,
+            vault_balance: odds.vault_balance,
+            payout_per_sol: odds.payout_per_sol,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OddsView {
+    pub total_amount: u64,
+    pub available_for_winners: u64,
+    pub options: Vec<OptionOddsView>,
+}
+
+impl From<&Odds> for OddsView {
+    fn from(odds: &Odds) -> Self {
+        OddsView {
+            total_amount: odds.total_amount,
+            available_for_winners: odds.available_for_winners,
+            options: odds.options.iter().map(Into::into).collect(),
+        }
+    }
+}
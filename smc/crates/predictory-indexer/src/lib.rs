@@ -0,0 +1,11 @@
+//! Indexes Predictory transactions into SQLite.
+//!
+//! Transactions are read from an RPC node or from `getTransaction` JSON
+//! dumps, decoded with the program's instruction and event types, and
+//! written into the normalized tables described in [`schema`].
+
+pub mod schema;
+pub mod source;
+pub mod store;
+
+pub use store::Store;
@@ -0,0 +1,95 @@
+//! Indexer daemon for the Predictory program.
+
+use std::{path::PathBuf, thread, time::Duration};
+
+use clap::{Parser, Subcommand};
+use predictory_client::rpc::{RpcClient, LOCALNET_URL};
+use predictory_indexer::{source, Store};
+
+#[derive(Parser)]
+#[command(
+    name = "predictory-indexer",
+    version,
+    about = "Index Predictory transactions into SQLite"
+)]
+struct Cli {
+    /// SQLite database file
+    #[arg(
+        long,
+        global = true,
+        env = "PREDICTORY_DB",
+        default_value = "predictory.db"
+    )]
+    db: PathBuf,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Index program transactions from an RPC node
+    Sync {
+        /// RPC endpoint
+        #[arg(long, env = "ANCHOR_PROVIDER_URL", default_value = LOCALNET_URL)]
+        url: String,
+        /// Keep polling for new transactions
+        #[arg(long)]
+        follow: bool,
+        /// Polling interval, in seconds
+        #[arg(long, default_value_t = 5)]
+        interval: u64,
+        /// Process every transaction again, ignoring the stored cursor
+        #[arg(long)]
+        reprocess: bool,
+    },
+    /// Index `getTransaction` JSON dumps
+    Import {
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+    },
+    /// Drop every indexed row, keeping the schema
+    Reset,
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let mut store = Store::open(&cli.db)?;
+
+    match cli.command {
+        Command::Sync {
+            url,
+            follow,
+            interval,
+            reprocess,
+        } => {
+            let rpc = RpcClient::new(url);
+            let mut reprocess = reprocess;
+
+            loop {
+                let count = source::sync(&mut store, &rpc, reprocess)?;
+                reprocess = false;
+
+                if count > 0 || !follow {
+                    println!("Indexed {count} transactions");
+                }
+
+                if !follow {
+                    break;
+                }
+
+                thread::sleep(Duration::from_secs(interval));
+            }
+        }
+        Command::Import { paths } => {
+            for path in paths {
+                let count = source::import(&mut store, &path)?;
+
+                println!("Indexed {count} transactions from {}", path.display());
+            }
+        }
+        Command::Reset => store.reset()?,
+    }
+
+    Ok(())
+}
@@ -0,0 +1,183 @@
+//! SQLite schema of the index.
+//!
+//! Rows derived from a transaction are keyed by its signature, so a
+//! transaction can be processed again without duplicating data. Current
+//! state tables keep the slot of their last update and ignore older writes.
+
+use rusqlite::Connection;
+
+pub const SCHEMA_VERSION: i64 = 1;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS transactions (
+    signature   TEXT PRIMARY KEY,
+    slot        INTEGER NOT NULL,
+    block_time  INTEGER,
+    failed      INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS instructions (
+    signature   TEXT NOT NULL,
+    ix_index    INTEGER NOT NULL,
+    inner_index INTEGER NOT NULL,
+    name        TEXT,
+    accounts    TEXT NOT NULL,
+    PRIMARY KEY (signature, ix_index, inner_index)
+);
+
+CREATE TABLE IF NOT EXISTS event_log (
+    signature   TEXT NOT NULL,
+    seq         INTEGER NOT NULL,
+    slot        INTEGER NOT NULL,
+    name        TEXT NOT NULL,
+    data        TEXT NOT NULL,
+    PRIMARY KEY (signature, seq)
+);
+
+CREATE TABLE IF NOT EXISTS users (
+    address      TEXT PRIMARY KEY,
+    name         TEXT NOT NULL DEFAULT '',
+    stake        INTEGER NOT NULL DEFAULT 0,
+    trust_lvl    INTEGER NOT NULL DEFAULT 0,
+    created_slot INTEGER,
+    updated_slot INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS events (
+    event_id               TEXT PRIMARY KEY,
+    authority              TEXT NOT NULL,
+    name                   TEXT NOT NULL DEFAULT '',
+    description            TEXT NOT NULL DEFAULT '',
+    is_private             INTEGER NOT NULL DEFAULT 0,
+    status                 TEXT NOT NULL,
+    start_date             INTEGER,
+    end_date               INTEGER,
+    participation_deadline INTEGER,
+    stake                  INTEGER NOT NULL DEFAULT 0,
+    platform_fee           INTEGER NOT NULL DEFAULT 0,
+    org_reward             INTEGER NOT NULL DEFAULT 0,
+    result                 INTEGER,
+    total_amount           INTEGER NOT NULL DEFAULT 0,
+    frozen                 INTEGER NOT NULL DEFAULT 0,
+    hidden                 INTEGER NOT NULL DEFAULT 0,
+    created_slot           INTEGER,
+    updated_slot           INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS options (
+    event_id      TEXT NOT NULL,
+    idx           INTEGER NOT NULL,
+    description   TEXT NOT NULL DEFAULT '',
+    votes         INTEGER NOT NULL DEFAULT 0,
+    vault_balance INTEGER NOT NULL DEFAULT 0,
+    updated_slot  INTEGER NOT NULL,
+    PRIMARY KEY (event_id, idx)
+);
+
+CREATE TABLE IF NOT EXISTS votes (
+    signature            TEXT NOT NULL,
+    seq                  INTEGER NOT NULL,
+    slot                 INTEGER NOT NULL,
+    block_time           INTEGER,
+    event_id             TEXT NOT NULL,
+    user                 TEXT NOT NULL,
+    option               INTEGER NOT NULL,
+    amount               INTEGER NOT NULL,
+    option_votes         INTEGER NOT NULL,
+    option_vault_balance INTEGER NOT NULL,
+    total_amount         INTEGER NOT NULL,
+    PRIMARY KEY (signature, seq)
+);
+
+CREATE TABLE IF NOT EXISTS participations (
+    event_id     TEXT NOT NULL,
+    user         TEXT NOT NULL,
+    option       INTEGER NOT NULL,
+    amount       INTEGER NOT NULL,
+    claimed      INTEGER NOT NULL DEFAULT 0,
+    appealed     INTEGER NOT NULL DEFAULT 0,
+    created_slot INTEGER NOT NULL,
+    updated_slot INTEGER NOT NULL,
+    PRIMARY KEY (event_id, user)
+);
+
+CREATE TABLE IF NOT EXISTS claims (
+    signature   TEXT NOT NULL,
+    seq         INTEGER NOT NULL,
+    slot        INTEGER NOT NULL,
+    block_time  INTEGER,
+    event_id    TEXT NOT NULL,
+    user        TEXT NOT NULL,
+    kind        TEXT NOT NULL,
+    option      INTEGER,
+    won         INTEGER,
+    amount      INTEGER NOT NULL,
+    PRIMARY KEY (signature, seq)
+);
+
+CREATE TABLE IF NOT EXISTS appeals (
+    signature          TEXT NOT NULL,
+    seq                INTEGER NOT NULL,
+    slot               INTEGER NOT NULL,
+    block_time         INTEGER,
+    event_id           TEXT NOT NULL,
+    user               TEXT NOT NULL,
+    disagree_count     INTEGER NOT NULL,
+    disagree_trust_lvl INTEGER NOT NULL,
+    disagree_volume    INTEGER NOT NULL,
+    stake_slashed      INTEGER NOT NULL,
+    PRIMARY KEY (signature, seq)
+);
+
+CREATE TABLE IF NOT EXISTS fee_flows (
+    signature   TEXT NOT NULL,
+    seq         INTEGER NOT NULL,
+    slot        INTEGER NOT NULL,
+    block_time  INTEGER,
+    event_id    TEXT,
+    kind        TEXT NOT NULL,
+    recipient   TEXT,
+    amount      INTEGER NOT NULL,
+    PRIMARY KEY (signature, seq, kind)
+);
+
+CREATE TABLE IF NOT EXISTS sync_state (
+    key   TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS votes_event ON votes (event_id, slot);
+CREATE INDEX IF NOT EXISTS votes_user ON votes (user);
+CREATE INDEX IF NOT EXISTS claims_user ON claims (user);
+CREATE INDEX IF NOT EXISTS claims_event ON claims (event_id);
+CREATE INDEX IF NOT EXISTS participations_user ON participations (user);
+CREATE INDEX IF NOT EXISTS fee_flows_event ON fee_flows (event_id);
+
+CREATE VIEW IF NOT EXISTS event_volume AS
+SELECT
+    event_id,
+    COUNT(*) AS vote_count,
+    COUNT(DISTINCT user) AS participant_count,
+    SUM(amount) AS volume
+FROM votes
+GROUP BY event_id;
+
+CREATE VIEW IF NOT EXISTS user_pnl AS
+SELECT
+    address AS user,
+    (SELECT COALESCE(SUM(amount), 0) FROM votes WHERE votes.user = address) AS staked,
+    (SELECT COALESCE(SUM(amount), 0) FROM claims
+        WHERE claims.user = address AND kind IN ('claim', 'recharge')) AS returned,
+    (SELECT COALESCE(SUM(amount), 0) FROM claims
+        WHERE claims.user = address AND kind IN ('claim', 'recharge'))
+        - (SELECT COALESCE(SUM(amount), 0) FROM votes WHERE votes.user = address) AS pnl
+FROM users;
+";
+
+/// Creates the tables and views the index needs
+pub fn migrate(connection: &Connection) -> rusqlite::Result<()> {
+    connection.execute_batch(SCHEMA)?;
+    connection.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+
+    Ok(())
+}
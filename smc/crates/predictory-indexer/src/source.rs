@@ -0,0 +1,91 @@
+//! Transaction sources of the indexer.
+
+use std::{fs, path::Path};
+
+use anyhow::{anyhow, Context};
+use predictory_client::{
+    rpc::{ConfirmedTransaction, RpcClient},
+    PROGRAM_ID,
+};
+use serde_json::Value;
+
+use crate::Store;
+
+/// Signatures requested per `getSignaturesForAddress` call
+const PAGE_SIZE: usize = 1000;
+
+/// Indexes program transactions newer than the stored cursor, oldest first,
+/// and returns how many were processed
+pub fn sync(store: &mut Store, rpc: &RpcClient, reprocess: bool) -> anyhow::Result<usize> {
+    let until = match reprocess {
+        true => None,
+        false => store.cursor()?,
+    };
+
+    let mut signatures = vec![];
+    let mut before: Option<String> = None;
+
+    loop {
+        let page = rpc.get_signatures_for_address(
+            &PROGRAM_ID,
+            before.as_deref(),
+            until.as_deref(),
+            PAGE_SIZE,
+        )?;
+
+        let Some(last) = page.last() else {
+            break;
+        };
+
+        before = Some(last.signature.clone());
+        let full = page.len() == PAGE_SIZE;
+        signatures.extend(page.into_iter().map(|info| info.signature));
+
+        if !full {
+            break;
+        }
+    }
+
+    for signature in signatures.iter().rev() {
+        let transaction = rpc
+            .get_transaction(signature)?
+            .ok_or_else(|| anyhow!("transaction {signature} is not available"))?;
+
+        store.apply(&transaction)?;
+        store.set_cursor(signature)?;
+    }
+
+    Ok(signatures.len())
+}
+
+/// Indexes `getTransaction` results stored in a file, either as a single
+/// object, an array or one object per line, and returns how many were processed
+pub fn import(store: &mut Store, path: &Path) -> anyhow::Result<usize> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+
+    let values = match serde_json::from_str::<Value>(&content) {
+        Ok(Value::Array(values)) => values,
+        Ok(value) => vec![value],
+        Err(_) => content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<Result<Vec<Value>, _>>()
+            .with_context(|| format!("parsing {}", path.display()))?,
+    };
+
+    let mut transactions = values
+        .iter()
+        .map(ConfirmedTransaction::from_json)
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("decoding {}", path.display()))?;
+
+    transactions.sort_by_key(|transaction| transaction.slot);
+
+    for transaction in &transactions {
+        store.apply(transaction)?;
+    }
+
+    Ok(transactions.len())
+}
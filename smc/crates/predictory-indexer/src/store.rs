@@ -0,0 +1,1184 @@
+use std::path::Path;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use predictory_client::{
+    event_id::format_event_id,
+    events::{decode_logs, instruction_name, LoggedEvent, ProgramEvent},
+    pda,
+    rpc::{CompiledInstruction, ConfirmedTransaction},
+    text::from_fixed,
+    PROGRAM_ID,
+};
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+
+use crate::schema;
+
+/// Tables holding rows derived from a single transaction
+const SIGNATURE_TABLES: [&str; 7] = [
+    "instructions",
+    "event_log",
+    "votes",
+    "claims",
+    "appeals",
+    "fee_flows",
+    "transactions",
+];
+
+pub struct Store {
+    connection: Connection,
+}
+
+impl Store {
+    pub fn open(path: impl AsRef<Path>) -> rusqlite::Result<Self> {
+        let connection = Connection::open(path)?;
+        connection.pragma_update(None, "journal_mode", "WAL")?;
+        schema::migrate(&connection)?;
+
+        Ok(Store { connection })
+    }
+
+    pub fn connection(&self) -> &Connection {
+        &self.connection
+    }
+
+    pub fn cursor(&self) -> rusqlite::Result<Option<String>> {
+        self.connection
+            .query_row(
+                "SELECT value FROM sync_state WHERE key = 'last_signature'",
+                [],
+                |row| row.get(0),
+            )
+            .optional()
+    }
+
+    pub fn set_cursor(&self, signature: &str) -> rusqlite::Result<()> {
+        self.connection.execute(
+            "INSERT INTO sync_state (key, value) VALUES ('last_signature', ?1)
+             ON CONFLICT (key) DO UPDATE SET value = excluded.value",
+            [signature],
+        )?;
+
+        Ok(())
+    }
+
+    /// Removes every indexed row, keeping the schema
+    pub fn reset(&mut self) -> rusqlite::Result<()> {
+        self.connection.execute_batch(
+            "DELETE FROM transactions; DELETE FROM instructions; DELETE FROM event_log;
+             DELETE FROM users; DELETE FROM events; DELETE FROM options; DELETE FROM votes;
+             DELETE FROM participations; DELETE FROM claims; DELETE FROM appeals;
+             DELETE FROM fee_flows; DELETE FROM sync_state;",
+        )
+    }
+
+    /// Indexes a transaction, replacing rows from a previous run over it
+    pub fn apply(&mut self, transaction: &ConfirmedTransaction) -> rusqlite::Result<()> {
+        let tx = self.connection.transaction()?;
+
+        for table in SIGNATURE_TABLES {
+            tx.execute(
+                &format!("DELETE FROM {table} WHERE signature = ?1"),
+                [&transaction.signature],
+            )?;
+        }
+
+        tx.execute(
+            "INSERT INTO transactions (signature, slot, block_time, failed) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                transaction.signature,
+                transaction.slot,
+                transaction.block_time,
+                transaction.failed
+            ],
+        )?;
+
+        record_instructions(&tx, transaction)?;
+
+        // Failed transactions leave no state behind
+        if !transaction.failed {
+            let context = Context {
+                tx: &tx,
+                signature: &transaction.signature,
+                slot: transaction.slot as i64,
+                block_time: transaction.block_time,
+            };
+
+            for logged in decode_logs(&transaction.logs) {
+                context.apply_event(&logged)?;
+            }
+        }
+
+        tx.commit()
+    }
+}
+
+fn record_instructions(
+    tx: &Transaction,
+    transaction: &ConfirmedTransaction,
+) -> rusqlite::Result<()> {
+    let insert = |ix_index: usize, inner_index: i64, instruction: &CompiledInstruction| {
+        let program = transaction
+            .account_keys
+            .get(instruction.program_id_index as usize);
+
+        if program != Some(&PROGRAM_ID) {
+            return Ok(());
+        }
+
+        let accounts: Vec<String> = instruction
+            .accounts
+            .iter()
+            .filter_map(|index| transaction.account_keys.get(*index as usize))
+            .map(ToString::to_string)
+            .collect();
+
+        tx.execute(
+            "INSERT INTO instructions (signature, ix_index, inner_index, name, accounts)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                transaction.signature,
+                ix_index,
+                inner_index,
+                instruction_name(&instruction.data),
+                accounts.join(" "),
+            ],
+        )
+        .map(|_| ())
+    };
+
+    for (ix_index, instruction) in transaction.instructions.iter().enumerate() {
+        insert(ix_index, -1, instruction)?;
+    }
+
+    for (ix_index, inner) in &transaction.inner_instructions {
+        for (inner_index, instruction) in inner.iter().enumerate() {
+            insert(*ix_index as usize, inner_index as i64, instruction)?;
+        }
+    }
+
+    Ok(())
+}
+
+struct Context<'a> {
+    tx: &'a Transaction<'a>,
+    signature: &'a str,
+    slot: i64,
+    block_time: Option<i64>,
+}
+
+impl Context<'_> {
+    fn apply_event(&self, logged: &LoggedEvent) -> rusqlite::Result<()> {
+        let seq = logged.seq;
+
+        self.tx.execute(
+            "INSERT INTO event_log (signature, seq, slot, name, data) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                self.signature,
+                seq,
+                self.slot,
+                logged.event.name(),
+                STANDARD.encode(&logged.data)
+            ],
+        )?;
+
+        match &logged.event {
+            ProgramEvent::UserCreated(event) => {
+                self.tx.execute(
+                    "INSERT INTO users (address, name, trust_lvl, created_slot, updated_slot)
+                     VALUES (?1, ?2, ?3, ?4, ?4)
+                     ON CONFLICT (address) DO UPDATE SET
+                        name = excluded.name, created_slot = excluded.created_slot",
+                    params![
+                        event.user.to_string(),
+                        from_fixed(&event.name),
+                        event.trust_lvl,
+                        self.slot
+                    ],
+                )?;
+            }
+            ProgramEvent::StakeDeposited(event) => {
+                self.set_user_field("stake", &event.user.to_string(), event.stake)?
+            }
+            ProgramEvent::StakeWithdrawn(event) => {
+                self.set_user_field("stake", &event.user.to_string(), event.stake)?
+            }
+            ProgramEvent::TrustChanged(event) => {
+                self.set_user_field("trust_lvl", &event.user.to_string(), event.trust_lvl)?
+            }
+            ProgramEvent::EventCreated(event) => {
+                self.tx.execute(
+                    "INSERT INTO events (event_id, authority, name, description, is_private,
+                        status, start_date, end_date, participation_deadline, created_slot,
+                        updated_slot)
+                     VALUES (?1, ?2, ?3, ?4, ?5, 'draft', ?6, ?7, ?8, ?9, ?9)
+                     ON CONFLICT (event_id) DO UPDATE SET created_slot = excluded.created_slot",
+                    params![
+                        format_event_id(event.event_id),
+                        event.authority.to_string(),
+                        from_fixed(&event.name),
+                        from_fixed(&event.description),
+                        event.is_private,
+                        event.start_date,
+                        event.end_date,
+                        event.participation_deadline,
+                        self.slot
+                    ],
+                )?;
+            }
+            ProgramEvent::EventPublished(event) => {
+                self.update_event(
+                    event.event_id,
+                    "status = 'published', stake = ?3, platform_fee = ?4, org_reward = ?5",
+                    params![event.stake, event.platform_fee, event.org_reward],
+                )?;
+            }
+            ProgramEvent::EventMetaUpdated(event) => {
+                self.update_event(
+                    event.event_id,
+                    "name = ?3, description = ?4",
+                    params![from_fixed(&event.name), from_fixed(&event.description)],
+                )?;
+            }
+            ProgramEvent::EventDatesUpdated(event) => {
+                self.update_event(
+                    event.event_id,
+                    "start_date = ?3, end_date = ?4, participation_deadline = ?5",
+                    params![
+                        event.start_date,
+                        event.end_date,
+                        event.participation_deadline
+                    ],
+                )?;
+            }
+            ProgramEvent::EventDeleted(event) => {
+                self.update_event(event.event_id, "status = 'deleted'", params![])?;
+                self.tx.execute(
+                    "DELETE FROM options WHERE event_id = ?1",
+                    [format_event_id(event.event_id)],
+                )?;
+            }
+            ProgramEvent::EventCanceled(event) => {
+                self.update_event(event.event_id, "status = 'canceled'", params![])?;
+
+                if !event.stake_refunded && event.stake > 0 {
+                    self.fee_flow(
+                        seq,
+                        Some(event.event_id),
+                        "stake_slashed",
+                        Some(pda::treasury_address().to_string()),
+                        event.stake,
+                    )?;
+                }
+            }
+            ProgramEvent::EventCompleted(event) => {
+                self.update_event(
+                    event.event_id,
+                    "status = 'completed', result = ?3",
+                    params![event.result],
+                )?;
+            }
+            ProgramEvent::DisputeResolved(event) => {
+                self.update_event(event.event_id, "result = ?3", params![event.result])?;
+            }
+            ProgramEvent::EventAuthorityTransferred(event) => {
+                self.update_event(
+                    event.event_id,
+                    "authority = ?3",
+                    params![event.authority.to_string()],
+                )?;
+            }
+            ProgramEvent::EventFrozen(event) => {
+                self.update_event(event.event_id, "frozen = ?3", params![event.frozen])?;
+            }
+            ProgramEvent::EventModerated(event) => {
+                self.update_event(event.event_id, "hidden = ?3", params![event.hidden])?;
+            }
+            ProgramEvent::OptionCreated(event) => {
+                self.upsert_option(event.event_id, event.index, &event.description)?
+            }
+            ProgramEvent::OptionUpdated(event) => {
+                self.upsert_option(event.event_id, event.index, &event.description)?
+            }
+            ProgramEvent::OptionRemoved(event) => {
+                let event_id = format_event_id(event.event_id);
+
+                self.tx.execute(
+                    "DELETE FROM options WHERE event_id = ?1 AND idx = ?2",
+                    params![event_id, event.index],
+                )?;
+
+                if let Some(moved_from) = event.moved_from {
+                    self.tx.execute(
+                        "UPDATE options SET idx = ?3, updated_slot = ?4
+                         WHERE event_id = ?1 AND idx = ?2",
+                        params![event_id, moved_from, event.index, self.slot],
+                    )?;
+                }
+            }
+            ProgramEvent::VoteCast(event) => {
+                let event_id = format_event_id(event.event_id);
+                let user = event.user.to_string();
+
+                self.tx.execute(
+                    "INSERT INTO votes (signature, seq, slot, block_time, event_id, user, option,
+                        amount, option_votes, option_vault_balance, total_amount)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                    params![
+                        self.signature,
+                        seq,
+                        self.slot,
+                        self.block_time,
+                        event_id,
+                        user,
+                        event.option,
+                        event.amount,
+                        event.option_votes,
+                        event.option_vault_balance,
+                        event.total_amount
+                    ],
+                )?;
+                // Deposits are summed from the votes, so replaying a vote cannot inflate them
+                self.tx.execute(
+                    "INSERT INTO participations (event_id, user, option, amount, created_slot,
+                        updated_slot)
+                     VALUES (?1, ?2, ?3,
+                        (SELECT SUM(amount) FROM votes WHERE event_id = ?1 AND user = ?2), ?4, ?4)
+                     ON CONFLICT (event_id, user) DO UPDATE SET amount = excluded.amount",
+                    params![event_id, user, event.option, self.slot],
+                )?;
+                self.tx.execute(
+                    "INSERT INTO options (event_id, idx, votes, vault_balance, updated_slot)
+                     VALUES (?1, ?2, ?3, ?4, ?5)
+                     ON CONFLICT (event_id, idx) DO UPDATE SET
+                        votes = excluded.votes,
+                        vault_balance = excluded.vault_balance,
+                        updated_slot = excluded.updated_slot
+                     WHERE options.updated_slot <= excluded.updated_slot",
+                    params![
+                        event_id,
+                        event.option,
+                        event.option_votes,
+                        event.option_vault_balance,
+                        self.slot
+                    ],
+                )?;
+                self.update_event(
+                    event.event_id,
+                    "total_amount = ?3",
+                    params![event.total_amount],
+                )?;
+            }
+            ProgramEvent::RewardClaimed(event) => {
+                self.claim(
+                    seq,
+                    event.event_id,
+                    &event.user.to_string(),
+                    "claim",
+                    Some(event.option),
+                    Some(event.won),
+                    event.amount,
+                )?;
+                self.set_participation_flag(event.event_id, &event.user.to_string(), "claimed")?;
+            }
+            ProgramEvent::Recharged(event) => {
+                self.claim(
+                    seq,
+                    event.event_id,
+                    &event.user.to_string(),
+                    "recharge",
+                    None,
+                    None,
+                    event.amount,
+                )?;
+                self.set_participation_flag(event.event_id, &event.user.to_string(), "claimed")?;
+            }
+            ProgramEvent::TrustBurned(event) => {
+                self.claim(
+                    seq,
+                    event.event_id,
+                    &event.user.to_string(),
+                    "burn_trust",
+                    None,
+                    None,
+                    event.amount,
+                )?;
+            }
+            ProgramEvent::AppealFiled(event) => {
+                self.tx.execute(
+                    "INSERT INTO appeals (signature, seq, slot, block_time, event_id, user,
+                        disagree_count, disagree_trust_lvl, disagree_volume, stake_slashed)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                    params![
+                        self.signature,
+                        seq,
+                        self.slot,
+                        self.block_time,
+                        format_event_id(event.event_id),
+                        event.user.to_string(),
+                        event.disagree_count,
+                        event.disagree_trust_lvl,
+                        event.disagree_volume,
+                        event.stake_slashed
+                    ],
+                )?;
+                self.set_participation_flag(event.event_id, &event.user.to_string(), "appealed")?;
+            }
+            ProgramEvent::OrganizerStakeReleased(event) => {
+                let authority = Some(event.authority.to_string());
+
+                self.fee_flow(
+                    seq,
+                    Some(event.event_id),
+                    "stake_released",
+                    authority.clone(),
+                    event.stake,
+                )?;
+                self.fee_flow(
+                    seq,
+                    Some(event.event_id),
+                    "org_reward",
+                    authority,
+                    event.org_reward,
+                )?;
+                self.fee_flow(
+                    seq,
+                    Some(event.event_id),
+                    "platform_fee",
+                    Some(pda::treasury_address().to_string()),
+                    event.platform_fee,
+                )?;
+            }
+            ProgramEvent::OrganizerRewardClaimed(event) => {
+                self.fee_flow(
+                    seq,
+                    Some(event.event_id),
+                    "organizer_reward",
+                    Some(event.member.to_string()),
+                    event.amount,
+                )?;
+            }
+            ProgramEvent::TreasuryCollected(event) => {
+                self.fee_flow(
+                    seq,
+                    None,
+                    "treasury_collected",
+                    Some(pda::treasury_address().to_string()),
+                    event.amount,
+                )?;
+            }
+            ProgramEvent::TreasuryDistributed(event) => {
+                self.fee_flow(seq, None, "treasury_distributed", None, event.amount)?;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn set_user_field(&self, field: &str, user: &str, value: u64) -> rusqlite::Result<()> {
+        self.tx.execute(
+            &format!(
+                "INSERT INTO users (address, {field}, updated_slot) VALUES (?1, ?2, ?3)
+                 ON CONFLICT (address) DO UPDATE SET
+                    {field} = excluded.{field}, updated_slot = excluded.updated_slot
+                 WHERE users.updated_slot <= excluded.updated_slot"
+            ),
+            params![user, value, self.slot],
+        )?;
+
+        Ok(())
+    }
+
+    /// Updates an event row unless it already reflects a later slot;
+    /// `assignments` may use `?3` onwards for `values`
+    fn update_event(
+        &self,
+        event_id: u128,
+        assignments: &str,
+        values: &[&dyn rusqlite::ToSql],
+    ) -> rusqlite::Result<()> {
+        let event_id = format_event_id(event_id);
+        let mut arguments: Vec<&dyn rusqlite::ToSql> = vec![&event_id, &self.slot];
+        arguments.extend_from_slice(values);
+
+        self.tx.execute(
+            &format!(
+                "UPDATE events SET {assignments}, updated_slot = ?2
+                 WHERE event_id = ?1 AND updated_slot <= ?2"
+            ),
+            arguments.as_slice(),
+        )?;
+
+        Ok(())
+    }
+
+    fn upsert_option(&self, event_id: u128, index: u8, description: &[u8]) -> rusqlite::Result<()> {
+        self.tx.execute(
+            "INSERT INTO options (event_id, idx, description, updated_slot) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT (event_id, idx) DO UPDATE SET
+                description = excluded.description, updated_slot = excluded.updated_slot
+             WHERE options.updated_slot <= excluded.updated_slot",
+            params![
+                format_event_id(event_id),
+                index,
+                from_fixed(description),
+                self.slot
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    fn set_participation_flag(
+        &self,
+        event_id: u128,
+        user: &str,
+        flag: &str,
+    ) -> rusqlite::Result<()> {
+        self.tx.execute(
+            &format!(
+                "UPDATE participations SET {flag} = 1, updated_slot = MAX(updated_slot, ?3)
+                 WHERE event_id = ?1 AND user = ?2"
+            ),
+            params![format_event_id(event_id), user, self.slot],
+        )?;
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn claim(
+        &self,
+        seq: u32,
+        event_id: u128,
+        user: &str,
+        kind: &str,
+        option: Option<u8>,
+        won: Option<bool>,
+        amount: u64,
+    ) -> rusqlite::Result<()> {
+        self.tx.execute(
+            "INSERT INTO claims (signature, seq, slot, block_time, event_id, user, kind, option,
+                won, amount)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                self.signature,
+                seq,
+                self.slot,
+                self.block_time,
+                format_event_id(event_id),
+                user,
+                kind,
+                option,
+                won,
+                amount
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    fn fee_flow(
+        &self,
+        seq: u32,
+        event_id: Option<u128>,
+        kind: &str,
+        recipient: Option<String>,
+        amount: u64,
+    ) -> rusqlite::Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        self.tx.execute(
+            "INSERT INTO fee_flows (signature, seq, slot, block_time, event_id, kind, recipient,
+                amount)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                self.signature,
+                seq,
+                self.slot,
+                self.block_time,
+                event_id.map(format_event_id),
+                kind,
+                recipient,
+                amount
+            ],
+        )?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::{prelude::Pubkey, Event as _};
+    use predictory_client::{instructions as ix, predictory::events::*};
+
+    use super::*;
+
+    const EVENT_ID: u128 = 0x0123_4567_89ab_4cde_8f01_2345_6789_abcd;
+
+    fn store() -> Store {
+        Store::open(":memory:").unwrap()
+    }
+
+    /// Successful transaction with one `vote` instruction, logging the given events
+    fn transaction(signature: &str, slot: u64, events: &[Vec<u8>]) -> ConfirmedTransaction {
+        let sender = Pubkey::new_unique();
+        let mut logs = vec![format!("Program {PROGRAM_ID} invoke [1]")];
+        logs.extend(
+            events
+                .iter()
+                .map(|data| format!("Program data: {}", STANDARD.encode(data))),
+        );
+        logs.push(format!("Program {PROGRAM_ID} success"));
+
+        ConfirmedTransaction {
+            signature: signature.to_string(),
+            slot,
+            block_time: Some(1_700_000_000),
+            failed: false,
+            account_keys: vec![PROGRAM_ID, sender],
+            instructions: vec![CompiledInstruction {
+                program_id_index: 0,
+                accounts: vec![1],
+                data: ix::vote(&sender, EVENT_ID, 0, false, 100, 0).data,
+            }],
+            inner_instructions: vec![],
+            logs,
+        }
+    }
+
+    fn query<T: rusqlite::types::FromSql>(store: &Store, sql: &str) -> T {
+        store
+            .connection()
+            .query_row(sql, [], |row| row.get(0))
+            .unwrap()
+    }
+
+    fn created() -> Vec<u8> {
+        EventCreated {
+            event_id: EVENT_ID,
+            authority: Pubkey::new_unique(),
+            is_private: false,
+            name: [b'a'; 32],
+            description: [0; 256],
+            start_date: 100,
+            end_date: 200,
+            participation_deadline: None,
+        }
+        .data()
+    }
+
+    fn vote_cast(user: Pubkey, amount: u64) -> Vec<u8> {
+        VoteCast {
+            event_id: EVENT_ID,
+            user,
+            option: 1,
+            amount,
+            trust_lvl: 5,
+            option_votes: 1,
+            option_vault_balance: amount,
+            total_amount: amount,
+        }
+        .data()
+    }
+
+    fn completed(result: u8) -> Vec<u8> {
+        EventCompleted {
+            event_id: EVENT_ID,
+            resolver: Pubkey::new_unique(),
+            result,
+        }
+        .data()
+    }
+
+    /// Row counts of the tables filled from transactions
+    fn counts(store: &Store) -> Vec<i64> {
+        SIGNATURE_TABLES
+            .iter()
+            .map(|table| query(store, &format!("SELECT COUNT(*) FROM {table}")))
+            .collect()
+    }
+
+    #[test]
+    fn reprocessing_a_signature_is_idempotent() {
+        let mut store = store();
+        let user = Pubkey::new_unique();
+        let transaction = transaction(
+            "sig",
+            5,
+            &[
+                created(),
+                vote_cast(user, 100),
+                RewardClaimed {
+                    event_id: EVENT_ID,
+                    user,
+                    option: 1,
+                    won: true,
+                    amount: 150,
+                }
+                .data(),
+                OrganizerStakeReleased {
+                    event_id: EVENT_ID,
+                    authority: Pubkey::new_unique(),
+                    stake: 1_000,
+                    org_reward: 10,
+                    platform_fee: 5,
+                }
+                .data(),
+            ],
+        );
+
+        store.apply(&transaction).unwrap();
+        let first = counts(&store);
+        store.apply(&transaction).unwrap();
+
+        assert_eq!(counts(&store), first);
+        assert_eq!(first, [1, 4, 1, 1, 0, 3, 1]);
+        assert_eq!(
+            query::<i64>(&store, "SELECT amount FROM participations"),
+            100
+        );
+        assert_eq!(
+            query::<i64>(&store, "SELECT claimed FROM participations"),
+            1
+        );
+    }
+
+    #[test]
+    fn reprocessing_a_slot_is_idempotent() {
+        let mut store = store();
+        let user = Pubkey::new_unique();
+        let transactions = [
+            transaction("first", 5, &[created(), vote_cast(user, 100)]),
+            transaction("second", 5, &[vote_cast(user, 50)]),
+        ];
+
+        for transaction in &transactions {
+            store.apply(transaction).unwrap();
+        }
+        let first = counts(&store);
+        for transaction in &transactions {
+            store.apply(transaction).unwrap();
+        }
+
+        assert_eq!(counts(&store), first);
+        assert_eq!(
+            query::<i64>(&store, "SELECT amount FROM participations"),
+            150
+        );
+        assert_eq!(query::<i64>(&store, "SELECT volume FROM event_volume"), 150);
+    }
+
+    #[test]
+    fn replaying_an_older_slot_keeps_later_state() {
+        let mut store = store();
+        let older = transaction("older", 5, &[created(), completed(0)]);
+
+        store.apply(&older).unwrap();
+        store
+            .apply(&transaction("newer", 9, &[completed(1)]))
+            .unwrap();
+        store.apply(&older).unwrap();
+
+        assert_eq!(query::<i64>(&store, "SELECT result FROM events"), 1);
+        assert_eq!(query::<i64>(&store, "SELECT updated_slot FROM events"), 9);
+    }
+
+    #[test]
+    fn failed_transactions_leave_no_state() {
+        let mut store = store();
+        let mut transaction = transaction("sig", 5, &[created()]);
+        transaction.failed = true;
+
+        store.apply(&transaction).unwrap();
+
+        assert_eq!(counts(&store), [1, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(query::<i64>(&store, "SELECT COUNT(*) FROM events"), 0);
+        assert_eq!(
+            query::<String>(&store, "SELECT name FROM instructions"),
+            "vote"
+        );
+    }
+
+    #[test]
+    fn user_events() {
+        let mut store = store();
+        let user = Pubkey::new_unique();
+        let events = [
+            UserCreated {
+                user,
+                name: [b'a'; 32],
+                trust_lvl: 5,
+            }
+            .data(),
+            StakeDeposited {
+                user,
+                amount: 300,
+                stake: 300,
+            }
+            .data(),
+            StakeWithdrawn {
+                user,
+                amount: 100,
+                stake: 200,
+            }
+            .data(),
+            TrustChanged {
+                event_id: EVENT_ID,
+                user,
+                delta: 2,
+                trust_lvl: 7,
+            }
+            .data(),
+        ];
+
+        store.apply(&transaction("sig", 5, &events)).unwrap();
+
+        assert_eq!(
+            query::<String>(&store, "SELECT name FROM users"),
+            "a".repeat(32)
+        );
+        assert_eq!(query::<i64>(&store, "SELECT stake FROM users"), 200);
+        assert_eq!(query::<i64>(&store, "SELECT trust_lvl FROM users"), 7);
+    }
+
+    #[test]
+    fn event_lifecycle_events() {
+        let mut store = store();
+        let authority = Pubkey::new_unique();
+
+        store
+            .apply(&transaction(
+                "created",
+                5,
+                &[
+                    created(),
+                    EventPublished {
+                        event_id: EVENT_ID,
+                        authority,
+                        stake: 1_000,
+                        platform_fee: 5,
+                        org_reward: 10,
+                    }
+                    .data(),
+                    EventMetaUpdated {
+                        event_id: EVENT_ID,
+                        name: [b'b'; 32],
+                        description: [b'c'; 256],
+                    }
+                    .data(),
+                    EventDatesUpdated {
+                        event_id: EVENT_ID,
+                        start_date: 110,
+                        end_date: 210,
+                        participation_deadline: Some(190),
+                    }
+                    .data(),
+                    EventFrozen {
+                        event_id: EVENT_ID,
+                        frozen: true,
+                    }
+                    .data(),
+                    EventModerated {
+                        event_id: EVENT_ID,
+                        moderator: Pubkey::new_unique(),
+                        hidden: true,
+                    }
+                    .data(),
+                    EventAuthorityTransferred {
+                        event_id: EVENT_ID,
+                        previous_authority: Pubkey::new_unique(),
+                        authority,
+                        stake: 1_000,
+                    }
+                    .data(),
+                ],
+            ))
+            .unwrap();
+
+        let row: (String, String, String, i64, i64, i64, i64, i64, i64, String) = store
+            .connection()
+            .query_row(
+                "SELECT status, name, authority, stake, platform_fee, org_reward, end_date,
+                    participation_deadline, frozen + hidden, event_id
+                 FROM events",
+                [],
+                |row| {
+                    Ok((
+                        row.get(0)?,
+                        row.get(1)?,
+                        row.get(2)?,
+                        row.get(3)?,
+                        row.get(4)?,
+                        row.get(5)?,
+                        row.get(6)?,
+                        row.get(7)?,
+                        row.get(8)?,
+                        row.get(9)?,
+                    ))
+                },
+            )
+            .unwrap();
+
+        assert_eq!(
+            row,
+            (
+                "published".to_string(),
+                "b".repeat(32),
+                authority.to_string(),
+                1_000,
+                5,
+                10,
+                210,
+                190,
+                2,
+                format_event_id(EVENT_ID),
+            )
+        );
+
+        store
+            .apply(&transaction(
+                "completed",
+                6,
+                &[
+                    completed(0),
+                    DisputeResolved {
+                        event_id: EVENT_ID,
+                        resolver: Pubkey::new_unique(),
+                        result: 1,
+                    }
+                    .data(),
+                ],
+            ))
+            .unwrap();
+
+        assert_eq!(
+            query::<String>(&store, "SELECT status FROM events"),
+            "completed"
+        );
+        assert_eq!(query::<i64>(&store, "SELECT result FROM events"), 1);
+    }
+
+    #[test]
+    fn canceled_and_deleted_events() {
+        let mut store = store();
+        let canceled = EventCanceled {
+            event_id: EVENT_ID,
+            canceled_by: Pubkey::new_unique(),
+            stake: 1_000,
+            stake_refunded: false,
+        };
+
+        store
+            .apply(&transaction("canceled", 5, &[created(), canceled.data()]))
+            .unwrap();
+
+        assert_eq!(
+            query::<String>(&store, "SELECT status FROM events"),
+            "canceled"
+        );
+        assert_eq!(
+            query::<String>(&store, "SELECT kind FROM fee_flows"),
+            "stake_slashed"
+        );
+
+        store
+            .apply(&transaction(
+                "deleted",
+                6,
+                &[
+                    OptionCreated {
+                        event_id: EVENT_ID,
+                        index: 0,
+                        description: [b'x'; 256],
+                    }
+                    .data(),
+                    EventDeleted {
+                        event_id: EVENT_ID,
+                        authority: Pubkey::new_unique(),
+                    }
+                    .data(),
+                ],
+            ))
+            .unwrap();
+
+        assert_eq!(
+            query::<String>(&store, "SELECT status FROM events"),
+            "deleted"
+        );
+        assert_eq!(query::<i64>(&store, "SELECT COUNT(*) FROM options"), 0);
+    }
+
+    #[test]
+    fn option_events() {
+        let mut store = store();
+        let option = |index, description| {
+            OptionCreated {
+                event_id: EVENT_ID,
+                index,
+                description: [description; 256],
+            }
+            .data()
+        };
+
+        store
+            .apply(&transaction(
+                "sig",
+                5,
+                &[
+                    option(0, b'x'),
+                    option(1, b'y'),
+                    option(2, b'z'),
+                    OptionUpdated {
+                        event_id: EVENT_ID,
+                        index: 1,
+                        description: [b'w'; 256],
+                    }
+                    .data(),
+                    OptionRemoved {
+                        event_id: EVENT_ID,
+                        index: 0,
+                        moved_from: Some(2),
+                    }
+                    .data(),
+                ],
+            ))
+            .unwrap();
+
+        let options: Vec<(i64, String)> = store
+            .connection()
+            .prepare("SELECT idx, substr(description, 1, 1) FROM options ORDER BY idx")
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap();
+
+        assert_eq!(options, [(0, "z".to_string()), (1, "w".to_string())]);
+    }
+
+    #[test]
+    fn participation_events() {
+        let mut store = store();
+        let user = Pubkey::new_unique();
+
+        store
+            .apply(&transaction(
+                "sig",
+                5,
+                &[
+                    created(),
+                    vote_cast(user, 100),
+                    AppealFiled {
+                        event_id: EVENT_ID,
+                        user,
+                        disagree_count: 1,
+                        disagree_trust_lvl: 5,
+                        disagree_volume: 100,
+                        stake_slashed: true,
+                    }
+                    .data(),
+                    Recharged {
+                        event_id: EVENT_ID,
+                        user,
+                        amount: 100,
+                    }
+                    .data(),
+                    TrustBurned {
+                        event_id: EVENT_ID,
+                        user,
+                        burned_trust: 1,
+                        amount: 20,
+                        deposited_amount: 100,
+                    }
+                    .data(),
+                ],
+            ))
+            .unwrap();
+
+        assert_eq!(
+            query::<i64>(&store, "SELECT option_vault_balance FROM votes"),
+            100
+        );
+        assert_eq!(query::<i64>(&store, "SELECT votes FROM options"), 1);
+        assert_eq!(query::<i64>(&store, "SELECT total_amount FROM events"), 100);
+        assert_eq!(query::<i64>(&store, "SELECT stake_slashed FROM appeals"), 1);
+        assert_eq!(
+            query::<i64>(&store, "SELECT claimed + appealed FROM participations"),
+            2
+        );
+        assert_eq!(
+            query::<String>(&store, "SELECT group_concat(kind, ' ') FROM claims"),
+            "recharge burn_trust"
+        );
+    }
+
+    #[test]
+    fn fee_flow_events() {
+        let mut store = store();
+        let member = Pubkey::new_unique();
+
+        store
+            .apply(&transaction(
+                "sig",
+                5,
+                &[
+                    OrganizerRewardClaimed {
+                        event_id: EVENT_ID,
+                        member,
+                        amount: 7,
+                    }
+                    .data(),
+                    TreasuryCollected {
+                        amount: 5,
+                        total_collected: 5,
+                    }
+                    .data(),
+                    TreasuryDistributed {
+                        amount: 5,
+                        total_distributed: 5,
+                    }
+                    .data(),
+                ],
+            ))
+            .unwrap();
+
+        assert_eq!(
+            query::<String>(&store, "SELECT group_concat(kind, ' ') FROM fee_flows"),
+            "organizer_reward treasury_collected treasury_distributed"
+        );
+        assert_eq!(
+            query::<String>(
+                &store,
+                "SELECT recipient FROM fee_flows WHERE kind = 'organizer_reward'"
+            ),
+            member.to_string()
+        );
+    }
+
+    #[test]
+    fn other_events_are_only_logged() {
+        let mut store = store();
+        let expired = DisputeExpired {
+            event_id: EVENT_ID,
+            result: 1,
+        };
+
+        store
+            .apply(&transaction("sig", 5, &[expired.data()]))
+            .unwrap();
+
+        assert_eq!(
+            query::<String>(&store, "SELECT name FROM event_log"),
+            "DisputeExpired"
+        );
+        assert_eq!(
+            query::<String>(&store, "SELECT data FROM event_log"),
+            STANDARD.encode(expired.data())
+        );
+        assert_eq!(counts(&store), [1, 1, 0, 0, 0, 0, 1]);
+    }
+}
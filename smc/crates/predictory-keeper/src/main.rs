@@ -0,0 +1,131 @@
+//! Keeper daemon driving the Predictory transitions that need someone to
+//! send a transaction: canceling overdue events, settling the positions of
+//! completed events, and recharging and closing the keeper wallet positions.
+
+use std::{
+    path::PathBuf,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::anyhow;
+use clap::Parser;
+use predictory_client::rpc::{RpcClient, LOCALNET_URL};
+use solana_sdk::signature::{read_keypair_file, Signer};
+
+use crate::sender::{Outcome, Sender};
+
+mod sender;
+mod tasks;
+
+#[derive(Parser)]
+#[command(
+    name = "predictory-keeper",
+    version,
+    about = "Send the permissionless Predictory transitions"
+)]
+struct Cli {
+    /// RPC endpoint
+    #[arg(long, env = "ANCHOR_PROVIDER_URL", default_value = LOCALNET_URL)]
+    url: String,
+
+    /// Keypair paying for the keeper transactions
+    #[arg(long, env = "ANCHOR_WALLET")]
+    keypair: Option<PathBuf>,
+
+    /// Report and simulate the pending tasks without sending them
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Run a single pass and exit
+    #[arg(long)]
+    once: bool,
+
+    /// Delay between passes, in seconds
+    #[arg(long, default_value_t = 30)]
+    interval: u64,
+
+    /// Compute unit price of the first attempt, in micro-lamports
+    #[arg(long, default_value_t = 0)]
+    priority_fee: u64,
+
+    /// Upper bound of the priority fee escalated on retries, in micro-lamports
+    #[arg(long, default_value_t = 100_000)]
+    max_priority_fee: u64,
+
+    /// Compute unit limit requested for each transaction
+    #[arg(long)]
+    compute_unit_limit: Option<u32>,
+
+    /// Retries of a failed send before giving up on a task for this pass
+    #[arg(long, default_value_t = 3)]
+    retries: u32,
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    let path = match cli.keypair {
+        Some(path) => path,
+        None => default_keypair_path()?,
+    };
+    let keypair = read_keypair_file(&path)
+        .map_err(|error| anyhow!("failed to read keypair {}: {error}", path.display()))?;
+
+    let sender = Sender {
+        rpc: RpcClient::new(cli.url),
+        keypair,
+        priority_fee: cli.priority_fee,
+        max_priority_fee: cli.max_priority_fee,
+        compute_unit_limit: cli.compute_unit_limit,
+        retries: cli.retries,
+    };
+
+    loop {
+        if let Err(error) = run_pass(&sender, cli.dry_run) {
+            eprintln!("pass failed: {error}");
+        }
+
+        if cli.once {
+            return Ok(());
+        }
+
+        thread::sleep(Duration::from_secs(cli.interval));
+    }
+}
+
+fn run_pass(sender: &Sender, dry_run: bool) -> anyhow::Result<()> {
+    let keeper = sender.keypair.pubkey();
+    let tasks = tasks::plan(&sender.rpc, &keeper, now())?;
+
+    if tasks.is_empty() {
+        println!("Nothing to do");
+    }
+
+    for task in &tasks {
+        match sender.submit(task, dry_run) {
+            Ok(Outcome::Sent(signature)) => println!("{task}: {signature}"),
+            Ok(Outcome::Simulated { units_consumed }) => match units_consumed {
+                Some(units) => println!("{task}: would succeed using {units} CU"),
+                None => println!("{task}: would succeed"),
+            },
+            Ok(Outcome::Rejected(reason)) => println!("{task}: skipped, {reason}"),
+            Err(error) => eprintln!("{task}: gave up, {error}"),
+        }
+    }
+
+    Ok(())
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+fn default_keypair_path() -> anyhow::Result<PathBuf> {
+    let home = std::env::var_os("HOME").ok_or_else(|| anyhow!("HOME is not set"))?;
+
+    Ok(PathBuf::from(home).join(".config/solana/id.json"))
+}
@@ -0,0 +1,96 @@
+use std::{thread, time::Duration};
+
+use predictory_client::{rpc::RpcClient, ClientError};
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    signature::{Keypair, Signature, Signer},
+};
+
+use crate::tasks::Task;
+
+/// Priority fee used for the first escalation when no base fee is set, in micro-lamports per CU
+const MIN_ESCALATED_FEE: u64 = 1_000;
+/// Delay before the first retry, doubled on every attempt
+const RETRY_BACKOFF: Duration = Duration::from_secs(1);
+
+pub struct Sender {
+    pub rpc: RpcClient,
+    pub keypair: Keypair,
+    /// Compute unit price of the first attempt, in micro-lamports
+    pub priority_fee: u64,
+    /// Upper bound of the escalated compute unit price
+    pub max_priority_fee: u64,
+    pub compute_unit_limit: Option<u32>,
+    pub retries: u32,
+}
+
+pub enum Outcome {
+    Sent(Signature),
+    /// The simulation succeeded and nothing was sent
+    Simulated {
+        units_consumed: Option<u64>,
+    },
+    /// The program rejected the transaction, so retrying would not help
+    Rejected(String),
+}
+
+impl Sender {
+    /// Simulates the task and, unless `dry_run` is set, sends it, retrying transport
+    /// failures and expired transactions with a doubled priority fee
+    pub fn submit(&self, task: &Task, dry_run: bool) -> anyhow::Result<Outcome> {
+        let mut attempt = 0;
+
+        loop {
+            match self.attempt(task, attempt, dry_run) {
+                Ok(outcome) => return Ok(outcome),
+                Err(error) if attempt < self.retries => {
+                    eprintln!("{task}: attempt {} failed: {error}", attempt + 1);
+
+                    thread::sleep(RETRY_BACKOFF * 2u32.pow(attempt));
+                    attempt += 1;
+                }
+                Err(error) => return Err(error.into()),
+            }
+        }
+    }
+
+    fn attempt(&self, task: &Task, attempt: u32, dry_run: bool) -> Result<Outcome, ClientError> {
+        let mut instructions = vec![ComputeBudgetInstruction::set_compute_unit_price(
+            self.priority_fee(attempt),
+        )];
+
+        if let Some(limit) = self.compute_unit_limit {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(limit));
+        }
+
+        instructions.push(task.instruction(&self.keypair.pubkey()));
+
+        let transaction = self
+            .rpc
+            .sign_instructions(&instructions, &[&self.keypair])?;
+        let simulation = self.rpc.simulate_transaction(&transaction)?;
+
+        if let Some(err) = simulation.err {
+            return Ok(Outcome::Rejected(err.to_string()));
+        }
+
+        if dry_run {
+            return Ok(Outcome::Simulated {
+                units_consumed: simulation.units_consumed,
+            });
+        }
+
+        self.rpc.send_and_confirm(&transaction).map(Outcome::Sent)
+    }
+
+    fn priority_fee(&self, attempt: u32) -> u64 {
+        if attempt == 0 {
+            return self.priority_fee;
+        }
+
+        self.priority_fee
+            .max(MIN_ESCALATED_FEE)
+            .saturating_mul(1 << attempt.min(32))
+            .min(self.max_priority_fee)
+    }
+}
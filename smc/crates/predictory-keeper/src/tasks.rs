@@ -0,0 +1,482 @@
+use std::fmt;
+
+use predictory_client::{
+    accounts,
+    event_id::format_event_id,
+    instructions::{self as ix, SettlementPosition},
+    predictory::{
+        context::{APPELLATION_DEADLINE, COMPLETION_DEADLINE, RESOLUTION_DEADLINE},
+        state::{
+            attestation::{ResolutionAttestation, ResolutionStatus},
+            event::Event,
+            participation::Participation,
+            portfolio::Portfolio,
+        },
+    },
+    rpc::RpcClient,
+};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+/// Positions settled per `settle_batch` transaction, bounded by the transaction size
+const SETTLEMENT_PAGE_SIZE: usize = 7;
+
+/// Transaction the keeper can send on its own
+#[derive(Debug, Clone)]
+pub enum Task {
+    /// Cancel an event its organizers failed to complete in time
+    CancelOverdue { event_id: u128, authority: Pubkey },
+    /// Pay out a page of unclaimed positions of a completed event; without positions
+    /// it only releases the organizer stake left behind by `claim_many`
+    Settle {
+        event_id: u128,
+        authority: Pubkey,
+        result: u8,
+        options_table: bool,
+        positions: Vec<SettlementPosition>,
+    },
+    /// Keep the original result of a dispute no resolver settled in time
+    ExpireDispute { event_id: u128 },
+    /// Seal the outcome of a completed event in its resolution attestation
+    Finalize { event_id: u128 },
+    /// Get back a deposit of the keeper wallet from a canceled event
+    Recharge { event_id: u128, page: u16 },
+    /// Close an empty portfolio page of the keeper wallet
+    ClosePortfolioPage { page: u16 },
+}
+
+impl Task {
+    pub fn instruction(&self, keeper: &Pubkey) -> Instruction {
+        match self {
+            Task::CancelOverdue {
+                event_id,
+                authority,
+            } => ix::cancel_event(keeper, *event_id, authority, false),
+            Task::Settle {
+                event_id,
+                authority,
+                result,
+                options_table,
+                positions,
+            } => ix::settle_batch(
+                keeper,
+                *event_id,
+                authority,
+                *result,
+                *options_table,
+                positions,
+            ),
+            Task::ExpireDispute { event_id } => ix::expire_dispute(keeper, *event_id),
+            Task::Finalize { event_id } => ix::finalize_event(keeper, *event_id),
+            Task::Recharge { event_id, page } => ix::recharge(keeper, *event_id, *page),
+            Task::ClosePortfolioPage { page } => ix::close_portfolio_page(keeper, *page),
+        }
+    }
+}
+
+impl fmt::Display for Task {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Task::CancelOverdue { event_id, .. } => {
+                write!(f, "cancel overdue event {}", format_event_id(*event_id))
+            }
+            Task::Settle {
+                event_id,
+                positions,
+                ..
+            } if positions.is_empty() => write!(
+                f,
+                "release organizer stake of event {}",
+                format_event_id(*event_id)
+            ),
+            Task::Settle {
+                event_id,
+                positions,
+                ..
+            } => write!(
+                f,
+                "settle {} positions of event {}",
+                positions.len(),
+                format_event_id(*event_id)
+            ),
+            Task::ExpireDispute { event_id } => {
+                write!(f, "expire dispute of event {}", format_event_id(*event_id))
+            }
+            Task::Finalize { event_id } => {
+                write!(f, "finalize event {}", format_event_id(*event_id))
+            }
+            Task::Recharge { event_id, .. } => {
+                write!(f, "recharge event {}", format_event_id(*event_id))
+            }
+            Task::ClosePortfolioPage { page } => write!(f, "close portfolio page {page}"),
+        }
+    }
+}
+
+/// Scans program accounts for the transitions the keeper can drive at `now`
+pub fn plan(rpc: &RpcClient, keeper: &Pubkey, now: i64) -> anyhow::Result<Vec<Task>> {
+    Ok(Snapshot::fetch(rpc)?.tasks(keeper, now))
+}
+
+/// Program accounts the keeper plans its tasks from
+#[derive(Default)]
+struct Snapshot {
+    events: Vec<Event>,
+    /// Events whose resolution attestation is no longer pending
+    finalized: Vec<u128>,
+    participations: Vec<Participation>,
+    portfolios: Vec<Portfolio>,
+}
+
+impl Snapshot {
+    fn fetch(rpc: &RpcClient) -> anyhow::Result<Self> {
+        Ok(Snapshot {
+            events: accounts::fetch_all_versioned::<Event>(rpc)?
+                .into_iter()
+                .map(|(_, event)| event)
+                .collect(),
+            finalized: accounts::fetch_all::<ResolutionAttestation>(rpc)?
+                .into_iter()
+                .filter(|(_, attestation)| attestation.status != ResolutionStatus::Pending)
+                .map(|(_, attestation)| attestation.event_id)
+                .collect(),
+            participations: accounts::fetch_all_versioned::<Participation>(rpc)?
+                .into_iter()
+                .map(|(_, participation)| participation)
+                .collect(),
+            portfolios: accounts::fetch_all::<Portfolio>(rpc)?
+                .into_iter()
+                .map(|(_, portfolio)| portfolio)
+                .collect(),
+        })
+    }
+
+    fn tasks(&self, keeper: &Pubkey, now: i64) -> Vec<Task> {
+        let events = &self.events;
+        let participations = &self.participations;
+        let mut tasks = vec![];
+
+        for event in events {
+            if is_overdue(event, now) {
+                tasks.push(Task::CancelOverdue {
+                    event_id: event.id(),
+                    authority: event.authority,
+                });
+            }
+        }
+
+        // Settlement and finalization of these events follow on the next pass
+        for event in events {
+            if is_dispute_expired(event, now) {
+                tasks.push(Task::ExpireDispute {
+                    event_id: event.id(),
+                });
+            }
+        }
+
+        for event in events {
+            if is_settleable(event, now) && !self.finalized.contains(&event.id()) {
+                tasks.push(Task::Finalize {
+                    event_id: event.id(),
+                });
+            }
+        }
+
+        for event in events {
+            let Some(result) = event.result().filter(|_| is_settleable(event, now)) else {
+                continue;
+            };
+
+            let positions: Vec<SettlementPosition> = participations
+                .iter()
+                .filter(|participation| {
+                    participation.event_id() == event.id() && participation.is_claimed == 0
+                })
+                .map(|participation| SettlementPosition {
+                    participant: participation.payer,
+                    portfolio_page: participation.portfolio_page,
+                })
+                .collect();
+
+            if positions.is_empty() && event.stake != 0 {
+                tasks.push(Task::Settle {
+                    event_id: event.id(),
+                    authority: event.authority,
+                    result,
+                    options_table: event.options_table != 0,
+                    positions: vec![],
+                });
+            }
+
+            for page in positions.chunks(SETTLEMENT_PAGE_SIZE) {
+                tasks.push(Task::Settle {
+                    event_id: event.id(),
+                    authority: event.authority,
+                    result,
+                    options_table: event.options_table != 0,
+                    positions: page.to_vec(),
+                });
+            }
+        }
+
+        // Deposits in canceled events can only be recharged by their owner
+        for participation in participations
+            .iter()
+            .filter(|participation| participation.payer == *keeper && participation.is_claimed == 0)
+        {
+            let canceled = events
+                .iter()
+                .any(|event| event.id() == participation.event_id() && event.canceled != 0);
+
+            if canceled {
+                tasks.push(Task::Recharge {
+                    event_id: participation.event_id(),
+                    page: participation.portfolio_page,
+                });
+            }
+        }
+
+        // Pages emptied by the settlements above are closed on the next pass
+        for portfolio in &self.portfolios {
+            if portfolio.owner == *keeper && portfolio.entries.is_empty() {
+                tasks.push(Task::ClosePortfolioPage {
+                    page: portfolio.page,
+                });
+            }
+        }
+
+        tasks
+    }
+}
+
+/// Published events without a result past the completion deadline can be canceled by anyone
+fn is_overdue(event: &Event, now: i64) -> bool {
+    event.published != 0
+        && event.canceled == 0
+        && event.result().is_none()
+        && now > event.end_date + COMPLETION_DEADLINE
+}
+
+/// Disputes left unresolved past the resolution deadline can be expired by anyone
+fn is_dispute_expired(event: &Event, now: i64) -> bool {
+    event.disputed != 0
+        && now > event.end_date + COMPLETION_DEADLINE + APPELLATION_DEADLINE + RESOLUTION_DEADLINE
+}
+
+/// Completed events can be settled by anyone once the appellation window is over
+fn is_settleable(event: &Event, now: i64) -> bool {
+    event.result().is_some()
+        && event.frozen == 0
+        && event.disputed == 0
+        && now > event.end_date + COMPLETION_DEADLINE + APPELLATION_DEADLINE
+}
+
+#[cfg(test)]
+mod tests {
+    use predictory_client::predictory::state::{
+        legacy::Versioned,
+        portfolio::{PortfolioEntry, PositionStatus},
+    };
+
+    use super::*;
+
+    const END_DATE: i64 = 1_700_000_000;
+    /// First second the appellation window of events ending at `END_DATE` is over
+    const SETTLEABLE: i64 = END_DATE + COMPLETION_DEADLINE + APPELLATION_DEADLINE + 1;
+
+    /// Zero-copy account with every field zeroed, as on creation
+    fn zeroed<T: Versioned>() -> T {
+        let mut data = T::DISCRIMINATOR.to_vec();
+        data.resize(T::SPACE, 0);
+        accounts::decode(&data).unwrap()
+    }
+
+    fn event(id: u128) -> Event {
+        let mut event: Event = zeroed();
+        event.id = id.to_le_bytes();
+        event.authority = Pubkey::new_unique();
+        event.end_date = END_DATE;
+        event.published = 1;
+        event
+    }
+
+    fn completed(id: u128) -> Event {
+        let mut event = event(id);
+        event.set_result(1);
+        event
+    }
+
+    fn participation(event_id: u128, payer: Pubkey) -> Participation {
+        let mut participation: Participation = zeroed();
+        participation.event_id = event_id.to_le_bytes();
+        participation.payer = payer;
+        participation
+    }
+
+    fn descriptions(snapshot: &Snapshot, keeper: &Pubkey, now: i64) -> Vec<String> {
+        snapshot
+            .tasks(keeper, now)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn completed_events_are_finalized_and_settled() {
+        let keeper = Pubkey::new_unique();
+        let mut snapshot = Snapshot {
+            events: vec![completed(1)],
+            participations: (0..SETTLEMENT_PAGE_SIZE + 1)
+                .map(|_| participation(1, Pubkey::new_unique()))
+                .collect(),
+            ..Default::default()
+        };
+        let id = format_event_id(1);
+
+        // Nothing is due until the appellation window is over
+        assert!(snapshot.tasks(&keeper, SETTLEABLE - 1).is_empty());
+
+        assert_eq!(
+            descriptions(&snapshot, &keeper, SETTLEABLE),
+            [
+                format!("finalize event {id}"),
+                format!("settle {SETTLEMENT_PAGE_SIZE} positions of event {id}"),
+                format!("settle 1 positions of event {id}"),
+            ]
+        );
+
+        let tasks = snapshot.tasks(&keeper, SETTLEABLE);
+        let Task::Settle {
+            result, positions, ..
+        } = &tasks[1]
+        else {
+            panic!("expected a settlement, got {}", tasks[1]);
+        };
+        assert_eq!(*result, 1);
+        assert_eq!(positions[0].participant, snapshot.participations[0].payer);
+
+        // Finalized events with claimed positions leave only the organizer stake
+        snapshot.finalized = vec![1];
+        snapshot.events[0].stake = 100;
+        for participation in &mut snapshot.participations {
+            participation.is_claimed = 1;
+        }
+        assert_eq!(
+            descriptions(&snapshot, &keeper, SETTLEABLE),
+            [format!("release organizer stake of event {id}")]
+        );
+
+        snapshot.events[0].stake = 0;
+        assert!(snapshot.tasks(&keeper, SETTLEABLE).is_empty());
+    }
+
+    #[test]
+    fn frozen_and_disputed_events_are_skipped() {
+        let keeper = Pubkey::new_unique();
+        let mut frozen = completed(1);
+        frozen.frozen = 1;
+        let mut disputed = completed(2);
+        disputed.disputed = 1;
+        let snapshot = Snapshot {
+            events: vec![frozen, disputed],
+            participations: vec![
+                participation(1, Pubkey::new_unique()),
+                participation(2, Pubkey::new_unique()),
+            ],
+            ..Default::default()
+        };
+
+        assert!(snapshot.tasks(&keeper, SETTLEABLE).is_empty());
+
+        // Disputes are expired once the resolver missed its deadline
+        let expired = END_DATE + COMPLETION_DEADLINE + APPELLATION_DEADLINE + RESOLUTION_DEADLINE;
+        assert!(snapshot.tasks(&keeper, expired).is_empty());
+        assert_eq!(
+            descriptions(&snapshot, &keeper, expired + 1),
+            [format!("expire dispute of event {}", format_event_id(2))]
+        );
+    }
+
+    #[test]
+    fn overdue_events_are_canceled() {
+        let keeper = Pubkey::new_unique();
+        let mut draft = event(2);
+        draft.published = 0;
+        let mut canceled = event(3);
+        canceled.canceled = 1;
+        let snapshot = Snapshot {
+            events: vec![event(1), draft, canceled, completed(4)],
+            finalized: vec![4],
+            ..Default::default()
+        };
+        let overdue = END_DATE + COMPLETION_DEADLINE;
+
+        assert!(snapshot.tasks(&keeper, overdue).is_empty());
+        assert_eq!(
+            descriptions(&snapshot, &keeper, overdue + 1),
+            [format!("cancel overdue event {}", format_event_id(1))]
+        );
+    }
+
+    #[test]
+    fn only_unclaimed_keeper_deposits_are_recharged() {
+        let keeper = Pubkey::new_unique();
+        let mut canceled = event(1);
+        canceled.canceled = 1;
+        let mut claimed = participation(2, keeper);
+        claimed.is_claimed = 1;
+        let mut deposit = participation(1, keeper);
+        deposit.portfolio_page = 3;
+        let snapshot = Snapshot {
+            events: vec![canceled, event(2)],
+            participations: vec![
+                deposit,
+                claimed,
+                participation(1, Pubkey::new_unique()),
+                participation(2, keeper),
+            ],
+            ..Default::default()
+        };
+
+        let tasks = snapshot.tasks(&keeper, END_DATE);
+        assert!(matches!(
+            tasks[..],
+            [Task::Recharge {
+                event_id: 1,
+                page: 3
+            }]
+        ));
+    }
+
+    #[test]
+    fn only_empty_keeper_pages_are_closed() {
+        let keeper = Pubkey::new_unique();
+        let page = |owner, page, entries| Portfolio {
+            version: Portfolio::VERSION,
+            owner,
+            page,
+            entries,
+        };
+        let snapshot = Snapshot {
+            portfolios: vec![
+                page(
+                    keeper,
+                    0,
+                    vec![PortfolioEntry {
+                        event_id: 1,
+                        option: 0,
+                        amount: 100,
+                        status: PositionStatus::Open,
+                    }],
+                ),
+                page(keeper, 1, vec![]),
+                page(Pubkey::new_unique(), 2, vec![]),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            descriptions(&snapshot, &keeper, END_DATE),
+            ["close portfolio page 1"]
+        );
+    }
+}
@@ -0,0 +1,366 @@
+#![allow(unexpected_cfgs)]
+
+//! Program integrating Predictory the way a third party would: a PDA-owned user creating
+//! events and voting through CPI, and a resolution callback recording event results
+
+use anchor_lang::prelude::*;
+use predictory::{
+    context::{CreateEventArgs, CALLBACK_AUTHORITY_SEED},
+    cpi::{self, accounts as predictory_accounts},
+    program::Predictory,
+    state::event::Event,
+};
+
+declare_id!("BrsaoxnUwbRdWsPqyZpHepjsQWrMrQEGWnYfPTyr5ro7");
+
+/// Seed of the system-owned PDA acting as this program's Predictory user
+pub const VAULT_SEED: &[u8] = b"vault";
+
+#[program]
+pub mod mock_consumer {
+    use super::*;
+
+    pub fn create_user(ctx: Context<CreateUser>, name: [u8; 32]) -> Result<()> {
+        let bump = [ctx.bumps.vault];
+        let signer: &[&[&[u8]]] = &[&[VAULT_SEED, &bump]];
+
+        let accounts = predictory_accounts::CreateUser {
+            sender: ctx.accounts.vault.to_account_info(),
+            user: ctx.accounts.user.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+
+        cpi::create_user(
+            CpiContext::new_with_signer(
+                ctx.accounts.predictory.to_account_info(),
+                accounts,
+                signer,
+            ),
+            name,
+        )
+    }
+
+    pub fn transfer_stake(ctx: Context<CreateUser>, stake: u64) -> Result<()> {
+        let bump = [ctx.bumps.vault];
+        let signer: &[&[&[u8]]] = &[&[VAULT_SEED, &bump]];
+
+        let accounts = predictory_accounts::TransferStake {
+            sender: ctx.accounts.vault.to_account_info(),
+            user: ctx.accounts.user.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+
+        cpi::transfer_stake(
+            CpiContext::new_with_signer(
+                ctx.accounts.predictory.to_account_info(),
+                accounts,
+                signer,
+            ),
+            stake,
+        )
+    }
+
+    pub fn create_portfolio_page(ctx: Context<CreatePortfolioPage>, page: u16) -> Result<()> {
+        let bump = [ctx.bumps.vault];
+        let signer: &[&[&[u8]]] = &[&[VAULT_SEED, &bump]];
+
+        let accounts = predictory_accounts::CreatePortfolioPage {
+            sender: ctx.accounts.vault.to_account_info(),
+            portfolio: ctx.accounts.portfolio.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+
+        cpi::create_portfolio_page(
+            CpiContext::new_with_signer(
+                ctx.accounts.predictory.to_account_info(),
+                accounts,
+                signer,
+            ),
+            page,
+        )
+    }
+
+    pub fn create_event(
+        ctx: Context<CreateEvent>,
+        event_id: u128,
+        args: CreateEventArgs,
+    ) -> Result<()> {
+        let bump = [ctx.bumps.vault];
+        let signer: &[&[&[u8]]] = &[&[VAULT_SEED, &bump]];
+
+        let accounts = predictory_accounts::CreateEvent {
+            authority: ctx.accounts.vault.to_account_info(),
+            user: ctx.accounts.user.to_account_info(),
+            state: ctx.accounts.state.to_account_info(),
+            event: ctx.accounts.event.to_account_info(),
+            event_meta: ctx.accounts.event_meta.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            rent: ctx.accounts.rent.to_account_info(),
+        };
+
+        cpi::create_event(
+            CpiContext::new_with_signer(
+                ctx.accounts.predictory.to_account_info(),
+                accounts,
+                signer,
+            ),
+            event_id,
+            args,
+        )
+    }
+
+    pub fn vote(ctx: Context<Vote>, event_id: u128, option_ix: u8, amount: u64) -> Result<()> {
+        let bump = [ctx.bumps.vault];
+        let signer: &[&[&[u8]]] = &[&[VAULT_SEED, &bump]];
+
+        let accounts = predictory_accounts::Vote {
+            sender: ctx.accounts.vault.to_account_info(),
+            user: ctx.accounts.user.to_account_info(),
+            state: ctx.accounts.state.to_account_info(),
+            event: ctx.accounts.event.to_account_info(),
+            option: ctx.accounts.option.as_ref().map(|o| o.to_account_info()),
+            options_table: ctx
+                .accounts
+                .options_table
+                .as_ref()
+                .map(|t| t.to_account_info()),
+            participation: ctx.accounts.participation.to_account_info(),
+            portfolio: ctx.accounts.portfolio.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+
+        cpi::vote(
+            CpiContext::new_with_signer(
+                ctx.accounts.predictory.to_account_info(),
+                accounts,
+                signer,
+            ),
+            event_id,
+            option_ix,
+            amount,
+        )
+    }
+
+    pub fn watch_event(ctx: Context<WatchEvent>, event_id: u128) -> Result<()> {
+        let record = &mut ctx.accounts.record;
+
+        record.event_id = event_id;
+        record.resolved = false;
+        record.result = 0;
+        record.rejecting = false;
+
+        Ok(())
+    }
+
+    /// Makes the resolution callback fail, standing in for a broken consumer
+    pub fn set_rejecting(
+        ctx: Context<SetRejecting>,
+        _event_id: u128,
+        rejecting: bool,
+    ) -> Result<()> {
+        ctx.accounts.record.rejecting = rejecting;
+
+        Ok(())
+    }
+
+    /// Resolution callback, invoked by Predictory when a watched event gets its result.
+    /// The result is read from the event account rather than trusted from the arguments
+    pub fn on_event_resolved(
+        ctx: Context<OnEventResolved>,
+        event_id: u128,
+        _result: u8,
+    ) -> Result<()> {
+        let result = ctx
+            .accounts
+            .event
+            .load()?
+            .result()
+            .ok_or(MockError::Unresolved)?;
+        let record = &mut ctx.accounts.record;
+
+        require!(!record.rejecting, MockError::Rejected);
+
+        record.resolved = true;
+        record.result = result;
+
+        msg!("Event {} resolved with option {}", event_id, result);
+
+        Ok(())
+    }
+}
+
+// --------------------------- Context ----------------------------- //
+
+// Predictory accounts are unchecked here, the CPI validates them
+
+#[derive(Accounts)]
+pub struct CreateUser<'info> {
+    /// CHECK: system-owned PDA signing for this program, funded by the caller
+    #[account(mut, seeds = [VAULT_SEED], bump)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: validated by Predictory
+    #[account(mut)]
+    pub user: UncheckedAccount<'info>,
+
+    pub predictory: Program<'info, Predictory>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreatePortfolioPage<'info> {
+    /// CHECK: system-owned PDA signing for this program
+    #[account(mut, seeds = [VAULT_SEED], bump)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: validated by Predictory
+    #[account(mut)]
+    pub portfolio: UncheckedAccount<'info>,
+
+    pub predictory: Program<'info, Predictory>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateEvent<'info> {
+    /// CHECK: system-owned PDA signing for this program
+    #[account(mut, seeds = [VAULT_SEED], bump)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: validated by Predictory
+    pub user: UncheckedAccount<'info>,
+
+    /// CHECK: validated by Predictory
+    pub state: UncheckedAccount<'info>,
+
+    /// CHECK: validated by Predictory
+    #[account(mut)]
+    pub event: UncheckedAccount<'info>,
+
+    /// CHECK: validated by Predictory
+    #[account(mut)]
+    pub event_meta: UncheckedAccount<'info>,
+
+    pub predictory: Program<'info, Predictory>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct Vote<'info> {
+    /// CHECK: system-owned PDA signing for this program
+    #[account(mut, seeds = [VAULT_SEED], bump)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: validated by Predictory
+    #[account(mut)]
+    pub user: UncheckedAccount<'info>,
+
+    /// CHECK: validated by Predictory
+    pub state: UncheckedAccount<'info>,
+
+    /// CHECK: validated by Predictory
+    #[account(mut)]
+    pub event: UncheckedAccount<'info>,
+
+    /// CHECK: validated by Predictory
+    #[account(mut)]
+    pub option: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: validated by Predictory
+    #[account(mut)]
+    pub options_table: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: validated by Predictory
+    #[account(mut)]
+    pub participation: UncheckedAccount<'info>,
+
+    /// CHECK: validated by Predictory
+    #[account(mut)]
+    pub portfolio: UncheckedAccount<'info>,
+
+    pub predictory: Program<'info, Predictory>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(event_id: u128)]
+pub struct WatchEvent<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    #[account(
+        init,
+        payer = sender,
+        seeds = [b"record".as_ref(), &event_id.to_le_bytes()],
+        bump,
+        space = 8 + Record::INIT_SPACE
+    )]
+    pub record: Account<'info, Record>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(event_id: u128)]
+pub struct SetRejecting<'info> {
+    #[account(
+        mut,
+        seeds = [b"record".as_ref(), &event_id.to_le_bytes()],
+        bump,
+    )]
+    pub record: Account<'info, Record>,
+}
+
+#[derive(Accounts)]
+#[instruction(event_id: u128)]
+pub struct OnEventResolved<'info> {
+    /// Predictory callback authority of the event, only Predictory can sign for it
+    #[account(
+        constraint = callback_authority.key() == callback_authority_address(event_id) @ MockError::UnknownCaller,
+    )]
+    pub callback_authority: Signer<'info>,
+
+    /// The resolved event, owned by Predictory and holding the result
+    #[account(
+        constraint = event.load()?.id() == event_id @ MockError::UnknownCaller,
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        mut,
+        seeds = [b"record".as_ref(), &event_id.to_le_bytes()],
+        bump,
+    )]
+    pub record: Account<'info, Record>,
+}
+
+// ---------------------------- State ------------------------------ //
+
+/// Last known result of a watched event
+#[account]
+#[derive(InitSpace)]
+pub struct Record {
+    pub event_id: u128,
+    pub resolved: bool,
+    pub result: u8,
+    pub rejecting: bool,
+}
+
+#[error_code]
+pub enum MockError {
+    #[msg("Callback not signed by Predictory")]
+    UnknownCaller,
+    #[msg("Callback rejected")]
+    Rejected,
+    #[msg("Event has no result")]
+    Unresolved,
+}
+
+fn callback_authority_address(event_id: u128) -> Pubkey {
+    Pubkey::find_program_address(
+        &[CALLBACK_AUTHORITY_SEED, &event_id.to_le_bytes()],
+        &predictory::ID,
+    )
+    .0
+}
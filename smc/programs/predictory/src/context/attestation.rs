@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    context::{APPELLATION_DEADLINE, COMPLETION_DEADLINE},
+    error::ProgramError,
+    events::EventFinalized,
+    id,
+    state::{
+        attestation::{ResolutionAttestation, ResolutionStatus},
+        event::Event,
+    },
+};
+
+// --------------------------- Context ----------------------------- //
+
+#[derive(Accounts)]
+#[instruction(
+    event_id: u128,
+)]
+pub struct FinalizeEvent<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    #[account(
+        seeds = [b"event".as_ref(), &event_id.to_le_bytes()],
+        bump = event.load()?.bump,
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    /// Created here for events resolved before attestations existed
+    #[account(
+        init_if_needed,
+        payer = sender,
+        owner = id(),
+        seeds = [b"attestation".as_ref(), &event_id.to_le_bytes()],
+        constraint = attestation.status == ResolutionStatus::Pending @ ProgramError::EventFinalized,
+        bump,
+        space = ResolutionAttestation::LEN
+    )]
+    pub attestation: Account<'info, ResolutionAttestation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// ------------------------ Implementation ------------------------- //
+
+impl FinalizeEvent<'_> {
+    pub fn finalize_event(&mut self, event_id: u128) -> Result<()> {
+        let event = self.event.load()?;
+
+        let (status, result) = if event.canceled != 0 {
+            (ResolutionStatus::Canceled, 0)
+        } else {
+            require!(event.resolved != 0, ProgramError::EventIsNotOver);
+            require!(event.disputed == 0, ProgramError::EventDisputed);
+            require!(event.frozen == 0, ProgramError::EventFrozen);
+            require!(
+                Clock::get()?.unix_timestamp
+                    > event.end_date + COMPLETION_DEADLINE + APPELLATION_DEADLINE,
+                ProgramError::AppellationWindowOpen
+            );
+
+            (ResolutionStatus::Resolved, event.result)
+        };
+
+        // Attestations created here predate the resolver record, the event authority
+        // set the result or canceled the event
+        let resolver = match self.attestation.resolver {
+            resolver if resolver == Pubkey::default() => event.authority,
+            resolver => resolver,
+        };
+
+        record_resolution(&mut self.attestation, event_id, status, result, resolver)?;
+
+        msg!("Event finalized: {}", uuid::Uuid::from_u128(event_id));
+
+        Ok(())
+    }
+}
+
+/// This method writes the event outcome to its attestation, sealing it unless still pending
+pub fn record_resolution(
+    attestation: &mut ResolutionAttestation,
+    event_id: u128,
+    status: ResolutionStatus,
+    result: u8,
+    resolver: Pubkey,
+) -> Result<()> {
+    attestation.version = ResolutionAttestation::VERSION;
+    attestation.event_id = event_id;
+    attestation.status = status;
+    attestation.result = result;
+    attestation.resolver = resolver;
+
+    if status == ResolutionStatus::Pending {
+        return Ok(());
+    }
+
+    let slot = Clock::get()?.slot;
+    attestation.finalized_slot = slot;
+
+    emit!(EventFinalized {
+        event_id,
+        status,
+        result,
+        resolver,
+        slot,
+    });
+
+    Ok(())
+}
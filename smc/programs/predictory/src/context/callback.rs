@@ -0,0 +1,236 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        hash::hash,
+        instruction::{AccountMeta, Instruction},
+        program::invoke_signed,
+    },
+};
+
+use crate::{
+    context::{APPELLATION_DEADLINE, COMPLETION_DEADLINE},
+    error::ProgramError,
+    events::{CallbackNotified, EventCallbackSet},
+    id,
+    state::{
+        callback::{EventCallback, EventResolved},
+        event::Event,
+    },
+};
+
+/// Seed of the per-event PDA signing resolution callbacks, `[CALLBACK_AUTHORITY_SEED, event_id]`;
+/// callback programs check it against the event id to trust the call
+pub const CALLBACK_AUTHORITY_SEED: &[u8] = b"callback_authority";
+
+// --------------------------- Context ----------------------------- //
+
+#[derive(Accounts)]
+#[instruction(
+    event_id: u128,
+)]
+pub struct SetEventCallback<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"event".as_ref(), &event_id.to_le_bytes()],
+        constraint = event.load()?.authority == authority.key() @ ProgramError::AuthorityMismatch,
+        constraint = event.load()?.published == 0 @ ProgramError::EventAlreadyPublished,
+        bump = event.load()?.bump,
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        owner = id(),
+        seeds = [b"callback".as_ref(), &event_id.to_le_bytes()],
+        bump,
+        space = EventCallback::LEN
+    )]
+    pub callback: Account<'info, EventCallback>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    event_id: u128,
+)]
+pub struct RemoveEventCallback<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"event".as_ref(), &event_id.to_le_bytes()],
+        constraint = event.load()?.authority == authority.key() @ ProgramError::AuthorityMismatch,
+        constraint = event.load()?.published == 0 @ ProgramError::EventAlreadyPublished,
+        bump = event.load()?.bump,
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"callback".as_ref(), &event_id.to_le_bytes()],
+        bump,
+    )]
+    pub callback: Account<'info, EventCallback>,
+}
+
+/// Resolution does not wait for the callback program: once the result can no longer be disputed,
+/// anyone notifies it here, and a failing program only fails this instruction
+#[derive(Accounts)]
+#[instruction(
+    event_id: u128,
+)]
+pub struct NotifyCallback<'info> {
+    #[account(
+        seeds = [b"event".as_ref(), &event_id.to_le_bytes()],
+        constraint = event.load()?.resolved != 0 @ ProgramError::EventIsNotOver,
+        constraint = event.load()?.disputed == 0 @ ProgramError::EventDisputed,
+        constraint = event.load()?.end_date + COMPLETION_DEADLINE + APPELLATION_DEADLINE < Clock::get()?.unix_timestamp @ ProgramError::AppellationWindowOpen,
+        bump = event.load()?.bump,
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        mut,
+        seeds = [b"callback".as_ref(), &event_id.to_le_bytes()],
+        constraint = callback.is_pending(event.load()?.result) @ ProgramError::CallbackNotified,
+        bump,
+    )]
+    pub callback: Account<'info, EventCallback>,
+
+    /// CHECK: the program registered by the event authority
+    #[account(
+        executable,
+        constraint = callback_program.key() == callback.program @ ProgramError::InvalidCallbackAccounts,
+    )]
+    pub callback_program: UncheckedAccount<'info>,
+
+    /// CHECK: PDA signing the callback of this event only, owns no data
+    #[account(
+        seeds = [CALLBACK_AUTHORITY_SEED, &event_id.to_le_bytes()],
+        bump,
+    )]
+    pub callback_authority: UncheckedAccount<'info>,
+}
+
+// ------------------------ Implementation ------------------------- //
+
+impl SetEventCallback<'_> {
+    pub fn set_event_callback(&mut self, event_id: u128, program: Pubkey) -> Result<()> {
+        let callback = &mut self.callback;
+
+        callback.version = EventCallback::VERSION;
+        callback.event_id = event_id;
+        callback.program = program;
+        callback.notified = false;
+
+        self.event.load_mut()?.callback = 1;
+
+        emit!(EventCallbackSet {
+            event_id,
+            program: Some(program),
+        });
+
+        msg!(
+            "Event {} notifies program {} of its result",
+            uuid::Uuid::from_u128(event_id),
+            program
+        );
+
+        Ok(())
+    }
+}
+
+impl RemoveEventCallback<'_> {
+    pub fn remove_event_callback(&mut self, event_id: u128) -> Result<()> {
+        self.event.load_mut()?.callback = 0;
+
+        emit!(EventCallbackSet {
+            event_id,
+            program: None,
+        });
+
+        msg!("Event {} callback removed", uuid::Uuid::from_u128(event_id));
+
+        Ok(())
+    }
+}
+
+impl<'info> NotifyCallback<'info> {
+    /// This method invokes the callback program with the event result, signing as the callback
+    /// authority of the event. Remaining accounts are forwarded after the callback authority and the event
+    pub fn notify_callback(
+        &mut self,
+        event_id: u128,
+        authority_bump: u8,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let result = self.event.load()?.result;
+
+        let mut data = hash(format!("global:{}", EventResolved::INSTRUCTION).as_bytes()).to_bytes()
+            [..8]
+            .to_vec();
+        EventResolved { event_id, result }.serialize(&mut data)?;
+
+        let accounts = [
+            AccountMeta::new_readonly(self.callback_authority.key(), true),
+            AccountMeta::new_readonly(self.event.key(), false),
+        ]
+        .into_iter()
+        .chain(remaining_accounts.iter().map(|account| AccountMeta {
+            pubkey: account.key(),
+            is_signer: account.is_signer,
+            is_writable: account.is_writable,
+        }))
+        .collect();
+
+        let infos = [
+            self.callback_authority.to_account_info(),
+            self.event.to_account_info(),
+        ]
+        .into_iter()
+        .chain(remaining_accounts.iter().cloned())
+        .chain([self.callback_program.to_account_info()])
+        .collect::<Vec<_>>();
+
+        invoke_signed(
+            &Instruction {
+                program_id: self.callback_program.key(),
+                accounts,
+                data,
+            },
+            &infos,
+            &[&[
+                CALLBACK_AUTHORITY_SEED,
+                &event_id.to_le_bytes(),
+                &[authority_bump],
+            ]],
+        )?;
+
+        let callback = &mut self.callback;
+
+        callback.notified = true;
+        callback.notified_result = result;
+
+        emit!(CallbackNotified {
+            event_id,
+            program: callback.program,
+            result,
+        });
+
+        msg!(
+            "Program {} notified of event {} result {}",
+            callback.program,
+            uuid::Uuid::from_u128(event_id),
+            result
+        );
+
+        Ok(())
+    }
+}
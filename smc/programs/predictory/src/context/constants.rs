@@ -1,3 +1,7 @@
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+
+use crate::state::OPTIONS_TABLE_SIZE;
+
 pub const UUID_VERSION: usize = 4;
 
 #[cfg(feature = "testing")]
@@ -10,5 +14,55 @@ pub const APPELLATION_DEADLINE: i64 = 1; // Only for testing
 #[cfg(not(feature = "testing"))]
 pub const APPELLATION_DEADLINE: i64 = 60 * 60 * 24; // 1 days
 
-pub const MAX_OPTION_COUNT: u8 = 20;
+#[cfg(feature = "testing")]
+pub const RESOLUTION_DEADLINE: i64 = 2; // Only for testing
+#[cfg(not(feature = "testing"))]
+pub const RESOLUTION_DEADLINE: i64 = 60 * 60 * 24 * 7; // 7 days
+
+#[cfg(feature = "testing")]
+pub const PARAM_CHANGE_DELAY: i64 = 0; // Only for testing
+#[cfg(not(feature = "testing"))]
+pub const PARAM_CHANGE_DELAY: i64 = 60 * 60 * 24 * 2; // 2 days
+
+#[cfg(feature = "testing")]
+pub const GOVERNANCE_VOTING_PERIOD: i64 = 2; // Only for testing
+#[cfg(not(feature = "testing"))]
+pub const GOVERNANCE_VOTING_PERIOD: i64 = 60 * 60 * 24 * 3; // 3 days
+
+#[cfg(feature = "testing")]
+pub const GOVERNANCE_EXECUTION_DELAY: i64 = 0; // Only for testing
+#[cfg(not(feature = "testing"))]
+pub const GOVERNANCE_EXECUTION_DELAY: i64 = 60 * 60 * 24 * 2; // 2 days
+
+#[cfg(feature = "testing")]
+pub const PROPOSAL_TRUST_THRESHOLD: u64 = 5; // Only for testing
+#[cfg(not(feature = "testing"))]
+pub const PROPOSAL_TRUST_THRESHOLD: u64 = 100; // earned trust
+
+#[cfg(feature = "testing")]
+pub const GOVERNANCE_QUORUM: u64 = 10; // Only for testing
+#[cfg(not(feature = "testing"))]
+pub const GOVERNANCE_QUORUM: u64 = 10_000; // total trust weight
+
+pub const MAX_MULTIPLIER: u64 = 1_000;
+pub const MAX_EVENT_PRICE: u64 = 10 * LAMPORTS_PER_SOL;
+pub const MAX_PLATFORM_FEE: u64 = LAMPORTS_PER_SOL;
+pub const MAX_ORG_REWARD: u64 = 50; // percent
+
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+pub const MAX_OPTION_COUNT: u8 = OPTIONS_TABLE_SIZE as u8;
+pub const MIN_OPTION_COUNT: u8 = 2;
 pub const INITIAL_LVL: u64 = 5;
+
+/// Accounts passed per position to `settle_batch`: participation, user and portfolio
+pub const SETTLEMENT_ACCOUNTS_LEN: usize = 3;
+
+/// Accounts passed per position to `claim_many`: event, option and participation
+pub const CLAIM_ACCOUNTS_LEN: usize = 3;
+
+/// Accounts passed per option to `create_event_options` and `delete_event`: option and option meta
+pub const OPTION_ACCOUNTS_LEN: usize = 2;
+
+/// Accounts passed per option of an event with an options table: option meta
+pub const TABLE_OPTION_ACCOUNTS_LEN: usize = 1;
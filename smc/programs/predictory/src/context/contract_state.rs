@@ -1,6 +1,21 @@
 use anchor_lang::prelude::*;
 
-use crate::{error::ProgramError, id, program::Predictory, state::contract_state::State};
+use crate::{
+    context::apply_param,
+    error::ProgramError,
+    events::{
+        AuthorityTimelockSet, AuthorityTransferProposed, AuthorityTransferred, ContractInitialized,
+        PauseFlagsSet,
+    },
+    id,
+    program::Predictory,
+    state::{
+        contract_state::State,
+        param::ParamKind,
+        role::{Role, RoleMember},
+        treasury::Treasury,
+    },
+};
 
 // --------------------------- Context ----------------------------- //
 
@@ -19,6 +34,16 @@ pub struct InitializeContractState<'info> {
     )]
     pub state: Account<'info, State>,
 
+    #[account(
+        init,
+        payer = authority,
+        owner = id(),
+        seeds = [b"treasury".as_ref()],
+        bump,
+        space = Treasury::LEN
+    )]
+    pub treasury: Account<'info, Treasury>,
+
     #[account(
         constraint = program_account.key() == id() @ ProgramError::InvalidProgramAccount,
         constraint = program_account.programdata_address()? == Some(program_data.key()) @ ProgramError::InvalidProgramData,
@@ -41,12 +66,48 @@ pub struct UpdateContractState<'info> {
     #[account(
         mut,
         seeds = [b"state".as_ref()],
+        constraint = state.version == State::VERSION @ ProgramError::StateNotMigrated,
         constraint = state.authority == authority.key() @ ProgramError::AuthorityMismatch,
         bump,
     )]
     pub state: Account<'info, State>,
 }
 
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    pub sender: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state".as_ref()],
+        constraint = state.version == State::VERSION @ ProgramError::StateNotMigrated,
+        constraint = state.pending_authority == Some(sender.key()) @ ProgramError::AuthorityMismatch,
+        bump,
+    )]
+    pub state: Account<'info, State>,
+}
+
+/// Pause flags are set by the contract authority or a guardian
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    pub sender: Signer<'info>,
+
+    /// Guardian role of the sender, left out when the contract authority signs
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::Guardian as u8], sender.key().as_ref()],
+        bump,
+    )]
+    pub role: Option<Account<'info, RoleMember>>,
+
+    #[account(
+        mut,
+        seeds = [b"state".as_ref()],
+        constraint = state.version == State::VERSION @ ProgramError::StateNotMigrated,
+        bump,
+    )]
+    pub state: Account<'info, State>,
+}
+
 // ------------------------ Implementation ------------------------- //
 
 impl InitializeContractState<'_> {
@@ -61,12 +122,22 @@ impl InitializeContractState<'_> {
         let state = &mut self.state;
 
         state.authority = authority;
-        state.multiplier = multiplier;
-        state.event_price = event_price;
-        state.platform_fee = platform_fee;
-        state.org_reward = org_reward;
+        apply_param(state, ParamKind::Multiplier, multiplier)?;
+        apply_param(state, ParamKind::EventPrice, event_price)?;
+        apply_param(state, ParamKind::PlatformFee, platform_fee)?;
+        apply_param(state, ParamKind::OrgReward, org_reward)?;
         state.version = State::VERSION;
 
+        self.treasury.version = Treasury::VERSION;
+
+        emit!(ContractInitialized {
+            authority,
+            multiplier,
+            event_price,
+            platform_fee,
+            org_reward,
+        });
+
         msg!("Contract state initialized");
 
         Ok(())
@@ -74,52 +145,107 @@ impl InitializeContractState<'_> {
 }
 
 impl UpdateContractState<'_> {
-    pub fn set_authority(&mut self, authority: Pubkey) -> Result<()> {
+    pub fn propose_authority(&mut self, authority: Pubkey) -> Result<()> {
         let state = &mut self.state;
 
-        state.authority = authority;
+        state.pending_authority = Some(authority);
+        state.authority_transfer_eta = Clock::get()?.unix_timestamp + state.authority_timelock;
 
-        msg!("Contract state updated: authority set to {authority}",);
+        emit!(AuthorityTransferProposed {
+            authority: state.authority,
+            pending_authority: state.pending_authority,
+            eta: state.authority_transfer_eta,
+        });
+
+        msg!(
+            "Contract authority transfer proposed to {authority}, available at {}",
+            state.authority_transfer_eta
+        );
 
         Ok(())
     }
 
-    pub fn set_multiplier(&mut self, multiplier: u64) -> Result<()> {
+    pub fn cancel_authority_transfer(&mut self) -> Result<()> {
         let state = &mut self.state;
 
-        state.multiplier = multiplier;
+        state.pending_authority = None;
+        state.authority_transfer_eta = 0;
+
+        emit!(AuthorityTransferProposed {
+            authority: state.authority,
+            pending_authority: None,
+            eta: 0,
+        });
 
-        msg!("Contract state multiplier updated",);
+        msg!("Contract authority transfer cancelled");
 
         Ok(())
     }
 
-    pub fn set_price(&mut self, event_price: u64) -> Result<()> {
+    pub fn set_authority_timelock(&mut self, timelock: i64) -> Result<()> {
+        require!(timelock >= 0, ProgramError::InvalidTimelock);
+
         let state = &mut self.state;
 
-        state.event_price = event_price;
+        state.authority_timelock = timelock;
 
-        msg!("Contract sale pricer updated",);
+        emit!(AuthorityTimelockSet { timelock });
+
+        msg!("Contract authority timelock set to {timelock}");
 
         Ok(())
     }
+}
 
-    pub fn set_platform_fee(&mut self, fee: u64) -> Result<()> {
+impl AcceptAuthority<'_> {
+    pub fn accept_authority(&mut self) -> Result<()> {
         let state = &mut self.state;
 
-        state.platform_fee = fee;
+        require!(
+            Clock::get()?.unix_timestamp >= state.authority_transfer_eta,
+            ProgramError::AuthorityTimelocked
+        );
+
+        emit!(AuthorityTransferred {
+            previous_authority: state.authority,
+            authority: self.sender.key(),
+        });
 
-        msg!("Contract fee updated");
+        state.authority = self.sender.key();
+        state.pending_authority = None;
+        state.authority_transfer_eta = 0;
+
+        msg!(
+            "Contract state updated: authority set to {}",
+            state.authority
+        );
 
         Ok(())
     }
+}
+
+impl SetPaused<'_> {
+    pub fn set_paused(&mut self, flags: u8) -> Result<()> {
+        require!(
+            flags & !State::PAUSE_ALL == 0,
+            ProgramError::InvalidPauseFlags
+        );
 
-    pub fn set_org_reward(&mut self, reward: u64) -> Result<()> {
         let state = &mut self.state;
 
-        state.org_reward = reward;
+        require!(
+            state.authority == self.sender.key() || self.role.is_some(),
+            ProgramError::AuthorityMismatch
+        );
+
+        state.paused = flags;
+
+        emit!(PauseFlagsSet {
+            guardian: self.sender.key(),
+            flags,
+        });
 
-        msg!("Contract org reward updated");
+        msg!("Contract pause flags set to {:#07b}", flags);
 
         Ok(())
     }
@@ -1,12 +1,26 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    context::{withdraw_sol, COMPLETION_DEADLINE, UUID_VERSION},
+    context::{
+        close_account, collect_to_treasury, is_event_organizer, option_accounts_len,
+        record_resolution, validate_event_dates, withdraw_sol, APPELLATION_DEADLINE,
+        COMPLETION_DEADLINE, MIN_OPTION_COUNT, RESOLUTION_DEADLINE, UUID_VERSION,
+    },
     error::ProgramError,
+    events::{
+        DisputeExpired, DisputeResolved, EventAuthorityProposed, EventAuthorityTransferred,
+        EventCanceled, EventCompleted, EventCreated, EventDatesUpdated, EventDeleted, EventFrozen,
+        EventMetaUpdated, EventModerated, EventPublished,
+    },
     id,
     state::{
+        attestation::{ResolutionAttestation, ResolutionStatus},
         contract_state::State,
         event::{Event, EventMeta},
+        option::OptionsTable,
+        organizer::EventOrganizer,
+        role::{Role, RoleMember},
+        treasury::Treasury,
         user::User,
     },
 };
@@ -21,14 +35,14 @@ pub struct CreateEvent<'info> {
     pub authority: Signer<'info>,
 
     #[account(
-        mut,
         seeds = [b"user".as_ref(), authority.key().as_ref()],
-        bump,
+        bump = user.load()?.bump,
     )]
-    pub user: Account<'info, User>,
+    pub user: AccountLoader<'info, User>,
 
     #[account(
         seeds = [b"state".as_ref()],
+        constraint = !state.is_paused(State::PAUSE_EVENT_CREATION) @ ProgramError::EventCreationPaused,
         bump,
     )]
     pub state: Account<'info, State>,
@@ -41,7 +55,7 @@ pub struct CreateEvent<'info> {
         bump,
         space = Event::LEN
     )]
-    pub event: Account<'info, Event>,
+    pub event: AccountLoader<'info, Event>,
 
     #[account(
         init,
@@ -69,11 +83,17 @@ pub struct UpdateEvent<'info> {
     #[account(
         mut,
         seeds = [b"event".as_ref(), &event_id.to_le_bytes()],
-        constraint = event.authority == authority.key() @ ProgramError::AuthorityMismatch,
-        constraint = event.start_date > Clock::get()?.unix_timestamp @ ProgramError::EventAlreadyStarted,
+        constraint = is_event_organizer(&*event.load()?, &organizer, &authority.key(), EventOrganizer::EDIT_META) @ ProgramError::AuthorityMismatch,
+        constraint = event.load()?.published == 0 || event.load()?.start_date > Clock::get()?.unix_timestamp @ ProgramError::EventAlreadyStarted,
+        bump = event.load()?.bump,
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        seeds = [b"organizer".as_ref(), &event_id.to_le_bytes(), authority.key().as_ref()],
         bump,
     )]
-    pub event: Account<'info, Event>,
+    pub organizer: Option<Account<'info, EventOrganizer>>,
 
     #[account(
         mut,
@@ -87,34 +107,118 @@ pub struct UpdateEvent<'info> {
 #[instruction(
     event_id: u128,
 )]
-pub struct CancelEvent<'info> {
+pub struct PublishEvent<'info> {
     #[account(mut)]
-    pub sender: Signer<'info>,
+    pub authority: Signer<'info>,
 
-    /// CHECK: this is admin account
-    #[account(mut)]
-    pub contract_admin: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"user".as_ref(), authority.key().as_ref()],
+        bump = user.load()?.bump,
+    )]
+    pub user: AccountLoader<'info, User>,
 
     #[account(
         seeds = [b"state".as_ref()],
-        constraint = state.authority == contract_admin.key() @ ProgramError::AuthorityMismatch,
+        constraint = !state.is_paused(State::PAUSE_EVENT_CREATION) @ ProgramError::EventCreationPaused,
         bump,
     )]
     pub state: Account<'info, State>,
 
     #[account(
         mut,
-        seeds = [b"user".as_ref(), event.authority.as_ref()],
+        seeds = [b"event".as_ref(), &event_id.to_le_bytes()],
+        constraint = event.load()?.authority == authority.key() @ ProgramError::AuthorityMismatch,
+        constraint = event.load()?.published == 0 @ ProgramError::EventAlreadyPublished,
+        constraint = event.load()?.canceled == 0 @ ProgramError::CanceledEvent,
+        bump = event.load()?.bump,
+    )]
+    pub event: AccountLoader<'info, Event>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    event_id: u128,
+)]
+pub struct DeleteEvent<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"event".as_ref(), &event_id.to_le_bytes()],
+        constraint = event.load()?.authority == authority.key() @ ProgramError::AuthorityMismatch,
+        constraint = event.load()?.published == 0 @ ProgramError::EventAlreadyPublished,
+        bump = event.load()?.bump,
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"event_meta".as_ref(), &event_id.to_le_bytes()],
+        bump,
+    )]
+    pub event_meta: Account<'info, EventMeta>,
+
+    /// Option pools, required when the event has an options table
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"options_table".as_ref(), &event_id.to_le_bytes()],
+        bump = options_table.load()?.bump,
+    )]
+    pub options_table: Option<AccountLoader<'info, OptionsTable>>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    event_id: u128,
+)]
+pub struct CancelEvent<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury".as_ref()],
+        bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::Moderator as u8], sender.key().as_ref()],
         bump,
     )]
-    pub user: Account<'info, User>,
+    pub moderator: Option<Account<'info, RoleMember>>,
+
+    #[account(
+        mut,
+        seeds = [b"user".as_ref(), event.load()?.authority.as_ref()],
+        bump = user.load()?.bump,
+    )]
+    pub user: AccountLoader<'info, User>,
 
     #[account(
         mut,
         seeds = [b"event".as_ref(), &event_id.to_le_bytes()],
+        bump = event.load()?.bump,
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        init_if_needed,
+        payer = sender,
+        owner = id(),
+        seeds = [b"attestation".as_ref(), &event_id.to_le_bytes()],
+        constraint = attestation.status == ResolutionStatus::Pending @ ProgramError::EventFinalized,
         bump,
+        space = ResolutionAttestation::LEN
     )]
-    pub event: Account<'info, Event>,
+    pub attestation: Account<'info, ResolutionAttestation>,
+
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
@@ -134,50 +238,209 @@ pub struct CompleteEvent<'info> {
     #[account(
         mut,
         seeds = [b"event".as_ref(), &event_id.to_le_bytes()],
-        constraint = event.authority == authority.key() @ ProgramError::AuthorityMismatch,
-        constraint = event.end_date < Clock::get()?.unix_timestamp @ ProgramError::EventIsNotOver,
+        constraint = is_event_organizer(&*event.load()?, &organizer, &authority.key(), EventOrganizer::RESOLVE) @ ProgramError::AuthorityMismatch,
+        constraint = event.load()?.published != 0 @ ProgramError::EventNotPublished,
+        constraint = event.load()?.frozen == 0 @ ProgramError::EventFrozen,
+        constraint = event.load()?.end_date < Clock::get()?.unix_timestamp @ ProgramError::EventIsNotOver,
+        bump = event.load()?.bump,
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        seeds = [b"organizer".as_ref(), &event_id.to_le_bytes(), authority.key().as_ref()],
+        bump,
+    )]
+    pub organizer: Option<Account<'info, EventOrganizer>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        owner = id(),
+        seeds = [b"attestation".as_ref(), &event_id.to_le_bytes()],
+        constraint = attestation.status == ResolutionStatus::Pending @ ProgramError::EventFinalized,
         bump,
+        space = ResolutionAttestation::LEN
     )]
-    pub event: Account<'info, Event>,
+    pub attestation: Account<'info, ResolutionAttestation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    event_id: u128,
+)]
+pub struct ProposeEventAuthority<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"event".as_ref(), &event_id.to_le_bytes()],
+        constraint = event.load()?.authority == authority.key() @ ProgramError::AuthorityMismatch,
+        constraint = event.load()?.canceled == 0 @ ProgramError::CanceledEvent,
+        constraint = event.load()?.disputed == 0 @ ProgramError::EventDisputed,
+        bump = event.load()?.bump,
+    )]
+    pub event: AccountLoader<'info, Event>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    event_id: u128,
+)]
+pub struct AcceptEventAuthority<'info> {
+    pub sender: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"event".as_ref(), &event_id.to_le_bytes()],
+        constraint = event.load()?.pending_authority() == Some(sender.key()) @ ProgramError::AuthorityMismatch,
+        constraint = event.load()?.canceled == 0 @ ProgramError::CanceledEvent,
+        constraint = event.load()?.disputed == 0 @ ProgramError::EventDisputed,
+        bump = event.load()?.bump,
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        mut,
+        seeds = [b"user".as_ref(), event.load()?.authority.as_ref()],
+        bump = previous_user.load()?.bump,
+    )]
+    pub previous_user: AccountLoader<'info, User>,
+
+    #[account(
+        mut,
+        seeds = [b"user".as_ref(), sender.key().as_ref()],
+        bump = user.load()?.bump,
+    )]
+    pub user: AccountLoader<'info, User>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    event_id: u128,
+)]
+pub struct FreezeEvent<'info> {
+    pub sender: Signer<'info>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::Guardian as u8], sender.key().as_ref()],
+        bump,
+    )]
+    pub role: Account<'info, RoleMember>,
+
+    #[account(
+        mut,
+        seeds = [b"event".as_ref(), &event_id.to_le_bytes()],
+        bump = event.load()?.bump,
+    )]
+    pub event: AccountLoader<'info, Event>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    event_id: u128,
+)]
+pub struct ModerateEvent<'info> {
+    pub sender: Signer<'info>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::Moderator as u8], sender.key().as_ref()],
+        bump,
+    )]
+    pub role: Account<'info, RoleMember>,
+
+    #[account(
+        mut,
+        seeds = [b"event_meta".as_ref(), &event_id.to_le_bytes()],
+        bump,
+    )]
+    pub event_meta: Account<'info, EventMeta>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    event_id: u128,
+)]
+pub struct ResolveDispute<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::Resolver as u8], sender.key().as_ref()],
+        bump,
+    )]
+    pub role: Account<'info, RoleMember>,
+
+    #[account(
+        mut,
+        seeds = [b"event".as_ref(), &event_id.to_le_bytes()],
+        constraint = event.load()?.disputed != 0 @ ProgramError::EventNotDisputed,
+        bump = event.load()?.bump,
+    )]
+    pub event: AccountLoader<'info, Event>,
+    #[account(
+        init_if_needed,
+        payer = sender,
+        owner = id(),
+        seeds = [b"attestation".as_ref(), &event_id.to_le_bytes()],
+        constraint = attestation.status == ResolutionStatus::Pending @ ProgramError::EventFinalized,
+        bump,
+        space = ResolutionAttestation::LEN
+    )]
+    pub attestation: Account<'info, ResolutionAttestation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Disputes left unresolved past `RESOLUTION_DEADLINE` keep the original result
+#[derive(Accounts)]
+#[instruction(
+    event_id: u128,
+)]
+pub struct ExpireDispute<'info> {
+    pub sender: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"event".as_ref(), &event_id.to_le_bytes()],
+        constraint = event.load()?.disputed != 0 @ ProgramError::EventNotDisputed,
+        bump = event.load()?.bump,
+    )]
+    pub event: AccountLoader<'info, Event>,
 }
 
 // -------------------------- Arguments ---------------------------- //
 
 #[derive(AnchorDeserialize, AnchorSerialize)]
 pub struct CreateEventArgs {
-    name: [u8; 32],
-    is_private: bool,
-    description: [u8; 256],
-    start_date: i64,
-    end_date: i64,
-    participation_deadline: Option<i64>,
+    pub name: [u8; 32],
+    pub is_private: bool,
+    pub description: [u8; 256],
+    pub start_date: i64,
+    pub end_date: i64,
+    pub participation_deadline: Option<i64>,
 }
 
 // ------------------------ Implementation ------------------------- //
 
 impl CreateEvent<'_> {
-    pub fn create_event(&mut self, event_id: u128, args: CreateEventArgs) -> Result<()> {
+    pub fn create_event(&mut self, event_id: u128, args: CreateEventArgs, bump: u8) -> Result<()> {
         let id = uuid::Uuid::from_u128(event_id);
         self.validate(id, &args)?;
 
-        let user = &mut self.user;
-
-        require!(
-            user.stake >= self.state.event_price,
-            ProgramError::StakeTooLow
-        );
-
-        let event = &mut self.event;
+        let event = &mut self.event.load_init()?;
         let event_meta = &mut self.event_meta;
-        let stake = self.state.event_price;
 
-        event.id = event_id;
+        event.id = event_id.to_le_bytes();
         event.authority = self.authority.key();
-        event.stake = stake;
         event.start_date = args.start_date;
         event.end_date = args.end_date;
-        event.participation_deadline = args.participation_deadline;
+        event.set_participation_deadline(args.participation_deadline);
+        event.platform_fee = self.state.platform_fee;
+        event.org_reward = self.state.org_reward;
         event.version = Event::VERSION;
+        event.bump = bump;
 
         event_meta.is_private = args.is_private;
         event_meta.event_id = event_id;
@@ -185,16 +448,18 @@ impl CreateEvent<'_> {
         event_meta.name = args.name;
         event_meta.version = EventMeta::VERSION;
 
-        user.stake -= stake;
-        user.locked_stake += stake;
-
-        withdraw_sol(
-            &self.user.to_account_info(),
-            &self.event.to_account_info(),
-            stake,
-        )?;
+        emit!(EventCreated {
+            event_id,
+            authority: event.authority,
+            is_private: event_meta.is_private,
+            name: event_meta.name,
+            description: event_meta.description,
+            start_date: event.start_date,
+            end_date: event.end_date,
+            participation_deadline: event.participation_deadline(),
+        });
 
-        msg!("Event {} initialized", id);
+        msg!("Event {} drafted", id);
 
         Ok(())
     }
@@ -205,28 +470,18 @@ impl CreateEvent<'_> {
             ProgramError::InvalidUUID
         );
 
-        require!(
-            args.start_date < args.end_date,
-            ProgramError::InvalidEndDate
-        );
-
-        if let Some(deadline) = args.participation_deadline {
-            require!(
-                (args.start_date..=args.end_date).contains(&deadline),
-                ProgramError::InvalidEndDate
-            );
-        }
-
-        Ok(())
+        validate_event_dates(args.start_date, args.end_date, args.participation_deadline)
     }
 }
 
 impl UpdateEvent<'_> {
-    pub fn update_event_name(&mut self, _event_id: u128, name: [u8; 32]) -> Result<()> {
+    pub fn update_event_name(&mut self, event_id: u128, name: [u8; 32]) -> Result<()> {
         let event_meta = &mut self.event_meta;
 
         event_meta.name = name;
 
+        self.emit_meta_updated(event_id);
+
         msg!("Event name updated");
 
         Ok(())
@@ -234,27 +489,33 @@ impl UpdateEvent<'_> {
 
     pub fn update_event_description(
         &mut self,
-        _event_id: u128,
+        event_id: u128,
         description: [u8; 256],
     ) -> Result<()> {
         let event_meta = &mut self.event_meta;
 
         event_meta.description = description;
 
+        self.emit_meta_updated(event_id);
+
         msg!("Event description updated");
 
         Ok(())
     }
 
-    pub fn update_event_end_date(&mut self, _event_id: u128, end_date: i64) -> Result<()> {
-        let event = &mut self.event;
+    pub fn update_event_end_date(&mut self, event_id: u128, end_date: i64) -> Result<()> {
+        {
+            let event = &mut self.event.load_mut()?;
+
+            require!(event.start_date < end_date, ProgramError::InvalidEndDate);
+            if let Some(deadline) = event.participation_deadline() {
+                require!(deadline <= end_date, ProgramError::InvalidEndDate);
+            }
 
-        require!(event.start_date < end_date, ProgramError::InvalidEndDate);
-        if let Some(deadline) = event.participation_deadline {
-            require!(deadline <= end_date, ProgramError::InvalidEndDate);
+            event.end_date = end_date;
         }
 
-        event.end_date = end_date;
+        self.emit_dates_updated(event_id)?;
 
         msg!("Event end date updated");
 
@@ -263,21 +524,173 @@ impl UpdateEvent<'_> {
 
     pub fn update_event_participation_deadline(
         &mut self,
-        _event_id: u128,
+        event_id: u128,
         participation_deadline: Option<i64>,
     ) -> Result<()> {
-        let event = &mut self.event;
+        {
+            let event = &mut self.event.load_mut()?;
+
+            if let Some(deadline) = participation_deadline {
+                require!(
+                    (event.start_date..=event.end_date).contains(&deadline),
+                    ProgramError::InvalidEndDate
+                );
+            }
+
+            event.set_participation_deadline(participation_deadline);
+        }
+
+        self.emit_dates_updated(event_id)?;
+
+        msg!("Event end participation deadline updated");
+
+        Ok(())
+    }
+
+    fn emit_meta_updated(&self, event_id: u128) {
+        emit!(EventMetaUpdated {
+            event_id,
+            name: self.event_meta.name,
+            description: self.event_meta.description,
+        });
+    }
+
+    fn emit_dates_updated(&self, event_id: u128) -> Result<()> {
+        let event = self.event.load()?;
+
+        emit!(EventDatesUpdated {
+            event_id,
+            start_date: event.start_date,
+            end_date: event.end_date,
+            participation_deadline: event.participation_deadline(),
+        });
+
+        Ok(())
+    }
+}
+
+impl PublishEvent<'_> {
+    pub fn publish_event(&mut self, event_id: u128) -> Result<()> {
+        let user = &mut self.user.load_mut()?;
+        let event = &mut self.event.load_mut()?;
+
+        require!(
+            event.option_count >= MIN_OPTION_COUNT,
+            ProgramError::NotEnoughOptions
+        );
+        validate_event_dates(
+            event.start_date,
+            event.end_date,
+            event.participation_deadline(),
+        )?;
+
+        // A draft may have outlived its dates, it can't open for bets then
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(event.end_date > now, ProgramError::InvalidEndDate);
+        if let Some(deadline) = event.participation_deadline() {
+            require!(deadline > now, ProgramError::InvalidEndDate);
+        }
+        require!(
+            user.stake >= self.state.event_price,
+            ProgramError::StakeTooLow
+        );
+
+        let stake = self.state.event_price;
+
+        event.stake = stake;
+        event.published = 1;
+
+        // Nobody could bet on the draft yet, so the terms are refreshed
+        event.platform_fee = self.state.platform_fee;
+        event.org_reward = self.state.org_reward;
+
+        user.stake -= stake;
+        user.locked_stake += stake;
+
+        withdraw_sol(
+            &self.user.to_account_info(),
+            &self.event.to_account_info(),
+            stake,
+        )?;
+
+        emit!(EventPublished {
+            event_id,
+            authority: event.authority,
+            stake,
+            platform_fee: event.platform_fee,
+            org_reward: event.org_reward,
+        });
+
+        msg!("Event {} published", uuid::Uuid::from_u128(event_id));
+
+        Ok(())
+    }
+}
+
+impl<'info> DeleteEvent<'info> {
+    pub fn delete_event(
+        &mut self,
+        event_id: u128,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        let event = self.event.load()?;
+
+        require!(event.callback == 0, ProgramError::CallbackRegistered);
+
+        // Events with an options table only have option metas to close
+        let prefixes = [b"option".as_ref(), b"option_meta"];
+        let prefixes = &prefixes[prefixes.len() - option_accounts_len(&event)..];
+
+        require!(
+            self.options_table.is_some() == (event.options_table != 0),
+            ProgramError::InvalidOptionPool
+        );
+        let options_len = event.option_count as usize * prefixes.len();
+
+        require!(
+            remaining_accounts.len() >= options_len,
+            ProgramError::InvalidIndex
+        );
+
+        let (option_accounts, organizer_accounts) = remaining_accounts.split_at(options_len);
+
+        for (index, accounts) in option_accounts.chunks_exact(prefixes.len()).enumerate() {
+            for (account, prefix) in accounts.iter().zip(prefixes) {
+                let (address, _) = Pubkey::find_program_address(
+                    &[prefix, &event_id.to_le_bytes(), &[index as u8]],
+                    &id(),
+                );
+                require_keys_eq!(account.key(), address, ProgramError::InvalidIndex);
+
+                close_account(account, &self.authority.to_account_info())?;
+            }
+        }
+
+        // Co-organizer accounts follow the options and are closed with the event
+        let mut organizer_count = event.organizer_count;
+
+        for account in organizer_accounts {
+            let organizer = Account::<EventOrganizer>::try_from(account)?;
 
-        if let Some(deadline) = participation_deadline {
+            // Program accounts are only created at their PDAs, so a matching event pins the address
             require!(
-                (event.start_date..=event.end_date).contains(&deadline),
-                ProgramError::InvalidEndDate
+                organizer.event_id == event_id,
+                ProgramError::InvalidOrganizerAccounts
             );
+
+            close_account(account, &self.authority.to_account_info())?;
+            organizer_count = organizer_count.saturating_sub(1);
         }
 
-        event.participation_deadline = participation_deadline;
+        require!(organizer_count == 0, ProgramError::EventHasOrganizers);
 
-        msg!("Event end participation deadline updated");
+        emit!(EventDeleted {
+            event_id,
+            authority: self.authority.key(),
+        });
+
+        msg!("Event {} deleted", uuid::Uuid::from_u128(event_id));
 
         Ok(())
     }
@@ -286,37 +699,58 @@ impl UpdateEvent<'_> {
 impl CancelEvent<'_> {
     pub fn cancel_event(&mut self, event_id: u128) -> Result<()> {
         let event_acc = self.event.to_account_info();
-        let event = &mut self.event;
+        let event = &mut self.event.load_mut()?;
+        let user = &mut self.user.load_mut()?;
 
         let now = Clock::get()?.unix_timestamp;
 
         require!(
-            event.authority == self.sender.key() || now > event.end_date + COMPLETION_DEADLINE,
+            event.authority == self.sender.key()
+                || self.moderator.is_some()
+                || now > event.end_date + COMPLETION_DEADLINE,
             ProgramError::AuthorityMismatch
         );
 
+        let stake_refunded = event.start_date > now;
+        // A stake already released or slashed is zero and moves nowhere
+        let stake = event.stake;
+
         // TODO: what happens with his trust coins?
         // TODO: Do i need to add appell on appel?
-        if event.start_date <= now {
-            msg!("Event is already started, returning stake to contract admin");
+        if stake == 0 {
+            msg!("Event stake is already released");
+        } else if !stake_refunded {
+            msg!("Event is already started, moving stake to the treasury");
 
-            self.user.locked_stake -= event.stake;
+            user.locked_stake -= stake;
 
-            withdraw_sol(
-                &event_acc,
-                &self.contract_admin.to_account_info(),
-                event.stake,
-            )?;
+            collect_to_treasury(&event_acc, &mut self.treasury, stake)?;
         } else {
             msg!("Event is not started yet, returning stake to user");
 
-            self.user.locked_stake -= event.stake;
-            self.user.stake += event.stake;
+            user.locked_stake -= stake;
+            user.stake += stake;
 
-            withdraw_sol(&event_acc, &self.user.to_account_info(), event.stake)?;
+            withdraw_sol(&event_acc, &self.user.to_account_info(), stake)?;
         }
 
-        event.canceled = true;
+        event.stake = 0;
+        event.canceled = 1;
+
+        record_resolution(
+            &mut self.attestation,
+            event_id,
+            ResolutionStatus::Canceled,
+            0,
+            self.sender.key(),
+        )?;
+
+        emit!(EventCanceled {
+            event_id,
+            canceled_by: self.sender.key(),
+            stake,
+            stake_refunded,
+        });
 
         msg!("Event cancelled: {}", uuid::Uuid::from_u128(event_id));
 
@@ -326,9 +760,27 @@ impl CancelEvent<'_> {
 
 impl CompleteEvent<'_> {
     pub fn complete_event(&mut self, event_id: u128, result: u8) -> Result<()> {
-        let event = &mut self.event;
+        let event = &mut self.event.load_mut()?;
+
+        require!(result < event.option_count, ProgramError::InvalidIndex);
+        // A disputed result is only changed by resolvers
+        require!(event.disputed == 0, ProgramError::EventDisputed);
 
-        event.result = Some(result);
+        event.set_result(result);
+
+        record_resolution(
+            &mut self.attestation,
+            event_id,
+            ResolutionStatus::Pending,
+            result,
+            self.authority.key(),
+        )?;
+
+        emit!(EventCompleted {
+            event_id,
+            resolver: self.authority.key(),
+            result,
+        });
 
         msg!(
             "Event completed, result - {}: {}",
@@ -339,3 +791,171 @@ impl CompleteEvent<'_> {
         Ok(())
     }
 }
+
+impl ProposeEventAuthority<'_> {
+    pub fn propose_event_authority(
+        &mut self,
+        event_id: u128,
+        new_authority: Option<Pubkey>,
+    ) -> Result<()> {
+        let event = &mut self.event.load_mut()?;
+
+        require!(
+            new_authority != Some(event.authority),
+            ProgramError::AuthorityMismatch
+        );
+
+        event.set_pending_authority(new_authority);
+
+        emit!(EventAuthorityProposed {
+            event_id,
+            pending_authority: new_authority,
+        });
+
+        match new_authority {
+            Some(new_authority) => msg!(
+                "Event {} authority transfer proposed to {}",
+                uuid::Uuid::from_u128(event_id),
+                new_authority
+            ),
+            None => msg!(
+                "Event {} authority transfer cancelled",
+                uuid::Uuid::from_u128(event_id)
+            ),
+        }
+
+        Ok(())
+    }
+}
+
+impl AcceptEventAuthority<'_> {
+    pub fn accept_event_authority(&mut self, event_id: u128) -> Result<()> {
+        let event = &mut self.event.load_mut()?;
+
+        // Organizer stake is held by the event, only the accounting moves. A released stake is zero,
+        // so nothing moves then
+        self.previous_user.load_mut()?.locked_stake -= event.stake;
+        self.user.load_mut()?.locked_stake += event.stake;
+
+        emit!(EventAuthorityTransferred {
+            event_id,
+            previous_authority: event.authority,
+            authority: self.sender.key(),
+            stake: event.stake,
+        });
+
+        event.authority = self.sender.key();
+        event.set_pending_authority(None);
+
+        msg!(
+            "Event {} authority transferred to {}",
+            uuid::Uuid::from_u128(event_id),
+            event.authority
+        );
+
+        Ok(())
+    }
+}
+
+impl FreezeEvent<'_> {
+    pub fn set_event_frozen(&mut self, event_id: u128, frozen: bool) -> Result<()> {
+        let event = &mut self.event.load_mut()?;
+
+        event.frozen = u8::from(frozen);
+
+        emit!(EventFrozen { event_id, frozen });
+
+        msg!(
+            "Event {} frozen flag set to {}",
+            uuid::Uuid::from_u128(event_id),
+            frozen
+        );
+
+        Ok(())
+    }
+}
+
+impl ModerateEvent<'_> {
+    pub fn moderate_event(&mut self, event_id: u128, hidden: bool) -> Result<()> {
+        let event_meta = &mut self.event_meta;
+
+        event_meta.hidden = hidden;
+
+        emit!(EventModerated {
+            event_id,
+            moderator: self.sender.key(),
+            hidden,
+        });
+
+        msg!(
+            "Event {} hidden flag set to {} by moderator {}",
+            uuid::Uuid::from_u128(event_id),
+            hidden,
+            self.sender.key()
+        );
+
+        Ok(())
+    }
+}
+
+impl ResolveDispute<'_> {
+    pub fn resolve_dispute(&mut self, event_id: u128, result: u8) -> Result<()> {
+        {
+            let event = &mut self.event.load_mut()?;
+
+            require!(result < event.option_count, ProgramError::InvalidIndex);
+
+            event.set_result(result);
+            event.disputed = 0;
+        }
+
+        record_resolution(
+            &mut self.attestation,
+            event_id,
+            ResolutionStatus::Pending,
+            result,
+            self.sender.key(),
+        )?;
+
+        emit!(DisputeResolved {
+            event_id,
+            resolver: self.sender.key(),
+            result,
+        });
+
+        msg!(
+            "Event dispute resolved, result - {}: {}",
+            result,
+            uuid::Uuid::from_u128(event_id)
+        );
+
+        Ok(())
+    }
+}
+
+impl ExpireDispute<'_> {
+    pub fn expire_dispute(&mut self, event_id: u128) -> Result<()> {
+        let event = &mut self.event.load_mut()?;
+
+        require!(
+            Clock::get()?.unix_timestamp
+                > event.end_date + COMPLETION_DEADLINE + APPELLATION_DEADLINE + RESOLUTION_DEADLINE,
+            ProgramError::DisputeWindowOpen
+        );
+
+        event.disputed = 0;
+
+        emit!(DisputeExpired {
+            event_id,
+            result: event.result,
+        });
+
+        msg!(
+            "Event dispute expired, result - {}: {}",
+            event.result,
+            uuid::Uuid::from_u128(event_id)
+        );
+
+        Ok(())
+    }
+}
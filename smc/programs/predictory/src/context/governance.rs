@@ -0,0 +1,235 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    context::{
+        apply_param, validate_param, GOVERNANCE_EXECUTION_DELAY, GOVERNANCE_QUORUM,
+        GOVERNANCE_VOTING_PERIOD, INITIAL_LVL, PROPOSAL_TRUST_THRESHOLD,
+    },
+    error::ProgramError,
+    events::{ProposalCreated, ProposalExecuted, ProposalVoted},
+    id,
+    state::{
+        contract_state::State,
+        governance::{Proposal, ProposalVote},
+        param::ParamKind,
+        user::User,
+    },
+};
+
+// --------------------------- Context ----------------------------- //
+
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state".as_ref()],
+        constraint = state.version == State::VERSION @ ProgramError::StateNotMigrated,
+        bump,
+    )]
+    pub state: Account<'info, State>,
+
+    #[account(
+        seeds = [b"user".as_ref(), sender.key().as_ref()],
+        constraint = voting_weight(&*user.load()?) >= PROPOSAL_TRUST_THRESHOLD @ ProgramError::TrustTooLow,
+        bump = user.load()?.bump,
+    )]
+    pub user: AccountLoader<'info, User>,
+
+    #[account(
+        init,
+        payer = sender,
+        owner = id(),
+        seeds = [b"proposal".as_ref(), &state.proposal_count.to_le_bytes()],
+        bump,
+        space = Proposal::LEN
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    proposal_id: u64,
+)]
+pub struct VoteProposal<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    #[account(
+        seeds = [b"user".as_ref(), sender.key().as_ref()],
+        constraint = voting_weight(&*user.load()?) > 0 @ ProgramError::TrustTooLow,
+        bump = user.load()?.bump,
+    )]
+    pub user: AccountLoader<'info, User>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal".as_ref(), &proposal_id.to_le_bytes()],
+        bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        init,
+        payer = sender,
+        owner = id(),
+        seeds = [b"proposal_vote".as_ref(), &proposal_id.to_le_bytes(), sender.key().as_ref()],
+        bump,
+        space = ProposalVote::LEN
+    )]
+    pub proposal_vote: Account<'info, ProposalVote>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    proposal_id: u64,
+)]
+pub struct ExecuteProposal<'info> {
+    pub sender: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state".as_ref()],
+        constraint = state.version == State::VERSION @ ProgramError::StateNotMigrated,
+        bump,
+    )]
+    pub state: Account<'info, State>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal".as_ref(), &proposal_id.to_le_bytes()],
+        constraint = !proposal.executed @ ProgramError::ProposalAlreadyExecuted,
+        bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+// ------------------------ Implementation ------------------------- //
+
+/// Trust earned from settled positions. The level every new user starts with is left out,
+/// so fresh accounts carry no weight. Trust only grows through settlements, so the weight
+/// of a user whose trust was not raised since a proposal was created is their weight then
+fn voting_weight(user: &User) -> u64 {
+    user.trust_lvl.saturating_sub(INITIAL_LVL)
+}
+
+impl CreateProposal<'_> {
+    pub fn create_proposal(&mut self, kind: ParamKind, value: u64) -> Result<()> {
+        validate_param(kind, value)?;
+
+        let state = &mut self.state;
+        let proposal = &mut self.proposal;
+
+        proposal.id = state.proposal_count;
+        proposal.proposer = self.sender.key();
+        proposal.kind = kind;
+        proposal.value = value;
+        proposal.voting_end_date = Clock::get()?.unix_timestamp + GOVERNANCE_VOTING_PERIOD;
+        proposal.version = Proposal::VERSION;
+
+        state.proposal_count += 1;
+
+        emit!(ProposalCreated {
+            proposal_id: proposal.id,
+            proposer: proposal.proposer,
+            kind,
+            value,
+            voting_end_date: proposal.voting_end_date,
+        });
+
+        msg!(
+            "Proposal {} created, voting ends at {}",
+            proposal.id,
+            proposal.voting_end_date
+        );
+
+        Ok(())
+    }
+}
+
+impl VoteProposal<'_> {
+    pub fn vote_proposal(&mut self, proposal_id: u64, support: bool) -> Result<()> {
+        let proposal = &mut self.proposal;
+        let proposal_vote = &mut self.proposal_vote;
+
+        require!(
+            Clock::get()?.unix_timestamp <= proposal.voting_end_date,
+            ProgramError::ProposalVotingClosed
+        );
+
+        let user = self.user.load()?;
+        let created_at = proposal.voting_end_date - GOVERNANCE_VOTING_PERIOD;
+
+        require!(
+            user.trust_raised_at <= created_at,
+            ProgramError::TrustRaisedAfterProposal
+        );
+
+        let weight = voting_weight(&user);
+
+        if support {
+            proposal.votes_for += weight;
+        } else {
+            proposal.votes_against += weight;
+        }
+
+        proposal_vote.proposal_id = proposal_id;
+        proposal_vote.voter = self.sender.key();
+        proposal_vote.weight = weight;
+        proposal_vote.support = support;
+        proposal_vote.version = ProposalVote::VERSION;
+
+        emit!(ProposalVoted {
+            proposal_id,
+            voter: proposal_vote.voter,
+            weight,
+            support,
+            votes_for: proposal.votes_for,
+            votes_against: proposal.votes_against,
+        });
+
+        msg!("Vote on proposal {proposal_id} with weight {weight}");
+
+        Ok(())
+    }
+}
+
+impl ExecuteProposal<'_> {
+    pub fn execute_proposal(&mut self, proposal_id: u64) -> Result<()> {
+        let proposal = &mut self.proposal;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            now > proposal.voting_end_date,
+            ProgramError::ProposalVotingActive
+        );
+        require!(
+            now > proposal.voting_end_date + GOVERNANCE_EXECUTION_DELAY,
+            ProgramError::ProposalTimelocked
+        );
+        require!(
+            proposal.total_votes() >= GOVERNANCE_QUORUM
+                && proposal.votes_for > proposal.votes_against,
+            ProgramError::ProposalRejected
+        );
+
+        apply_param(&mut self.state, proposal.kind, proposal.value)?;
+        proposal.executed = true;
+
+        emit!(ProposalExecuted {
+            proposal_id,
+            kind: proposal.kind,
+            value: proposal.value,
+        });
+
+        msg!("Proposal {proposal_id} executed: {}", proposal.value);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,426 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    context::{legacy_version, load_versioned, migrate_account, store_migrated},
+    events::AccountMigrated,
+    id,
+    state::{
+        appeal::Appellation,
+        contract_state::State,
+        event::{Event, EventMeta},
+        legacy::EventOptionV1,
+        option::{EventOption, EventOptionMeta},
+        participation::Participation,
+        portfolio::{Portfolio, PortfolioEntry, PositionStatus},
+        user::User,
+        DISCRIMINATOR_LENGTH,
+    },
+};
+
+// --------------------------- Context ----------------------------- //
+
+#[derive(Accounts)]
+pub struct MigrateState<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: decoded according to its layout version
+    #[account(
+        mut,
+        seeds = [b"state".as_ref()],
+        bump,
+    )]
+    pub state: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    owner: Pubkey,
+)]
+pub struct MigrateUser<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: decoded according to its layout version
+    #[account(
+        mut,
+        seeds = [b"user".as_ref(), owner.as_ref()],
+        bump,
+    )]
+    pub user: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    event_id: u128,
+)]
+pub struct MigrateEvent<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"state".as_ref()],
+        bump,
+    )]
+    pub state: Account<'info, State>,
+
+    /// CHECK: decoded according to its layout version
+    #[account(
+        mut,
+        seeds = [b"event".as_ref(), &event_id.to_le_bytes()],
+        bump,
+    )]
+    pub event: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    event_id: u128,
+)]
+pub struct MigrateEventMeta<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: decoded according to its layout version
+    #[account(
+        mut,
+        seeds = [b"event_meta".as_ref(), &event_id.to_le_bytes()],
+        bump,
+    )]
+    pub event_meta: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    event_id: u128,
+    index: u8,
+)]
+pub struct MigrateEventOption<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Receives the description of the legacy option
+    #[account(
+        init,
+        payer = payer,
+        owner = id(),
+        seeds = [b"option_meta".as_ref(), &event_id.to_le_bytes(), &[index]],
+        bump,
+        space = EventOptionMeta::LEN
+    )]
+    pub option_meta: Account<'info, EventOptionMeta>,
+
+    /// CHECK: decoded according to its layout version
+    #[account(
+        mut,
+        seeds = [b"option".as_ref(), &event_id.to_le_bytes(), &[index]],
+        bump,
+    )]
+    pub option: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    event_id: u128,
+    participant: Pubkey,
+)]
+pub struct MigrateParticipation<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: decoded according to its layout version
+    #[account(
+        mut,
+        seeds = [b"participation".as_ref(), &event_id.to_le_bytes(), participant.as_ref()],
+        bump,
+    )]
+    pub participation: UncheckedAccount<'info>,
+
+    /// First portfolio page of the participant, version 1 participations are recorded on it
+    #[account(
+        init_if_needed,
+        payer = payer,
+        owner = id(),
+        seeds = [b"portfolio".as_ref(), participant.as_ref(), &0u16.to_le_bytes()],
+        bump,
+        space = Portfolio::LEN
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    event_id: u128,
+)]
+pub struct MigrateAppellation<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: decoded according to its layout version
+    #[account(
+        mut,
+        seeds = [b"appeal".as_ref(), &event_id.to_le_bytes()],
+        bump,
+    )]
+    pub appellation: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// ------------------------ Implementation ------------------------- //
+
+impl MigrateState<'_> {
+    pub fn migrate_state(&mut self) -> Result<()> {
+        let version = migrate_account::<State>(
+            &self.state.to_account_info(),
+            &self.payer.to_account_info(),
+            &self.system_program.to_account_info(),
+        )?;
+
+        msg!("Contract state migrated from version {}", version);
+
+        Ok(())
+    }
+}
+
+impl MigrateUser<'_> {
+    pub fn migrate_user(&mut self, owner: Pubkey, bump: u8) -> Result<()> {
+        let user_acc = self.user.to_account_info();
+
+        let version = legacy_version::<User>(&user_acc)?;
+        let mut user = load_versioned::<User>(&user_acc)?;
+
+        user.bump = bump;
+
+        store_migrated(
+            &user_acc,
+            &user,
+            &self.payer.to_account_info(),
+            &self.system_program.to_account_info(),
+        )?;
+
+        emit!(AccountMigrated {
+            account: user_acc.key(),
+            from_version: version,
+            to_version: User::VERSION,
+        });
+
+        msg!("User {} migrated from version {}", owner, version);
+
+        Ok(())
+    }
+}
+
+impl MigrateEvent<'_> {
+    pub fn migrate_event(&mut self, event_id: u128, bump: u8) -> Result<()> {
+        let event_acc = self.event.to_account_info();
+
+        let version = legacy_version::<Event>(&event_acc)?;
+        let mut event = load_versioned::<Event>(&event_acc)?;
+
+        snapshot_fee_terms(&mut event, &self.state);
+        event.bump = bump;
+
+        store_migrated(
+            &event_acc,
+            &event,
+            &self.payer.to_account_info(),
+            &self.system_program.to_account_info(),
+        )?;
+
+        emit!(AccountMigrated {
+            account: event_acc.key(),
+            from_version: version,
+            to_version: Event::VERSION,
+        });
+
+        msg!(
+            "Event {} migrated from version {version}",
+            uuid::Uuid::from_u128(event_id)
+        );
+
+        Ok(())
+    }
+}
+
+/// Version 1 events were settled with the live contract terms
+fn snapshot_fee_terms(event: &mut Event, state: &State) {
+    event.platform_fee = state.platform_fee;
+    event.org_reward = state.org_reward;
+}
+
+impl MigrateEventMeta<'_> {
+    pub fn migrate_event_meta(&mut self, event_id: u128) -> Result<()> {
+        let version = migrate_account::<EventMeta>(
+            &self.event_meta.to_account_info(),
+            &self.payer.to_account_info(),
+            &self.system_program.to_account_info(),
+        )?;
+
+        msg!(
+            "Event meta {} migrated from version {version}",
+            uuid::Uuid::from_u128(event_id)
+        );
+
+        Ok(())
+    }
+}
+
+impl MigrateEventOption<'_> {
+    pub fn migrate_event_option(&mut self, event_id: u128, index: u8, bump: u8) -> Result<()> {
+        let option_acc = self.option.to_account_info();
+
+        let version = legacy_version::<EventOption>(&option_acc)?;
+        let mut option = load_versioned::<EventOption>(&option_acc)?;
+
+        // The description moves to the option meta account
+        let legacy = EventOptionV1::deserialize(
+            &mut &option_acc.try_borrow_data()?[DISCRIMINATOR_LENGTH..],
+        )?;
+        let option_meta = &mut self.option_meta;
+
+        option_meta.version = EventOptionMeta::VERSION;
+        option_meta.index = legacy.index;
+        option_meta.event_id = legacy.event_id;
+        option_meta.description = legacy.description;
+
+        option.bump = bump;
+
+        store_migrated(
+            &option_acc,
+            &option,
+            &self.payer.to_account_info(),
+            &self.system_program.to_account_info(),
+        )?;
+
+        emit!(AccountMigrated {
+            account: option_acc.key(),
+            from_version: version,
+            to_version: EventOption::VERSION,
+        });
+
+        msg!(
+            "Event option {index} of {} migrated from version {version}",
+            uuid::Uuid::from_u128(event_id)
+        );
+
+        Ok(())
+    }
+}
+
+impl MigrateParticipation<'_> {
+    pub fn migrate_participation(
+        &mut self,
+        event_id: u128,
+        participant: Pubkey,
+        bump: u8,
+    ) -> Result<()> {
+        let participation_acc = self.participation.to_account_info();
+
+        let version = legacy_version::<Participation>(&participation_acc)?;
+        let mut participation = load_versioned::<Participation>(&participation_acc)?;
+
+        participation.bump = bump;
+
+        // Version 1 positions had no portfolio, claims look them up on the first page
+        let portfolio = &mut self.portfolio;
+
+        if portfolio.version == 0 {
+            portfolio.owner = participant;
+            portfolio.version = Portfolio::VERSION;
+        }
+
+        if participation.is_claimed == 0
+            && !portfolio.is_full()
+            && portfolio.entry_mut(event_id).is_none()
+        {
+            portfolio.entries.push(PortfolioEntry {
+                event_id,
+                option: participation.option,
+                amount: participation.deposited_amount,
+                status: PositionStatus::Open,
+            });
+        }
+
+        store_migrated(
+            &participation_acc,
+            &participation,
+            &self.payer.to_account_info(),
+            &self.system_program.to_account_info(),
+        )?;
+
+        emit!(AccountMigrated {
+            account: participation_acc.key(),
+            from_version: version,
+            to_version: Participation::VERSION,
+        });
+
+        msg!(
+            "Participation of {participant} in {} migrated from version {version}",
+            uuid::Uuid::from_u128(event_id)
+        );
+
+        Ok(())
+    }
+}
+
+impl MigrateAppellation<'_> {
+    pub fn migrate_appellation(&mut self, event_id: u128) -> Result<()> {
+        let version = migrate_account::<Appellation>(
+            &self.appellation.to_account_info(),
+            &self.payer.to_account_info(),
+            &self.system_program.to_account_info(),
+        )?;
+
+        msg!(
+            "Appellation of {} migrated from version {version}",
+            uuid::Uuid::from_u128(event_id)
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::legacy::tests::event_v1;
+
+    #[test]
+    fn event_v1_fee_terms() {
+        let state = State {
+            version: State::VERSION,
+            authority: Pubkey::new_unique(),
+            multiplier: 2,
+            event_price: 1_000,
+            platform_fee: 33,
+            org_reward: 44,
+            paused: 0,
+            pending_authority: None,
+            authority_transfer_eta: 0,
+            authority_timelock: 0,
+            proposal_count: 0,
+        };
+        let mut event = event_v1(&Pubkey::new_unique()).unwrap();
+
+        assert_eq!(event.platform_fee, 0);
+        assert_eq!(event.org_reward, 0);
+
+        snapshot_fee_terms(&mut event, &state);
+
+        assert_eq!(event.platform_fee, 33);
+        assert_eq!(event.org_reward, 44);
+    }
+}
@@ -1,15 +1,37 @@
+mod attestation;
+mod callback;
 mod constants;
 mod contract_state;
 mod event;
+mod governance;
+mod migration;
 mod option;
+mod organizer;
+mod param;
 mod participation;
+mod payout;
+mod portfolio;
+mod quote;
+mod role;
+mod treasury;
 mod user;
 mod utils;
 
-pub(crate) use constants::*;
-pub(crate) use contract_state::*;
-pub(crate) use event::*;
-pub(crate) use option::*;
-pub(crate) use participation::*;
-pub(crate) use user::*;
-pub(crate) use utils::*;
+pub use attestation::*;
+pub use callback::*;
+pub use constants::*;
+pub use contract_state::*;
+pub use event::*;
+pub use governance::*;
+pub use migration::*;
+pub use option::*;
+pub use organizer::*;
+pub use param::*;
+pub use participation::*;
+pub use payout::*;
+pub use portfolio::*;
+pub use quote::*;
+pub use role::*;
+pub use treasury::*;
+pub use user::*;
+pub use utils::*;
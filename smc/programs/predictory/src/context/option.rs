@@ -1,13 +1,58 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    context::MAX_OPTION_COUNT,
+    context::{
+        create_pda_account, is_event_organizer, option_accounts_len, require_option_account,
+        MAX_OPTION_COUNT,
+    },
     error::ProgramError,
+    events::{OptionCreated, OptionRemoved, OptionUpdated, OptionsTableCreated},
     id,
-    state::{event::Event, option::EventOption},
+    state::{
+        event::Event,
+        option::{EventOption, EventOptionMeta, OptionsTable},
+        organizer::EventOrganizer,
+        ACCOUNT_RESERVE_SPACE,
+    },
 };
 // --------------------------- Context ----------------------------- //
 
+#[derive(Accounts)]
+#[instruction(
+    event_id: u128,
+)]
+pub struct CreateOptionsTable<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        owner = id(),
+        seeds = [b"options_table".as_ref(), &event_id.to_le_bytes()],
+        bump,
+        space = OptionsTable::LEN
+    )]
+    pub options_table: AccountLoader<'info, OptionsTable>,
+
+    #[account(
+        mut,
+        seeds = [b"event".as_ref(), &event_id.to_le_bytes()],
+        constraint = is_event_organizer(&*event.load()?, &organizer, &authority.key(), EventOrganizer::MANAGE_OPTIONS) @ ProgramError::AuthorityMismatch,
+        constraint = event.load()?.published == 0 || event.load()?.start_date > Clock::get()?.unix_timestamp @ ProgramError::EventAlreadyStarted,
+        bump = event.load()?.bump,
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        seeds = [b"organizer".as_ref(), &event_id.to_le_bytes(), authority.key().as_ref()],
+        bump,
+    )]
+    pub organizer: Option<Account<'info, EventOrganizer>>,
+
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 #[instruction(
     event_id: u128,
@@ -17,6 +62,7 @@ pub struct CreateEventOption<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
 
+    /// Option pool, omitted when the event has an options table
     #[account(
         init,
         payer = authority,
@@ -25,16 +71,32 @@ pub struct CreateEventOption<'info> {
         bump,
         space = EventOption::LEN
     )]
-    pub option: Account<'info, EventOption>,
+    pub option: Option<AccountLoader<'info, EventOption>>,
+
+    #[account(
+        init,
+        payer = authority,
+        owner = id(),
+        seeds = [b"option_meta".as_ref(), &event_id.to_le_bytes(), &[index]],
+        bump,
+        space = EventOptionMeta::LEN
+    )]
+    pub option_meta: Account<'info, EventOptionMeta>,
 
     #[account(
         mut,
         seeds = [b"event".as_ref(), &event_id.to_le_bytes()],
-        constraint = event.authority == authority.key() @ ProgramError::AuthorityMismatch,
-        constraint = event.start_date > Clock::get()?.unix_timestamp @ ProgramError::EventAlreadyStarted,
+        constraint = is_event_organizer(&*event.load()?, &organizer, &authority.key(), EventOrganizer::MANAGE_OPTIONS) @ ProgramError::AuthorityMismatch,
+        constraint = event.load()?.published == 0 || event.load()?.start_date > Clock::get()?.unix_timestamp @ ProgramError::EventAlreadyStarted,
+        bump = event.load()?.bump,
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        seeds = [b"organizer".as_ref(), &event_id.to_le_bytes(), authority.key().as_ref()],
         bump,
     )]
-    pub event: Account<'info, Event>,
+    pub organizer: Option<Account<'info, EventOrganizer>>,
 
     pub system_program: Program<'info, System>,
 }
@@ -50,44 +112,185 @@ pub struct UpdateEventOption<'info> {
 
     #[account(
         seeds = [b"event".as_ref(), &event_id.to_le_bytes()],
-        constraint = event.authority == authority.key() @ ProgramError::AuthorityMismatch,
-        constraint = event.start_date > Clock::get()?.unix_timestamp @ ProgramError::EventAlreadyStarted,
+        constraint = is_event_organizer(&*event.load()?, &organizer, &authority.key(), EventOrganizer::MANAGE_OPTIONS) @ ProgramError::AuthorityMismatch,
+        constraint = event.load()?.published == 0 || event.load()?.start_date > Clock::get()?.unix_timestamp @ ProgramError::EventAlreadyStarted,
+        bump = event.load()?.bump,
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        seeds = [b"organizer".as_ref(), &event_id.to_le_bytes(), authority.key().as_ref()],
+        bump,
+    )]
+    pub organizer: Option<Account<'info, EventOrganizer>>,
+
+    #[account(
+        mut,
+        seeds = [b"option_meta".as_ref(), &event_id.to_le_bytes(), &[index]],
+        bump,
+    )]
+    pub option_meta: Account<'info, EventOptionMeta>,
+}
+
+/// Accounts of the new options are passed as remaining accounts, in `(option, option_meta)` pairs,
+/// or as single option metas when the event has an options table
+#[derive(Accounts)]
+#[instruction(
+    event_id: u128,
+)]
+pub struct CreateEventOptions<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"event".as_ref(), &event_id.to_le_bytes()],
+        constraint = is_event_organizer(&*event.load()?, &organizer, &authority.key(), EventOrganizer::MANAGE_OPTIONS) @ ProgramError::AuthorityMismatch,
+        constraint = event.load()?.published == 0 || event.load()?.start_date > Clock::get()?.unix_timestamp @ ProgramError::EventAlreadyStarted,
+        bump = event.load()?.bump,
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        seeds = [b"organizer".as_ref(), &event_id.to_le_bytes(), authority.key().as_ref()],
         bump,
     )]
-    pub event: Account<'info, Event>,
+    pub organizer: Option<Account<'info, EventOrganizer>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    event_id: u128,
+    index: u8
+)]
+pub struct RemoveEventOption<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
 
+    #[account(
+        mut,
+        seeds = [b"event".as_ref(), &event_id.to_le_bytes()],
+        constraint = is_event_organizer(&*event.load()?, &organizer, &authority.key(), EventOrganizer::MANAGE_OPTIONS) @ ProgramError::AuthorityMismatch,
+        constraint = event.load()?.published == 0 || event.load()?.start_date > Clock::get()?.unix_timestamp @ ProgramError::EventAlreadyStarted,
+        constraint = event.load()?.participation_count == 0 @ ProgramError::EventHasVotes,
+        // Checked before the seeds of the last option are derived from the count
+        constraint = event.load()?.option_count > 0 @ ProgramError::InvalidIndex,
+        bump = event.load()?.bump,
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        seeds = [b"organizer".as_ref(), &event_id.to_le_bytes(), authority.key().as_ref()],
+        bump,
+    )]
+    pub organizer: Option<Account<'info, EventOrganizer>>,
+
+    /// Option pool, omitted when the event has an options table
     #[account(
         mut,
         seeds = [b"option".as_ref(), &event_id.to_le_bytes(), &[index]],
+        bump = option.load()?.bump,
+    )]
+    pub option: Option<AccountLoader<'info, EventOption>>,
+
+    #[account(
+        mut,
+        seeds = [b"option_meta".as_ref(), &event_id.to_le_bytes(), &[index]],
         bump,
     )]
-    pub option: Account<'info, EventOption>,
+    pub option_meta: Account<'info, EventOptionMeta>,
+
+    /// Option with the highest index, required unless it is the one being removed
+    /// or the event has an options table
+    #[account(
+        mut,
+        seeds = [b"option".as_ref(), &event_id.to_le_bytes(), &[event.load()?.option_count - 1]],
+        bump = last_option.load()?.bump,
+    )]
+    pub last_option: Option<AccountLoader<'info, EventOption>>,
+
+    /// Text of the option with the highest index, passed along with `last_option`
+    #[account(
+        mut,
+        seeds = [b"option_meta".as_ref(), &event_id.to_le_bytes(), &[event.load()?.option_count - 1]],
+        bump,
+    )]
+    pub last_option_meta: Option<Account<'info, EventOptionMeta>>,
 }
 
 // ------------------------ Implementation ------------------------- //
 
+impl CreateOptionsTable<'_> {
+    pub fn create_options_table(&mut self, event_id: u128, bump: u8) -> Result<()> {
+        let event = &mut self.event.load_mut()?;
+
+        // Pools cannot move between storages, so the table is only set up before the first option
+        require!(
+            event.option_count == 0 && event.options_table == 0,
+            ProgramError::InvalidOptionPool
+        );
+
+        let table = &mut self.options_table.load_init()?;
+
+        table.version = OptionsTable::VERSION;
+        table.bump = bump;
+        table.event_id = event_id.to_le_bytes();
+
+        event.options_table = 1;
+
+        emit!(OptionsTableCreated { event_id });
+
+        msg!(
+            "Options table created for event {}",
+            uuid::Uuid::from_u128(event_id)
+        );
+
+        Ok(())
+    }
+}
+
 impl CreateEventOption<'_> {
     pub fn create_event_option(
         &mut self,
         event_id: u128,
         index: u8,
         description: [u8; 256],
+        bump: Option<u8>,
     ) -> Result<()> {
-        let option = &mut self.option;
-        let event = &mut self.event;
+        let option_meta = &mut self.option_meta;
+        let event = &mut self.event.load_mut()?;
 
         require!(index == event.option_count, ProgramError::InvalidIndex);
         require!(
             event.option_count < MAX_OPTION_COUNT,
             ProgramError::TooManyOptions
         );
+        require_option_account(event, self.option.is_some())?;
+
+        if let (Some(option), Some(bump)) = (&self.option, bump) {
+            let option = &mut option.load_init()?;
 
-        option.event_id = event_id;
-        option.description = description;
-        option.index = index;
+            option.event_id = event_id.to_le_bytes();
+            option.index = index;
+            option.bump = bump;
+            option.version = EventOption::VERSION;
+        }
+
+        option_meta.event_id = event_id;
+        option_meta.index = index;
+        option_meta.description = description;
+        option_meta.version = EventOptionMeta::VERSION;
 
         event.option_count += 1;
 
+        emit!(OptionCreated {
+            event_id,
+            index,
+            description,
+        });
+
         msg!(
             "{} option added to event {}",
             index,
@@ -105,9 +308,15 @@ impl UpdateEventOption<'_> {
         index: u8,
         description: [u8; 256],
     ) -> Result<()> {
-        let option = &mut self.option;
+        let option_meta = &mut self.option_meta;
 
-        option.description = description;
+        option_meta.description = description;
+
+        emit!(OptionUpdated {
+            event_id,
+            index,
+            description,
+        });
 
         msg!(
             "{} option updated in event {}",
@@ -118,3 +327,186 @@ impl UpdateEventOption<'_> {
         Ok(())
     }
 }
+
+impl<'info> CreateEventOptions<'info> {
+    pub fn create_event_options(
+        &mut self,
+        event_id: u128,
+        descriptions: Vec<String>,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let event = &mut self.event.load_mut()?;
+        let accounts_len = option_accounts_len(event);
+
+        require!(
+            remaining_accounts.len() == descriptions.len() * accounts_len,
+            ProgramError::InvalidIndex
+        );
+        require!(
+            event.option_count as usize + descriptions.len() <= MAX_OPTION_COUNT as usize,
+            ProgramError::TooManyOptions
+        );
+
+        for (accounts, description) in remaining_accounts
+            .chunks_exact(accounts_len)
+            .zip(descriptions)
+        {
+            let index = event.option_count;
+            let description = fixed_description(&description)?;
+
+            let option_meta_acc = &accounts[accounts_len - 1];
+
+            // Events with an options table only get the option text
+            if let [option_acc, _] = accounts {
+                let option = EventOption {
+                    version: EventOption::VERSION,
+                    bump: create_option_account(
+                        &self.authority,
+                        option_acc,
+                        b"option",
+                        event_id,
+                        index,
+                        EventOption::LEN,
+                        &self.system_program,
+                    )?,
+                    index,
+                    padding: [0; 5],
+                    event_id: event_id.to_le_bytes(),
+                    votes: 0,
+                    vault_balance: 0,
+                    reserved: [0; ACCOUNT_RESERVE_SPACE],
+                };
+                option.try_serialize(&mut &mut option_acc.try_borrow_mut_data()?[..])?;
+            }
+
+            create_option_account(
+                &self.authority,
+                option_meta_acc,
+                b"option_meta",
+                event_id,
+                index,
+                EventOptionMeta::LEN,
+                &self.system_program,
+            )?;
+
+            let option_meta = EventOptionMeta {
+                version: EventOptionMeta::VERSION,
+                index,
+                event_id,
+                description,
+            };
+            option_meta.try_serialize(&mut &mut option_meta_acc.try_borrow_mut_data()?[..])?;
+
+            emit!(OptionCreated {
+                event_id,
+                index,
+                description,
+            });
+
+            event.option_count += 1;
+        }
+
+        msg!(
+            "{} options added to event {}",
+            remaining_accounts.len() / accounts_len,
+            uuid::Uuid::from_u128(event_id)
+        );
+
+        Ok(())
+    }
+}
+
+/// This method pads an option text into the stored description
+fn fixed_description(text: &str) -> Result<[u8; 256]> {
+    let bytes = text.as_bytes();
+    require!(bytes.len() <= 256, ProgramError::DescriptionTooLong);
+
+    let mut description = [0u8; 256];
+    description[..bytes.len()].copy_from_slice(bytes);
+
+    Ok(description)
+}
+
+/// This method creates an option account at its PDA and returns the canonical bump
+fn create_option_account<'info>(
+    authority: &Signer<'info>,
+    account: &AccountInfo<'info>,
+    prefix: &[u8],
+    event_id: u128,
+    index: u8,
+    space: usize,
+    system_program: &Program<'info, System>,
+) -> Result<u8> {
+    let (address, bump) =
+        Pubkey::find_program_address(&[prefix, &event_id.to_le_bytes(), &[index]], &id());
+    require_keys_eq!(account.key(), address, ProgramError::InvalidIndex);
+
+    create_pda_account(
+        authority.to_account_info(),
+        account.clone(),
+        space,
+        &[prefix, &event_id.to_le_bytes(), &[index], &[bump]],
+        system_program.to_account_info(),
+    )?;
+
+    Ok(bump)
+}
+
+impl RemoveEventOption<'_> {
+    pub fn remove_event_option(&mut self, event_id: u128, index: u8) -> Result<()> {
+        let authority = self.authority.to_account_info();
+        let event = &mut self.event.load_mut()?;
+        let last_index = event
+            .option_count
+            .checked_sub(1)
+            .ok_or(ProgramError::InvalidIndex)?;
+
+        require_option_account(event, self.option.is_some())?;
+        require!(
+            event.options_table == 0 || self.last_option.is_none(),
+            ProgramError::InvalidOptionPool
+        );
+
+        if index == last_index {
+            require!(
+                self.last_option.is_none() && self.last_option_meta.is_none(),
+                ProgramError::InvalidIndex
+            );
+
+            if let Some(option) = &self.option {
+                option.close(authority.clone())?;
+            }
+            self.option_meta.close(authority)?;
+        } else {
+            let Some(last_option_meta) = &self.last_option_meta else {
+                return err!(ProgramError::InvalidIndex);
+            };
+
+            // Last option takes the freed index to keep indices sequential,
+            // pools are still empty so only the text moves
+            self.option_meta.description = last_option_meta.description;
+
+            match &self.last_option {
+                Some(last_option) => last_option.close(authority.clone())?,
+                None => require!(event.options_table != 0, ProgramError::InvalidIndex),
+            }
+            last_option_meta.close(authority)?;
+        }
+
+        event.option_count -= 1;
+
+        emit!(OptionRemoved {
+            event_id,
+            index,
+            moved_from: (index != last_index).then_some(last_index),
+        });
+
+        msg!(
+            "{} option removed from event {}",
+            index,
+            uuid::Uuid::from_u128(event_id)
+        );
+
+        Ok(())
+    }
+}
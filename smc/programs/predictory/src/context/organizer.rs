@@ -0,0 +1,290 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    context::withdraw_sol,
+    error::ProgramError,
+    events::{OrganizerRemoved, OrganizerRewardClaimed, OrganizerSet},
+    id,
+    state::{contract_state::State, event::Event, organizer::EventOrganizer, user::User},
+};
+
+// --------------------------- Context ----------------------------- //
+
+#[derive(Accounts)]
+#[instruction(
+    event_id: u128,
+    member: Pubkey,
+)]
+pub struct AddEventOrganizer<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"event".as_ref(), &event_id.to_le_bytes()],
+        constraint = event.load()?.authority == authority.key() @ ProgramError::AuthorityMismatch,
+        constraint = event.load()?.result().is_none() @ ProgramError::EventAlreadyResolved,
+        bump = event.load()?.bump,
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        init,
+        payer = authority,
+        owner = id(),
+        seeds = [b"organizer".as_ref(), &event_id.to_le_bytes(), member.as_ref()],
+        bump,
+        space = EventOrganizer::LEN
+    )]
+    pub organizer: Account<'info, EventOrganizer>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    event_id: u128,
+    member: Pubkey,
+)]
+pub struct UpdateEventOrganizer<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"event".as_ref(), &event_id.to_le_bytes()],
+        constraint = event.load()?.authority == authority.key() @ ProgramError::AuthorityMismatch,
+        constraint = event.load()?.result().is_none() @ ProgramError::EventAlreadyResolved,
+        bump = event.load()?.bump,
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        mut,
+        seeds = [b"organizer".as_ref(), &event_id.to_le_bytes(), member.as_ref()],
+        bump,
+    )]
+    pub organizer: Account<'info, EventOrganizer>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    event_id: u128,
+    member: Pubkey,
+)]
+pub struct RemoveEventOrganizer<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"event".as_ref(), &event_id.to_le_bytes()],
+        constraint = event.load()?.authority == authority.key() @ ProgramError::AuthorityMismatch,
+        constraint = event.load()?.result().is_none() @ ProgramError::EventAlreadyResolved,
+        bump = event.load()?.bump,
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"organizer".as_ref(), &event_id.to_le_bytes(), member.as_ref()],
+        bump,
+    )]
+    pub organizer: Account<'info, EventOrganizer>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    event_id: u128,
+)]
+pub struct ClaimOrganizerReward<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    #[account(
+        seeds = [b"state".as_ref()],
+        constraint = !state.is_paused(State::PAUSE_CLAIMS) @ ProgramError::ClaimsPaused,
+        bump,
+    )]
+    pub state: Account<'info, State>,
+
+    #[account(
+        mut,
+        seeds = [b"user".as_ref(), sender.key().as_ref()],
+        bump = user.load()?.bump,
+    )]
+    pub user: AccountLoader<'info, User>,
+
+    #[account(
+        mut,
+        seeds = [b"event".as_ref(), &event_id.to_le_bytes()],
+        constraint = event.load()?.frozen == 0 @ ProgramError::EventFrozen,
+        bump = event.load()?.bump,
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        mut,
+        seeds = [b"organizer".as_ref(), &event_id.to_le_bytes(), sender.key().as_ref()],
+        bump,
+    )]
+    pub organizer: Account<'info, EventOrganizer>,
+}
+
+// ------------------------ Implementation ------------------------- //
+
+impl AddEventOrganizer<'_> {
+    pub fn add_event_organizer(
+        &mut self,
+        event_id: u128,
+        member: Pubkey,
+        permissions: u8,
+        reward_share: u8,
+    ) -> Result<()> {
+        require!(
+            permissions & !EventOrganizer::ALL_PERMISSIONS == 0,
+            ProgramError::InvalidPermissions
+        );
+        let event = &mut self.event.load_mut()?;
+
+        require!(
+            event.organizer_shares as u16 + reward_share as u16 <= 100,
+            ProgramError::InvalidRewardShare
+        );
+
+        let organizer = &mut self.organizer;
+
+        organizer.event_id = event_id;
+        organizer.member = member;
+        organizer.permissions = permissions;
+        organizer.reward_share = reward_share;
+        organizer.version = EventOrganizer::VERSION;
+
+        event.organizer_shares += reward_share;
+        event.organizer_count += 1;
+
+        emit!(OrganizerSet {
+            event_id,
+            member,
+            permissions,
+            reward_share,
+            organizer_shares: event.organizer_shares,
+        });
+
+        msg!(
+            "Co-organizer {} added to event {}",
+            member,
+            uuid::Uuid::from_u128(event_id)
+        );
+
+        Ok(())
+    }
+}
+
+impl UpdateEventOrganizer<'_> {
+    pub fn update_event_organizer(
+        &mut self,
+        event_id: u128,
+        member: Pubkey,
+        permissions: u8,
+        reward_share: u8,
+    ) -> Result<()> {
+        let event = &mut self.event.load_mut()?;
+        let organizer = &mut self.organizer;
+
+        require!(
+            permissions & !EventOrganizer::ALL_PERMISSIONS == 0,
+            ProgramError::InvalidPermissions
+        );
+
+        let shares = event.organizer_shares - organizer.reward_share;
+        require!(
+            shares as u16 + reward_share as u16 <= 100,
+            ProgramError::InvalidRewardShare
+        );
+
+        event.organizer_shares = shares + reward_share;
+        organizer.permissions = permissions;
+        organizer.reward_share = reward_share;
+
+        emit!(OrganizerSet {
+            event_id,
+            member,
+            permissions,
+            reward_share,
+            organizer_shares: event.organizer_shares,
+        });
+
+        msg!(
+            "Co-organizer {} updated in event {}",
+            member,
+            uuid::Uuid::from_u128(event_id)
+        );
+
+        Ok(())
+    }
+}
+
+impl RemoveEventOrganizer<'_> {
+    pub fn remove_event_organizer(&mut self, event_id: u128, member: Pubkey) -> Result<()> {
+        let event = &mut self.event.load_mut()?;
+
+        event.organizer_shares -= self.organizer.reward_share;
+        // Co-organizers added before the count existed are not part of it
+        event.organizer_count = event.organizer_count.saturating_sub(1);
+
+        emit!(OrganizerRemoved {
+            event_id,
+            member,
+            organizer_shares: event.organizer_shares,
+        });
+
+        msg!(
+            "Co-organizer {} removed from event {}",
+            member,
+            uuid::Uuid::from_u128(event_id)
+        );
+
+        Ok(())
+    }
+}
+
+impl ClaimOrganizerReward<'_> {
+    pub fn claim_organizer_reward(&mut self, event_id: u128) -> Result<()> {
+        let event = &self.event.load()?;
+        let organizer = &mut self.organizer;
+
+        require!(!organizer.reward_claimed, ProgramError::AlreadyClaimed);
+        require!(
+            event.org_reward_amount != 0,
+            ProgramError::OrganizerRewardUnavailable
+        );
+
+        let amount = event.org_reward_amount * organizer.reward_share as u64 / 100;
+
+        self.user.load_mut()?.stake += amount;
+        organizer.reward_claimed = true;
+
+        withdraw_sol(
+            &self.event.to_account_info(),
+            &self.user.to_account_info(),
+            amount,
+        )?;
+
+        emit!(OrganizerRewardClaimed {
+            event_id,
+            member: organizer.member,
+            amount,
+        });
+
+        msg!(
+            "Co-organizer {} claimed {} from event {}",
+            organizer.member,
+            amount,
+            uuid::Uuid::from_u128(event_id)
+        );
+
+        Ok(())
+    }
+}
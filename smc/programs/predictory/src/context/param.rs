@@ -0,0 +1,153 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    context::{apply_param, validate_param, PARAM_CHANGE_DELAY},
+    error::ProgramError,
+    events::{ParamChangeApplied, ParamChangeCancelled, ParamChangeQueued},
+    id,
+    state::{
+        contract_state::State,
+        param::{ParamChange, ParamKind},
+        role::{Role, RoleMember},
+    },
+};
+
+// --------------------------- Context ----------------------------- //
+
+#[derive(Accounts)]
+#[instruction(
+    kind: ParamKind,
+)]
+pub struct QueueParamChange<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::FeeManager as u8], authority.key().as_ref()],
+        bump,
+    )]
+    pub role: Account<'info, RoleMember>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        owner = id(),
+        seeds = [b"param_change".as_ref(), &[kind as u8]],
+        bump,
+        space = ParamChange::LEN
+    )]
+    pub param_change: Account<'info, ParamChange>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    kind: ParamKind,
+)]
+pub struct ApplyParamChange<'info> {
+    pub sender: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state".as_ref()],
+        constraint = state.version == State::VERSION @ ProgramError::StateNotMigrated,
+        bump,
+    )]
+    pub state: Account<'info, State>,
+
+    #[account(
+        mut,
+        seeds = [b"param_change".as_ref(), &[kind as u8]],
+        constraint = param_change.pending @ ProgramError::NoPendingChange,
+        bump,
+    )]
+    pub param_change: Account<'info, ParamChange>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    kind: ParamKind,
+)]
+pub struct CancelParamChange<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::FeeManager as u8], authority.key().as_ref()],
+        bump,
+    )]
+    pub role: Account<'info, RoleMember>,
+
+    #[account(
+        mut,
+        seeds = [b"param_change".as_ref(), &[kind as u8]],
+        constraint = param_change.pending @ ProgramError::NoPendingChange,
+        bump,
+    )]
+    pub param_change: Account<'info, ParamChange>,
+}
+
+// ------------------------ Implementation ------------------------- //
+
+impl QueueParamChange<'_> {
+    pub fn queue_param_change(&mut self, kind: ParamKind, value: u64) -> Result<()> {
+        validate_param(kind, value)?;
+
+        let param_change = &mut self.param_change;
+
+        param_change.kind = kind;
+        param_change.value = value;
+        param_change.effective_at = Clock::get()?.unix_timestamp + PARAM_CHANGE_DELAY;
+        param_change.pending = true;
+        param_change.version = ParamChange::VERSION;
+
+        emit!(ParamChangeQueued {
+            kind,
+            value,
+            effective_at: param_change.effective_at,
+        });
+
+        msg!(
+            "Parameter change queued: {} effective at {}",
+            value,
+            param_change.effective_at
+        );
+
+        Ok(())
+    }
+}
+
+impl ApplyParamChange<'_> {
+    pub fn apply_param_change(&mut self, _kind: ParamKind) -> Result<()> {
+        let param_change = &mut self.param_change;
+
+        require!(
+            Clock::get()?.unix_timestamp >= param_change.effective_at,
+            ProgramError::ParamChangeTimelocked
+        );
+
+        apply_param(&mut self.state, param_change.kind, param_change.value)?;
+        param_change.pending = false;
+
+        emit!(ParamChangeApplied {
+            kind: param_change.kind,
+            value: param_change.value,
+        });
+
+        msg!("Parameter change applied: {}", param_change.value);
+
+        Ok(())
+    }
+}
+
+impl CancelParamChange<'_> {
+    pub fn cancel_param_change(&mut self, kind: ParamKind) -> Result<()> {
+        self.param_change.pending = false;
+
+        emit!(ParamChangeCancelled { kind });
+
+        msg!("Parameter change cancelled");
+
+        Ok(())
+    }
+}
@@ -4,12 +4,26 @@ use anchor_lang::{
 };
 
 use crate::{
-    context::{withdraw_sol, APPELLATION_DEADLINE, COMPLETION_DEADLINE},
+    context::{
+        collect_to_treasury, deposit_to_option, option_pool, trust_reward, withdraw_sol,
+        Settlement, APPELLATION_DEADLINE, CLAIM_ACCOUNTS_LEN, COMPLETION_DEADLINE,
+        SETTLEMENT_ACCOUNTS_LEN,
+    },
     error::ProgramError,
+    events::{
+        AppealFiled, OrganizerStakeReleased, Recharged, RewardClaimed, TrustBurned, TrustChanged,
+        VoteCast,
+    },
     id,
     state::{
-        appeal::Appellation, contract_state::State, event::Event, option::EventOption,
-        participation::Participation, user::User,
+        appeal::Appellation,
+        contract_state::State,
+        event::Event,
+        option::{EventOption, OptionsTable},
+        participation::Participation,
+        portfolio::{Portfolio, PortfolioEntry, PositionStatus},
+        treasury::Treasury,
+        user::User,
     },
 };
 
@@ -27,25 +41,41 @@ pub struct Vote<'info> {
     #[account(
         mut,
         seeds = [b"user".as_ref(), sender.key().as_ref()],
+        bump = user.load()?.bump,
+    )]
+    pub user: AccountLoader<'info, User>,
+
+    #[account(
+        seeds = [b"state".as_ref()],
+        constraint = !state.is_paused(State::PAUSE_VOTING) @ ProgramError::VotingPaused,
         bump,
     )]
-    pub user: Account<'info, User>,
+    pub state: Account<'info, State>,
 
     #[account(
         mut,
         seeds = [b"event".as_ref(), &event_id.to_le_bytes()],
     // TODO: check this
-        // constraint = event.authority != sender.key() @ ProgramError::CreatorParticipation,
-        bump,
+        // constraint = event.load()?.authority != sender.key() @ ProgramError::CreatorParticipation,
+        constraint = event.load()?.frozen == 0 @ ProgramError::EventFrozen,
+        bump = event.load()?.bump,
     )]
-    pub event: Account<'info, Event>,
+    pub event: AccountLoader<'info, Event>,
 
     #[account(
         mut,
         seeds = [b"option".as_ref(), &event_id.to_le_bytes(), &[option_ix]],
-        bump,
+        bump = option.load()?.bump,
     )]
-    pub option: Account<'info, EventOption>,
+    pub option: Option<AccountLoader<'info, EventOption>>,
+
+    /// Option pools, passed instead of `option` when the event has an options table
+    #[account(
+        mut,
+        seeds = [b"options_table".as_ref(), &event_id.to_le_bytes()],
+        bump = options_table.load()?.bump,
+    )]
+    pub options_table: Option<AccountLoader<'info, OptionsTable>>,
 
     #[account(
         init,
@@ -55,7 +85,15 @@ pub struct Vote<'info> {
         bump,
         space = Participation::LEN
     )]
-    pub participation: Account<'info, Participation>,
+    pub participation: AccountLoader<'info, Participation>,
+
+    #[account(
+        mut,
+        seeds = [b"portfolio".as_ref(), sender.key().as_ref(), &portfolio.page.to_le_bytes()],
+        constraint = !portfolio.is_full() @ ProgramError::PortfolioPageFull,
+        bump,
+    )]
+    pub portfolio: Account<'info, Portfolio>,
 
     pub system_program: Program<'info, System>,
 }
@@ -68,27 +106,30 @@ pub struct ClaimEventReward<'info> {
     #[account(mut)]
     pub sender: Signer<'info>,
 
-    /// CHECK: this is admin account
-    #[account(mut)]
-    pub contract_admin: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"treasury".as_ref()],
+        bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
 
     #[account(
         mut,
         seeds = [b"user".as_ref(), sender.key().as_ref()],
-        bump,
+        bump = user.load()?.bump,
     )]
-    pub user: Account<'info, User>,
+    pub user: AccountLoader<'info, User>,
 
     #[account(
         mut,
-        seeds = [b"user".as_ref(), event.authority.key().as_ref()],
-        bump,
+        seeds = [b"user".as_ref(), event.load()?.authority.key().as_ref()],
+        bump = event_admin.load()?.bump,
     )]
-    pub event_admin: Account<'info, User>,
+    pub event_admin: AccountLoader<'info, User>,
 
     #[account(
         seeds = [b"state".as_ref()],
-        constraint = state.authority == contract_admin.key() @ ProgramError::AuthorityMismatch,
+        constraint = !state.is_paused(State::PAUSE_CLAIMS) @ ProgramError::ClaimsPaused,
         bump,
     )]
     pub state: Account<'info, State>,
@@ -96,55 +137,167 @@ pub struct ClaimEventReward<'info> {
     #[account(
         mut,
         seeds = [b"event".as_ref(), &event_id.to_le_bytes()],
-        bump,
+        constraint = event.load()?.frozen == 0 @ ProgramError::EventFrozen,
+        bump = event.load()?.bump,
     )]
-    pub event: Account<'info, Event>,
+    pub event: AccountLoader<'info, Event>,
 
     #[account(
-        seeds = [b"option".as_ref(), &event_id.to_le_bytes(), &[participation.option]],
-        bump,
+        seeds = [b"option".as_ref(), &event_id.to_le_bytes(), &[participation.load()?.option]],
+        bump = option.load()?.bump,
     )]
-    pub option: Account<'info, EventOption>,
+    pub option: Option<AccountLoader<'info, EventOption>>,
+
+    /// Option pools, passed instead of `option` when the event has an options table
+    #[account(
+        seeds = [b"options_table".as_ref(), &event_id.to_le_bytes()],
+        bump = options_table.load()?.bump,
+    )]
+    pub options_table: Option<AccountLoader<'info, OptionsTable>>,
 
     #[account(
         mut,
         seeds = [b"participation".as_ref(), &event_id.to_le_bytes(), sender.key().as_ref()],
-        constraint = participation.payer == sender.key() @ ProgramError::AuthorityMismatch,
+        constraint = participation.load()?.payer == sender.key() @ ProgramError::AuthorityMismatch,
+        bump = participation.load()?.bump,
+    )]
+    pub participation: AccountLoader<'info, Participation>,
+
+    #[account(
+        mut,
+        seeds = [b"portfolio".as_ref(), sender.key().as_ref(), &participation.load()?.portfolio_page.to_le_bytes()],
         bump,
     )]
-    pub participation: Account<'info, Participation>,
+    pub portfolio: Account<'info, Portfolio>,
 
     pub system_program: Program<'info, System>,
 }
 
+/// Accounts of the positions to settle are passed as remaining accounts,
+/// in `(participation, user, portfolio)` triples; without any position only the organizer stake is released
 #[derive(Accounts)]
 #[instruction(
     event_id: u128,
 )]
-pub struct Recharge<'info> {
-    #[account(mut)]
+pub struct SettleBatch<'info> {
     pub sender: Signer<'info>,
 
+    #[account(
+        mut,
+        seeds = [b"treasury".as_ref()],
+        bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        mut,
+        seeds = [b"user".as_ref(), event.load()?.authority.key().as_ref()],
+        bump = event_admin.load()?.bump,
+    )]
+    pub event_admin: AccountLoader<'info, User>,
+
+    #[account(
+        seeds = [b"state".as_ref()],
+        constraint = !state.is_paused(State::PAUSE_CLAIMS) @ ProgramError::ClaimsPaused,
+        bump,
+    )]
+    pub state: Account<'info, State>,
+
     #[account(
         mut,
         seeds = [b"event".as_ref(), &event_id.to_le_bytes()],
+        constraint = event.load()?.frozen == 0 @ ProgramError::EventFrozen,
+        bump = event.load()?.bump,
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    /// Winning option
+    #[account(
+        seeds = [b"option".as_ref(), &event_id.to_le_bytes(), &[event.load()?.result]],
+        bump = option.load()?.bump,
+    )]
+    pub option: Option<AccountLoader<'info, EventOption>>,
+
+    /// Option pools, passed instead of `option` when the event has an options table
+    #[account(
+        seeds = [b"options_table".as_ref(), &event_id.to_le_bytes()],
+        bump = options_table.load()?.bump,
+    )]
+    pub options_table: Option<AccountLoader<'info, OptionsTable>>,
+}
+
+/// Accounts of the claimed positions are passed as remaining accounts, in `(event, option, participation)`
+/// triples followed by the portfolio pages these participations are recorded on; the options table
+/// takes the place of the option for events that have one
+#[derive(Accounts)]
+pub struct ClaimMany<'info> {
+    pub sender: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury".as_ref()],
         bump,
     )]
-    pub event: Account<'info, Event>,
+    pub treasury: Account<'info, Treasury>,
 
     #[account(
         mut,
         seeds = [b"user".as_ref(), sender.key().as_ref()],
+        bump = user.load()?.bump,
+    )]
+    pub user: AccountLoader<'info, User>,
+
+    #[account(
+        seeds = [b"state".as_ref()],
+        constraint = !state.is_paused(State::PAUSE_CLAIMS) @ ProgramError::ClaimsPaused,
         bump,
     )]
-    pub user: Account<'info, User>,
+    pub state: Account<'info, State>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    event_id: u128,
+)]
+pub struct Recharge<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    #[account(
+        seeds = [b"state".as_ref()],
+        constraint = !state.is_paused(State::PAUSE_CLAIMS) @ ProgramError::ClaimsPaused,
+        bump,
+    )]
+    pub state: Account<'info, State>,
+
+    #[account(
+        mut,
+        seeds = [b"event".as_ref(), &event_id.to_le_bytes()],
+        constraint = event.load()?.frozen == 0 @ ProgramError::EventFrozen,
+        bump = event.load()?.bump,
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        mut,
+        seeds = [b"user".as_ref(), sender.key().as_ref()],
+        bump = user.load()?.bump,
+    )]
+    pub user: AccountLoader<'info, User>,
 
     #[account(
         mut,
         seeds = [b"participation".as_ref(), &event_id.to_le_bytes(), sender.key().as_ref()],
+        bump = participant.load()?.bump,
+    )]
+    pub participant: AccountLoader<'info, Participation>,
+
+    #[account(
+        mut,
+        seeds = [b"portfolio".as_ref(), sender.key().as_ref(), &participant.load()?.portfolio_page.to_le_bytes()],
         bump,
     )]
-    pub participant: Account<'info, Participation>,
+    pub portfolio: Account<'info, Portfolio>,
 
     pub system_program: Program<'info, System>,
 }
@@ -157,15 +310,18 @@ pub struct AppealResult<'info> {
     #[account(mut)]
     pub sender: Signer<'info>,
 
-    /// CHECK: this is admin account
-    #[account(mut)]
-    pub contract_admin: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"treasury".as_ref()],
+        bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
 
     #[account(
-            seeds = [b"state".as_ref()],
-            constraint = state.authority == contract_admin.key() @ ProgramError::AuthorityMismatch,
-            bump,
-        )]
+        seeds = [b"state".as_ref()],
+        constraint = !state.is_paused(State::PAUSE_APPEALS) @ ProgramError::AppealsPaused,
+        bump,
+    )]
     pub state: Account<'info, State>,
 
     #[account(
@@ -178,31 +334,56 @@ pub struct AppealResult<'info> {
     )]
     pub appellation: Account<'info, Appellation>,
 
+    /// Organizer user, its locked stake is slashed when the appeals outweigh the result
     #[account(
+        mut,
+        seeds = [b"user".as_ref(), event.load()?.authority.key().as_ref()],
+        bump = event_admin.load()?.bump,
+    )]
+    pub event_admin: AccountLoader<'info, User>,
+
+    #[account(
+        mut,
         seeds = [b"event".as_ref(), &event_id.to_le_bytes()],
-        constraint = event.end_date < Clock::get()?.unix_timestamp @ ProgramError::ActiveEvent,
-        bump,
+        constraint = event.load()?.end_date < Clock::get()?.unix_timestamp @ ProgramError::ActiveEvent,
+        constraint = event.load()?.frozen == 0 @ ProgramError::EventFrozen,
+        bump = event.load()?.bump,
     )]
-    pub event: Account<'info, Event>,
+    pub event: AccountLoader<'info, Event>,
 
     #[account(
-        seeds = [b"option".as_ref(), &event_id.to_le_bytes(), &[participation.option]],
-        bump,
+        seeds = [b"option".as_ref(), &event_id.to_le_bytes(), &[participation.load()?.option]],
+        bump = option.load()?.bump,
     )]
-    pub option: Account<'info, EventOption>,
+    pub option: Option<AccountLoader<'info, EventOption>>,
+
+    /// Option pools, passed instead of `option` when the event has an options table
+    #[account(
+        seeds = [b"options_table".as_ref(), &event_id.to_le_bytes()],
+        bump = options_table.load()?.bump,
+    )]
+    pub options_table: Option<AccountLoader<'info, OptionsTable>>,
 
     #[account(
         seeds = [b"user".as_ref(), sender.key().as_ref()],
-        bump,
+        bump = user.load()?.bump,
     )]
-    pub user: Account<'info, User>,
+    pub user: AccountLoader<'info, User>,
 
     #[account(
+        mut,
         seeds = [b"participation".as_ref(), &event_id.to_le_bytes(), sender.key().as_ref()],
-        constraint = participation.payer == sender.key() @ ProgramError::AuthorityMismatch,
+        constraint = participation.load()?.payer == sender.key() @ ProgramError::AuthorityMismatch,
+        bump = participation.load()?.bump,
+    )]
+    pub participation: AccountLoader<'info, Participation>,
+
+    #[account(
+        mut,
+        seeds = [b"portfolio".as_ref(), sender.key().as_ref(), &participation.load()?.portfolio_page.to_le_bytes()],
         bump,
     )]
-    pub participation: Account<'info, Participation>,
+    pub portfolio: Account<'info, Portfolio>,
 
     pub system_program: Program<'info, System>,
 }
@@ -217,29 +398,40 @@ pub struct BurnTrust<'info> {
 
     #[account(
             seeds = [b"state".as_ref()],
+            constraint = !state.is_paused(State::PAUSE_CLAIMS) @ ProgramError::ClaimsPaused,
             bump,
         )]
     pub state: Account<'info, State>,
 
     #[account(
+        mut,
         seeds = [b"event".as_ref(), &event_id.to_le_bytes()],
-        bump,
+        constraint = event.load()?.frozen == 0 @ ProgramError::EventFrozen,
+        bump = event.load()?.bump,
     )]
-    pub event: Account<'info, Event>,
+    pub event: AccountLoader<'info, Event>,
 
     #[account(
         mut,
         seeds = [b"user".as_ref(), sender.key().as_ref()],
-        bump,
+        bump = user.load()?.bump,
     )]
-    pub user: Account<'info, User>,
+    pub user: AccountLoader<'info, User>,
 
     #[account(
+        mut,
         seeds = [b"participation".as_ref(), &event_id.to_le_bytes(), sender.key().as_ref()],
-        constraint = participation.payer == sender.key() @ ProgramError::AuthorityMismatch,
+        constraint = participation.load()?.payer == sender.key() @ ProgramError::AuthorityMismatch,
+        bump = participation.load()?.bump,
+    )]
+    pub participation: AccountLoader<'info, Participation>,
+
+    #[account(
+        mut,
+        seeds = [b"portfolio".as_ref(), sender.key().as_ref(), &participation.load()?.portfolio_page.to_le_bytes()],
         bump,
     )]
-    pub participation: Account<'info, Participation>,
+    pub portfolio: Account<'info, Portfolio>,
 
     pub system_program: Program<'info, System>,
 }
@@ -247,10 +439,9 @@ pub struct BurnTrust<'info> {
 // ------------------------ Implementation ------------------------- //
 
 impl Vote<'_> {
-    pub fn vote(&mut self, event_id: u128, option_ix: u8, amount: u64) -> Result<()> {
-        let event = &mut self.event;
-        let option = &mut self.option;
-        let user = &mut self.user;
+    pub fn vote(&mut self, event_id: u128, option_ix: u8, amount: u64, bump: u8) -> Result<()> {
+        let event = &mut self.event.load_mut()?;
+        let user = &mut self.user.load_mut()?;
 
         let now = Clock::get()?.unix_timestamp;
 
@@ -259,26 +450,36 @@ impl Vote<'_> {
             ProgramError::InactiveEvent
         );
 
-        if let Some(deadline) = event.participation_deadline {
+        if let Some(deadline) = event.participation_deadline() {
             require!(deadline > now, ProgramError::ParticipationDeadlinePassed);
         }
 
-        require!(!event.canceled, ProgramError::CanceledEvent);
+        require!(event.published != 0, ProgramError::EventNotPublished);
+        require!(event.canceled == 0, ProgramError::CanceledEvent);
 
-        let participation = &mut self.participation;
+        let participation = &mut self.participation.load_init()?;
 
-        participation.event_id = event_id;
+        participation.event_id = event_id.to_le_bytes();
         participation.payer = self.sender.key();
         participation.option = option_ix;
         participation.deposited_amount = amount;
+        participation.portfolio_page = self.portfolio.page;
         participation.version = Participation::VERSION;
+        participation.bump = bump;
+
+        self.portfolio.entries.push(PortfolioEntry {
+            event_id,
+            option: option_ix,
+            amount,
+            status: PositionStatus::Open,
+        });
 
         event.participation_count += 1;
         event.total_trust += user.trust_lvl;
         event.total_amount += amount;
 
-        option.vault_balance += amount;
-        option.votes += 1;
+        let (option_votes, option_vault_balance) =
+            deposit_to_option(event, &self.option, &self.options_table, option_ix, amount)?;
 
         user.stake -= amount;
 
@@ -288,11 +489,22 @@ impl Vote<'_> {
             amount,
         )?;
 
+        emit!(VoteCast {
+            event_id,
+            user: participation.payer,
+            option: option_ix,
+            amount,
+            trust_lvl: user.trust_lvl,
+            option_votes,
+            option_vault_balance,
+            total_amount: event.total_amount,
+        });
+
         msg!(
             "New participation: user {} deposited {} SOL to {} event",
-            participation.payer,
+            self.sender.key(),
             lamports_to_sol(amount),
-            uuid::Uuid::from_u128(participation.event_id)
+            uuid::Uuid::from_u128(event_id)
         );
 
         Ok(())
@@ -301,12 +513,13 @@ impl Vote<'_> {
 
 impl ClaimEventReward<'_> {
     pub fn claim_event_reward(&mut self, event_id: u128) -> Result<()> {
-        let user = &mut self.user;
-        let event = &mut self.event;
+        let event = &mut self.event.load_mut()?;
+        let participation = &mut self.participation.load_mut()?;
 
-        require!(event.result.is_some(), ProgramError::EventIsNotOver);
-        require!(!self.participation.is_claimed, ProgramError::AlreadyClaimed);
-        require!(!self.participation.appealed, ProgramError::AlreadyAppealed);
+        require!(event.resolved != 0, ProgramError::EventIsNotOver);
+        require!(participation.is_claimed == 0, ProgramError::AlreadyClaimed);
+        // A resolver may still change the result, so nobody claims until the dispute is settled
+        require!(event.disputed == 0, ProgramError::EventDisputed);
 
         let now = Clock::get()?.unix_timestamp;
 
@@ -315,100 +528,471 @@ impl ClaimEventReward<'_> {
             ProgramError::EarlyClaim
         );
 
-        // TODO: check this
-        // let org_reward = event.total_amount * self.state.org_reward / 100;
-        // let available_for_winners =
-        // event.total_amount * (1 - self.state.platform_fee - self.state.org_reward);
+        // The organizer may claim its own position, so both user accounts are borrowed in turn
+        release_organizer_stake(
+            event,
+            &self.event.to_account_info(),
+            &mut *self.event_admin.load_mut()?,
+            &self.event_admin.to_account_info(),
+            &mut self.treasury,
+            event_id,
+        )?;
 
-        let org_reward = (event.total_amount as f64 * self.state.org_reward as f64 / 100.0) as u64;
+        let (_, option_vault) = option_pool(
+            event,
+            &self.option,
+            &self.options_table,
+            participation.option,
+        )?;
 
-        let available_for_winners = if event.total_amount < self.state.platform_fee + org_reward {
-            event.total_amount
-        } else {
-            event.total_amount - self.state.platform_fee - org_reward
-        };
+        settle_participation(
+            event,
+            &self.event.to_account_info(),
+            option_vault,
+            self.state.multiplier,
+            &mut *self.user.load_mut()?,
+            &self.user.to_account_info(),
+            participation,
+            &mut self.portfolio,
+            event_id,
+        )?;
 
-        // Releasing creator stake
-        if event.stake != 0 {
-            let mut amount = event.stake;
+        msg!(
+            "User {} claimed {} event",
+            self.sender.key(),
+            uuid::Uuid::from_u128(event_id)
+        );
 
-            if available_for_winners != event.total_amount {
-                amount += org_reward;
+        Ok(())
+    }
+}
 
-                withdraw_sol(
-                    &event.to_account_info(),
-                    &self.contract_admin.to_account_info(),
-                    self.state.platform_fee,
-                )?;
-            }
+impl<'info> SettleBatch<'info> {
+    pub fn settle_batch(
+        &mut self,
+        event_id: u128,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        let event_info = self.event.to_account_info();
+        let event = &mut self.event.load_mut()?;
 
-            self.event_admin.locked_stake -= event.stake;
-            self.event_admin.stake += amount;
+        require!(event.resolved != 0, ProgramError::EventIsNotOver);
+        require!(event.disputed == 0, ProgramError::EventDisputed);
 
-            withdraw_sol(
-                &event.to_account_info(),
-                &self.event_admin.to_account_info(),
-                amount,
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            now > event.end_date + COMPLETION_DEADLINE + APPELLATION_DEADLINE,
+            ProgramError::EarlyClaim
+        );
+        let positions = remaining_accounts.chunks_exact(SETTLEMENT_ACCOUNTS_LEN);
+
+        require!(
+            positions.remainder().is_empty(),
+            ProgramError::InvalidSettlementAccounts
+        );
+
+        release_organizer_stake(
+            event,
+            &event_info,
+            &mut *self.event_admin.load_mut()?,
+            &self.event_admin.to_account_info(),
+            &mut self.treasury,
+            event_id,
+        )?;
+
+        let (_, winning_vault) =
+            option_pool(event, &self.option, &self.options_table, event.result)?;
+        let mut settled = 0;
+
+        for accounts in positions {
+            let participation = AccountLoader::<Participation>::try_from(&accounts[0])?;
+            let user = AccountLoader::<User>::try_from(&accounts[1])?;
+            let mut portfolio = Account::<Portfolio>::try_from(&accounts[2])?;
+
+            let participation = &mut participation.load_mut()?;
+            // The organizer account is not borrowed anymore, so its own position is credited as well
+            let user_info = user.to_account_info();
+            let user = &mut user.load_mut()?;
+
+            let id_bytes = event_id.to_le_bytes();
+            let payer = participation.payer;
+
+            // Stored canonical bumps spare the address search
+            require_pda(
+                &accounts[0],
+                &[b"participation".as_ref(), &id_bytes, payer.as_ref()],
+                participation.bump,
+                ProgramError::InvalidSettlementAccounts,
+            )?;
+            require_pda(
+                &accounts[1],
+                &[b"user".as_ref(), payer.as_ref()],
+                user.bump,
+                ProgramError::InvalidSettlementAccounts,
             )?;
 
-            event.stake = 0;
+            // Portfolio pages store no bump, so theirs is searched
+            let (portfolio_address, _) = Pubkey::find_program_address(
+                &[
+                    b"portfolio".as_ref(),
+                    payer.as_ref(),
+                    &participation.portfolio_page.to_le_bytes(),
+                ],
+                &id(),
+            );
+            require_keys_eq!(
+                accounts[2].key(),
+                portfolio_address,
+                ProgramError::InvalidSettlementAccounts
+            );
+
+            // Settled positions are skipped, so a crank can safely resend a page
+            if participation.is_claimed != 0 {
+                continue;
+            }
+
+            settle_participation(
+                event,
+                &event_info,
+                winning_vault,
+                self.state.multiplier,
+                user,
+                &user_info,
+                participation,
+                &mut portfolio,
+                event_id,
+            )?;
+
+            portfolio.exit(&id())?;
+
+            settled += 1;
         }
 
-        if event.result.unwrap() == self.participation.option {
-            let claim_amount = self.participation.deposited_amount / self.option.vault_balance
-                * available_for_winners;
+        msg!(
+            "Settled {} participations of {} event",
+            settled,
+            uuid::Uuid::from_u128(event_id)
+        );
 
-            user.stake += claim_amount;
+        Ok(())
+    }
+}
 
-            withdraw_sol(
-                &self.event.to_account_info(),
-                &user.to_account_info(),
-                claim_amount,
+/// This method releases the organizer stake on the first settlement of an event,
+/// paying the organizer reward and collecting the platform fee
+fn release_organizer_stake<'info>(
+    event: &mut Event,
+    event_info: &AccountInfo<'info>,
+    event_admin: &mut User,
+    event_admin_info: &AccountInfo<'info>,
+    treasury: &mut Account<'info, Treasury>,
+    event_id: u128,
+) -> Result<()> {
+    if event.stake == 0 {
+        return Ok(());
+    }
+
+    let settlement = Settlement::for_event(event);
+    let mut amount = event.stake;
+    let mut released_reward = 0;
+    let mut collected_fee = 0;
+
+    if settlement.is_charged() {
+        // Co-organizer shares stay on the event until claimed
+        let organizers_reward = settlement.org_reward * event.organizer_shares as u64 / 100;
+
+        released_reward = settlement.org_reward - organizers_reward;
+        collected_fee = settlement.platform_fee;
+
+        amount += released_reward;
+        event.org_reward_amount = settlement.org_reward;
+
+        collect_to_treasury(event_info, treasury, settlement.platform_fee)?;
+    }
+
+    event_admin.locked_stake -= event.stake;
+    event_admin.stake += amount;
+
+    withdraw_sol(event_info, event_admin_info, amount)?;
+
+    emit!(OrganizerStakeReleased {
+        event_id,
+        authority: event.authority,
+        stake: event.stake,
+        org_reward: released_reward,
+        platform_fee: collected_fee,
+    });
+
+    event.stake = 0;
+
+    Ok(())
+}
+
+/// This method moves the organizer stake to the treasury along with the platform fee and the organizer
+/// reward, which the organizer forfeits; settlement then finds no stake left to release
+fn slash_organizer_stake<'info>(
+    event: &mut Event,
+    event_info: &AccountInfo<'info>,
+    event_admin: &mut User,
+    treasury: &mut Account<'info, Treasury>,
+) -> Result<()> {
+    let settlement = Settlement::for_event(event);
+
+    event_admin.locked_stake -= event.stake;
+
+    collect_to_treasury(
+        event_info,
+        treasury,
+        event.stake + settlement.platform_fee + settlement.org_reward,
+    )?;
+
+    event.stake = 0;
+
+    Ok(())
+}
+
+/// This method pays out a participation of a completed event and credits its trust reward,
+/// `winning_vault` being the vault balance of the winning option; returns the paid out amount
+#[allow(clippy::too_many_arguments)]
+fn settle_participation<'info>(
+    event: &Event,
+    event_info: &AccountInfo<'info>,
+    winning_vault: u64,
+    multiplier: u64,
+    user: &mut User,
+    user_info: &AccountInfo<'info>,
+    participation: &mut Participation,
+    portfolio: &mut Account<'info, Portfolio>,
+    event_id: u128,
+) -> Result<u64> {
+    let settlement = Settlement::for_event(event);
+    let won = event.result() == Some(participation.option);
+    let trust_before = user.trust_lvl;
+    let mut claimed = 0;
+
+    if won {
+        let claim_amount = settlement.winner_payout(participation.deposited_amount, winning_vault);
+
+        user.stake += claim_amount;
+
+        withdraw_sol(event_info, user_info, claim_amount)?;
+
+        user.trust_lvl += trust_reward(claim_amount, multiplier);
+        claimed = claim_amount;
+    } else {
+        user.trust_lvl += trust_reward(participation.deposited_amount, multiplier);
+    }
+
+    if user.trust_lvl > trust_before {
+        user.trust_raised_at = Clock::get()?.unix_timestamp;
+    }
+
+    participation.is_claimed = 1;
+    portfolio.remove(event_id);
+
+    emit!(RewardClaimed {
+        event_id,
+        user: participation.payer,
+        option: participation.option,
+        won,
+        amount: claimed,
+    });
+    emit!(TrustChanged {
+        event_id,
+        user: participation.payer,
+        delta: (user.trust_lvl - trust_before) as i64,
+        trust_lvl: user.trust_lvl,
+    });
+
+    Ok(claimed)
+}
+
+impl<'info> ClaimMany<'info> {
+    pub fn claim_many(
+        &mut self,
+        claim_count: u8,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<Vec<ClaimResult>> {
+        let sender = self.sender.key();
+        let claims_len = claim_count as usize * CLAIM_ACCOUNTS_LEN;
+
+        require!(
+            claim_count > 0 && claims_len <= remaining_accounts.len(),
+            ProgramError::InvalidClaimAccounts
+        );
+
+        let (claims, pages) = remaining_accounts.split_at(claims_len);
+        let mut portfolios = pages
+            .iter()
+            .map(Account::<Portfolio>::try_from)
+            .collect::<Result<Vec<_>>>()?;
+
+        require!(
+            portfolios.iter().all(|portfolio| portfolio.owner == sender),
+            ProgramError::InvalidClaimAccounts
+        );
+
+        let user_info = self.user.to_account_info();
+        let user = &mut self.user.load_mut()?;
+        let now = Clock::get()?.unix_timestamp;
+        let mut results = Vec::with_capacity(claim_count as usize);
+
+        for accounts in claims.chunks_exact(CLAIM_ACCOUNTS_LEN) {
+            let event_loader = AccountLoader::<Event>::try_from(&accounts[0])?;
+            let participation = AccountLoader::<Participation>::try_from(&accounts[2])?;
+
+            let event = &mut event_loader.load_mut()?;
+            let participation = &mut participation.load_mut()?;
+            let event_id = participation.event_id();
+
+            let id_bytes = participation.event_id;
+
+            // Stored canonical bumps spare the address search
+            require_pda(
+                &accounts[0],
+                &[b"event".as_ref(), &id_bytes],
+                event.bump,
+                ProgramError::InvalidClaimAccounts,
             )?;
+            let option_vault = if event.options_table != 0 {
+                let table = AccountLoader::<OptionsTable>::try_from(&accounts[1])?;
+                let table = &table.load()?;
+
+                require_pda(
+                    &accounts[1],
+                    &[b"options_table".as_ref(), &id_bytes],
+                    table.bump,
+                    ProgramError::InvalidClaimAccounts,
+                )?;
 
-            // TODO: check this
-            let trust_reward =
-                (claim_amount as f64 / LAMPORTS_PER_SOL as f64) * self.state.multiplier as f64;
-            user.trust_lvl += trust_reward as u64;
-        } else {
-            let trust_reward = (self.participation.deposited_amount as f64
-                / LAMPORTS_PER_SOL as f64)
-                * self.state.multiplier as f64;
-            user.trust_lvl += trust_reward as u64;
+                table.vault_balances[participation.option as usize]
+            } else {
+                let option = AccountLoader::<EventOption>::try_from(&accounts[1])?;
+                let option = &option.load()?;
+
+                require_pda(
+                    &accounts[1],
+                    &[b"option".as_ref(), &id_bytes, &[participation.option]],
+                    option.bump,
+                    ProgramError::InvalidClaimAccounts,
+                )?;
+
+                option.vault_balance
+            };
+            require_pda(
+                &accounts[2],
+                &[b"participation".as_ref(), &id_bytes, sender.as_ref()],
+                participation.bump,
+                ProgramError::InvalidClaimAccounts,
+            )?;
+
+            // Claimed and not yet claimable positions are reported and skipped
+            let status = if participation.is_claimed != 0 {
+                ClaimStatus::AlreadyClaimed
+            } else if event.resolved == 0
+                || event.frozen != 0
+                || event.disputed != 0
+                || now <= event.end_date + COMPLETION_DEADLINE + APPELLATION_DEADLINE
+            {
+                ClaimStatus::NotClaimable
+            } else {
+                ClaimStatus::Claimed
+            };
+
+            if status != ClaimStatus::Claimed {
+                results.push(ClaimResult {
+                    event_id,
+                    status,
+                    amount: 0,
+                });
+                continue;
+            }
+
+            let portfolio = portfolios
+                .iter_mut()
+                .find(|portfolio| portfolio.page == participation.portfolio_page)
+                .ok_or(ProgramError::InvalidClaimAccounts)?;
+
+            // Other organizers get their stake back on `claim_event_reward` or `settle_batch`
+            if event.authority == sender {
+                release_organizer_stake(
+                    event,
+                    &accounts[0],
+                    user,
+                    &user_info,
+                    &mut self.treasury,
+                    event_id,
+                )?;
+            }
+
+            let amount = settle_participation(
+                event,
+                &accounts[0],
+                option_vault,
+                self.state.multiplier,
+                user,
+                &user_info,
+                participation,
+                portfolio,
+                event_id,
+            )?;
+
+            results.push(ClaimResult {
+                event_id,
+                status,
+                amount,
+            });
         }
 
-        self.participation.is_claimed = true;
+        for portfolio in &portfolios {
+            portfolio.exit(&id())?;
+        }
 
         msg!(
-            "User {} claimed {} event",
-            self.participation.payer,
-            uuid::Uuid::from_u128(event_id)
+            "User {} claimed {} events",
+            sender,
+            results
+                .iter()
+                .filter(|result| result.status == ClaimStatus::Claimed)
+                .count()
         );
 
-        Ok(())
+        Ok(results)
     }
 }
 
 impl Recharge<'_> {
     pub fn recharge(&mut self, event_id: u128) -> Result<()> {
-        require!(self.event.canceled, ProgramError::EventIsNotCancelled);
+        require!(
+            self.event.load()?.canceled != 0,
+            ProgramError::EventIsNotCancelled
+        );
 
-        let user = &mut self.user;
+        let user = &mut self.user.load_mut()?;
+        let participant = &mut self.participant.load_mut()?;
 
-        user.stake += self.participant.deposited_amount;
+        user.stake += participant.deposited_amount;
 
         withdraw_sol(
             &self.event.to_account_info(),
             &self.user.to_account_info(),
-            self.participant.deposited_amount,
+            participant.deposited_amount,
         )?;
 
-        self.participant.is_claimed = true;
+        participant.is_claimed = 1;
+        self.portfolio.remove(event_id);
+
+        emit!(Recharged {
+            event_id,
+            user: participant.payer,
+            amount: participant.deposited_amount,
+        });
 
         msg!(
             "New recharge: user {} recharged {} SOL from {} event",
-            self.participant.payer,
-            lamports_to_sol(self.participant.deposited_amount),
+            self.sender.key(),
+            lamports_to_sol(participant.deposited_amount),
             uuid::Uuid::from_u128(event_id)
         );
 
@@ -417,15 +1001,17 @@ impl Recharge<'_> {
 }
 
 impl AppealResult<'_> {
-    pub fn appeal(&mut self, _event_id: u128) -> Result<()> {
-        require!(self.event.result.is_some(), ProgramError::EventIsNotOver);
-        require!(!self.participation.is_claimed, ProgramError::AlreadyClaimed);
-        require!(!self.participation.appealed, ProgramError::AlreadyAppealed);
+    pub fn appeal(&mut self, event_id: u128) -> Result<()> {
+        let event = &mut self.event.load_mut()?;
+        let participation = &mut self.participation.load_mut()?;
+
+        require!(event.resolved != 0, ProgramError::EventIsNotOver);
+        require!(participation.is_claimed == 0, ProgramError::AlreadyClaimed);
+        require!(participation.appealed == 0, ProgramError::AlreadyAppealed);
 
         let now = Clock::get()?.unix_timestamp;
         let appellation = &mut self.appellation;
-        let participation = &mut self.participation;
-        let event = &self.event;
+        let trust_lvl = self.user.load()?.trust_lvl;
 
         require!(
             now <= event.end_date + COMPLETION_DEADLINE + APPELLATION_DEADLINE,
@@ -433,37 +1019,63 @@ impl AppealResult<'_> {
         );
 
         appellation.disagree_count += 1;
-        appellation.disagree_trust_lvl += self.user.trust_lvl;
+        appellation.disagree_trust_lvl += trust_lvl;
         appellation.disagree_volume += participation.deposited_amount;
-        participation.appealed = true;
+        participation.appealed = 1;
+
+        if let Some(entry) = self.portfolio.entry_mut(event_id) {
+            entry.status = PositionStatus::Appealed;
+        }
 
         let disagree_ratio = appellation.disagree_count as f64 / event.participation_count as f64;
         let trust_ratio = appellation.disagree_trust_lvl as f64 / event.total_trust as f64;
-        let volume_ratio = appellation.disagree_volume as f64
-            / (event.total_amount - self.option.vault_balance) as f64;
-
-        // Closing event
-        if disagree_ratio < trust_ratio * volume_ratio {
-            self.user.locked_stake -= event.stake;
-            self.user.stake -= event.stake;
-
-            withdraw_sol(
-                &event.to_account_info(),
-                &self.contract_admin.to_account_info(),
-                event.stake,
+        let (_, option_vault) = option_pool(
+            event,
+            &self.option,
+            &self.options_table,
+            participation.option,
+        )?;
+        let volume_ratio =
+            appellation.disagree_volume as f64 / (event.total_amount - option_vault) as f64;
+
+        // Payouts only wait for a resolver once the appeals outweigh the result
+        let outweighed = disagree_ratio < trust_ratio * volume_ratio;
+
+        if outweighed {
+            event.disputed = 1;
+        }
+
+        // The stake is zeroed once slashed, so later appeals leave the pools untouched
+        let stake_slashed = event.stake != 0 && outweighed;
+
+        if stake_slashed {
+            slash_organizer_stake(
+                event,
+                &self.event.to_account_info(),
+                &mut *self.event_admin.load_mut()?,
+                &mut self.treasury,
             )?;
         }
 
+        emit!(AppealFiled {
+            event_id,
+            user: self.sender.key(),
+            disagree_count: appellation.disagree_count,
+            disagree_trust_lvl: appellation.disagree_trust_lvl,
+            disagree_volume: appellation.disagree_volume,
+            stake_slashed,
+        });
+
         Ok(())
     }
 }
 
 impl BurnTrust<'_> {
-    pub fn burn_trust(&mut self, _event_id: u128) -> Result<()> {
+    pub fn burn_trust(&mut self, event_id: u128) -> Result<()> {
         let now = Clock::get()?.unix_timestamp;
-        let event = &self.event;
-        let user = &mut self.user;
-        let participation = &mut self.participation;
+        let event = &self.event.load()?;
+        let user = &mut self.user.load_mut()?;
+        let participation = &mut self.participation.load_mut()?;
 
         require!(
             (event.start_date..=event.end_date).contains(&now),
@@ -489,12 +1101,71 @@ impl BurnTrust<'_> {
         user.trust_lvl -= burned_trust;
         participation.deposited_amount -= amount_to_claim;
 
+        if let Some(entry) = self.portfolio.entry_mut(event_id) {
+            entry.amount = participation.deposited_amount;
+        }
+
         withdraw_sol(
             &self.event.to_account_info(),
             &self.user.to_account_info(),
             amount_to_claim,
         )?;
 
+        emit!(TrustBurned {
+            event_id,
+            user: self.sender.key(),
+            burned_trust,
+            amount: amount_to_claim,
+            deposited_amount: participation.deposited_amount,
+        });
+        emit!(TrustChanged {
+            event_id,
+            user: self.sender.key(),
+            delta: -(burned_trust as i64),
+            trust_lvl: user.trust_lvl,
+        });
+
         Ok(())
     }
 }
+
+/// This method checks that a remaining account is the program address derived from the seeds and its stored bump
+fn require_pda(
+    account: &AccountInfo,
+    seeds: &[&[u8]],
+    bump: u8,
+    error: ProgramError,
+) -> Result<()> {
+    let bump = [bump];
+    let seeds = [seeds, &[bump.as_ref()]].concat();
+    let address = Pubkey::create_program_address(&seeds, &id()).map_err(|_| error)?;
+    require_keys_eq!(account.key(), address, error);
+
+    Ok(())
+}
+
+// -------------------------- Return data -------------------------- //
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ClaimStatus {
+    /// The position is paid out
+    Claimed,
+
+    /// The position was claimed or recharged before
+    AlreadyClaimed,
+
+    /// The event has no result, is frozen, disputed or still open to appeals
+    NotClaimable,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ClaimResult {
+    /// Event of the position
+    pub event_id: u128,
+
+    /// Outcome of the claim
+    pub status: ClaimStatus,
+
+    /// Amount paid out
+    pub amount: u64,
+}
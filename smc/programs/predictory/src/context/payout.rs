@@ -0,0 +1,59 @@
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+
+use crate::state::event::Event;
+
+/// Split of the event volume applied on settlement
+pub struct Settlement {
+    /// Organizer reward, including the co-organizer shares
+    pub org_reward: u64,
+
+    /// Flat platform fee moved to the treasury
+    pub platform_fee: u64,
+
+    /// Amount shared between the winning participants
+    pub available_for_winners: u64,
+}
+
+impl Settlement {
+    /// This method computes the settlement using the fee terms snapshotted on the event
+    pub fn for_event(event: &Event) -> Self {
+        Self::new(event.total_amount, event.platform_fee, event.org_reward)
+    }
+
+    pub fn new(total_amount: u64, platform_fee: u64, org_reward_pct: u64) -> Self {
+        let org_reward = (total_amount as u128 * org_reward_pct as u128 / 100) as u64;
+
+        // Fees are waived when the volume can not cover them
+        if total_amount < platform_fee + org_reward {
+            return Self {
+                org_reward: 0,
+                platform_fee: 0,
+                available_for_winners: total_amount,
+            };
+        }
+
+        Self {
+            org_reward,
+            platform_fee,
+            available_for_winners: total_amount - platform_fee - org_reward,
+        }
+    }
+
+    pub fn is_charged(&self) -> bool {
+        self.org_reward != 0 || self.platform_fee != 0
+    }
+
+    /// This method returns the payout of a winning deposit
+    pub fn winner_payout(&self, deposit: u64, winning_vault: u64) -> u64 {
+        if winning_vault == 0 {
+            return 0;
+        }
+
+        (deposit as u128 * self.available_for_winners as u128 / winning_vault as u128) as u64
+    }
+}
+
+/// This method returns the trust level earned for the settled amount
+pub fn trust_reward(amount: u64, multiplier: u64) -> u64 {
+    (amount as u128 * multiplier as u128 / LAMPORTS_PER_SOL as u128) as u64
+}
@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::ProgramError,
+    events::{PortfolioPageClosed, PortfolioPageCreated},
+    id,
+    state::{event::Event, portfolio::Portfolio},
+};
+
+// --------------------------- Context ----------------------------- //
+
+#[derive(Accounts)]
+#[instruction(
+    page: u16,
+)]
+pub struct CreatePortfolioPage<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    #[account(
+        init,
+        payer = sender,
+        owner = id(),
+        seeds = [b"portfolio".as_ref(), sender.key().as_ref(), &page.to_le_bytes()],
+        bump,
+        space = Portfolio::LEN
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    page: u16,
+)]
+pub struct ClosePortfolioPage<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    #[account(
+        mut,
+        close = sender,
+        seeds = [b"portfolio".as_ref(), sender.key().as_ref(), &page.to_le_bytes()],
+        constraint = portfolio.entries.is_empty() @ ProgramError::PortfolioPageNotEmpty,
+        bump,
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+}
+
+/// Events of the refreshed positions are passed as remaining accounts
+#[derive(Accounts)]
+#[instruction(
+    owner: Pubkey,
+    page: u16,
+)]
+pub struct RefreshPortfolio<'info> {
+    pub sender: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"portfolio".as_ref(), owner.as_ref(), &page.to_le_bytes()],
+        bump,
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+}
+
+// ------------------------ Implementation ------------------------- //
+
+impl CreatePortfolioPage<'_> {
+    pub fn create_portfolio_page(&mut self, page: u16) -> Result<()> {
+        let portfolio = &mut self.portfolio;
+
+        portfolio.owner = self.sender.key();
+        portfolio.page = page;
+        portfolio.version = Portfolio::VERSION;
+
+        emit!(PortfolioPageCreated {
+            owner: portfolio.owner,
+            page,
+        });
+
+        msg!("Portfolio page {} created for {}", page, portfolio.owner);
+
+        Ok(())
+    }
+}
+
+impl ClosePortfolioPage<'_> {
+    pub fn close_portfolio_page(&mut self, page: u16) -> Result<()> {
+        emit!(PortfolioPageClosed {
+            owner: self.sender.key(),
+            page,
+        });
+
+        msg!("Portfolio page {} closed for {}", page, self.sender.key());
+
+        Ok(())
+    }
+}
+
+impl<'info> RefreshPortfolio<'info> {
+    pub fn refresh_portfolio(
+        &mut self,
+        owner: Pubkey,
+        page: u16,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        require!(
+            !remaining_accounts.is_empty(),
+            ProgramError::InvalidPortfolioAccounts
+        );
+
+        for account in remaining_accounts {
+            let event = AccountLoader::<Event>::try_from(account)?;
+            let event = &event.load()?;
+
+            // Program accounts are only created at their PDAs, so matching fields pin the addresses
+            let entry = self
+                .portfolio
+                .entry_mut(event.id())
+                .ok_or(ProgramError::InvalidPortfolioAccounts)?;
+
+            entry.refresh(event);
+        }
+
+        msg!(
+            "Portfolio page {} of {} refreshed for {} events",
+            page,
+            owner,
+            remaining_accounts.len()
+        );
+
+        Ok(())
+    }
+}
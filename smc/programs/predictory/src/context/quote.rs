@@ -0,0 +1,307 @@
+use anchor_lang::{prelude::*, solana_program::native_token::LAMPORTS_PER_SOL};
+
+use crate::{
+    context::{option_pool, trust_reward, Settlement},
+    error::ProgramError,
+    id,
+    state::{
+        contract_state::State,
+        event::Event,
+        option::{EventOption, OptionsTable},
+        participation::Participation,
+    },
+};
+
+// --------------------------- Context ----------------------------- //
+
+#[derive(Accounts)]
+#[instruction(
+    event_id: u128,
+    option_ix: u8,
+)]
+pub struct QuoteVote<'info> {
+    #[account(
+        seeds = [b"state".as_ref()],
+        bump,
+    )]
+    pub state: Account<'info, State>,
+
+    #[account(
+        seeds = [b"event".as_ref(), &event_id.to_le_bytes()],
+        bump = event.load()?.bump,
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        seeds = [b"option".as_ref(), &event_id.to_le_bytes(), &[option_ix]],
+        bump = option.load()?.bump,
+    )]
+    pub option: Option<AccountLoader<'info, EventOption>>,
+
+    /// Option pools, passed instead of `option` when the event has an options table
+    #[account(
+        seeds = [b"options_table".as_ref(), &event_id.to_le_bytes()],
+        bump = options_table.load()?.bump,
+    )]
+    pub options_table: Option<AccountLoader<'info, OptionsTable>>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    event_id: u128,
+    user: Pubkey,
+)]
+pub struct QuoteClaim<'info> {
+    #[account(
+        seeds = [b"state".as_ref()],
+        bump,
+    )]
+    pub state: Account<'info, State>,
+
+    #[account(
+        seeds = [b"event".as_ref(), &event_id.to_le_bytes()],
+        bump = event.load()?.bump,
+    )]
+    pub event: AccountLoader<'info, Event>,
+
+    #[account(
+        seeds = [b"participation".as_ref(), &event_id.to_le_bytes(), user.as_ref()],
+        bump = participation.load()?.bump,
+    )]
+    pub participation: AccountLoader<'info, Participation>,
+
+    #[account(
+        seeds = [b"option".as_ref(), &event_id.to_le_bytes(), &[participation.load()?.option]],
+        bump = option.load()?.bump,
+    )]
+    pub option: Option<AccountLoader<'info, EventOption>>,
+
+    /// Option pools, passed instead of `option` when the event has an options table
+    #[account(
+        seeds = [b"options_table".as_ref(), &event_id.to_le_bytes()],
+        bump = options_table.load()?.bump,
+    )]
+    pub options_table: Option<AccountLoader<'info, OptionsTable>>,
+}
+
+/// Accounts of the option pools are passed as remaining accounts, every option in index order
+/// or the options table alone when the event has one
+#[derive(Accounts)]
+#[instruction(
+    event_id: u128,
+)]
+pub struct EventOdds<'info> {
+    #[account(
+        seeds = [b"event".as_ref(), &event_id.to_le_bytes()],
+        bump = event.load()?.bump,
+    )]
+    pub event: AccountLoader<'info, Event>,
+}
+
+// -------------------------- Return data -------------------------- //
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VoteQuote {
+    /// Payout if the option wins, including the deposit
+    pub payout: u64,
+
+    /// Trust level earned if the option wins
+    pub trust_reward_win: u64,
+
+    /// Trust level earned if the option loses
+    pub trust_reward_loss: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ClaimQuote {
+    /// Whether the event has a result
+    pub resolved: bool,
+
+    /// Whether the participation is on the winning option
+    pub won: bool,
+
+    /// Whether the participation is already claimed or recharged
+    pub claimed: bool,
+
+    /// Amount paid out on claim
+    pub payout: u64,
+
+    /// Trust level earned on claim
+    pub trust_reward: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct OptionOdds {
+    /// Option index
+    pub index: u8,
+
+    /// Option votes
+    pub votes: u64,
+
+    /// Option vault balance
+    pub vault_balance: u64,
+
+    /// Payout for one staked SOL if the option wins
+    pub payout_per_sol: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Odds {
+    /// Total amount of tokens in all pools
+    pub total_amount: u64,
+
+    /// Amount shared between the winners after fees
+    pub available_for_winners: u64,
+
+    /// Per-option odds
+    pub options: Vec<OptionOdds>,
+}
+
+// ------------------------ Implementation ------------------------- //
+
+impl QuoteVote<'_> {
+    pub fn quote_vote(&self, _event_id: u128, option_ix: u8, amount: u64) -> Result<VoteQuote> {
+        let event = &self.event.load()?;
+        let (_, option_vault) = option_pool(event, &self.option, &self.options_table, option_ix)?;
+
+        let settlement = Settlement::new(
+            event.total_amount + amount,
+            event.platform_fee,
+            event.org_reward,
+        );
+
+        let payout = settlement.winner_payout(amount, option_vault + amount);
+
+        Ok(VoteQuote {
+            payout,
+            trust_reward_win: trust_reward(payout, self.state.multiplier),
+            trust_reward_loss: trust_reward(amount, self.state.multiplier),
+        })
+    }
+}
+
+impl QuoteClaim<'_> {
+    pub fn quote_claim(&self, _event_id: u128, _user: Pubkey) -> Result<ClaimQuote> {
+        let event = &self.event.load()?;
+        let participation = &self.participation.load()?;
+
+        let Some(result) = event.result() else {
+            return Ok(ClaimQuote {
+                resolved: false,
+                won: false,
+                claimed: participation.is_claimed != 0,
+                payout: 0,
+                trust_reward: 0,
+            });
+        };
+
+        let won = result == participation.option;
+        let payout = if won {
+            let (_, option_vault) = option_pool(
+                event,
+                &self.option,
+                &self.options_table,
+                participation.option,
+            )?;
+
+            Settlement::for_event(event).winner_payout(participation.deposited_amount, option_vault)
+        } else {
+            0
+        };
+        let rewarded_amount = if won {
+            payout
+        } else {
+            participation.deposited_amount
+        };
+
+        Ok(ClaimQuote {
+            resolved: true,
+            won,
+            claimed: participation.is_claimed != 0,
+            payout,
+            trust_reward: trust_reward(rewarded_amount, self.state.multiplier),
+        })
+    }
+}
+
+impl<'info> EventOdds<'info> {
+    pub fn event_odds(
+        &self,
+        event_id: u128,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<Odds> {
+        let event = &self.event.load()?;
+        let pools = if event.options_table != 0 {
+            option_table_pools(event, event_id, remaining_accounts)?
+        } else {
+            option_account_pools(event, event_id, remaining_accounts)?
+        };
+
+        let settlement = Settlement::for_event(event);
+        let options = pools
+            .into_iter()
+            .enumerate()
+            .map(|(index, (votes, vault_balance))| OptionOdds {
+                index: index as u8,
+                votes,
+                vault_balance,
+                payout_per_sol: settlement.winner_payout(LAMPORTS_PER_SOL, vault_balance),
+            })
+            .collect();
+
+        Ok(Odds {
+            total_amount: event.total_amount,
+            available_for_winners: settlement.available_for_winners,
+            options,
+        })
+    }
+}
+
+/// This method reads `(votes, vault_balance)` of every option from the option accounts
+fn option_account_pools(
+    event: &Event,
+    event_id: u128,
+    remaining_accounts: &[AccountInfo],
+) -> Result<Vec<(u64, u64)>> {
+    require!(
+        remaining_accounts.len() == event.option_count as usize,
+        ProgramError::InvalidIndex
+    );
+
+    let mut pools = Vec::with_capacity(remaining_accounts.len());
+
+    for (index, account) in remaining_accounts.iter().enumerate() {
+        let (address, _) = Pubkey::find_program_address(
+            &[b"option".as_ref(), &event_id.to_le_bytes(), &[index as u8]],
+            &id(),
+        );
+        require_keys_eq!(account.key(), address, ProgramError::InvalidIndex);
+
+        let option = EventOption::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+
+        pools.push((option.votes, option.vault_balance));
+    }
+
+    Ok(pools)
+}
+
+/// This method reads `(votes, vault_balance)` of every option from the event options table
+fn option_table_pools(
+    event: &Event,
+    event_id: u128,
+    remaining_accounts: &[AccountInfo],
+) -> Result<Vec<(u64, u64)>> {
+    let [account] = remaining_accounts else {
+        return err!(ProgramError::InvalidOptionPool);
+    };
+
+    let (address, _) =
+        Pubkey::find_program_address(&[b"options_table".as_ref(), &event_id.to_le_bytes()], &id());
+    require_keys_eq!(account.key(), address, ProgramError::InvalidOptionPool);
+
+    let table = OptionsTable::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+
+    Ok((0..event.option_count as usize)
+        .map(|index| (table.votes[index], table.vault_balances[index]))
+        .collect())
+}
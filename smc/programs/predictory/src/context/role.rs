@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::ProgramError,
+    events::{RoleGranted, RoleRevoked},
+    id,
+    state::{
+        contract_state::State,
+        role::{Role, RoleMember},
+    },
+};
+
+// --------------------------- Context ----------------------------- //
+
+#[derive(Accounts)]
+#[instruction(
+    role: Role,
+    member: Pubkey,
+)]
+pub struct GrantRole<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"state".as_ref()],
+        constraint = state.authority == authority.key() @ ProgramError::AuthorityMismatch,
+        bump,
+    )]
+    pub state: Account<'info, State>,
+
+    #[account(
+        init,
+        payer = authority,
+        owner = id(),
+        seeds = [b"role".as_ref(), &[role as u8], member.as_ref()],
+        bump,
+        space = RoleMember::LEN
+    )]
+    pub role_member: Account<'info, RoleMember>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    role: Role,
+    member: Pubkey,
+)]
+pub struct RevokeRole<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"state".as_ref()],
+        constraint = state.authority == authority.key() @ ProgramError::AuthorityMismatch,
+        bump,
+    )]
+    pub state: Account<'info, State>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"role".as_ref(), &[role as u8], member.as_ref()],
+        bump,
+    )]
+    pub role_member: Account<'info, RoleMember>,
+}
+
+// ------------------------ Implementation ------------------------- //
+
+impl GrantRole<'_> {
+    pub fn grant_role(&mut self, role: Role, member: Pubkey) -> Result<()> {
+        let role_member = &mut self.role_member;
+
+        role_member.role = role;
+        role_member.member = member;
+        role_member.version = RoleMember::VERSION;
+
+        emit!(RoleGranted { role, member });
+
+        msg!("Role granted to {member}");
+
+        Ok(())
+    }
+}
+
+impl RevokeRole<'_> {
+    pub fn revoke_role(&mut self, role: Role, member: Pubkey) -> Result<()> {
+        emit!(RoleRevoked { role, member });
+
+        msg!("Role revoked from {member}");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,156 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    context::{withdraw_sol, BPS_DENOMINATOR},
+    error::ProgramError,
+    events::{TreasuryDistributed, TreasurySplitUpdated},
+    id,
+    state::{
+        contract_state::State,
+        role::{Role, RoleMember},
+        treasury::{Treasury, TreasuryRecipient},
+        MAX_TREASURY_RECIPIENTS,
+    },
+};
+
+// --------------------------- Context ----------------------------- //
+
+/// Creates the treasury of deployments initialized before it existed
+#[derive(Accounts)]
+pub struct InitializeTreasury<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"state".as_ref()],
+        constraint = state.authority == authority.key() @ ProgramError::AuthorityMismatch,
+        bump,
+    )]
+    pub state: Account<'info, State>,
+
+    #[account(
+        init,
+        payer = authority,
+        owner = id(),
+        seeds = [b"treasury".as_ref()],
+        bump,
+        space = Treasury::LEN
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetTreasurySplit<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::FeeManager as u8], authority.key().as_ref()],
+        bump,
+    )]
+    pub role: Account<'info, RoleMember>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury".as_ref()],
+        bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+}
+
+#[derive(Accounts)]
+pub struct DistributeTreasury<'info> {
+    pub sender: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury".as_ref()],
+        constraint = !treasury.recipients.is_empty() @ ProgramError::InvalidTreasurySplit,
+        bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+}
+
+// ------------------------ Implementation ------------------------- //
+
+impl InitializeTreasury<'_> {
+    pub fn initialize_treasury(&mut self) -> Result<()> {
+        self.treasury.version = Treasury::VERSION;
+
+        msg!("Treasury initialized");
+
+        Ok(())
+    }
+}
+
+impl SetTreasurySplit<'_> {
+    pub fn set_treasury_split(&mut self, recipients: Vec<TreasuryRecipient>) -> Result<()> {
+        let total_bps: u64 = recipients.iter().map(|r| r.share_bps as u64).sum();
+
+        require!(
+            !recipients.is_empty()
+                && recipients.len() <= MAX_TREASURY_RECIPIENTS
+                && total_bps == BPS_DENOMINATOR,
+            ProgramError::InvalidTreasurySplit
+        );
+
+        emit!(TreasurySplitUpdated {
+            recipients: recipients.iter().map(|r| r.wallet).collect(),
+            shares_bps: recipients.iter().map(|r| r.share_bps).collect(),
+        });
+
+        self.treasury.recipients = recipients;
+
+        msg!(
+            "Treasury split updated: {} recipients",
+            self.treasury.recipients.len()
+        );
+
+        Ok(())
+    }
+}
+
+impl<'info> DistributeTreasury<'info> {
+    pub fn distribute_treasury(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let treasury_acc = self.treasury.to_account_info();
+        let treasury = &mut self.treasury;
+
+        require!(
+            remaining_accounts.len() == treasury.recipients.len(),
+            ProgramError::RecipientMismatch
+        );
+
+        let rent = Rent::get()?.minimum_balance(treasury_acc.data_len());
+        let available = treasury_acc.lamports().saturating_sub(rent);
+
+        require!(available > 0, ProgramError::NothingToDistribute);
+
+        let mut distributed = 0;
+
+        for (recipient, account) in treasury.recipients.iter().zip(remaining_accounts) {
+            require_keys_eq!(
+                account.key(),
+                recipient.wallet,
+                ProgramError::RecipientMismatch
+            );
+
+            let amount =
+                (available as u128 * recipient.share_bps as u128 / BPS_DENOMINATOR as u128) as u64;
+
+            withdraw_sol(&treasury_acc, account, amount)?;
+            distributed += amount;
+        }
+
+        treasury.total_distributed += distributed;
+
+        emit!(TreasuryDistributed {
+            amount: distributed,
+            total_distributed: treasury.total_distributed,
+        });
+
+        msg!("Treasury distributed {distributed} lamports");
+
+        Ok(())
+    }
+}
@@ -3,8 +3,9 @@ use anchor_lang::prelude::*;
 use crate::{
     context::{transfer_sol, withdraw_sol, INITIAL_LVL},
     error::ProgramError,
+    events::{StakeDeposited, StakeWithdrawn, UserCreated},
     id,
-    state::user::User,
+    state::{contract_state::State, user::User},
 };
 
 // --------------------------- Context ----------------------------- //
@@ -22,7 +23,7 @@ pub struct CreateUser<'info> {
         bump,
         space = User::LEN
     )]
-    pub user: Account<'info, User>,
+    pub user: AccountLoader<'info, User>,
 
     pub system_program: Program<'info, System>,
 }
@@ -35,9 +36,9 @@ pub struct TransferStake<'info> {
     #[account(
         mut,
         seeds = [b"user".as_ref(), sender.key().as_ref()],
-        bump,
+        bump = user.load()?.bump,
     )]
-    pub user: Account<'info, User>,
+    pub user: AccountLoader<'info, User>,
 
     pub system_program: Program<'info, System>,
 }
@@ -47,12 +48,19 @@ pub struct WithdrawStake<'info> {
     #[account(mut)]
     pub sender: Signer<'info>,
 
+    #[account(
+        seeds = [b"state".as_ref()],
+        constraint = !state.is_paused(State::PAUSE_WITHDRAWALS) @ ProgramError::WithdrawalsPaused,
+        bump,
+    )]
+    pub state: Account<'info, State>,
+
     #[account(
         mut,
         seeds = [b"user".as_ref(), sender.key().as_ref()],
-        bump,
+        bump = user.load()?.bump,
     )]
-    pub user: Account<'info, User>,
+    pub user: AccountLoader<'info, User>,
 
     pub system_program: Program<'info, System>,
 }
@@ -60,15 +68,22 @@ pub struct WithdrawStake<'info> {
 // ------------------------ Implementation ------------------------- //
 
 impl CreateUser<'_> {
-    pub fn create_user(&mut self, name: [u8; 32]) -> Result<()> {
-        let user = &mut self.user;
+    pub fn create_user(&mut self, name: [u8; 32], bump: u8) -> Result<()> {
+        let user = &mut self.user.load_init()?;
 
         user.name = name;
         user.payer = self.sender.key();
         user.trust_lvl = INITIAL_LVL;
         user.version = User::VERSION;
+        user.bump = bump;
+
+        emit!(UserCreated {
+            user: user.payer,
+            name: user.name,
+            trust_lvl: user.trust_lvl,
+        });
 
-        msg!("New user created {}", user.payer,);
+        msg!("New user created {}", self.sender.key());
 
         Ok(())
     }
@@ -83,10 +98,16 @@ impl TransferStake<'_> {
             self.system_program.to_account_info(),
         )?;
 
-        let user = &mut self.user;
+        let user = &mut self.user.load_mut()?;
         user.stake += stake;
 
-        msg!("New user created {}", user.payer,);
+        emit!(StakeDeposited {
+            user: user.payer,
+            amount: stake,
+            stake: user.stake,
+        });
+
+        msg!("User stake deposited - {} for {}", stake, self.sender.key());
 
         Ok(())
     }
@@ -94,7 +115,7 @@ impl TransferStake<'_> {
 
 impl WithdrawStake<'_> {
     pub fn withdraw(&mut self, withdraw_amount: Option<u64>) -> Result<()> {
-        let user = &mut self.user;
+        let user = &mut self.user.load_mut()?;
 
         let amount = if let Some(amount) = withdraw_amount {
             require!(amount < user.stake, ProgramError::InsufficientFunds);
@@ -104,19 +125,25 @@ impl WithdrawStake<'_> {
         };
 
         if amount == 0 {
-            msg!("User has no available stake - {}", self.user.payer,);
+            msg!("User has no available stake - {}", self.sender.key());
             return Ok(());
         }
 
         withdraw_sol(
-            &user.to_account_info(),
+            &self.user.to_account_info(),
             &self.sender.to_account_info(),
             amount,
         )?;
 
         user.stake -= amount;
 
-        msg!("User stake withdrawn - {amount} for {}", self.user.payer,);
+        emit!(StakeWithdrawn {
+            user: user.payer,
+            amount,
+            stake: user.stake,
+        });
+
+        msg!("User stake withdrawn - {amount} for {}", self.sender.key());
 
         Ok(())
     }
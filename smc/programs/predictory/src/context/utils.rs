@@ -1,5 +1,25 @@
 use anchor_lang::{prelude::*, system_program};
 
+use crate::{
+    context::{
+        MAX_EVENT_PRICE, MAX_MULTIPLIER, MAX_ORG_REWARD, MAX_PLATFORM_FEE, OPTION_ACCOUNTS_LEN,
+        TABLE_OPTION_ACCOUNTS_LEN,
+    },
+    error::ProgramError,
+    events::{AccountMigrated, TreasuryCollected},
+    id,
+    state::{
+        contract_state::State,
+        event::Event,
+        legacy::Versioned,
+        option::{EventOption, OptionsTable},
+        organizer::EventOrganizer,
+        param::ParamKind,
+        treasury::Treasury,
+        DISCRIMINATOR_LENGTH,
+    },
+};
+
 /// This method transfers sol from user to program account
 pub fn transfer_sol<'info>(
     from: AccountInfo<'info>,
@@ -23,3 +43,309 @@ pub fn withdraw_sol<'info>(
 
     Ok(())
 }
+
+/// This method moves protocol revenue from program account into the treasury
+pub fn collect_to_treasury<'info>(
+    from: &AccountInfo<'info>,
+    treasury: &mut Account<'info, Treasury>,
+    amount: u64,
+) -> Result<()> {
+    withdraw_sol(from, &treasury.to_account_info(), amount)?;
+    treasury.total_collected += amount;
+
+    emit!(TreasuryCollected {
+        amount,
+        total_collected: treasury.total_collected,
+    });
+
+    Ok(())
+}
+
+/// This method creates program owned PDA account funded by the payer.
+/// Lamports sent to the address beforehand can't block the creation:
+/// the rent shortfall is topped up and the account allocated and assigned directly
+pub fn create_pda_account<'info>(
+    payer: AccountInfo<'info>,
+    account: AccountInfo<'info>,
+    space: usize,
+    signer_seeds: &[&[u8]],
+    system_program: AccountInfo<'info>,
+) -> Result<()> {
+    let lamports = Rent::get()?.minimum_balance(space);
+    let signer = &[signer_seeds];
+    let balance = account.lamports();
+
+    if balance == 0 {
+        let cpi_ctx = CpiContext::new_with_signer(
+            system_program,
+            system_program::CreateAccount {
+                from: payer,
+                to: account,
+            },
+            signer,
+        );
+
+        return system_program::create_account(cpi_ctx, lamports, space as u64, &id());
+    }
+
+    if balance < lamports {
+        transfer_sol(
+            payer,
+            account.clone(),
+            lamports - balance,
+            system_program.clone(),
+        )?;
+    }
+
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Allocate {
+                account_to_allocate: account.clone(),
+            },
+            signer,
+        ),
+        space as u64,
+    )?;
+
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program,
+            system_program::Assign {
+                account_to_assign: account,
+            },
+            signer,
+        ),
+        &id(),
+    )
+}
+
+/// This method closes program account and returns its rent to the receiver
+pub fn close_account<'info>(
+    account: &AccountInfo<'info>,
+    receiver: &AccountInfo<'info>,
+) -> Result<()> {
+    withdraw_sol(account, receiver, account.lamports())?;
+
+    account.assign(&system_program::ID);
+    account.realloc(0, false)?;
+
+    Ok(())
+}
+
+/// This method decodes a program account written with any supported layout version
+pub fn load_versioned<T: Versioned>(account: &AccountInfo) -> Result<T> {
+    require_keys_eq!(*account.owner, id(), ErrorCode::AccountOwnedByWrongProgram);
+
+    let data = account.try_borrow_data()?;
+
+    require!(
+        data.len() > DISCRIMINATOR_LENGTH && data.starts_with(T::DISCRIMINATOR),
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+
+    match data[DISCRIMINATOR_LENGTH] {
+        version if version == T::CURRENT_VERSION => T::try_deserialize(&mut &data[..]),
+        version => T::from_legacy(version, &mut &data[DISCRIMINATOR_LENGTH..]),
+    }
+}
+
+/// This method returns the layout version of a program account that still has to be migrated
+pub fn legacy_version<T: Versioned>(account: &AccountInfo) -> Result<u8> {
+    let data = account.try_borrow_data()?;
+    let version = *data
+        .get(DISCRIMINATOR_LENGTH)
+        .ok_or(ErrorCode::AccountDidNotDeserialize)?;
+
+    require!(
+        version < T::CURRENT_VERSION,
+        ProgramError::AccountAlreadyMigrated
+    );
+
+    Ok(version)
+}
+
+/// This method resizes the account to the current layout and writes the upgraded data
+pub fn store_migrated<'info, T: Versioned>(
+    account: &AccountInfo<'info>,
+    value: &T,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(T::SPACE);
+
+    if account.lamports() < rent {
+        transfer_sol(
+            payer.clone(),
+            account.clone(),
+            rent - account.lamports(),
+            system_program.clone(),
+        )?;
+    }
+
+    // Zero-copy layouts are read with their exact size, so accounts may also shrink
+    if account.data_len() != T::SPACE {
+        account.realloc(T::SPACE, true)?;
+    }
+
+    let mut data = account.try_borrow_mut_data()?;
+    value.try_serialize(&mut &mut data[..])
+}
+
+/// This method upgrades a program account from a legacy layout to the current one
+pub fn migrate_account<'info, T: Versioned>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<u8> {
+    let version = legacy_version::<T>(account)?;
+    let value = load_versioned::<T>(account)?;
+
+    store_migrated(account, &value, payer, system_program)?;
+
+    emit!(AccountMigrated {
+        account: account.key(),
+        from_version: version,
+        to_version: T::CURRENT_VERSION,
+    });
+
+    Ok(version)
+}
+
+/// This method checks that the signer is the event authority or a co-organizer holding the permission
+pub fn is_event_organizer(
+    event: &Event,
+    organizer: &Option<Account<EventOrganizer>>,
+    signer: &Pubkey,
+    permission: u8,
+) -> bool {
+    if event.authority == *signer {
+        return true;
+    }
+
+    organizer
+        .as_ref()
+        .is_some_and(|organizer| organizer.member == *signer && organizer.can(permission))
+}
+
+/// This method returns how many accounts are passed per option when options are created or deleted in bulk
+pub fn option_accounts_len(event: &Event) -> usize {
+    if event.options_table != 0 {
+        TABLE_OPTION_ACCOUNTS_LEN
+    } else {
+        OPTION_ACCOUNTS_LEN
+    }
+}
+
+/// This method checks that an option account is passed exactly when the event has no options table
+pub fn require_option_account(event: &Event, option: bool) -> Result<()> {
+    require!(
+        option != (event.options_table != 0),
+        ProgramError::InvalidOptionPool
+    );
+
+    Ok(())
+}
+
+/// This method reads `(votes, vault_balance)` of an option from its account or from the event options table
+pub fn option_pool(
+    event: &Event,
+    option: &Option<AccountLoader<EventOption>>,
+    options_table: &Option<AccountLoader<OptionsTable>>,
+    index: u8,
+) -> Result<(u64, u64)> {
+    match (option, options_table) {
+        (Some(option), None) if event.options_table == 0 => {
+            let option = option.load()?;
+
+            Ok((option.votes, option.vault_balance))
+        }
+        (None, Some(options_table)) if event.options_table != 0 => {
+            require!(index < event.option_count, ProgramError::InvalidIndex);
+
+            let table = options_table.load()?;
+            let index = index as usize;
+
+            Ok((table.votes[index], table.vault_balances[index]))
+        }
+        _ => err!(ProgramError::InvalidOptionPool),
+    }
+}
+
+/// This method adds a vote of `amount` to an option pool and returns its updated `(votes, vault_balance)`
+pub fn deposit_to_option(
+    event: &Event,
+    option: &Option<AccountLoader<EventOption>>,
+    options_table: &Option<AccountLoader<OptionsTable>>,
+    index: u8,
+    amount: u64,
+) -> Result<(u64, u64)> {
+    match (option, options_table) {
+        (Some(option), None) if event.options_table == 0 => {
+            let option = &mut option.load_mut()?;
+
+            option.vault_balance += amount;
+            option.votes += 1;
+
+            Ok((option.votes, option.vault_balance))
+        }
+        (None, Some(options_table)) if event.options_table != 0 => {
+            require!(index < event.option_count, ProgramError::InvalidIndex);
+
+            let table = &mut options_table.load_mut()?;
+            let index = index as usize;
+
+            table.vault_balances[index] += amount;
+            table.votes[index] += 1;
+
+            Ok((table.votes[index], table.vault_balances[index]))
+        }
+        _ => err!(ProgramError::InvalidOptionPool),
+    }
+}
+
+/// This method checks that event dates and participation deadline are consistent
+pub fn validate_event_dates(
+    start_date: i64,
+    end_date: i64,
+    participation_deadline: Option<i64>,
+) -> Result<()> {
+    require!(start_date < end_date, ProgramError::InvalidEndDate);
+
+    if let Some(deadline) = participation_deadline {
+        require!(
+            (start_date..=end_date).contains(&deadline),
+            ProgramError::InvalidEndDate
+        );
+    }
+
+    Ok(())
+}
+
+/// This method checks that the protocol parameter value is within hard bounds
+pub fn validate_param(kind: ParamKind, value: u64) -> Result<()> {
+    let valid = match kind {
+        ParamKind::Multiplier => (1..=MAX_MULTIPLIER).contains(&value),
+        ParamKind::EventPrice => value <= MAX_EVENT_PRICE,
+        ParamKind::PlatformFee => value <= MAX_PLATFORM_FEE,
+        ParamKind::OrgReward => value <= MAX_ORG_REWARD,
+    };
+
+    require!(valid, ProgramError::ParamOutOfBounds);
+
+    Ok(())
+}
+
+/// This method validates and writes the protocol parameter into the contract state
+pub fn apply_param(state: &mut State, kind: ParamKind, value: u64) -> Result<()> {
+    validate_param(kind, value)?;
+
+    match kind {
+        ParamKind::Multiplier => state.multiplier = value,
+        ParamKind::EventPrice => state.event_price = value,
+        ParamKind::PlatformFee => state.platform_fee = value,
+        ParamKind::OrgReward => state.org_reward = value,
+    }
+
+    Ok(())
+}
@@ -56,4 +56,104 @@ pub enum ProgramError {
     NotEnoughTrust,
     #[msg("Not funds to withdraw")]
     InsufficientFunds,
+    #[msg("Portfolio page is full")]
+    PortfolioPageFull,
+    #[msg("Portfolio page still has open positions")]
+    PortfolioPageNotEmpty,
+    #[msg("Invalid organizer permissions")]
+    InvalidPermissions,
+    #[msg("Organizer reward shares exceed 100%")]
+    InvalidRewardShare,
+    #[msg("Event is already resolved")]
+    EventAlreadyResolved,
+    #[msg("Organizer reward is not released yet")]
+    OrganizerRewardUnavailable,
+    #[msg("Event result is disputed")]
+    EventDisputed,
+    #[msg("Event is not published")]
+    EventNotPublished,
+    #[msg("Event is already published")]
+    EventAlreadyPublished,
+    #[msg("Event needs at least two options")]
+    NotEnoughOptions,
+    #[msg("Event already has votes")]
+    EventHasVotes,
+    #[msg("Event creation is paused")]
+    EventCreationPaused,
+    #[msg("Voting is paused")]
+    VotingPaused,
+    #[msg("Claims are paused")]
+    ClaimsPaused,
+    #[msg("Withdrawals are paused")]
+    WithdrawalsPaused,
+    #[msg("Appeals are paused")]
+    AppealsPaused,
+    #[msg("Event is frozen")]
+    EventFrozen,
+    #[msg("Invalid pause flags")]
+    InvalidPauseFlags,
+    #[msg("Authority transfer timelock has not passed")]
+    AuthorityTimelocked,
+    #[msg("Invalid timelock")]
+    InvalidTimelock,
+    #[msg("Parameter value is out of bounds")]
+    ParamOutOfBounds,
+    #[msg("Parameter change timelock has not passed")]
+    ParamChangeTimelocked,
+    #[msg("No pending parameter change")]
+    NoPendingChange,
+    #[msg("Event is not disputed")]
+    EventNotDisputed,
+    #[msg("Trust level is too low")]
+    TrustTooLow,
+    #[msg("Proposal voting is closed")]
+    ProposalVotingClosed,
+    #[msg("Proposal voting is still active")]
+    ProposalVotingActive,
+    #[msg("Proposal is timelocked")]
+    ProposalTimelocked,
+    #[msg("Proposal did not pass")]
+    ProposalRejected,
+    #[msg("Proposal is already executed")]
+    ProposalAlreadyExecuted,
+    #[msg("Trust level was raised after the proposal was created")]
+    TrustRaisedAfterProposal,
+    #[msg("Invalid treasury split")]
+    InvalidTreasurySplit,
+    #[msg("Treasury recipient mismatch")]
+    RecipientMismatch,
+    #[msg("Nothing to distribute")]
+    NothingToDistribute,
+    #[msg("Account is already migrated")]
+    AccountAlreadyMigrated,
+    #[msg("Unsupported account version")]
+    UnsupportedAccountVersion,
+    #[msg("Contract state must be migrated first")]
+    StateNotMigrated,
+    #[msg("Invalid settlement accounts")]
+    InvalidSettlementAccounts,
+    #[msg("Invalid claim accounts")]
+    InvalidClaimAccounts,
+    #[msg("Option accounts do not match the event option storage")]
+    InvalidOptionPool,
+    #[msg("Invalid callback accounts")]
+    InvalidCallbackAccounts,
+    #[msg("Event callback must be removed first")]
+    CallbackRegistered,
+    #[msg("Event outcome is already final")]
+    EventFinalized,
+    #[msg("Appellation window is still open")]
+    AppellationWindowOpen,
+    #[msg("Event still has co-organizers")]
+    EventHasOrganizers,
+    #[msg("Co-organizer accounts don't belong to the event")]
+    InvalidOrganizerAccounts,
+    #[msg("Callback program already has the event result")]
+    CallbackNotified,
+    #[msg("Option description is longer than 256 bytes")]
+    DescriptionTooLong,
+    #[msg("Events are not on the portfolio page")]
+    InvalidPortfolioAccounts,
+    #[msg("Resolvers can still settle the dispute")]
+    DisputeWindowOpen,
 }
@@ -0,0 +1,399 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{attestation::ResolutionStatus, param::ParamKind, role::Role};
+
+// ------------------------- Contract State ------------------------ //
+
+#[event]
+pub struct ContractInitialized {
+    pub authority: Pubkey,
+    pub multiplier: u64,
+    pub event_price: u64,
+    pub platform_fee: u64,
+    pub org_reward: u64,
+}
+
+#[event]
+pub struct AuthorityTransferProposed {
+    pub authority: Pubkey,
+    pub pending_authority: Option<Pubkey>,
+    pub eta: i64,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub previous_authority: Pubkey,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTimelockSet {
+    pub timelock: i64,
+}
+
+#[event]
+pub struct PauseFlagsSet {
+    pub guardian: Pubkey,
+    pub flags: u8,
+}
+
+#[event]
+pub struct RoleGranted {
+    pub role: Role,
+    pub member: Pubkey,
+}
+
+#[event]
+pub struct RoleRevoked {
+    pub role: Role,
+    pub member: Pubkey,
+}
+
+#[event]
+pub struct ParamChangeQueued {
+    pub kind: ParamKind,
+    pub value: u64,
+    pub effective_at: i64,
+}
+
+#[event]
+pub struct ParamChangeApplied {
+    pub kind: ParamKind,
+    pub value: u64,
+}
+
+#[event]
+pub struct ParamChangeCancelled {
+    pub kind: ParamKind,
+}
+
+#[event]
+pub struct ProposalCreated {
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub kind: ParamKind,
+    pub value: u64,
+    pub voting_end_date: i64,
+}
+
+#[event]
+pub struct ProposalVoted {
+    pub proposal_id: u64,
+    pub voter: Pubkey,
+    pub weight: u64,
+    pub support: bool,
+    pub votes_for: u64,
+    pub votes_against: u64,
+}
+
+#[event]
+pub struct ProposalExecuted {
+    pub proposal_id: u64,
+    pub kind: ParamKind,
+    pub value: u64,
+}
+
+#[event]
+pub struct TreasuryCollected {
+    pub amount: u64,
+    pub total_collected: u64,
+}
+
+#[event]
+pub struct TreasurySplitUpdated {
+    pub recipients: Vec<Pubkey>,
+    pub shares_bps: Vec<u16>,
+}
+
+#[event]
+pub struct TreasuryDistributed {
+    pub amount: u64,
+    pub total_distributed: u64,
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+// ----------------------------- User ------------------------------ //
+
+#[event]
+pub struct UserCreated {
+    pub user: Pubkey,
+    pub name: [u8; 32],
+    pub trust_lvl: u64,
+}
+
+#[event]
+pub struct StakeDeposited {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub stake: u64,
+}
+
+#[event]
+pub struct StakeWithdrawn {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub stake: u64,
+}
+
+#[event]
+pub struct PortfolioPageCreated {
+    pub owner: Pubkey,
+    pub page: u16,
+}
+
+#[event]
+pub struct PortfolioPageClosed {
+    pub owner: Pubkey,
+    pub page: u16,
+}
+
+// ----------------------------- Event ----------------------------- //
+
+#[event]
+pub struct EventCreated {
+    pub event_id: u128,
+    pub authority: Pubkey,
+    pub is_private: bool,
+    pub name: [u8; 32],
+    pub description: [u8; 256],
+    pub start_date: i64,
+    pub end_date: i64,
+    pub participation_deadline: Option<i64>,
+}
+
+#[event]
+pub struct EventPublished {
+    pub event_id: u128,
+    pub authority: Pubkey,
+    pub stake: u64,
+    pub platform_fee: u64,
+    pub org_reward: u64,
+}
+
+#[event]
+pub struct EventMetaUpdated {
+    pub event_id: u128,
+    pub name: [u8; 32],
+    pub description: [u8; 256],
+}
+
+#[event]
+pub struct EventDatesUpdated {
+    pub event_id: u128,
+    pub start_date: i64,
+    pub end_date: i64,
+    pub participation_deadline: Option<i64>,
+}
+
+#[event]
+pub struct EventDeleted {
+    pub event_id: u128,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct EventCanceled {
+    pub event_id: u128,
+    pub canceled_by: Pubkey,
+    pub stake: u64,
+    /// Whether the stake went back to the organizer rather than the treasury
+    pub stake_refunded: bool,
+}
+
+#[event]
+pub struct EventCompleted {
+    pub event_id: u128,
+    pub resolver: Pubkey,
+    pub result: u8,
+}
+
+#[event]
+pub struct DisputeResolved {
+    pub event_id: u128,
+    pub resolver: Pubkey,
+    pub result: u8,
+}
+
+#[event]
+pub struct DisputeExpired {
+    pub event_id: u128,
+    pub result: u8,
+}
+
+#[event]
+pub struct EventAuthorityProposed {
+    pub event_id: u128,
+    pub pending_authority: Option<Pubkey>,
+}
+
+#[event]
+pub struct EventAuthorityTransferred {
+    pub event_id: u128,
+    pub previous_authority: Pubkey,
+    pub authority: Pubkey,
+    pub stake: u64,
+}
+
+#[event]
+pub struct EventCallbackSet {
+    pub event_id: u128,
+    /// `None` when the callback is removed
+    pub program: Option<Pubkey>,
+}
+
+#[event]
+pub struct CallbackNotified {
+    pub event_id: u128,
+    pub program: Pubkey,
+    pub result: u8,
+}
+
+#[event]
+pub struct EventFinalized {
+    pub event_id: u128,
+    pub status: ResolutionStatus,
+    pub result: u8,
+    pub resolver: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct EventFrozen {
+    pub event_id: u128,
+    pub frozen: bool,
+}
+
+#[event]
+pub struct EventModerated {
+    pub event_id: u128,
+    pub moderator: Pubkey,
+    pub hidden: bool,
+}
+
+// ---------------------------- Options ---------------------------- //
+
+#[event]
+pub struct OptionCreated {
+    pub event_id: u128,
+    pub index: u8,
+    pub description: [u8; 256],
+}
+
+#[event]
+pub struct OptionUpdated {
+    pub event_id: u128,
+    pub index: u8,
+    pub description: [u8; 256],
+}
+
+#[event]
+pub struct OptionsTableCreated {
+    pub event_id: u128,
+}
+
+#[event]
+pub struct OptionRemoved {
+    pub event_id: u128,
+    pub index: u8,
+    /// Index of the option that took the freed slot, if any
+    pub moved_from: Option<u8>,
+}
+
+// --------------------------- Organizers -------------------------- //
+
+#[event]
+pub struct OrganizerSet {
+    pub event_id: u128,
+    pub member: Pubkey,
+    pub permissions: u8,
+    pub reward_share: u8,
+    pub organizer_shares: u8,
+}
+
+#[event]
+pub struct OrganizerRemoved {
+    pub event_id: u128,
+    pub member: Pubkey,
+    pub organizer_shares: u8,
+}
+
+#[event]
+pub struct OrganizerRewardClaimed {
+    pub event_id: u128,
+    pub member: Pubkey,
+    pub amount: u64,
+}
+
+// ------------------------- Participation ------------------------- //
+
+#[event]
+pub struct VoteCast {
+    pub event_id: u128,
+    pub user: Pubkey,
+    pub option: u8,
+    pub amount: u64,
+    pub trust_lvl: u64,
+    pub option_votes: u64,
+    pub option_vault_balance: u64,
+    pub total_amount: u64,
+}
+
+#[event]
+pub struct OrganizerStakeReleased {
+    pub event_id: u128,
+    pub authority: Pubkey,
+    pub stake: u64,
+    pub org_reward: u64,
+    pub platform_fee: u64,
+}
+
+#[event]
+pub struct RewardClaimed {
+    pub event_id: u128,
+    pub user: Pubkey,
+    pub option: u8,
+    pub won: bool,
+    pub amount: u64,
+}
+
+#[event]
+pub struct Recharged {
+    pub event_id: u128,
+    pub user: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct AppealFiled {
+    pub event_id: u128,
+    pub user: Pubkey,
+    pub disagree_count: u64,
+    pub disagree_trust_lvl: u64,
+    pub disagree_volume: u64,
+    /// Whether the appeal slashed the organizer stake
+    pub stake_slashed: bool,
+}
+
+#[event]
+pub struct TrustChanged {
+    pub event_id: u128,
+    pub user: Pubkey,
+    /// Signed trust level change
+    pub delta: i64,
+    pub trust_lvl: u64,
+}
+
+#[event]
+pub struct TrustBurned {
+    pub event_id: u128,
+    pub user: Pubkey,
+    pub burned_trust: u64,
+    pub amount: u64,
+    pub deposited_amount: u64,
+}
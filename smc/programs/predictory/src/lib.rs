@@ -2,10 +2,12 @@
 
 use anchor_lang::prelude::*;
 use context::*;
+use state::{param::ParamKind, role::Role, treasury::TreasuryRecipient};
 
-mod context;
-mod error;
-mod state;
+pub mod context;
+pub mod error;
+pub mod events;
+pub mod state;
 
 declare_id!("EV4SZ8mid5j4AVTBsi9tE4faCVHuu2XEPk857KWvrnL4");
 
@@ -30,34 +32,131 @@ pub mod predictory {
         )
     }
 
-    pub fn set_contract_authority(
-        ctx: Context<UpdateContractState>,
-        authority: Pubkey,
+    pub fn propose_authority(ctx: Context<UpdateContractState>, authority: Pubkey) -> Result<()> {
+        ctx.accounts.propose_authority(authority)
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        ctx.accounts.accept_authority()
+    }
+
+    pub fn cancel_authority_transfer(ctx: Context<UpdateContractState>) -> Result<()> {
+        ctx.accounts.cancel_authority_transfer()
+    }
+
+    pub fn set_authority_timelock(ctx: Context<UpdateContractState>, timelock: i64) -> Result<()> {
+        ctx.accounts.set_authority_timelock(timelock)
+    }
+
+    pub fn queue_param_change(
+        ctx: Context<QueueParamChange>,
+        kind: ParamKind,
+        value: u64,
     ) -> Result<()> {
-        ctx.accounts.set_authority(authority)
+        ctx.accounts.queue_param_change(kind, value)
     }
 
-    pub fn set_contract_multiplier(
-        ctx: Context<UpdateContractState>,
-        multiplier: u64,
+    pub fn apply_param_change(ctx: Context<ApplyParamChange>, kind: ParamKind) -> Result<()> {
+        ctx.accounts.apply_param_change(kind)
+    }
+
+    pub fn cancel_param_change(ctx: Context<CancelParamChange>, kind: ParamKind) -> Result<()> {
+        ctx.accounts.cancel_param_change(kind)
+    }
+
+    pub fn create_proposal(
+        ctx: Context<CreateProposal>,
+        kind: ParamKind,
+        value: u64,
+    ) -> Result<()> {
+        ctx.accounts.create_proposal(kind, value)
+    }
+
+    pub fn vote_proposal(
+        ctx: Context<VoteProposal>,
+        proposal_id: u64,
+        support: bool,
+    ) -> Result<()> {
+        ctx.accounts.vote_proposal(proposal_id, support)
+    }
+
+    pub fn execute_proposal(ctx: Context<ExecuteProposal>, proposal_id: u64) -> Result<()> {
+        ctx.accounts.execute_proposal(proposal_id)
+    }
+
+    pub fn initialize_treasury(ctx: Context<InitializeTreasury>) -> Result<()> {
+        ctx.accounts.initialize_treasury()
+    }
+
+    pub fn set_treasury_split(
+        ctx: Context<SetTreasurySplit>,
+        recipients: Vec<TreasuryRecipient>,
+    ) -> Result<()> {
+        ctx.accounts.set_treasury_split(recipients)
+    }
+
+    pub fn distribute_treasury<'info>(
+        ctx: Context<'_, '_, '_, 'info, DistributeTreasury<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.distribute_treasury(ctx.remaining_accounts)
+    }
+
+    pub fn migrate_state(ctx: Context<MigrateState>) -> Result<()> {
+        ctx.accounts.migrate_state()
+    }
+
+    pub fn migrate_user(ctx: Context<MigrateUser>, owner: Pubkey) -> Result<()> {
+        ctx.accounts.migrate_user(owner, ctx.bumps.user)
+    }
+
+    pub fn migrate_event(ctx: Context<MigrateEvent>, event_id: u128) -> Result<()> {
+        ctx.accounts.migrate_event(event_id, ctx.bumps.event)
+    }
+
+    pub fn migrate_event_meta(ctx: Context<MigrateEventMeta>, event_id: u128) -> Result<()> {
+        ctx.accounts.migrate_event_meta(event_id)
+    }
+
+    pub fn migrate_event_option(
+        ctx: Context<MigrateEventOption>,
+        event_id: u128,
+        index: u8,
+    ) -> Result<()> {
+        ctx.accounts
+            .migrate_event_option(event_id, index, ctx.bumps.option)
+    }
+
+    pub fn migrate_participation(
+        ctx: Context<MigrateParticipation>,
+        event_id: u128,
+        participant: Pubkey,
     ) -> Result<()> {
-        ctx.accounts.set_multiplier(multiplier)
+        ctx.accounts
+            .migrate_participation(event_id, participant, ctx.bumps.participation)
     }
 
-    pub fn set_event_price(ctx: Context<UpdateContractState>, event_price: u64) -> Result<()> {
-        ctx.accounts.set_price(event_price)
+    pub fn migrate_appellation(ctx: Context<MigrateAppellation>, event_id: u128) -> Result<()> {
+        ctx.accounts.migrate_appellation(event_id)
     }
 
-    pub fn set_contract_fee(ctx: Context<UpdateContractState>, fee: u64) -> Result<()> {
-        ctx.accounts.set_platform_fee(fee)
+    pub fn grant_role(ctx: Context<GrantRole>, role: Role, member: Pubkey) -> Result<()> {
+        ctx.accounts.grant_role(role, member)
     }
 
-    pub fn set_org_reward(ctx: Context<UpdateContractState>, reward: u64) -> Result<()> {
-        ctx.accounts.set_org_reward(reward)
+    pub fn revoke_role(ctx: Context<RevokeRole>, role: Role, member: Pubkey) -> Result<()> {
+        ctx.accounts.revoke_role(role, member)
+    }
+
+    pub fn set_paused(ctx: Context<SetPaused>, flags: u8) -> Result<()> {
+        ctx.accounts.set_paused(flags)
+    }
+
+    pub fn set_event_frozen(ctx: Context<FreezeEvent>, event_id: u128, frozen: bool) -> Result<()> {
+        ctx.accounts.set_event_frozen(event_id, frozen)
     }
 
     pub fn create_user(ctx: Context<CreateUser>, name: [u8; 32]) -> Result<()> {
-        ctx.accounts.create_user(name)
+        ctx.accounts.create_user(name, ctx.bumps.user)
     }
 
     pub fn transfer_stake(ctx: Context<TransferStake>, stake: u64) -> Result<()> {
@@ -68,12 +167,40 @@ pub mod predictory {
         ctx.accounts.withdraw(amount)
     }
 
+    pub fn create_portfolio_page(ctx: Context<CreatePortfolioPage>, page: u16) -> Result<()> {
+        ctx.accounts.create_portfolio_page(page)
+    }
+
+    pub fn close_portfolio_page(ctx: Context<ClosePortfolioPage>, page: u16) -> Result<()> {
+        ctx.accounts.close_portfolio_page(page)
+    }
+
+    pub fn refresh_portfolio<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RefreshPortfolio<'info>>,
+        owner: Pubkey,
+        page: u16,
+    ) -> Result<()> {
+        ctx.accounts
+            .refresh_portfolio(owner, page, ctx.remaining_accounts)
+    }
+
     pub fn create_event(
         ctx: Context<CreateEvent>,
         event_id: u128,
         args: CreateEventArgs,
     ) -> Result<()> {
-        ctx.accounts.create_event(event_id, args)
+        ctx.accounts.create_event(event_id, args, ctx.bumps.event)
+    }
+
+    pub fn publish_event(ctx: Context<PublishEvent>, event_id: u128) -> Result<()> {
+        ctx.accounts.publish_event(event_id)
+    }
+
+    pub fn delete_event<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DeleteEvent<'info>>,
+        event_id: u128,
+    ) -> Result<()> {
+        ctx.accounts.delete_event(event_id, ctx.remaining_accounts)
     }
 
     pub fn update_event_name(
@@ -109,6 +236,50 @@ pub mod predictory {
             .update_event_participation_deadline(event_id, deadline)
     }
 
+    pub fn propose_event_authority(
+        ctx: Context<ProposeEventAuthority>,
+        event_id: u128,
+        new_authority: Option<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts
+            .propose_event_authority(event_id, new_authority)
+    }
+
+    pub fn accept_event_authority(
+        ctx: Context<AcceptEventAuthority>,
+        event_id: u128,
+    ) -> Result<()> {
+        ctx.accounts.accept_event_authority(event_id)
+    }
+
+    pub fn set_event_callback(
+        ctx: Context<SetEventCallback>,
+        event_id: u128,
+        program: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.set_event_callback(event_id, program)
+    }
+
+    pub fn remove_event_callback(ctx: Context<RemoveEventCallback>, event_id: u128) -> Result<()> {
+        ctx.accounts.remove_event_callback(event_id)
+    }
+
+    pub fn notify_callback<'info>(
+        ctx: Context<'_, '_, '_, 'info, NotifyCallback<'info>>,
+        event_id: u128,
+    ) -> Result<()> {
+        ctx.accounts.notify_callback(
+            event_id,
+            ctx.bumps.callback_authority,
+            ctx.remaining_accounts,
+        )
+    }
+
+    pub fn create_options_table(ctx: Context<CreateOptionsTable>, event_id: u128) -> Result<()> {
+        ctx.accounts
+            .create_options_table(event_id, ctx.bumps.options_table)
+    }
+
     pub fn create_event_option(
         ctx: Context<CreateEventOption>,
         event_id: u128,
@@ -116,7 +287,24 @@ pub mod predictory {
         description: [u8; 256],
     ) -> Result<()> {
         ctx.accounts
-            .create_event_option(event_id, index, description)
+            .create_event_option(event_id, index, description, ctx.bumps.option)
+    }
+
+    pub fn create_event_options<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateEventOptions<'info>>,
+        event_id: u128,
+        descriptions: Vec<String>,
+    ) -> Result<()> {
+        ctx.accounts
+            .create_event_options(event_id, descriptions, ctx.remaining_accounts)
+    }
+
+    pub fn remove_event_option(
+        ctx: Context<RemoveEventOption>,
+        event_id: u128,
+        index: u8,
+    ) -> Result<()> {
+        ctx.accounts.remove_event_option(event_id, index)
     }
 
     pub fn update_event_option(
@@ -129,6 +317,43 @@ pub mod predictory {
             .update_event_option(event_id, index, description)
     }
 
+    pub fn add_event_organizer(
+        ctx: Context<AddEventOrganizer>,
+        event_id: u128,
+        member: Pubkey,
+        permissions: u8,
+        reward_share: u8,
+    ) -> Result<()> {
+        ctx.accounts
+            .add_event_organizer(event_id, member, permissions, reward_share)
+    }
+
+    pub fn update_event_organizer(
+        ctx: Context<UpdateEventOrganizer>,
+        event_id: u128,
+        member: Pubkey,
+        permissions: u8,
+        reward_share: u8,
+    ) -> Result<()> {
+        ctx.accounts
+            .update_event_organizer(event_id, member, permissions, reward_share)
+    }
+
+    pub fn remove_event_organizer(
+        ctx: Context<RemoveEventOrganizer>,
+        event_id: u128,
+        member: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.remove_event_organizer(event_id, member)
+    }
+
+    pub fn claim_organizer_reward(
+        ctx: Context<ClaimOrganizerReward>,
+        event_id: u128,
+    ) -> Result<()> {
+        ctx.accounts.claim_organizer_reward(event_id)
+    }
+
     pub fn cancel_event(ctx: Context<CancelEvent>, event_id: u128) -> Result<()> {
         ctx.accounts.cancel_event(event_id)
     }
@@ -137,14 +362,69 @@ pub mod predictory {
         ctx.accounts.complete_event(event_id, result)
     }
 
+    pub fn moderate_event(ctx: Context<ModerateEvent>, event_id: u128, hidden: bool) -> Result<()> {
+        ctx.accounts.moderate_event(event_id, hidden)
+    }
+
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>, event_id: u128, result: u8) -> Result<()> {
+        ctx.accounts.resolve_dispute(event_id, result)
+    }
+
+    pub fn expire_dispute(ctx: Context<ExpireDispute>, event_id: u128) -> Result<()> {
+        ctx.accounts.expire_dispute(event_id)
+    }
+
+    pub fn finalize_event(ctx: Context<FinalizeEvent>, event_id: u128) -> Result<()> {
+        ctx.accounts.finalize_event(event_id)
+    }
+
+    pub fn quote_vote(
+        ctx: Context<QuoteVote>,
+        event_id: u128,
+        option_ix: u8,
+        amount: u64,
+    ) -> Result<VoteQuote> {
+        ctx.accounts.quote_vote(event_id, option_ix, amount)
+    }
+
+    pub fn quote_claim(
+        ctx: Context<QuoteClaim>,
+        event_id: u128,
+        user: Pubkey,
+    ) -> Result<ClaimQuote> {
+        ctx.accounts.quote_claim(event_id, user)
+    }
+
+    pub fn event_odds<'info>(
+        ctx: Context<'_, '_, '_, 'info, EventOdds<'info>>,
+        event_id: u128,
+    ) -> Result<Odds> {
+        ctx.accounts.event_odds(event_id, ctx.remaining_accounts)
+    }
+
     pub fn vote(ctx: Context<Vote>, event_id: u128, option_ix: u8, amount: u64) -> Result<()> {
-        ctx.accounts.vote(event_id, option_ix, amount)
+        ctx.accounts
+            .vote(event_id, option_ix, amount, ctx.bumps.participation)
     }
 
     pub fn claim_event_reward(ctx: Context<ClaimEventReward>, event_id: u128) -> Result<()> {
         ctx.accounts.claim_event_reward(event_id)
     }
 
+    pub fn settle_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleBatch<'info>>,
+        event_id: u128,
+    ) -> Result<()> {
+        ctx.accounts.settle_batch(event_id, ctx.remaining_accounts)
+    }
+
+    pub fn claim_many<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimMany<'info>>,
+        claim_count: u8,
+    ) -> Result<Vec<ClaimResult>> {
+        ctx.accounts.claim_many(claim_count, ctx.remaining_accounts)
+    }
+
     pub fn recharge(ctx: Context<Recharge>, event_id: u128) -> Result<()> {
         ctx.accounts.recharge(event_id)
     }
@@ -1,4 +1,4 @@
-use super::DISCRIMINATOR_LENGTH;
+use super::{ACCOUNT_RESERVE_SPACE, DISCRIMINATOR_LENGTH};
 use anchor_lang::prelude::*;
 
 #[account]
@@ -21,6 +21,6 @@ pub struct Appellation {
 }
 
 impl Appellation {
-    pub const LEN: usize = DISCRIMINATOR_LENGTH + Appellation::INIT_SPACE;
-    pub const VERSION: u8 = 1;
+    pub const LEN: usize = DISCRIMINATOR_LENGTH + ACCOUNT_RESERVE_SPACE + Appellation::INIT_SPACE;
+    pub const VERSION: u8 = 2;
 }
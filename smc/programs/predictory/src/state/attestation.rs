@@ -0,0 +1,42 @@
+use super::{ACCOUNT_RESERVE_SPACE, DISCRIMINATOR_LENGTH};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum ResolutionStatus {
+    /// Result set, still open to appeals and disputes
+    Pending,
+    /// Result final, the appellation window is over
+    Resolved,
+    /// Event canceled, no result
+    Canceled,
+}
+
+/// Outcome of an event for other programs to read, fields are only ever appended
+#[account]
+#[derive(InitSpace)]
+pub struct ResolutionAttestation {
+    /// Layout version
+    pub version: u8,
+
+    /// Event UUID
+    pub event_id: u128,
+
+    /// Resolution status
+    pub status: ResolutionStatus,
+
+    /// Index of the outcome option, meaningful once resolved
+    pub result: u8,
+
+    /// Slot the status became final at, zero while pending
+    pub finalized_slot: u64,
+
+    /// Signer that set the result or canceled the event,
+    /// the default key for events resolved before attestations
+    pub resolver: Pubkey,
+}
+
+impl ResolutionAttestation {
+    pub const LEN: usize =
+        DISCRIMINATOR_LENGTH + ACCOUNT_RESERVE_SPACE + ResolutionAttestation::INIT_SPACE;
+    pub const VERSION: u8 = 1;
+}
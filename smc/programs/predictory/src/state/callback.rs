@@ -0,0 +1,47 @@
+use super::{ACCOUNT_RESERVE_SPACE, DISCRIMINATOR_LENGTH};
+use anchor_lang::prelude::*;
+
+/// Program notified when the event gets its result
+#[account]
+#[derive(InitSpace)]
+pub struct EventCallback {
+    /// Account version
+    pub version: u8,
+
+    /// Event UUID
+    pub event_id: u128,
+
+    /// Program invoked with the event result
+    pub program: Pubkey,
+
+    /// Whether the program accepted a result
+    pub notified: bool,
+
+    /// Last result the program accepted
+    pub notified_result: u8,
+}
+
+impl EventCallback {
+    pub const LEN: usize = DISCRIMINATOR_LENGTH + ACCOUNT_RESERVE_SPACE + EventCallback::INIT_SPACE;
+    pub const VERSION: u8 = 2;
+
+    /// Whether the program still has to learn the result
+    pub fn is_pending(&self, result: u8) -> bool {
+        !self.notified || self.notified_result != result
+    }
+}
+
+/// Arguments of the `on_event_resolved` instruction a callback program exposes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct EventResolved {
+    /// Event UUID
+    pub event_id: u128,
+
+    /// Index of the outcome option
+    pub result: u8,
+}
+
+impl EventResolved {
+    /// Name of the callback instruction, hashed into its discriminator like an Anchor instruction
+    pub const INSTRUCTION: &'static str = "on_event_resolved";
+}
@@ -21,9 +21,40 @@ pub struct State {
 
     /// Event price
     pub org_reward: u64,
+
+    /// Paused operation flags
+    pub paused: u8,
+
+    /// Proposed new contract authority
+    pub pending_authority: Option<Pubkey>,
+
+    /// Time after which the pending authority can accept the transfer
+    pub authority_transfer_eta: i64,
+
+    /// Delay between authority transfer proposal and acceptance, in seconds
+    pub authority_timelock: i64,
+
+    /// Number of governance proposals created
+    pub proposal_count: u64,
 }
 
 impl State {
     pub const LEN: usize = DISCRIMINATOR_LENGTH + ACCOUNT_RESERVE_SPACE + State::INIT_SPACE;
-    pub const VERSION: u8 = 1;
+    pub const VERSION: u8 = 2;
+
+    pub const PAUSE_EVENT_CREATION: u8 = 1 << 0;
+    pub const PAUSE_VOTING: u8 = 1 << 1;
+    pub const PAUSE_CLAIMS: u8 = 1 << 2;
+    pub const PAUSE_WITHDRAWALS: u8 = 1 << 3;
+    pub const PAUSE_APPEALS: u8 = 1 << 4;
+
+    pub const PAUSE_ALL: u8 = Self::PAUSE_EVENT_CREATION
+        | Self::PAUSE_VOTING
+        | Self::PAUSE_CLAIMS
+        | Self::PAUSE_WITHDRAWALS
+        | Self::PAUSE_APPEALS;
+
+    pub fn is_paused(&self, flag: u8) -> bool {
+        self.paused & flag != 0
+    }
 }
@@ -1,18 +1,61 @@
-use super::DISCRIMINATOR_LENGTH;
+use super::{ACCOUNT_RESERVE_SPACE, DISCRIMINATOR_LENGTH};
 use anchor_lang::prelude::*;
 
-#[account]
-#[derive(InitSpace)]
+/// Event data read on every vote and claim, loaded without deserialization.
+/// Fields are grouped by alignment so the `repr(C)` layout has no implicit padding.
+#[account(zero_copy)]
 pub struct Event {
     /// Account version
     pub version: u8,
 
-    /// Event UUID
-    pub id: u128,
+    /// Canonical bump of the event address
+    pub bump: u8,
+
+    /// Event option count
+    pub option_count: u8,
+
+    /// Index of the outcome option, meaningful once resolved
+    pub result: u8,
+
+    /// Sum of co-organizer reward shares, in percent
+    pub organizer_shares: u8,
+
+    /// Number of open co-organizer accounts
+    pub organizer_count: u8,
+
+    /// Whether the sale is canceled
+    pub canceled: u8,
+
+    /// Whether the event has a result
+    pub resolved: u8,
+
+    /// Whether the event result has been appealed
+    pub disputed: u8,
+
+    /// Whether the event left the draft state
+    pub published: u8,
+
+    /// Whether the event is frozen by the contract admin
+    pub frozen: u8,
+
+    /// Whether the option pools are kept in the options table instead of the option accounts
+    pub options_table: u8,
+
+    /// Whether a callback program is notified of the event result
+    pub callback: u8,
+
+    /// Aligns the following fields to 8 bytes
+    pub padding: [u8; 3],
+
+    /// Event UUID, little-endian
+    pub id: [u8; 16],
 
     /// Event authority
     pub authority: Pubkey,
 
+    /// Proposed new event authority, the default key when none
+    pub pending_authority: Pubkey,
+
     /// Authority stake
     pub stake: u64,
 
@@ -22,11 +65,8 @@ pub struct Event {
     /// Event end date
     pub end_date: i64,
 
-    /// Event participation deadline
-    pub participation_deadline: Option<i64>,
-
-    /// Event option count
-    pub option_count: u8,
+    /// Event participation deadline, zero when not set
+    pub participation_deadline: i64,
 
     /// Event participation count
     pub participation_count: u64,
@@ -37,16 +77,53 @@ pub struct Event {
     /// Total amount of tokens in all pools
     pub total_trust: u64,
 
-    /// Whether the sale is canceled
-    pub canceled: bool,
+    /// Organizer reward released on settlement
+    pub org_reward_amount: u64,
 
-    /// Index of the outcome option
-    pub result: Option<u8>,
+    /// Platform fee snapshot taken when the event is published
+    pub platform_fee: u64,
+
+    /// Organizer reward snapshot taken when the event is published, in percent
+    pub org_reward: u64,
+
+    /// Space for future fields
+    pub reserved: [u8; ACCOUNT_RESERVE_SPACE],
 }
 
 impl Event {
-    pub const LEN: usize = DISCRIMINATOR_LENGTH + Event::INIT_SPACE;
-    pub const VERSION: u8 = 1;
+    pub const LEN: usize = DISCRIMINATOR_LENGTH + std::mem::size_of::<Event>();
+    pub const VERSION: u8 = 3;
+
+    /// Event UUID
+    pub fn id(&self) -> u128 {
+        u128::from_le_bytes(self.id)
+    }
+
+    /// Index of the outcome option
+    pub fn result(&self) -> Option<u8> {
+        (self.resolved != 0).then_some(self.result)
+    }
+
+    pub fn set_result(&mut self, result: u8) {
+        self.resolved = 1;
+        self.result = result;
+    }
+
+    pub fn participation_deadline(&self) -> Option<i64> {
+        Some(self.participation_deadline).filter(|deadline| *deadline != 0)
+    }
+
+    pub fn set_participation_deadline(&mut self, deadline: Option<i64>) {
+        self.participation_deadline = deadline.unwrap_or_default();
+    }
+
+    pub fn pending_authority(&self) -> Option<Pubkey> {
+        Some(self.pending_authority).filter(|authority| *authority != Pubkey::default())
+    }
+
+    pub fn set_pending_authority(&mut self, authority: Option<Pubkey>) {
+        self.pending_authority = authority.unwrap_or_default();
+    }
 }
 
 #[account]
@@ -61,6 +138,9 @@ pub struct EventMeta {
     /// Whether the event is private
     pub is_private: bool,
 
+    /// Whether the event is hidden by a moderator
+    pub hidden: bool,
+
     /// Event name
     pub name: [u8; 32],
 
@@ -70,6 +150,26 @@ pub struct EventMeta {
 }
 
 impl EventMeta {
-    pub const LEN: usize = DISCRIMINATOR_LENGTH + EventMeta::INIT_SPACE;
-    pub const VERSION: u8 = 1;
+    pub const LEN: usize = DISCRIMINATOR_LENGTH + ACCOUNT_RESERVE_SPACE + EventMeta::INIT_SPACE;
+    pub const VERSION: u8 = 2;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem::{align_of, offset_of, size_of};
+
+    #[test]
+    fn event_layout() {
+        assert_eq!(size_of::<Event>(), 208);
+        assert_eq!(align_of::<Event>(), 8);
+        assert_eq!(offset_of!(Event, version), 0);
+        assert_eq!(offset_of!(Event, callback), 12);
+        assert_eq!(offset_of!(Event, id), 16);
+        assert_eq!(offset_of!(Event, authority), 32);
+        assert_eq!(offset_of!(Event, pending_authority), 64);
+        assert_eq!(offset_of!(Event, stake), 96);
+        assert_eq!(offset_of!(Event, org_reward), 168);
+        assert_eq!(offset_of!(Event, reserved), 176);
+    }
 }
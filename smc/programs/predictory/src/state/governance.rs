@@ -0,0 +1,66 @@
+use super::{param::ParamKind, DISCRIMINATOR_LENGTH};
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Proposal {
+    /// Account version
+    pub version: u8,
+
+    /// Proposal sequence number
+    pub id: u64,
+
+    /// Proposer wallet account
+    pub proposer: Pubkey,
+
+    /// Proposed parameter
+    pub kind: ParamKind,
+
+    /// Proposed parameter value
+    pub value: u64,
+
+    /// Trust weight voted in favor
+    pub votes_for: u64,
+
+    /// Trust weight voted against
+    pub votes_against: u64,
+
+    /// Voting end date
+    pub voting_end_date: i64,
+
+    /// Whether the proposal was executed
+    pub executed: bool,
+}
+
+impl Proposal {
+    pub const LEN: usize = DISCRIMINATOR_LENGTH + Proposal::INIT_SPACE;
+    pub const VERSION: u8 = 1;
+
+    pub fn total_votes(&self) -> u64 {
+        self.votes_for + self.votes_against
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ProposalVote {
+    /// Account version
+    pub version: u8,
+
+    /// Proposal sequence number
+    pub proposal_id: u64,
+
+    /// Voter wallet account
+    pub voter: Pubkey,
+
+    /// Voter trust weight, as of the proposal creation
+    pub weight: u64,
+
+    /// Whether the voter supports the proposal
+    pub support: bool,
+}
+
+impl ProposalVote {
+    pub const LEN: usize = DISCRIMINATOR_LENGTH + ProposalVote::INIT_SPACE;
+    pub const VERSION: u8 = 1;
+}
@@ -0,0 +1,580 @@
+use anchor_lang::{prelude::*, Discriminator};
+
+use super::{
+    appeal::Appellation,
+    contract_state::State,
+    event::{Event, EventMeta},
+    option::EventOption,
+    participation::Participation,
+    user::User,
+    ACCOUNT_RESERVE_SPACE,
+};
+use crate::error::ProgramError;
+
+/// Program account with a versioned layout
+pub trait Versioned: AccountSerialize + AccountDeserialize + Discriminator {
+    /// Current layout version
+    const CURRENT_VERSION: u8;
+
+    /// Current account size
+    const SPACE: usize;
+
+    /// Decodes account data, without discriminator, written with an older layout
+    fn from_legacy(version: u8, data: &mut &[u8]) -> Result<Self>;
+}
+
+fn decode_v1<L: AnchorDeserialize + Into<T>, T>(version: u8, data: &mut &[u8]) -> Result<T> {
+    match version {
+        1 => Ok(L::deserialize(data)?.into()),
+        _ => err!(ProgramError::UnsupportedAccountVersion),
+    }
+}
+
+/// Decodes a Borsh layout shared by versions 1 and 2
+fn decode_v2<L: AnchorDeserialize + Into<T>, T>(version: u8, data: &mut &[u8]) -> Result<T> {
+    match version {
+        1 | 2 => Ok(L::deserialize(data)?.into()),
+        _ => err!(ProgramError::UnsupportedAccountVersion),
+    }
+}
+
+/// Implements `AccountSerialize` for zero-copy accounts, writing the layout `AccountLoader` reads
+macro_rules! zero_copy_serialize {
+    ($($account:ty),*) => {
+        $(
+            impl AccountSerialize for $account {
+                fn try_serialize<W: std::io::Write>(&self, writer: &mut W) -> Result<()> {
+                    writer
+                        .write_all(Self::DISCRIMINATOR)
+                        .and_then(|_| writer.write_all(bytemuck::bytes_of(self)))
+                        .map_err(|_| ErrorCode::AccountDidNotSerialize.into())
+                }
+            }
+        )*
+    };
+}
+
+zero_copy_serialize!(User, Event, EventOption, Participation);
+
+// ---------------------------- State ------------------------------ //
+
+#[derive(AnchorDeserialize)]
+pub struct StateV1 {
+    pub version: u8,
+    pub authority: Pubkey,
+    pub multiplier: u64,
+    pub event_price: u64,
+    pub platform_fee: u64,
+    pub org_reward: u64,
+}
+
+impl From<StateV1> for State {
+    fn from(legacy: StateV1) -> Self {
+        State {
+            version: State::VERSION,
+            authority: legacy.authority,
+            multiplier: legacy.multiplier,
+            event_price: legacy.event_price,
+            platform_fee: legacy.platform_fee,
+            org_reward: legacy.org_reward,
+            paused: 0,
+            pending_authority: None,
+            authority_transfer_eta: 0,
+            authority_timelock: 0,
+            proposal_count: 0,
+        }
+    }
+}
+
+impl Versioned for State {
+    const CURRENT_VERSION: u8 = State::VERSION;
+    const SPACE: usize = State::LEN;
+
+    fn from_legacy(version: u8, data: &mut &[u8]) -> Result<Self> {
+        decode_v1::<StateV1, _>(version, data)
+    }
+}
+
+// ----------------------------- User ------------------------------ //
+
+/// Borsh layout of versions 1 and 2
+#[derive(AnchorDeserialize)]
+pub struct UserV1 {
+    pub version: u8,
+    pub payer: Pubkey,
+    pub stake: u64,
+    pub locked_stake: u64,
+    pub trust_lvl: u64,
+    pub name: [u8; 32],
+}
+
+impl From<UserV1> for User {
+    fn from(legacy: UserV1) -> Self {
+        User {
+            version: User::VERSION,
+            // Derived by the migration
+            bump: 0,
+            padding: [0; 6],
+            payer: legacy.payer,
+            stake: legacy.stake,
+            locked_stake: legacy.locked_stake,
+            trust_lvl: legacy.trust_lvl,
+            name: legacy.name,
+            trust_raised_at: 0,
+            reserved: [0; ACCOUNT_RESERVE_SPACE - 8],
+        }
+    }
+}
+
+impl Versioned for User {
+    const CURRENT_VERSION: u8 = User::VERSION;
+    const SPACE: usize = User::LEN;
+
+    fn from_legacy(version: u8, data: &mut &[u8]) -> Result<Self> {
+        decode_v2::<UserV1, _>(version, data)
+    }
+}
+
+// ----------------------------- Event ----------------------------- //
+
+#[derive(AnchorDeserialize)]
+pub struct EventV1 {
+    pub version: u8,
+    pub id: u128,
+    pub authority: Pubkey,
+    pub stake: u64,
+    pub start_date: i64,
+    pub end_date: i64,
+    pub participation_deadline: Option<i64>,
+    pub option_count: u8,
+    pub participation_count: u64,
+    pub total_amount: u64,
+    pub total_trust: u64,
+    pub canceled: bool,
+    pub result: Option<u8>,
+}
+
+impl From<EventV1> for Event {
+    fn from(legacy: EventV1) -> Self {
+        let mut event = Event {
+            version: Event::VERSION,
+            // Derived by the migration
+            bump: 0,
+            option_count: legacy.option_count,
+            result: 0,
+            organizer_shares: 0,
+            organizer_count: 0,
+            canceled: u8::from(legacy.canceled),
+            resolved: 0,
+            disputed: 0,
+            // Events had no draft state before, they were live once created
+            published: 1,
+            frozen: 0,
+            options_table: 0,
+            callback: 0,
+            padding: [0; 3],
+            id: legacy.id.to_le_bytes(),
+            authority: legacy.authority,
+            pending_authority: Pubkey::default(),
+            stake: legacy.stake,
+            start_date: legacy.start_date,
+            end_date: legacy.end_date,
+            participation_deadline: 0,
+            participation_count: legacy.participation_count,
+            total_amount: legacy.total_amount,
+            total_trust: legacy.total_trust,
+            org_reward_amount: 0,
+            // Fee terms are snapshotted from the contract state by the migration
+            platform_fee: 0,
+            org_reward: 0,
+            reserved: [0; ACCOUNT_RESERVE_SPACE],
+        };
+
+        event.set_participation_deadline(legacy.participation_deadline);
+
+        if let Some(result) = legacy.result {
+            event.set_result(result);
+        }
+
+        event
+    }
+}
+
+impl Versioned for Event {
+    const CURRENT_VERSION: u8 = Event::VERSION;
+    const SPACE: usize = Event::LEN;
+
+    fn from_legacy(version: u8, data: &mut &[u8]) -> Result<Self> {
+        decode_v1::<EventV1, _>(version, data)
+    }
+}
+
+#[derive(AnchorDeserialize)]
+pub struct EventMetaV1 {
+    pub version: u8,
+    pub event_id: u128,
+    pub is_private: bool,
+    pub name: [u8; 32],
+    pub description: [u8; 256],
+}
+
+impl From<EventMetaV1> for EventMeta {
+    fn from(legacy: EventMetaV1) -> Self {
+        EventMeta {
+            version: EventMeta::VERSION,
+            event_id: legacy.event_id,
+            is_private: legacy.is_private,
+            hidden: false,
+            name: legacy.name,
+            description: legacy.description,
+        }
+    }
+}
+
+impl Versioned for EventMeta {
+    const CURRENT_VERSION: u8 = EventMeta::VERSION;
+    const SPACE: usize = EventMeta::LEN;
+
+    fn from_legacy(version: u8, data: &mut &[u8]) -> Result<Self> {
+        decode_v1::<EventMetaV1, _>(version, data)
+    }
+}
+
+// ------------------------- Event Option -------------------------- //
+
+/// Borsh layout of versions 1 and 2, the description moved to `EventOptionMeta` since
+#[derive(AnchorDeserialize)]
+pub struct EventOptionV1 {
+    pub version: u8,
+    pub index: u8,
+    pub event_id: u128,
+    pub description: [u8; 256],
+    pub votes: u64,
+    pub vault_balance: u64,
+}
+
+impl From<EventOptionV1> for EventOption {
+    fn from(legacy: EventOptionV1) -> Self {
+        EventOption {
+            version: EventOption::VERSION,
+            // Derived by the migration
+            bump: 0,
+            index: legacy.index,
+            padding: [0; 5],
+            event_id: legacy.event_id.to_le_bytes(),
+            votes: legacy.votes,
+            vault_balance: legacy.vault_balance,
+            reserved: [0; ACCOUNT_RESERVE_SPACE],
+        }
+    }
+}
+
+impl Versioned for EventOption {
+    const CURRENT_VERSION: u8 = EventOption::VERSION;
+    const SPACE: usize = EventOption::LEN;
+
+    fn from_legacy(version: u8, data: &mut &[u8]) -> Result<Self> {
+        decode_v2::<EventOptionV1, _>(version, data)
+    }
+}
+
+// ------------------------- Participation ------------------------- //
+
+#[derive(AnchorDeserialize)]
+pub struct ParticipationV1 {
+    pub version: u8,
+    pub event_id: u128,
+    pub payer: Pubkey,
+    pub option: u8,
+    pub deposited_amount: u64,
+    pub is_claimed: bool,
+    pub appealed: bool,
+}
+
+impl From<ParticipationV1> for Participation {
+    fn from(legacy: ParticipationV1) -> Self {
+        Participation {
+            version: Participation::VERSION,
+            // Derived by the migration
+            bump: 0,
+            option: legacy.option,
+            is_claimed: u8::from(legacy.is_claimed),
+            appealed: u8::from(legacy.appealed),
+            padding: [0; 1],
+            // Recorded on the first portfolio page by the migration
+            portfolio_page: 0,
+            event_id: legacy.event_id.to_le_bytes(),
+            payer: legacy.payer,
+            deposited_amount: legacy.deposited_amount,
+            reserved: [0; ACCOUNT_RESERVE_SPACE],
+        }
+    }
+}
+
+impl Versioned for Participation {
+    const CURRENT_VERSION: u8 = Participation::VERSION;
+    const SPACE: usize = Participation::LEN;
+
+    fn from_legacy(version: u8, data: &mut &[u8]) -> Result<Self> {
+        decode_v1::<ParticipationV1, _>(version, data)
+    }
+}
+
+// -------------------------- Appellation -------------------------- //
+
+#[derive(AnchorDeserialize)]
+pub struct AppellationV1 {
+    pub version: u8,
+    pub event_id: u128,
+    pub disagree_count: u64,
+    pub disagree_trust_lvl: u64,
+    pub disagree_volume: u64,
+}
+
+impl From<AppellationV1> for Appellation {
+    fn from(legacy: AppellationV1) -> Self {
+        Appellation {
+            version: Appellation::VERSION,
+            event_id: legacy.event_id,
+            disagree_count: legacy.disagree_count,
+            disagree_trust_lvl: legacy.disagree_trust_lvl,
+            disagree_volume: legacy.disagree_volume,
+        }
+    }
+}
+
+impl Versioned for Appellation {
+    const CURRENT_VERSION: u8 = Appellation::VERSION;
+    const SPACE: usize = Appellation::LEN;
+
+    fn from_legacy(version: u8, data: &mut &[u8]) -> Result<Self> {
+        decode_v1::<AppellationV1, _>(version, data)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::{context::load_versioned, id};
+
+    /// Decodes account data laid out as the listed fields behind the discriminator of `T`
+    pub(crate) fn decode<T: Versioned>(fields: &[&[u8]]) -> Result<T> {
+        let mut data = T::DISCRIMINATOR.to_vec();
+        fields
+            .iter()
+            .for_each(|field| data.extend_from_slice(field));
+
+        let key = Pubkey::new_unique();
+        let owner = id();
+        let mut lamports = 0;
+        let account = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+
+        load_versioned(&account)
+    }
+
+    /// Version 1 event that ended with the second option as result
+    pub(crate) fn event_v1(authority: &Pubkey) -> Result<Event> {
+        decode(&[
+            &[1],
+            &7u128.to_le_bytes(),
+            authority.as_ref(),
+            &500u64.to_le_bytes(),
+            &100i64.to_le_bytes(),
+            &200i64.to_le_bytes(),
+            &[1],
+            &150i64.to_le_bytes(),
+            &[2],
+            &3u64.to_le_bytes(),
+            &9_000u64.to_le_bytes(),
+            &30u64.to_le_bytes(),
+            &[0],
+            &[1, 1],
+        ])
+    }
+
+    #[test]
+    fn state_v1() {
+        let authority = Pubkey::new_unique();
+        let state: State = decode(&[
+            &[1],
+            authority.as_ref(),
+            &2u64.to_le_bytes(),
+            &1_000u64.to_le_bytes(),
+            &33u64.to_le_bytes(),
+            &44u64.to_le_bytes(),
+        ])
+        .unwrap();
+
+        assert_eq!(state.version, State::VERSION);
+        assert_eq!(state.authority, authority);
+        assert_eq!(state.multiplier, 2);
+        assert_eq!(state.event_price, 1_000);
+        assert_eq!(state.platform_fee, 33);
+        assert_eq!(state.org_reward, 44);
+        assert_eq!(state.paused, 0);
+        assert_eq!(state.pending_authority, None);
+    }
+
+    #[test]
+    fn user_v1() {
+        let payer = Pubkey::new_unique();
+        let user: User = decode(&[
+            &[1],
+            payer.as_ref(),
+            &10u64.to_le_bytes(),
+            &4u64.to_le_bytes(),
+            &6u64.to_le_bytes(),
+            &[b'a'; 32],
+        ])
+        .unwrap();
+
+        assert_eq!(user.version, User::VERSION);
+        assert_eq!(user.payer, payer);
+        assert_eq!(user.stake, 10);
+        assert_eq!(user.locked_stake, 4);
+        assert_eq!(user.trust_lvl, 6);
+        assert_eq!(user.name, [b'a'; 32]);
+    }
+
+    #[test]
+    fn event_v1_layout() {
+        let authority = Pubkey::new_unique();
+        let event = event_v1(&authority).unwrap();
+
+        assert_eq!(event.version, Event::VERSION);
+        assert_eq!(event.id(), 7);
+        assert_eq!(event.authority, authority);
+        assert_eq!(event.stake, 500);
+        assert_eq!(event.start_date, 100);
+        assert_eq!(event.end_date, 200);
+        assert_eq!(event.participation_deadline(), Some(150));
+        assert_eq!(event.option_count, 2);
+        assert_eq!(event.participation_count, 3);
+        assert_eq!(event.total_amount, 9_000);
+        assert_eq!(event.total_trust, 30);
+        assert_eq!(event.canceled, 0);
+        assert_eq!(event.result(), Some(1));
+        assert_eq!(event.published, 1);
+        assert_eq!(event.pending_authority(), None);
+        assert_eq!(event.organizer_count, 0);
+    }
+
+    #[test]
+    fn event_option_v1() {
+        let option: EventOption = decode(&[
+            &[1],
+            &[3],
+            &7u128.to_le_bytes(),
+            &[b'x'; 256],
+            &12u64.to_le_bytes(),
+            &1_200u64.to_le_bytes(),
+        ])
+        .unwrap();
+
+        assert_eq!(option.version, EventOption::VERSION);
+        assert_eq!(option.index, 3);
+        assert_eq!(option.event_id(), 7);
+        assert_eq!(option.votes, 12);
+        assert_eq!(option.vault_balance, 1_200);
+    }
+
+    #[test]
+    fn participation_v1() {
+        let payer = Pubkey::new_unique();
+        let participation: Participation = decode(&[
+            &[1],
+            &7u128.to_le_bytes(),
+            payer.as_ref(),
+            &[1],
+            &800u64.to_le_bytes(),
+            &[0],
+            &[1],
+        ])
+        .unwrap();
+
+        assert_eq!(participation.version, Participation::VERSION);
+        assert_eq!(participation.event_id(), 7);
+        assert_eq!(participation.payer, payer);
+        assert_eq!(participation.option, 1);
+        assert_eq!(participation.deposited_amount, 800);
+        assert_eq!(participation.is_claimed, 0);
+        assert_eq!(participation.appealed, 1);
+        assert_eq!(participation.portfolio_page, 0);
+    }
+
+    #[test]
+    fn appellation_v1() {
+        let appellation: Appellation = decode(&[
+            &[1],
+            &7u128.to_le_bytes(),
+            &2u64.to_le_bytes(),
+            &11u64.to_le_bytes(),
+            &600u64.to_le_bytes(),
+        ])
+        .unwrap();
+
+        assert_eq!(appellation.version, Appellation::VERSION);
+        assert_eq!(appellation.event_id, 7);
+        assert_eq!(appellation.disagree_count, 2);
+        assert_eq!(appellation.disagree_trust_lvl, 11);
+        assert_eq!(appellation.disagree_volume, 600);
+    }
+
+    #[test]
+    fn unknown_version() {
+        let payer = Pubkey::new_unique();
+        let user = decode::<User>(&[&[0], payer.as_ref(), &[0; 24], &[0; 32]]);
+
+        assert!(user.is_err());
+    }
+
+    #[test]
+    fn events_and_participations_migrate_from_v1_only() {
+        let authority = Pubkey::new_unique();
+        let event = decode::<Event>(&[
+            &[2],
+            &7u128.to_le_bytes(),
+            authority.as_ref(),
+            &500u64.to_le_bytes(),
+            &100i64.to_le_bytes(),
+            &200i64.to_le_bytes(),
+            &[0],
+            &[2],
+            &0u64.to_le_bytes(),
+            &0u64.to_le_bytes(),
+            &0u64.to_le_bytes(),
+            &[0],
+            &[0],
+        ]);
+
+        assert_eq!(
+            event.err(),
+            Some(ProgramError::UnsupportedAccountVersion.into())
+        );
+
+        let participation = decode::<Participation>(&[
+            &[2],
+            &7u128.to_le_bytes(),
+            authority.as_ref(),
+            &[1],
+            &800u64.to_le_bytes(),
+            &[0],
+            &[0],
+            &0u16.to_le_bytes(),
+        ]);
+
+        assert_eq!(
+            participation.err(),
+            Some(ProgramError::UnsupportedAccountVersion.into())
+        );
+    }
+}
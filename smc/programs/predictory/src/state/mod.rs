@@ -1,11 +1,26 @@
-pub(crate) mod appeal;
-pub(crate) mod contract_state;
-pub(crate) mod event;
-pub(crate) mod option;
-pub(crate) mod participation;
-pub(crate) mod user;
+pub mod appeal;
+pub mod attestation;
+pub mod callback;
+pub mod contract_state;
+pub mod event;
+pub mod governance;
+pub mod legacy;
+pub mod option;
+pub mod organizer;
+pub mod param;
+pub mod participation;
+pub mod portfolio;
+pub mod role;
+pub mod treasury;
+pub mod user;
 
 /// Anchor discriminator length
 pub const DISCRIMINATOR_LENGTH: usize = 8;
 /// Account reserve space
 pub const ACCOUNT_RESERVE_SPACE: usize = 32;
+/// Number of option slots in an options table
+pub const OPTIONS_TABLE_SIZE: usize = 20;
+/// Maximum number of positions in one portfolio page
+pub const PORTFOLIO_PAGE_SIZE: usize = 32;
+/// Maximum number of treasury split recipients
+pub const MAX_TREASURY_RECIPIENTS: usize = 8;
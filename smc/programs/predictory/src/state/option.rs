@@ -1,29 +1,133 @@
-use super::DISCRIMINATOR_LENGTH;
+use super::{ACCOUNT_RESERVE_SPACE, DISCRIMINATOR_LENGTH, OPTIONS_TABLE_SIZE};
 use anchor_lang::prelude::*;
 
-#[account]
-#[derive(InitSpace)]
+/// Option pool read on every vote and claim, loaded without deserialization
+#[account(zero_copy)]
 pub struct EventOption {
     /// Account version
     pub version: u8,
 
+    /// Canonical bump of the option address
+    pub bump: u8,
+
     /// Option index
     pub index: u8,
 
-    /// Event UUID
-    pub event_id: u128,
+    /// Aligns the following fields to 8 bytes
+    pub padding: [u8; 5],
 
-    /// Option description
-    pub description: [u8; 256],
+    /// Event UUID, little-endian
+    pub event_id: [u8; 16],
 
     /// Option votes
     pub votes: u64,
 
     /// Option vault balance
     pub vault_balance: u64,
+
+    /// Space for future fields
+    pub reserved: [u8; ACCOUNT_RESERVE_SPACE],
 }
 
 impl EventOption {
-    pub const LEN: usize = DISCRIMINATOR_LENGTH + EventOption::INIT_SPACE;
+    pub const LEN: usize = DISCRIMINATOR_LENGTH + std::mem::size_of::<EventOption>();
+    pub const VERSION: u8 = 3;
+
+    /// Event UUID
+    pub fn event_id(&self) -> u128 {
+        u128::from_le_bytes(self.event_id)
+    }
+}
+
+/// Option text, only touched when the option is created or edited
+#[account]
+#[derive(InitSpace)]
+pub struct EventOptionMeta {
+    /// Account version
+    pub version: u8,
+
+    /// Option index
+    pub index: u8,
+
+    /// Event UUID
+    pub event_id: u128,
+
+    /// Option description
+    pub description: [u8; 256],
+}
+
+impl EventOptionMeta {
+    pub const LEN: usize =
+        DISCRIMINATOR_LENGTH + ACCOUNT_RESERVE_SPACE + EventOptionMeta::INIT_SPACE;
     pub const VERSION: u8 = 1;
 }
+
+/// Pools of every option of an event, replacing the option accounts for events that opt in
+#[account(zero_copy)]
+pub struct OptionsTable {
+    /// Account version
+    pub version: u8,
+
+    /// Canonical bump of the table address
+    pub bump: u8,
+
+    /// Aligns the following fields to 8 bytes
+    pub padding: [u8; 6],
+
+    /// Event UUID, little-endian
+    pub event_id: [u8; 16],
+
+    /// Votes per option index
+    pub votes: [u64; OPTIONS_TABLE_SIZE],
+
+    /// Vault balance per option index
+    pub vault_balances: [u64; OPTIONS_TABLE_SIZE],
+
+    /// Space for future fields
+    pub reserved: [u8; ACCOUNT_RESERVE_SPACE],
+}
+
+impl OptionsTable {
+    pub const LEN: usize = DISCRIMINATOR_LENGTH + std::mem::size_of::<OptionsTable>();
+    pub const VERSION: u8 = 1;
+
+    /// Event UUID
+    pub fn event_id(&self) -> u128 {
+        u128::from_le_bytes(self.event_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem::{align_of, offset_of, size_of};
+
+    #[test]
+    fn event_option_layout() {
+        assert_eq!(size_of::<EventOption>(), 72);
+        assert_eq!(align_of::<EventOption>(), 8);
+        assert_eq!(offset_of!(EventOption, version), 0);
+        assert_eq!(offset_of!(EventOption, index), 2);
+        assert_eq!(offset_of!(EventOption, event_id), 8);
+        assert_eq!(offset_of!(EventOption, votes), 24);
+        assert_eq!(offset_of!(EventOption, vault_balance), 32);
+        assert_eq!(offset_of!(EventOption, reserved), 40);
+    }
+
+    #[test]
+    fn options_table_layout() {
+        assert_eq!(size_of::<OptionsTable>(), 24 + 16 * OPTIONS_TABLE_SIZE + 32);
+        assert_eq!(align_of::<OptionsTable>(), 8);
+        assert_eq!(offset_of!(OptionsTable, version), 0);
+        assert_eq!(offset_of!(OptionsTable, event_id), 8);
+        assert_eq!(offset_of!(OptionsTable, votes), 24);
+        assert_eq!(
+            offset_of!(OptionsTable, vault_balances),
+            24 + 8 * OPTIONS_TABLE_SIZE
+        );
+        assert_eq!(
+            offset_of!(OptionsTable, reserved),
+            24 + 16 * OPTIONS_TABLE_SIZE
+        );
+    }
+}
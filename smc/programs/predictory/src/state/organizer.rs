@@ -0,0 +1,42 @@
+use super::DISCRIMINATOR_LENGTH;
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct EventOrganizer {
+    /// Account version
+    pub version: u8,
+
+    /// Event UUID
+    pub event_id: u128,
+
+    /// Co-organizer wallet account
+    pub member: Pubkey,
+
+    /// Granted permission flags
+    pub permissions: u8,
+
+    /// Co-organizer share of the organizer reward, in percent
+    pub reward_share: u8,
+
+    /// Whether the co-organizer has claimed the reward share
+    pub reward_claimed: bool,
+}
+
+impl EventOrganizer {
+    pub const LEN: usize = DISCRIMINATOR_LENGTH + EventOrganizer::INIT_SPACE;
+    pub const VERSION: u8 = 1;
+
+    /// Allows editing event name, description and dates
+    pub const EDIT_META: u8 = 1 << 0;
+    /// Allows creating and updating event options
+    pub const MANAGE_OPTIONS: u8 = 1 << 1;
+    /// Allows completing the event
+    pub const RESOLVE: u8 = 1 << 2;
+
+    pub const ALL_PERMISSIONS: u8 = Self::EDIT_META | Self::MANAGE_OPTIONS | Self::RESOLVE;
+
+    pub fn can(&self, permission: u8) -> bool {
+        self.permissions & permission == permission
+    }
+}
@@ -0,0 +1,38 @@
+use super::DISCRIMINATOR_LENGTH;
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum ParamKind {
+    /// Trust level multiplier
+    Multiplier,
+    /// Event publishing price
+    EventPrice,
+    /// Flat platform fee taken on settlement
+    PlatformFee,
+    /// Organizer reward, in percent of the event volume
+    OrgReward,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ParamChange {
+    /// Account version
+    pub version: u8,
+
+    /// Changed parameter
+    pub kind: ParamKind,
+
+    /// New parameter value
+    pub value: u64,
+
+    /// Time after which the change can be applied
+    pub effective_at: i64,
+
+    /// Whether the change is waiting to be applied
+    pub pending: bool,
+}
+
+impl ParamChange {
+    pub const LEN: usize = DISCRIMINATOR_LENGTH + ParamChange::INIT_SPACE;
+    pub const VERSION: u8 = 1;
+}
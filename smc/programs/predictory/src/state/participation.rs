@@ -1,32 +1,68 @@
-use super::DISCRIMINATOR_LENGTH;
+use super::{ACCOUNT_RESERVE_SPACE, DISCRIMINATOR_LENGTH};
 use anchor_lang::prelude::*;
 
-#[account]
-#[derive(InitSpace)]
+/// Fields are grouped by alignment so the `repr(C)` layout has no implicit padding
+#[account(zero_copy)]
 pub struct Participation {
     /// Participant round version, default to 1
     pub version: u8,
 
-    /// Event UUID
-    pub event_id: u128,
-
-    /// User wallet account
-    pub payer: Pubkey,
+    /// Canonical bump of the participation address
+    pub bump: u8,
 
     /// Chosen event option index
     pub option: u8,
 
-    /// How much the user has deposited
-    pub deposited_amount: u64,
-
     /// Whether the user has claimed tokens or recharged SOL
-    pub is_claimed: bool,
+    pub is_claimed: u8,
 
     /// Whether the user has appealed
-    pub appealed: bool,
+    pub appealed: u8,
+
+    /// Aligns the following fields
+    pub padding: [u8; 1],
+
+    /// Portfolio page that tracks this participation
+    pub portfolio_page: u16,
+
+    /// Event UUID, little-endian
+    pub event_id: [u8; 16],
+
+    /// User wallet account
+    pub payer: Pubkey,
+
+    /// How much the user has deposited
+    pub deposited_amount: u64,
+
+    /// Space for future fields
+    pub reserved: [u8; ACCOUNT_RESERVE_SPACE],
 }
 
 impl Participation {
-    pub const LEN: usize = DISCRIMINATOR_LENGTH + Participation::INIT_SPACE;
-    pub const VERSION: u8 = 1;
+    pub const LEN: usize = DISCRIMINATOR_LENGTH + std::mem::size_of::<Participation>();
+    pub const VERSION: u8 = 3;
+
+    /// Event UUID
+    pub fn event_id(&self) -> u128 {
+        u128::from_le_bytes(self.event_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem::{align_of, offset_of, size_of};
+
+    #[test]
+    fn participation_layout() {
+        assert_eq!(size_of::<Participation>(), 96);
+        assert_eq!(align_of::<Participation>(), 8);
+        assert_eq!(offset_of!(Participation, version), 0);
+        assert_eq!(offset_of!(Participation, appealed), 4);
+        assert_eq!(offset_of!(Participation, portfolio_page), 6);
+        assert_eq!(offset_of!(Participation, event_id), 8);
+        assert_eq!(offset_of!(Participation, payer), 24);
+        assert_eq!(offset_of!(Participation, deposited_amount), 56);
+        assert_eq!(offset_of!(Participation, reserved), 64);
+    }
 }
@@ -0,0 +1,82 @@
+use super::{event::Event, DISCRIMINATOR_LENGTH, PORTFOLIO_PAGE_SIZE};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum PositionStatus {
+    /// Position is waiting for the event outcome
+    Open,
+    /// Position owner has appealed the event result
+    Appealed,
+    /// Event resolved to the chosen option, the position can be claimed
+    Won,
+    /// Event resolved to another option, the position only earns trust
+    Lost,
+    /// Event was canceled, the deposit can be recharged
+    Refundable,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct PortfolioEntry {
+    /// Event UUID
+    pub event_id: u128,
+
+    /// Chosen event option index
+    pub option: u8,
+
+    /// How much the user has deposited
+    pub amount: u64,
+
+    /// Position settlement status
+    pub status: PositionStatus,
+}
+
+impl PortfolioEntry {
+    /// Takes the status of the position from its event, appeals stand until the dispute is settled
+    pub fn refresh(&mut self, event: &Event) {
+        if event.canceled != 0 {
+            self.status = PositionStatus::Refundable;
+        } else if let (Some(result), false) = (event.result(), event.disputed != 0) {
+            self.status = if result == self.option {
+                PositionStatus::Won
+            } else {
+                PositionStatus::Lost
+            };
+        }
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Portfolio {
+    /// Account version
+    pub version: u8,
+
+    /// User wallet account
+    pub owner: Pubkey,
+
+    /// Page index
+    pub page: u16,
+
+    /// Unsettled user positions
+    #[max_len(PORTFOLIO_PAGE_SIZE)]
+    pub entries: Vec<PortfolioEntry>,
+}
+
+impl Portfolio {
+    pub const LEN: usize = DISCRIMINATOR_LENGTH + Portfolio::INIT_SPACE;
+    pub const VERSION: u8 = 1;
+
+    pub fn is_full(&self) -> bool {
+        self.entries.len() >= PORTFOLIO_PAGE_SIZE
+    }
+
+    pub fn entry_mut(&mut self, event_id: u128) -> Option<&mut PortfolioEntry> {
+        self.entries.iter_mut().find(|e| e.event_id == event_id)
+    }
+
+    pub fn remove(&mut self, event_id: u128) {
+        if let Some(ix) = self.entries.iter().position(|e| e.event_id == event_id) {
+            self.entries.swap_remove(ix);
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use super::DISCRIMINATOR_LENGTH;
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum Role {
+    /// Manages protocol parameters and fees
+    FeeManager,
+    /// Cancels events and moderates content
+    Moderator,
+    /// Resolves disputed events
+    Resolver,
+    /// Pauses the contract and freezes events
+    Guardian,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct RoleMember {
+    /// Account version
+    pub version: u8,
+
+    /// Granted role
+    pub role: Role,
+
+    /// Member wallet account
+    pub member: Pubkey,
+}
+
+impl RoleMember {
+    pub const LEN: usize = DISCRIMINATOR_LENGTH + RoleMember::INIT_SPACE;
+    pub const VERSION: u8 = 1;
+}
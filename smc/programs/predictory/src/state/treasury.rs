@@ -0,0 +1,33 @@
+use super::{DISCRIMINATOR_LENGTH, MAX_TREASURY_RECIPIENTS};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct TreasuryRecipient {
+    /// Recipient wallet account
+    pub wallet: Pubkey,
+
+    /// Recipient share, in basis points
+    pub share_bps: u16,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Treasury {
+    /// Account version
+    pub version: u8,
+
+    /// Revenue split table
+    #[max_len(MAX_TREASURY_RECIPIENTS)]
+    pub recipients: Vec<TreasuryRecipient>,
+
+    /// Total amount collected by the treasury
+    pub total_collected: u64,
+
+    /// Total amount paid out to the recipients
+    pub total_distributed: u64,
+}
+
+impl Treasury {
+    pub const LEN: usize = DISCRIMINATOR_LENGTH + Treasury::INIT_SPACE;
+    pub const VERSION: u8 = 1;
+}
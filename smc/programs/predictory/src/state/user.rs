@@ -1,12 +1,17 @@
-use super::DISCRIMINATOR_LENGTH;
+use super::{ACCOUNT_RESERVE_SPACE, DISCRIMINATOR_LENGTH};
 use anchor_lang::prelude::*;
 
-#[account]
-#[derive(InitSpace)]
+#[account(zero_copy)]
 pub struct User {
     /// User round version, default to 1
     pub version: u8,
 
+    /// Canonical bump of the user address
+    pub bump: u8,
+
+    /// Aligns the following fields to 8 bytes
+    pub padding: [u8; 6],
+
     /// User wallet account
     pub payer: Pubkey,
 
@@ -19,11 +24,37 @@ pub struct User {
     /// User trust level
     pub trust_lvl: u64,
 
-    /// User name
+    /// User name, never read by instructions so it stays inline
     pub name: [u8; 32],
+
+    /// Unix time the trust level was last raised, zero before the first raise.
+    /// Carved from the reserve, which existing accounts left zeroed
+    pub trust_raised_at: i64,
+
+    /// Space for future fields
+    pub reserved: [u8; ACCOUNT_RESERVE_SPACE - 8],
 }
 
 impl User {
-    pub const LEN: usize = DISCRIMINATOR_LENGTH + User::INIT_SPACE;
-    pub const VERSION: u8 = 1;
+    pub const LEN: usize = DISCRIMINATOR_LENGTH + std::mem::size_of::<User>();
+    pub const VERSION: u8 = 3;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem::{align_of, offset_of, size_of};
+
+    #[test]
+    fn user_layout() {
+        assert_eq!(size_of::<User>(), 128);
+        assert_eq!(align_of::<User>(), 8);
+        assert_eq!(offset_of!(User, version), 0);
+        assert_eq!(offset_of!(User, payer), 8);
+        assert_eq!(offset_of!(User, stake), 40);
+        assert_eq!(offset_of!(User, trust_lvl), 56);
+        assert_eq!(offset_of!(User, name), 64);
+        assert_eq!(offset_of!(User, trust_raised_at), 96);
+        assert_eq!(offset_of!(User, reserved), 104);
+    }
 }
@@ -53,6 +53,7 @@ async function main() {
     .accounts({
       authority: provider.publicKey,
       option: optionAddress,
+      organizer: null,
     })
     .rpc();
 }
@@ -0,0 +1,476 @@
+import {
+  web3,
+  AnchorProvider,
+  setProvider,
+  Program,
+  workspace,
+  BN,
+} from "@coral-xyz/anchor";
+import { v4 as uuidv4 } from "uuid";
+
+import { Predictory } from "../target/types/predictory";
+
+import { expectThrowError } from "./util/console";
+import { programError } from "./util/error";
+import {
+  findAppealAddress,
+  findEventAddress,
+  findEventOptionAddress,
+  findParticipantAddress,
+  findPortfolioAddress,
+  findProgramDataAddress,
+  findTreasuryAddress,
+  findUserAddress,
+} from "./util/entity";
+import {
+  airdrop,
+  bufferFromString,
+  ignoreIfExist,
+  ONE_SOL,
+  uuidToBn,
+  waitUntil,
+} from "./util/setup";
+
+describe("Appeal tests", () => {
+  const provider = AnchorProvider.env();
+  setProvider(provider);
+
+  const program = workspace.Predictory as Program<Predictory>;
+
+  const organizer = web3.Keypair.generate();
+  const alice = web3.Keypair.generate();
+  const bob = web3.Keypair.generate();
+  const carol = web3.Keypair.generate();
+
+  const eventId = uuidToBn(uuidv4());
+
+  const [event] = findEventAddress(eventId);
+  const [organizerUser] = findUserAddress(organizer.publicKey);
+  const [treasury] = findTreasuryAddress();
+
+  let endDate: number;
+
+  async function createUser(owner: web3.Keypair) {
+    await airdrop(provider.connection, owner.publicKey);
+
+    await program.methods
+      .createUser(Array.from(bufferFromString("Appeal user", 32)))
+      .accounts({ sender: owner.publicKey })
+      .signers([owner])
+      .rpc();
+
+    await program.methods
+      .transferStake(ONE_SOL)
+      .accounts({ sender: owner.publicKey })
+      .signers([owner])
+      .rpc();
+
+    await program.methods
+      .createPortfolioPage(0)
+      .accounts({ sender: owner.publicKey })
+      .signers([owner])
+      .rpc();
+  }
+
+  function vote(
+    participant: web3.Keypair,
+    option: number,
+    amount: BN,
+    id = eventId
+  ) {
+    return program.methods
+      .vote(id, option, amount)
+      .accounts({
+        sender: participant.publicKey,
+        option: findEventOptionAddress(id, option)[0],
+        optionsTable: null,
+        portfolio: findPortfolioAddress(participant.publicKey, 0)[0],
+      })
+      .signers([participant])
+      .rpc();
+  }
+
+  function claim(participant: web3.Keypair, option: number, id = eventId) {
+    return program.methods
+      .claimEventReward(id)
+      .accounts({
+        sender: participant.publicKey,
+        option: findEventOptionAddress(id, option)[0],
+        optionsTable: null,
+        portfolio: findPortfolioAddress(participant.publicKey, 0)[0],
+      })
+      .signers([participant])
+      .rpc();
+  }
+
+  function appeal(appellant: web3.Keypair, option: number, id = eventId) {
+    return program.methods
+      .appeal(id)
+      .accounts({
+        sender: appellant.publicKey,
+        option: findEventOptionAddress(id, option)[0],
+        optionsTable: null,
+        portfolio: findPortfolioAddress(appellant.publicKey, 0)[0],
+      })
+      .signers([appellant])
+      .rpc();
+  }
+
+  async function createPublishedEvent(
+    id: BN,
+    name: string,
+    startDate: number,
+    endDate: number
+  ) {
+    await program.methods
+      .createEvent(id, {
+        name: Array.from(bufferFromString(name, 32)),
+        isPrivate: false,
+        description: Array.from(bufferFromString("Description", 256)),
+        startDate: new BN(startDate),
+        endDate: new BN(endDate),
+        participationDeadline: null,
+      })
+      .accounts({ authority: organizer.publicKey })
+      .signers([organizer])
+      .rpc();
+
+    for (const [index, description] of ["Yes", "No"].entries()) {
+      await program.methods
+        .createEventOption(
+          id,
+          index,
+          Array.from(bufferFromString(description, 256))
+        )
+        .accounts({
+          authority: organizer.publicKey,
+          option: findEventOptionAddress(id, index)[0],
+          organizer: null,
+        })
+        .signers([organizer])
+        .rpc();
+    }
+
+    await program.methods
+      .publishEvent(id)
+      .accounts({ authority: organizer.publicKey })
+      .signers([organizer])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    const [programData] = findProgramDataAddress();
+
+    await ignoreIfExist(() =>
+      program.methods
+        .initializeContractState(
+          provider.publicKey,
+          new BN(5),
+          ONE_SOL.muln(33).divn(1000),
+          ONE_SOL.muln(33).divn(1000),
+          new BN(10)
+        )
+        .accounts({
+          authority: provider.publicKey,
+          programData,
+        })
+        .rpc()
+    );
+
+    for (const owner of [organizer, alice, bob, carol]) {
+      await createUser(owner);
+    }
+
+    const now = Math.round(new Date().getTime() / 1000);
+    const startDate = now + 4;
+    endDate = now + 8;
+
+    await createPublishedEvent(eventId, "Appealed event", startDate, endDate);
+
+    await waitUntil(startDate + 1);
+
+    await vote(alice, 0, ONE_SOL.divn(10));
+    await vote(bob, 1, ONE_SOL.divn(5));
+    await vote(carol, 1, ONE_SOL.divn(10));
+  });
+
+  it("success - organizer stake is slashed once", async () => {
+    // The appellation window of the test build closes one second after the end date
+    await waitUntil(endDate + 1);
+
+    await program.methods
+      .completeEvent(eventId, 0)
+      .accounts({
+        authority: organizer.publicKey,
+        organizer: null,
+      })
+      .signers([organizer])
+      .rpc();
+
+    const fetchedEventBefore = await program.account.event.fetch(event);
+    const fetchedOrganizerBefore = await program.account.user.fetch(
+      organizerUser
+    );
+    const eventBalanceBefore = await provider.connection.getBalance(event);
+    const treasuryBalanceBefore = await provider.connection.getBalance(
+      treasury
+    );
+
+    // Both appeals outweigh the result: few appellants holding most of the losing volume
+    await appeal(bob, 1);
+
+    const fetchedEventAfterFirst = await program.account.event.fetch(event);
+    const fetchedOrganizerAfter = await program.account.user.fetch(
+      organizerUser
+    );
+    const eventBalanceAfterFirst = await provider.connection.getBalance(event);
+    const treasuryBalanceAfterFirst = await provider.connection.getBalance(
+      treasury
+    );
+
+    const orgReward = fetchedEventBefore.totalAmount
+      .mul(fetchedEventBefore.orgReward)
+      .divn(100);
+    const slashed = fetchedEventBefore.stake
+      .add(fetchedEventBefore.platformFee)
+      .add(orgReward)
+      .toNumber();
+
+    expect(fetchedEventBefore.stake.gtn(0)).toBeTruthy();
+    expect(fetchedEventAfterFirst.stake.isZero()).toBeTruthy();
+    expect(fetchedEventAfterFirst.disputed).toBeTruthy();
+    expect(
+      fetchedOrganizerAfter.lockedStake.eq(
+        fetchedOrganizerBefore.lockedStake.sub(fetchedEventBefore.stake)
+      )
+    ).toBeTruthy();
+    expect(
+      fetchedOrganizerAfter.stake.eq(fetchedOrganizerBefore.stake)
+    ).toBeTruthy();
+    expect(eventBalanceAfterFirst).toEqual(eventBalanceBefore - slashed);
+    expect(treasuryBalanceAfterFirst).toEqual(treasuryBalanceBefore + slashed);
+
+    await appeal(carol, 1);
+
+    const fetchedAppellationAccount = await program.account.appellation.fetch(
+      findAppealAddress(eventId)[0]
+    );
+
+    expect(fetchedAppellationAccount.disagreeCount.toNumber()).toEqual(2);
+    expect(await provider.connection.getBalance(event)).toEqual(
+      eventBalanceAfterFirst
+    );
+    expect(await provider.connection.getBalance(treasury)).toEqual(
+      treasuryBalanceAfterFirst
+    );
+
+    // Bettors' pools stay on the event
+    expect(eventBalanceAfterFirst).toBeGreaterThanOrEqual(
+      fetchedEventBefore.totalAmount.sub(orgReward).toNumber()
+    );
+  });
+
+  it("success - participations are marked appealed", async () => {
+    for (const appellant of [bob, carol]) {
+      const fetchedParticipationAccount =
+        await program.account.participation.fetch(
+          findParticipantAddress(eventId, appellant.publicKey)[0]
+        );
+
+      expect(fetchedParticipationAccount.appealed).toBeTruthy();
+    }
+  });
+
+  it("fail - claim while disputed", async () => {
+    await waitUntil(Math.round(new Date().getTime() / 1000) + 2);
+
+    await expectThrowError(
+      () => claim(alice, 0),
+      programError("EventDisputed")
+    );
+  });
+
+  it("fail - settle while disputed", async () => {
+    await expectThrowError(
+      () =>
+        program.methods
+          .settleBatch(eventId)
+          .accounts({
+            sender: provider.publicKey,
+            option: findEventOptionAddress(eventId, 0)[0],
+            optionsTable: null,
+          })
+          .remainingAccounts(
+            [
+              findParticipantAddress(eventId, alice.publicKey)[0],
+              findUserAddress(alice.publicKey)[0],
+              findPortfolioAddress(alice.publicKey, 0)[0],
+            ].map((pubkey) => ({ pubkey, isWritable: true, isSigner: false }))
+          )
+          .rpc(),
+      programError("EventDisputed")
+    );
+  });
+
+  it("success - only the resolved winners are paid", async () => {
+    await ignoreIfExist(() =>
+      program.methods
+        .grantRole({ resolver: {} }, provider.publicKey)
+        .accounts({ authority: provider.publicKey })
+        .rpc()
+    );
+
+    await program.methods
+      .resolveDispute(eventId, 1)
+      .accounts({ sender: provider.publicKey })
+      .rpc();
+
+    const eventBalanceBefore = await provider.connection.getBalance(event);
+
+    await claim(bob, 1);
+    await claim(carol, 1);
+
+    const fetchedEventAccount = await program.account.event.fetch(event);
+
+    expect(fetchedEventAccount.disputed).toBeFalsy();
+    expect(fetchedEventAccount.result).toEqual(1);
+    expect(
+      (await program.account.participation.fetch(
+        findParticipantAddress(eventId, alice.publicKey)[0]
+      )).isClaimed
+    ).toBeFalsy();
+
+    // Winners share the pools net of the fees already taken by the slash
+    const orgReward = fetchedEventAccount.totalAmount
+      .mul(fetchedEventAccount.orgReward)
+      .divn(100);
+    const paid =
+      eventBalanceBefore - (await provider.connection.getBalance(event));
+
+    expect(paid).toBeLessThanOrEqual(
+      fetchedEventAccount.totalAmount
+        .sub(fetchedEventAccount.platformFee)
+        .sub(orgReward)
+        .toNumber()
+    );
+  });
+
+  it("success - refresh marks the losing position", async () => {
+    const [portfolio] = findPortfolioAddress(alice.publicKey, 0);
+
+    await program.methods
+      .refreshPortfolio(alice.publicKey, 0)
+      .accounts({ sender: provider.publicKey })
+      .remainingAccounts([{ pubkey: event, isWritable: false, isSigner: false }])
+      .rpc();
+
+    const fetchedPortfolioAccount = await program.account.portfolio.fetch(
+      portfolio
+    );
+    const entry = fetchedPortfolioAccount.entries.find((entry) =>
+      entry.eventId.eq(eventId)
+    );
+
+    expect(entry.status).toEqual({ lost: {} });
+  });
+
+  describe("unresolved dispute", () => {
+    const expiringEventId = uuidToBn(uuidv4());
+    const [expiringEvent] = findEventAddress(expiringEventId);
+
+    let expiringEndDate: number;
+
+    beforeAll(async () => {
+      const now = Math.round(new Date().getTime() / 1000);
+      const startDate = now + 4;
+      expiringEndDate = now + 8;
+
+      await createPublishedEvent(
+        expiringEventId,
+        "Expiring dispute",
+        startDate,
+        expiringEndDate
+      );
+
+      await waitUntil(startDate + 1);
+
+      await vote(alice, 0, ONE_SOL.divn(10), expiringEventId);
+      await vote(bob, 1, ONE_SOL.divn(5), expiringEventId);
+      await vote(carol, 1, ONE_SOL.divn(10), expiringEventId);
+
+      await waitUntil(expiringEndDate + 1);
+
+      await program.methods
+        .completeEvent(expiringEventId, 0)
+        .accounts({
+          authority: organizer.publicKey,
+          organizer: null,
+        })
+        .signers([organizer])
+        .rpc();
+
+      await appeal(bob, 1, expiringEventId);
+    });
+
+    it("fail - expire while resolvers can still act", async () => {
+      await expectThrowError(
+        () =>
+          program.methods
+            .expireDispute(expiringEventId)
+            .accounts({ sender: provider.publicKey })
+            .rpc(),
+        programError("DisputeWindowOpen")
+      );
+    });
+
+    it("success - the original result stands", async () => {
+      // Appellation window of one second followed by a resolution deadline of two
+      await waitUntil(expiringEndDate + 4);
+
+      await program.methods
+        .expireDispute(expiringEventId)
+        .accounts({ sender: provider.publicKey })
+        .rpc();
+
+      const fetchedEventAccount = await program.account.event.fetch(
+        expiringEvent
+      );
+
+      expect(fetchedEventAccount.disputed).toBeFalsy();
+      expect(fetchedEventAccount.result).toEqual(0);
+
+      await claim(alice, 0, expiringEventId);
+
+      expect(
+        (await program.account.participation.fetch(
+          findParticipantAddress(expiringEventId, alice.publicKey)[0]
+        )).isClaimed
+      ).toBeTruthy();
+    });
+
+    it("fail - expire an undisputed event", async () => {
+      await expectThrowError(
+        () =>
+          program.methods
+            .expireDispute(expiringEventId)
+            .accounts({ sender: provider.publicKey })
+            .rpc(),
+        programError("EventNotDisputed")
+      );
+    });
+  });
+
+  it("fail - refresh with an event off the page", async () => {
+    await expectThrowError(
+      () =>
+        program.methods
+          .refreshPortfolio(bob.publicKey, 0)
+          .accounts({ sender: provider.publicKey })
+          .remainingAccounts([
+            { pubkey: event, isWritable: false, isSigner: false },
+          ])
+          .rpc(),
+      programError("InvalidPortfolioAccounts")
+    );
+  });
+});
@@ -0,0 +1,224 @@
+import {
+  web3,
+  AnchorProvider,
+  setProvider,
+  Program,
+  workspace,
+  BN,
+} from "@coral-xyz/anchor";
+import { v4 as uuidv4 } from "uuid";
+
+import { Predictory } from "../target/types/predictory";
+
+import { expectThrowError } from "./util/console";
+import { programError } from "./util/error";
+import {
+  findAttestationAddress,
+  findEventOptionAddress,
+  findProgramDataAddress,
+} from "./util/entity";
+import {
+  airdrop,
+  bufferFromString,
+  ignoreIfExist,
+  ONE_SOL,
+  uuidToBn,
+  waitUntil,
+} from "./util/setup";
+
+describe("Resolution attestation tests", () => {
+  const provider = AnchorProvider.env();
+  setProvider(provider);
+
+  const program = workspace.Predictory as Program<Predictory>;
+
+  const organizer = web3.Keypair.generate();
+
+  const eventId = uuidToBn(uuidv4());
+  const now = Math.round(new Date().getTime() / 1000);
+  const endDate = now + 4;
+
+  const [attestation] = findAttestationAddress(eventId);
+
+  async function createPublishedEvent(
+    id: BN,
+    startDate: number,
+    endDate: number
+  ) {
+    await program.methods
+      .createEvent(id, {
+        name: Array.from(bufferFromString("Attested event", 32)),
+        isPrivate: false,
+        description: Array.from(bufferFromString("Description", 256)),
+        startDate: new BN(startDate),
+        endDate: new BN(endDate),
+        participationDeadline: null,
+      })
+      .accounts({ authority: organizer.publicKey })
+      .signers([organizer])
+      .rpc();
+
+    for (const [index, description] of ["Yes", "No"].entries()) {
+      await program.methods
+        .createEventOption(
+          id,
+          index,
+          Array.from(bufferFromString(description, 256))
+        )
+        .accounts({
+          authority: organizer.publicKey,
+          option: findEventOptionAddress(id, index)[0],
+          organizer: null,
+        })
+        .signers([organizer])
+        .rpc();
+    }
+
+    await program.methods
+      .publishEvent(id)
+      .accounts({ authority: organizer.publicKey })
+      .signers([organizer])
+      .rpc();
+  }
+
+  function completeEvent(result: number) {
+    return program.methods
+      .completeEvent(eventId, result)
+      .accounts({
+        authority: organizer.publicKey,
+        organizer: null,
+      })
+      .signers([organizer])
+      .rpc();
+  }
+
+  function finalizeEvent(id: BN) {
+    return program.methods
+      .finalizeEvent(id)
+      .accounts({ sender: provider.publicKey })
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    await airdrop(provider.connection, organizer.publicKey);
+
+    const [programData] = findProgramDataAddress();
+
+    await ignoreIfExist(() =>
+      program.methods
+        .initializeContractState(
+          provider.publicKey,
+          new BN(5),
+          ONE_SOL.muln(33).divn(1000),
+          ONE_SOL.muln(33).divn(1000),
+          new BN(10)
+        )
+        .accounts({
+          authority: provider.publicKey,
+          programData,
+        })
+        .rpc()
+    );
+
+    await program.methods
+      .createUser(Array.from(bufferFromString("Organizer", 32)))
+      .accounts({ sender: organizer.publicKey })
+      .signers([organizer])
+      .rpc();
+
+    await program.methods
+      .transferStake(ONE_SOL)
+      .accounts({ sender: organizer.publicKey })
+      .signers([organizer])
+      .rpc();
+
+    await createPublishedEvent(eventId, now + 2, endDate);
+  });
+
+  it("fail - finalize an event without a result", async () => {
+    await expectThrowError(
+      () => finalizeEvent(eventId),
+      programError("EventIsNotOver")
+    );
+  });
+
+  it("fail - complete with an unknown option", async () => {
+    await waitUntil(endDate + 1);
+
+    await expectThrowError(
+      () => completeEvent(2),
+      programError("InvalidIndex")
+    );
+  });
+
+  it("success - completion records a pending attestation", async () => {
+    await completeEvent(1);
+
+    const fetchedAttestationAccount =
+      await program.account.resolutionAttestation.fetch(attestation);
+
+    expect(fetchedAttestationAccount.version).toEqual(1);
+    expect(fetchedAttestationAccount.eventId.eq(eventId)).toBeTruthy();
+    expect(fetchedAttestationAccount.status).toEqual({ pending: {} });
+    expect(fetchedAttestationAccount.result).toEqual(1);
+    expect(fetchedAttestationAccount.finalizedSlot.isZero()).toBeTruthy();
+    expect(
+      fetchedAttestationAccount.resolver.equals(organizer.publicKey)
+    ).toBeTruthy();
+  });
+
+  it("success - finalize after the appellation window", async () => {
+    // Appellation window of the test build
+    await waitUntil(Math.round(new Date().getTime() / 1000) + 2);
+
+    await finalizeEvent(eventId);
+
+    const fetchedAttestationAccount =
+      await program.account.resolutionAttestation.fetch(attestation);
+
+    expect(fetchedAttestationAccount.status).toEqual({ resolved: {} });
+    expect(fetchedAttestationAccount.result).toEqual(1);
+    expect(fetchedAttestationAccount.finalizedSlot.gtn(0)).toBeTruthy();
+  });
+
+  it("fail - finalize twice", async () => {
+    await expectThrowError(
+      () => finalizeEvent(eventId),
+      programError("EventFinalized")
+    );
+  });
+
+  it("fail - complete a finalized event", async () => {
+    await expectThrowError(
+      () => completeEvent(0),
+      programError("EventFinalized")
+    );
+  });
+
+  it("success - cancel seals the attestation", async () => {
+    const canceledEventId = uuidToBn(uuidv4());
+    const nowDate = Math.round(new Date().getTime() / 1000);
+
+    await createPublishedEvent(canceledEventId, nowDate + 60, nowDate + 120);
+
+    await program.methods
+      .cancelEvent(canceledEventId)
+      .accounts({
+        sender: organizer.publicKey,
+        moderator: null,
+      })
+      .signers([organizer])
+      .rpc();
+
+    const fetchedAttestationAccount =
+      await program.account.resolutionAttestation.fetch(
+        findAttestationAddress(canceledEventId)[0]
+      );
+
+    expect(fetchedAttestationAccount.status).toEqual({ canceled: {} });
+    expect(fetchedAttestationAccount.finalizedSlot.gtn(0)).toBeTruthy();
+    expect(
+      fetchedAttestationAccount.resolver.equals(organizer.publicKey)
+    ).toBeTruthy();
+  });
+});
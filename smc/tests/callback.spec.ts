@@ -0,0 +1,338 @@
+import {
+  web3,
+  AnchorProvider,
+  setProvider,
+  Program,
+  workspace,
+  BN,
+} from "@coral-xyz/anchor";
+import { v4 as uuidv4 } from "uuid";
+
+import { Predictory } from "../target/types/predictory";
+import { MockConsumer } from "../target/types/mock_consumer";
+
+import { expectThrowError } from "./util/console";
+import { programError } from "./util/error";
+import {
+  findCallbackAddress,
+  findContractStateAddress,
+  findEventAddress,
+  findEventMetaAddress,
+  findEventOptionAddress,
+  findParticipantAddress,
+  findPortfolioAddress,
+  findProgramDataAddress,
+  findUserAddress,
+} from "./util/entity";
+import {
+  airdrop,
+  bufferFromString,
+  ignoreIfExist,
+  ONE_SOL,
+  uuidToBn,
+  waitUntil,
+} from "./util/setup";
+
+describe("CPI and callback tests", () => {
+  const provider = AnchorProvider.env();
+  setProvider(provider);
+
+  const program = workspace.Predictory as Program<Predictory>;
+  const consumer = workspace.MockConsumer as Program<MockConsumer>;
+
+  const organizer = web3.Keypair.generate();
+
+  // System-owned PDA of the consumer program, acting as its Predictory user
+  const [vault] = web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("vault")],
+    consumer.programId
+  );
+  const [vaultUser] = findUserAddress(vault);
+  const [vaultPortfolio] = findPortfolioAddress(vault, 0);
+
+  const eventId = uuidToBn(uuidv4());
+  const now = Math.round(new Date().getTime() / 1000);
+  const startDate = now + 4;
+  const endDate = now + 10;
+
+  const [event] = findEventAddress(eventId);
+  const [callback] = findCallbackAddress(eventId);
+  const [record] = web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("record"), eventId.toBuffer("le", 16)],
+    consumer.programId
+  );
+
+  const amount = ONE_SOL.divn(10);
+
+  function eventArgs(name: string) {
+    return {
+      name: Array.from(bufferFromString(name, 32)),
+      isPrivate: false,
+      description: Array.from(bufferFromString("Description", 256)),
+      startDate: new BN(startDate),
+      endDate: new BN(endDate),
+      participationDeadline: null,
+    };
+  }
+
+  function notifyCallback(callbackProgram = consumer.programId) {
+    return program.methods
+      .notifyCallback(eventId)
+      .accounts({ callbackProgram })
+      .remainingAccounts([
+        { pubkey: record, isSigner: false, isWritable: true },
+      ])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    await airdrop(provider.connection, organizer.publicKey);
+
+    const [programData] = findProgramDataAddress();
+
+    await ignoreIfExist(() =>
+      program.methods
+        .initializeContractState(
+          provider.publicKey,
+          new BN(5),
+          ONE_SOL.muln(33).divn(1000),
+          ONE_SOL.muln(33).divn(1000),
+          new BN(10)
+        )
+        .accounts({
+          authority: provider.publicKey,
+          programData,
+        })
+        .rpc()
+    );
+
+    // The vault pays for the accounts it creates through CPI
+    await provider.sendAndConfirm(
+      new web3.Transaction().add(
+        web3.SystemProgram.transfer({
+          fromPubkey: provider.publicKey,
+          toPubkey: vault,
+          lamports: ONE_SOL.muln(2).toNumber(),
+        })
+      )
+    );
+
+    await program.methods
+      .createUser(Array.from(bufferFromString("Organizer", 32)))
+      .accounts({ sender: organizer.publicKey })
+      .signers([organizer])
+      .rpc();
+
+    await program.methods
+      .transferStake(ONE_SOL)
+      .accounts({ sender: organizer.publicKey })
+      .signers([organizer])
+      .rpc();
+  });
+
+  it("success - PDA user through CPI", async () => {
+    await consumer.methods
+      .createUser(Array.from(bufferFromString("Consumer", 32)))
+      .accounts({ user: vaultUser })
+      .rpc();
+
+    await consumer.methods
+      .transferStake(ONE_SOL)
+      .accounts({ user: vaultUser })
+      .rpc();
+
+    await consumer.methods
+      .createPortfolioPage(0)
+      .accounts({ portfolio: vaultPortfolio })
+      .rpc();
+
+    const fetchedUserAccount = await program.account.user.fetch(vaultUser);
+
+    expect(fetchedUserAccount.payer.equals(vault)).toBeTruthy();
+    expect(fetchedUserAccount.stake.eq(ONE_SOL)).toBeTruthy();
+  });
+
+  it("success - PDA user creates an event through CPI", async () => {
+    const vaultEventId = uuidToBn(uuidv4());
+    const [vaultEvent] = findEventAddress(vaultEventId);
+
+    await consumer.methods
+      .createEvent(vaultEventId, eventArgs("Consumer event"))
+      .accounts({
+        user: vaultUser,
+        state: findContractStateAddress()[0],
+        event: vaultEvent,
+        eventMeta: findEventMetaAddress(vaultEventId)[0],
+      })
+      .rpc();
+
+    const fetchedEventAccount = await program.account.event.fetch(vaultEvent);
+
+    expect(fetchedEventAccount.authority.equals(vault)).toBeTruthy();
+  });
+
+  it("success - set event callback", async () => {
+    await program.methods
+      .createEvent(eventId, eventArgs("Callback event"))
+      .accounts({ authority: organizer.publicKey })
+      .signers([organizer])
+      .rpc();
+
+    for (const [index, description] of ["Yes", "No"].entries()) {
+      await program.methods
+        .createEventOption(
+          eventId,
+          index,
+          Array.from(bufferFromString(description, 256))
+        )
+        .accounts({
+          authority: organizer.publicKey,
+          option: findEventOptionAddress(eventId, index)[0],
+          organizer: null,
+        })
+        .signers([organizer])
+        .rpc();
+    }
+
+    await program.methods
+      .setEventCallback(eventId, consumer.programId)
+      .accounts({ authority: organizer.publicKey })
+      .signers([organizer])
+      .rpc();
+
+    const fetchedEventAccount = await program.account.event.fetch(event);
+    const fetchedCallbackAccount = await program.account.eventCallback.fetch(
+      callback
+    );
+
+    expect(fetchedEventAccount.callback).toBeTruthy();
+    expect(fetchedCallbackAccount.eventId.eq(eventId)).toBeTruthy();
+    expect(
+      fetchedCallbackAccount.program.equals(consumer.programId)
+    ).toBeTruthy();
+  });
+
+  it("fail - delete event with a callback", async () => {
+    await expectThrowError(
+      () =>
+        program.methods
+          .deleteEvent(eventId)
+          .accounts({
+            authority: organizer.publicKey,
+            optionsTable: null,
+          })
+          .signers([organizer])
+          .rpc(),
+      programError("CallbackRegistered")
+    );
+  });
+
+  it("success - PDA user votes through CPI", async () => {
+    await program.methods
+      .publishEvent(eventId)
+      .accounts({ authority: organizer.publicKey })
+      .signers([organizer])
+      .rpc();
+
+    await consumer.methods
+      .watchEvent(eventId)
+      .accounts({ sender: provider.publicKey })
+      .rpc();
+
+    await waitUntil(startDate + 1);
+
+    await consumer.methods
+      .vote(eventId, 0, amount)
+      .accounts({
+        user: vaultUser,
+        state: findContractStateAddress()[0],
+        event,
+        option: findEventOptionAddress(eventId, 0)[0],
+        optionsTable: null,
+        participation: findParticipantAddress(eventId, vault)[0],
+        portfolio: vaultPortfolio,
+      })
+      .rpc();
+
+    const fetchedParticipationAccount =
+      await program.account.participation.fetch(
+        findParticipantAddress(eventId, vault)[0]
+      );
+
+    expect(fetchedParticipationAccount.option).toEqual(0);
+    expect(fetchedParticipationAccount.depositedAmount.eq(amount)).toBeTruthy();
+  });
+
+  it("success - a rejecting callback does not block completion", async () => {
+    await consumer.methods.setRejecting(eventId, true).rpc();
+
+    await waitUntil(endDate + 1);
+
+    await program.methods
+      .completeEvent(eventId, 1)
+      .accounts({
+        authority: organizer.publicKey,
+        organizer: null,
+      })
+      .signers([organizer])
+      .rpc();
+
+    const fetchedEventAccount = await program.account.event.fetch(event);
+    const fetchedRecordAccount = await consumer.account.record.fetch(record);
+
+    expect(fetchedEventAccount.resolved).toBeTruthy();
+    expect(fetchedEventAccount.result).toEqual(1);
+    expect(fetchedRecordAccount.resolved).toBeFalsy();
+  });
+
+  it("fail - notify while appeals are open", async () => {
+    await expectThrowError(
+      () => notifyCallback(),
+      programError("AppellationWindowOpen")
+    );
+  });
+
+  it("fail - notify another callback program", async () => {
+    // Appellation window of the test build
+    await waitUntil(Math.round(new Date().getTime() / 1000) + 2);
+
+    await expectThrowError(
+      () => notifyCallback(program.programId),
+      programError("InvalidCallbackAccounts")
+    );
+  });
+
+  it("fail - callback program rejects the result", async () => {
+    await expectThrowError(() => notifyCallback(), /Callback rejected/);
+
+    const fetchedCallbackAccount = await program.account.eventCallback.fetch(
+      callback
+    );
+
+    expect(fetchedCallbackAccount.notified).toBeFalsy();
+  });
+
+  it("success - notify the callback program", async () => {
+    await consumer.methods.setRejecting(eventId, false).rpc();
+
+    await notifyCallback();
+
+    const fetchedRecordAccount = await consumer.account.record.fetch(record);
+    const fetchedCallbackAccount = await program.account.eventCallback.fetch(
+      callback
+    );
+
+    expect(fetchedRecordAccount.eventId.eq(eventId)).toBeTruthy();
+    expect(fetchedRecordAccount.resolved).toBeTruthy();
+    expect(fetchedRecordAccount.result).toEqual(1);
+    expect(fetchedCallbackAccount.notified).toBeTruthy();
+    expect(fetchedCallbackAccount.notifiedResult).toEqual(1);
+  });
+
+  it("fail - notify twice", async () => {
+    await expectThrowError(
+      () => notifyCallback(),
+      programError("CallbackNotified")
+    );
+  });
+});
@@ -0,0 +1,186 @@
+import {
+  web3,
+  AnchorProvider,
+  setProvider,
+  Program,
+  workspace,
+  BN,
+} from "@coral-xyz/anchor";
+import { v4 as uuidv4 } from "uuid";
+
+import { Predictory } from "../target/types/predictory";
+
+import {
+  findEventOptionAddress,
+  findEventOptionMetaAddress,
+  findPortfolioAddress,
+  findProgramDataAddress,
+} from "./util/entity";
+import {
+  airdrop,
+  bufferFromString,
+  ignoreIfExist,
+  ONE_SOL,
+  uuidToBn,
+  waitUntil,
+} from "./util/setup";
+
+// Hot path budgets, a regression in the account layouts shows up here first
+const VOTE_CU_BUDGET = 40_000;
+const CLAIM_CU_BUDGET = 45_000;
+
+describe("Compute budget tests", () => {
+  const provider = AnchorProvider.env();
+  setProvider(provider);
+
+  const program = workspace.Predictory as Program<Predictory>;
+
+  const organizer = web3.Keypair.generate();
+  const participant = web3.Keypair.generate();
+
+  const eventId = uuidToBn(uuidv4());
+  const now = Math.round(new Date().getTime() / 1000);
+  const startDate = now + 3;
+  const endDate = now + 8;
+
+  async function computeUnits(signature: string): Promise<number> {
+    await provider.connection.confirmTransaction(signature, "confirmed");
+
+    const transaction = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+
+    return transaction.meta.computeUnitsConsumed;
+  }
+
+  async function createUser(owner: web3.Keypair) {
+    await program.methods
+      .createUser(Array.from(bufferFromString("Benchmark", 32)))
+      .accounts({ sender: owner.publicKey })
+      .signers([owner])
+      .rpc();
+
+    await program.methods
+      .transferStake(ONE_SOL)
+      .accounts({ sender: owner.publicKey })
+      .signers([owner])
+      .rpc();
+
+    await program.methods
+      .createPortfolioPage(0)
+      .accounts({ sender: owner.publicKey })
+      .signers([owner])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    await airdrop(provider.connection, organizer.publicKey);
+    await airdrop(provider.connection, participant.publicKey);
+
+    const [programData] = findProgramDataAddress();
+
+    await ignoreIfExist(() =>
+      program.methods
+        .initializeContractState(
+          provider.publicKey,
+          new BN(5),
+          ONE_SOL.muln(33).divn(1000),
+          ONE_SOL.muln(33).divn(1000),
+          new BN(10)
+        )
+        .accounts({
+          authority: provider.publicKey,
+          programData,
+        })
+        .rpc()
+    );
+
+    await createUser(organizer);
+    await createUser(participant);
+
+    await program.methods
+      .createEvent(eventId, {
+        name: Array.from(bufferFromString("Benchmark event", 32)),
+        isPrivate: false,
+        description: Array.from(bufferFromString("Description", 256)),
+        startDate: new BN(startDate),
+        endDate: new BN(endDate),
+        participationDeadline: null,
+      })
+      .accounts({ authority: organizer.publicKey })
+      .signers([organizer])
+      .rpc();
+
+    await program.methods
+      .createEventOptions(eventId, ["Yes", "No"])
+      .accounts({
+        authority: organizer.publicKey,
+        organizer: null,
+      })
+      .remainingAccounts(
+        [0, 1].flatMap((index) =>
+          [
+            findEventOptionAddress(eventId, index)[0],
+            findEventOptionMetaAddress(eventId, index)[0],
+          ].map((pubkey) => ({ pubkey, isSigner: false, isWritable: true }))
+        )
+      )
+      .signers([organizer])
+      .rpc();
+
+    await program.methods
+      .publishEvent(eventId)
+      .accounts({ authority: organizer.publicKey })
+      .signers([organizer])
+      .rpc();
+
+    await waitUntil(startDate + 1);
+  });
+
+  it("vote stays within budget", async () => {
+    const signature = await program.methods
+      .vote(eventId, 0, ONE_SOL.divn(10))
+      .accounts({
+        sender: participant.publicKey,
+        option: findEventOptionAddress(eventId, 0)[0],
+        optionsTable: null,
+        portfolio: findPortfolioAddress(participant.publicKey, 0)[0],
+      })
+      .signers([participant])
+      .rpc();
+
+    expect(await computeUnits(signature)).toBeLessThanOrEqual(VOTE_CU_BUDGET);
+  });
+
+  it("claim_event_reward stays within budget", async () => {
+    await waitUntil(endDate + 1);
+
+    await program.methods
+      .completeEvent(eventId, 0)
+      .accounts({
+        authority: organizer.publicKey,
+        organizer: null,
+      })
+      .signers([organizer])
+      .rpc();
+
+    // Appellation window of the test build
+    await waitUntil(Math.round(new Date().getTime() / 1000) + 2);
+
+    const signature = await program.methods
+      .claimEventReward(eventId)
+      .accounts({
+        sender: participant.publicKey,
+        option: findEventOptionAddress(eventId, 0)[0],
+        optionsTable: null,
+        portfolio: findPortfolioAddress(participant.publicKey, 0)[0],
+      })
+      .signers([participant])
+      .rpc();
+
+    expect(await computeUnits(signature)).toBeLessThanOrEqual(
+      CLAIM_CU_BUDGET
+    );
+  });
+});
@@ -7,6 +7,8 @@ import {
   BN,
 } from "@coral-xyz/anchor";
 
+import { expectThrowError } from "./util/console";
+import { programError } from "./util/error";
 import { TestToken } from "./util/token";
 import {
   airdrop,
@@ -24,8 +26,11 @@ import {
   findEventAddress,
   findEventMetaAddress,
   findEventOptionAddress,
+  findEventOptionMetaAddress,
   findParticipantAddress,
+  findPortfolioAddress,
   findProgramDataAddress,
+  findTreasuryAddress,
   findUserAddress,
 } from "./util/entity";
 
@@ -85,11 +90,19 @@ describe("General event test", () => {
       // Creation:
       try {
         await program.methods
-          .setContractAuthority(authority.publicKey)
+          .proposeAuthority(authority.publicKey)
           .accounts({
             authority: provider.publicKey,
           })
           .rpc();
+
+        await program.methods
+          .acceptAuthority()
+          .accounts({
+            sender: authority.publicKey,
+          })
+          .signers([authority])
+          .rpc();
       } catch (error) {
         if (!error.message.includes("AccountNotInitialized")) {
           throw error;
@@ -244,22 +257,23 @@ describe("General event test", () => {
       // Fetching event:
       const fetchedEventAccount = await program.account.event.fetch(event);
 
-      expect(fetchedEventAccount.id.eq(eventId)).toBeTruthy();
+      expect(new BN(fetchedEventAccount.id, "le").eq(eventId)).toBeTruthy();
       expect(fetchedEventAccount.authority).toEqual(authority.publicKey);
-      expect(fetchedEventAccount.stake.eq(eventPrice)).toBeTruthy();
+      expect(fetchedEventAccount.stake.eq(new BN(0))).toBeTruthy();
+      expect(fetchedEventAccount.published).toEqual(0);
       expect(fetchedEventAccount.startDate).toEqual(args.startDate);
       expect(fetchedEventAccount.endDate).toEqual(args.endDate);
-      expect(fetchedEventAccount.participationDeadline).toEqual(
-        args.participationDeadline
-      );
+      // Zero stands for no participation deadline
+      expect(fetchedEventAccount.participationDeadline.isZero()).toBeTruthy();
       expect(fetchedEventAccount.optionCount).toEqual(0);
-      expect(fetchedEventAccount.canceled).toEqual(false);
-      expect(fetchedEventAccount.result).toBeNull();
+      expect(fetchedEventAccount.canceled).toEqual(0);
+      expect(fetchedEventAccount.resolved).toEqual(0);
+      expect(fetchedEventAccount.bump).toEqual(findEventAddress(eventId)[1]);
 
       const fetchedUserAccount = await program.account.user.fetch(user);
 
-      expect(fetchedUserAccount.stake.eq(ONE_SOL.sub(eventPrice))).toBeTruthy();
-      expect(fetchedUserAccount.lockedStake.eq(eventPrice)).toBeTruthy();
+      expect(fetchedUserAccount.stake.eq(ONE_SOL)).toBeTruthy();
+      expect(fetchedUserAccount.lockedStake.eq(new BN(0))).toBeTruthy();
 
       // Fetching event meta:
       const fetchedEventMetaAccount = await program.account.eventMeta.fetch(
@@ -272,6 +286,115 @@ describe("General event test", () => {
     });
   });
 
+  describe("publish_event", () => {
+    beforeAll(async () => {
+      await createNewEvent();
+      await createOption();
+    });
+
+    it("fail - not enough options", async () => {
+      await expectThrowError(
+        () =>
+          program.methods
+            .publishEvent(eventId)
+            .accounts({
+              authority: authority.publicKey,
+            })
+            .signers([authority])
+            .rpc(),
+        programError("NotEnoughOptions")
+      );
+    });
+
+    it("success", async () => {
+      await createOption();
+
+      const [user] = findUserAddress(authority.publicKey);
+      const [event] = findEventAddress(eventId);
+      const fetchedUserBefore = await program.account.user.fetch(user);
+
+      await program.methods
+        .publishEvent(eventId)
+        .accounts({
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      // Fetching event:
+      const fetchedEventAccount = await program.account.event.fetch(event);
+
+      expect(fetchedEventAccount.published).toEqual(1);
+      expect(fetchedEventAccount.stake.eq(eventPrice)).toBeTruthy();
+
+      // Fetching user:
+      const fetchedUserAccount = await program.account.user.fetch(user);
+
+      expect(
+        fetchedUserAccount.stake.eq(fetchedUserBefore.stake.sub(eventPrice))
+      ).toBeTruthy();
+      expect(
+        fetchedUserAccount.lockedStake.eq(
+          fetchedUserBefore.lockedStake.add(eventPrice)
+        )
+      ).toBeTruthy();
+    });
+
+    it("fail - end date already passed", async () => {
+      const now = new BN(Math.round(new Date().getTime()) / 1000);
+
+      await createNewEvent(now.subn(20), now.subn(10));
+      await createOption();
+      await createOption();
+
+      await expectThrowError(
+        () =>
+          program.methods
+            .publishEvent(eventId)
+            .accounts({
+              authority: authority.publicKey,
+            })
+            .signers([authority])
+            .rpc(),
+        programError("InvalidEndDate")
+      );
+    });
+  });
+
+  describe("delete_event", () => {
+    beforeAll(async () => {
+      await createNewEvent();
+      await createOption();
+    });
+
+    it("success", async () => {
+      const [event] = findEventAddress(eventId);
+      const [eventMeta] = findEventMetaAddress(eventId);
+      const [eventOption] = findEventOptionAddress(eventId, 0);
+      const [eventOptionMeta] = findEventOptionMetaAddress(eventId, 0);
+
+      await program.methods
+        .deleteEvent(eventId)
+        .accounts({
+          authority: authority.publicKey,
+          optionsTable: null,
+        })
+        .remainingAccounts([
+          { pubkey: eventOption, isSigner: false, isWritable: true },
+          { pubkey: eventOptionMeta, isSigner: false, isWritable: true },
+        ])
+        .signers([authority])
+        .rpc();
+
+      expect(await provider.connection.getAccountInfo(event)).toBeNull();
+      expect(await provider.connection.getAccountInfo(eventMeta)).toBeNull();
+      expect(await provider.connection.getAccountInfo(eventOption)).toBeNull();
+      expect(
+        await provider.connection.getAccountInfo(eventOptionMeta)
+      ).toBeNull();
+    });
+  });
+
   describe("update_event", () => {
     beforeAll(async () => {
       await createNewEvent();
@@ -293,6 +416,7 @@ describe("General event test", () => {
         .updateEventName(eventId, newName)
         .accounts({
           authority: authority.publicKey,
+          organizer: null,
         })
         .signers([authority])
         .rpc();
@@ -302,6 +426,7 @@ describe("General event test", () => {
         .updateEventDescription(eventId, newDescription)
         .accounts({
           authority: authority.publicKey,
+          organizer: null,
         })
         .signers([authority])
         .rpc();
@@ -311,6 +436,7 @@ describe("General event test", () => {
         .updateEventParticipationDeadline(eventId, newParticipationDeadline)
         .accounts({
           authority: authority.publicKey,
+          organizer: null,
         })
         .signers([authority])
         .rpc();
@@ -320,6 +446,7 @@ describe("General event test", () => {
         .updateEventEndDate(eventId, newEndDate)
         .accounts({
           authority: authority.publicKey,
+          organizer: null,
         })
         .signers([authority])
         .rpc();
@@ -328,9 +455,9 @@ describe("General event test", () => {
       const fetchedEventAccount = await program.account.event.fetch(event);
 
       expect(fetchedEventAccount.endDate).toEqual(newEndDate);
-      expect(fetchedEventAccount.participationDeadline).toEqual(
-        newParticipationDeadline
-      );
+      expect(
+        fetchedEventAccount.participationDeadline.eq(newParticipationDeadline)
+      ).toBeTruthy();
 
       // Fetching event meta:
       const fetchedEventMetaAccount = await program.account.eventMeta.fetch(
@@ -345,12 +472,10 @@ describe("General event test", () => {
   describe("cancel_event", () => {
     it("before start", async () => {
       const now = new BN(Math.round(new Date().getTime()) / 1000);
-      await createNewEvent(now.addn(5), now.addn(10));
+      await createPublishedEvent(now.addn(5), now.addn(10));
 
-      const [state] = findContractStateAddress();
       const [event] = findEventAddress(eventId);
       const [user] = findUserAddress(authority.publicKey);
-      const fetchedStateAccount = await program.account.state.fetch(state);
 
       const userBalanceBefore = await provider.connection.getBalance(user);
       const fetcheUserAccountBefore = await program.account.user.fetch(user);
@@ -360,7 +485,7 @@ describe("General event test", () => {
         .cancelEvent(eventId)
         .accounts({
           sender: authority.publicKey,
-          contractAdmin: fetchedStateAccount.authority,
+          moderator: null,
         })
         .signers([authority])
         .rpc();
@@ -392,12 +517,10 @@ describe("General event test", () => {
 
     it("after start", async () => {
       const now = new BN(Math.round(new Date().getTime()) / 1000);
-      await createNewEvent(now.subn(5), now.addn(10));
+      await createPublishedEvent(now.subn(5), now.addn(10));
 
-      const [state] = findContractStateAddress();
       const [event] = findEventAddress(eventId);
       const [user] = findUserAddress(authority.publicKey);
-      const fetchedStateAccount = await program.account.state.fetch(state);
 
       const userBalanceBefore = await provider.connection.getBalance(user);
       const fetcheUserAccountBefore = await program.account.user.fetch(user);
@@ -407,7 +530,7 @@ describe("General event test", () => {
         .cancelEvent(eventId)
         .accounts({
           sender: authority.publicKey,
-          contractAdmin: fetchedStateAccount.authority,
+          moderator: null,
         })
         .signers([authority])
         .rpc();
@@ -431,12 +554,10 @@ describe("General event test", () => {
 
     it("by user after deadline", async () => {
       const now = new BN(Math.round(new Date().getTime()) / 1000);
-      await createNewEvent(now.subn(5), now.subn(1));
+      await createPublishedEvent(now.subn(5), now.subn(1));
 
-      const [state] = findContractStateAddress();
       const [event] = findEventAddress(eventId);
       const [user] = findUserAddress(authority.publicKey);
-      const fetchedStateAccount = await program.account.state.fetch(state);
 
       const userBalanceBefore = await provider.connection.getBalance(user);
       const fetcheUserAccountBefore = await program.account.user.fetch(user);
@@ -446,7 +567,7 @@ describe("General event test", () => {
         .cancelEvent(eventId)
         .accounts({
           sender: another_authority.publicKey,
-          contractAdmin: fetchedStateAccount.authority,
+          moderator: null,
         })
         .signers([another_authority])
         .rpc();
@@ -473,7 +594,7 @@ describe("General event test", () => {
     beforeAll(async () => {
       const now = new BN(Math.round(new Date().getTime()) / 1000);
 
-      await createNewEvent(now.subn(100), now.subn(50));
+      await createPublishedEvent(now.subn(100), now.subn(50));
     });
 
     it("success", async () => {
@@ -485,6 +606,7 @@ describe("General event test", () => {
         .completeEvent(eventId, resIndex)
         .accounts({
           authority: authority.publicKey,
+          organizer: null,
         })
         .signers([authority])
         .rpc();
@@ -492,6 +614,7 @@ describe("General event test", () => {
       // Fetching event:
       const fetchedEventAccount = await program.account.event.fetch(event);
 
+      expect(fetchedEventAccount.resolved).toEqual(1);
       expect(fetchedEventAccount.result).toEqual(resIndex);
     });
   });
@@ -517,6 +640,7 @@ describe("General event test", () => {
         .accounts({
           authority: authority.publicKey,
           option: eventOption,
+          organizer: null,
         })
         .signers([authority])
         .rpc();
@@ -526,10 +650,261 @@ describe("General event test", () => {
         eventOption
       );
 
-      expect(fetchedEventOptionAccount.eventId.eq(eventId)).toBeTruthy();
-      expect(fetchedEventOptionAccount.description).toEqual(description);
+      expect(
+        new BN(fetchedEventOptionAccount.eventId, "le").eq(eventId)
+      ).toBeTruthy();
+      expect(fetchedEventOptionAccount.bump).toEqual(
+        findEventOptionAddress(eventId, index)[1]
+      );
       expect(fetchedEventOptionAccount.vaultBalance.eq(new BN(0))).toBeTruthy();
       expect(fetchedEventOptionAccount.votes.eq(new BN(0))).toBeTruthy();
+
+      // Fetching event option meta:
+      const fetchedEventOptionMetaAccount =
+        await program.account.eventOptionMeta.fetch(
+          findEventOptionMetaAddress(eventId, index)[0]
+        );
+
+      expect(fetchedEventOptionMetaAccount.index).toEqual(index);
+      expect(fetchedEventOptionMetaAccount.description).toEqual(description);
+    });
+  });
+
+  describe("create_event_options", () => {
+    beforeAll(async () => {
+      await createNewEvent();
+    });
+
+    it("success", async () => {
+      const [event] = findEventAddress(eventId);
+      const descriptions = ["First", "Second", "Third"];
+      const options = descriptions.map(
+        (_, index) => findEventOptionAddress(eventId, index)[0]
+      );
+
+      // Create event options:
+      await program.methods
+        .createEventOptions(eventId, descriptions)
+        .accounts({
+          authority: authority.publicKey,
+          organizer: null,
+        })
+        .remainingAccounts(
+          options.flatMap((pubkey, index) =>
+            [pubkey, findEventOptionMetaAddress(eventId, index)[0]].map(
+              (pubkey) => ({
+                pubkey,
+                isSigner: false,
+                isWritable: true,
+              })
+            )
+          )
+        )
+        .signers([authority])
+        .rpc();
+
+      // Fetching event:
+      const fetchedEventAccount = await program.account.event.fetch(event);
+
+      expect(fetchedEventAccount.optionCount).toEqual(3);
+
+      // Fetching event options:
+      for (const [index, option] of options.entries()) {
+        const fetchedEventOptionAccount =
+          await program.account.eventOption.fetch(option);
+
+        const fetchedEventOptionMetaAccount =
+          await program.account.eventOptionMeta.fetch(
+            findEventOptionMetaAddress(eventId, index)[0]
+          );
+
+        expect(fetchedEventOptionAccount.index).toEqual(index);
+        expect(fetchedEventOptionAccount.bump).toEqual(
+          findEventOptionAddress(eventId, index)[1]
+        );
+        expect(fetchedEventOptionMetaAccount.description).toEqual(
+          Array.from(bufferFromString(descriptions[index], 256))
+        );
+      }
+    });
+
+    it("success - pre-funded option address", async () => {
+      const [option] = findEventOptionAddress(eventId, 3);
+      const [optionMeta] = findEventOptionMetaAddress(eventId, 3);
+
+      // Anyone can send lamports to the address before the option exists
+      await provider.sendAndConfirm(
+        new web3.Transaction().add(
+          web3.SystemProgram.transfer({
+            fromPubkey: provider.publicKey,
+            toPubkey: option,
+            lamports: 1000,
+          })
+        )
+      );
+
+      await program.methods
+        .createEventOptions(eventId, ["Fourth"])
+        .accounts({
+          authority: authority.publicKey,
+          organizer: null,
+        })
+        .remainingAccounts(
+          [option, optionMeta].map((pubkey) => ({
+            pubkey,
+            isSigner: false,
+            isWritable: true,
+          }))
+        )
+        .signers([authority])
+        .rpc();
+
+      const fetchedEventOptionAccount = await program.account.eventOption.fetch(
+        option
+      );
+
+      expect(fetchedEventOptionAccount.index).toEqual(3);
+    });
+
+    it("fail - description too long", async () => {
+      await expectThrowError(
+        () =>
+          program.methods
+            .createEventOptions(eventId, ["x".repeat(257)])
+            .accounts({
+              authority: authority.publicKey,
+              organizer: null,
+            })
+            .remainingAccounts(
+              [
+                findEventOptionAddress(eventId, 4)[0],
+                findEventOptionMetaAddress(eventId, 4)[0],
+              ].map((pubkey) => ({
+                pubkey,
+                isSigner: false,
+                isWritable: true,
+              }))
+            )
+            .signers([authority])
+            .rpc(),
+        programError("DescriptionTooLong")
+      );
+    });
+  });
+
+  describe("remove_event_option", () => {
+    beforeAll(async () => {
+      await createNewEvent();
+      await createOption();
+      await createOption();
+      await createOption();
+    });
+
+    it("success - compacts indices", async () => {
+      const [event] = findEventAddress(eventId);
+      const [removedOption] = findEventOptionAddress(eventId, 0);
+      const [removedOptionMeta] = findEventOptionMetaAddress(eventId, 0);
+      const [lastOption] = findEventOptionAddress(eventId, 2);
+      const [lastOptionMeta] = findEventOptionMetaAddress(eventId, 2);
+
+      const fetchedLastOptionBefore =
+        await program.account.eventOptionMeta.fetch(lastOptionMeta);
+
+      await program.methods
+        .removeEventOption(eventId, 0)
+        .accounts({
+          authority: authority.publicKey,
+          option: removedOption,
+          optionMeta: removedOptionMeta,
+          lastOption,
+          lastOptionMeta,
+          organizer: null,
+        })
+        .signers([authority])
+        .rpc();
+
+      // Fetching event:
+      const fetchedEventAccount = await program.account.event.fetch(event);
+
+      expect(fetchedEventAccount.optionCount).toEqual(2);
+
+      // Fetching event options:
+      const fetchedEventOptionMetaAccount =
+        await program.account.eventOptionMeta.fetch(removedOptionMeta);
+
+      expect(fetchedEventOptionMetaAccount.description).toEqual(
+        fetchedLastOptionBefore.description
+      );
+      expect(await provider.connection.getAccountInfo(lastOption)).toBeNull();
+      expect(
+        await provider.connection.getAccountInfo(lastOptionMeta)
+      ).toBeNull();
+    });
+
+    it("success - last option", async () => {
+      const [event] = findEventAddress(eventId);
+      const [lastOption] = findEventOptionAddress(eventId, 1);
+      const [lastOptionMeta] = findEventOptionMetaAddress(eventId, 1);
+
+      await program.methods
+        .removeEventOption(eventId, 1)
+        .accounts({
+          authority: authority.publicKey,
+          option: lastOption,
+          optionMeta: lastOptionMeta,
+          lastOption: null,
+          lastOptionMeta: null,
+          organizer: null,
+        })
+        .signers([authority])
+        .rpc();
+
+      // Fetching event:
+      const fetchedEventAccount = await program.account.event.fetch(event);
+
+      expect(fetchedEventAccount.optionCount).toEqual(1);
+      expect(await provider.connection.getAccountInfo(lastOption)).toBeNull();
+      expect(
+        await provider.connection.getAccountInfo(lastOptionMeta)
+      ).toBeNull();
+    });
+
+    it("success - only option", async () => {
+      const [event] = findEventAddress(eventId);
+
+      await program.methods
+        .removeEventOption(eventId, 0)
+        .accounts({
+          authority: authority.publicKey,
+          option: findEventOptionAddress(eventId, 0)[0],
+          optionMeta: findEventOptionMetaAddress(eventId, 0)[0],
+          lastOption: null,
+          lastOptionMeta: null,
+          organizer: null,
+        })
+        .signers([authority])
+        .rpc();
+
+      expect((await program.account.event.fetch(event)).optionCount).toEqual(
+        0
+      );
+    });
+
+    it("fail - event without options", async () => {
+      await expectThrowError(() =>
+        program.methods
+          .removeEventOption(eventId, 0)
+          .accounts({
+            authority: authority.publicKey,
+            option: findEventOptionAddress(eventId, 0)[0],
+            optionMeta: findEventOptionMetaAddress(eventId, 0)[0],
+            lastOption: findEventOptionAddress(eventId, 0)[0],
+            lastOptionMeta: findEventOptionMetaAddress(eventId, 0)[0],
+            organizer: null,
+          })
+          .signers([authority])
+          .rpc()
+      );
     });
   });
 
@@ -545,13 +920,14 @@ describe("General event test", () => {
         .accounts({
           authority: authority.publicKey,
           option: eventOption,
+          organizer: null,
         })
         .signers([authority])
         .rpc();
     });
 
     it("success", async () => {
-      const [eventOption] = findEventOptionAddress(eventId, 0);
+      const [eventOptionMeta] = findEventOptionMetaAddress(eventId, 0);
 
       const newDescription = Array.from(
         bufferFromString("New description", 256)
@@ -562,17 +938,19 @@ describe("General event test", () => {
         .updateEventOption(eventId, 0, newDescription)
         .accounts({
           authority: authority.publicKey,
-          option: eventOption,
+          optionMeta: eventOptionMeta,
+          organizer: null,
         })
         .signers([authority])
         .rpc();
 
-      // Fetching event option:
-      const fetchedEventOptionAccount = await program.account.eventOption.fetch(
-        eventOption
-      );
+      // Fetching event option meta:
+      const fetchedEventOptionMetaAccount =
+        await program.account.eventOptionMeta.fetch(eventOptionMeta);
 
-      expect(fetchedEventOptionAccount.description).toEqual(newDescription);
+      expect(fetchedEventOptionMetaAccount.description).toEqual(
+        newDescription
+      );
     });
   });
 
@@ -585,6 +963,7 @@ describe("General event test", () => {
 
       await createOption();
       await createOption();
+      await publishEvent();
     });
 
     it("success", async () => {
@@ -596,6 +975,7 @@ describe("General event test", () => {
         another_authority.publicKey
       );
       const [eventOption] = findEventOptionAddress(eventId, 0);
+      const [portfolio] = findPortfolioAddress(another_authority.publicKey, 0);
 
       const userBalanceBefore = await provider.connection.getBalance(user);
       const fetchedUserBefore = await program.account.user.fetch(user);
@@ -609,6 +989,8 @@ describe("General event test", () => {
           .accounts({
             sender: another_authority.publicKey,
             option: eventOption,
+            optionsTable: null,
+            portfolio,
           })
           .signers([another_authority])
           .rpc();
@@ -620,7 +1002,9 @@ describe("General event test", () => {
       const fetchedParticipationAccount =
         await program.account.participation.fetch(participant);
 
-      expect(fetchedParticipationAccount.eventId.eq(eventId)).toBeTruthy();
+      expect(
+        new BN(fetchedParticipationAccount.eventId, "le").eq(eventId)
+      ).toBeTruthy();
       expect(fetchedParticipationAccount.payer).toEqual(
         another_authority.publicKey
       );
@@ -630,6 +1014,22 @@ describe("General event test", () => {
       ).toBeTruthy();
       expect(fetchedParticipationAccount.isClaimed).toBeFalsy();
       expect(fetchedParticipationAccount.appealed).toBeFalsy();
+      expect(fetchedParticipationAccount.portfolioPage).toEqual(0);
+
+      // Fetching portfolio:
+      const fetchedPortfolioAccount = await program.account.portfolio.fetch(
+        portfolio
+      );
+
+      expect(fetchedPortfolioAccount.entries.length).toEqual(1);
+      expect(
+        fetchedPortfolioAccount.entries[0].eventId.eq(eventId)
+      ).toBeTruthy();
+      expect(fetchedPortfolioAccount.entries[0].option).toEqual(0);
+      expect(
+        fetchedPortfolioAccount.entries[0].amount.eq(participationAmount)
+      ).toBeTruthy();
+      expect(fetchedPortfolioAccount.entries[0].status).toEqual({ open: {} });
 
       // Fetching event option:
       const fetchedEventOptionAccount = await program.account.eventOption.fetch(
@@ -678,6 +1078,7 @@ describe("General event test", () => {
 
       await createOption(another_authority);
       await createOption(another_authority);
+      await publishEvent(another_authority);
 
       await sleep(2000);
 
@@ -690,6 +1091,33 @@ describe("General event test", () => {
       await completeEvent(0, another_authority);
     });
 
+    it("event odds", async () => {
+      const [event] = findEventAddress(eventId);
+      const options = [0, 1].map((index) => ({
+        pubkey: findEventOptionAddress(eventId, index)[0],
+        isWritable: false,
+        isSigner: false,
+      }));
+
+      const odds = await program.methods
+        .eventOdds(eventId)
+        .accounts({})
+        .remainingAccounts(options)
+        .view();
+
+      const fetchedEventAccount = await program.account.event.fetch(event);
+
+      expect(odds.totalAmount.eq(fetchedEventAccount.totalAmount)).toBeTruthy();
+      expect(odds.options.length).toEqual(2);
+      expect(
+        odds.options[0].payoutPerSol.eq(
+          ONE_SOL.mul(odds.availableForWinners).div(
+            odds.options[0].vaultBalance
+          )
+        )
+      ).toBeTruthy();
+    });
+
     it("first participant claim", async () => {
       // Fetching option index:
       const [event] = findEventAddress(eventId);
@@ -698,27 +1126,41 @@ describe("General event test", () => {
 
       const [participant] = findParticipantAddress(eventId, alice.publicKey);
       const [eventOption] = findEventOptionAddress(eventId, 0);
+      const [portfolio] = findPortfolioAddress(alice.publicKey, 0);
+      const [treasury] = findTreasuryAddress();
 
       const adminBalanceBefore = await provider.connection.getBalance(
         adminUser
       );
       const userBalanceBefore = await provider.connection.getBalance(user);
-      const contractAdminBalanceBefore = await provider.connection.getBalance(
-        authority.publicKey
+      const treasuryBalanceBefore = await provider.connection.getBalance(
+        treasury
       );
       const fetchedUserBefore = await program.account.user.fetch(user);
       const fetchedAdminUserBefore = await program.account.user.fetch(
         adminUser
       );
 
+      const quote = await program.methods
+        .quoteClaim(eventId, alice.publicKey)
+        .accounts({
+          option: eventOption,
+          optionsTable: null,
+        })
+        .view();
+
+      expect(quote.resolved).toBeTruthy();
+      expect(quote.won).toBeTruthy();
+
       try {
         // Create event option:
         await program.methods
           .claimEventReward(eventId)
           .accounts({
             sender: alice.publicKey,
-            contractAdmin: authority.publicKey,
             option: eventOption,
+            optionsTable: null,
+            portfolio,
           })
           .signers([alice])
           .rpc();
@@ -731,8 +1173,8 @@ describe("General event test", () => {
 
       const fetchedAdminUserAfter = await program.account.user.fetch(adminUser);
       const adminBalanceAfter = await provider.connection.getBalance(adminUser);
-      const contractAdminBalanceAfter = await provider.connection.getBalance(
-        authority.publicKey
+      const treasuryBalanceAfter = await provider.connection.getBalance(
+        treasury
       );
 
       // Reporter released:
@@ -760,8 +1202,8 @@ describe("General event test", () => {
         adminBalanceBefore + adminReward.toNumber() + eventPrice.toNumber()
       );
 
-      expect(contractAdminBalanceAfter).toEqual(
-        contractAdminBalanceBefore + platformFee.toNumber()
+      expect(treasuryBalanceAfter).toEqual(
+        treasuryBalanceBefore + platformFee.toNumber()
       );
 
       // Fetching participation:
@@ -771,6 +1213,17 @@ describe("General event test", () => {
       expect(fetchedParticipationAccount.isClaimed).toBeTruthy();
       expect(fetchedParticipationAccount.appealed).toBeFalsy();
 
+      // Fetching portfolio:
+      const fetchedPortfolioAccount = await program.account.portfolio.fetch(
+        portfolio
+      );
+
+      expect(
+        fetchedPortfolioAccount.entries.find((entry) =>
+          entry.eventId.eq(eventId)
+        )
+      ).toBeUndefined();
+
       // Fetching user:
       const userBalanceAfter = await provider.connection.getBalance(user);
       const fetchedUserAccount = await program.account.user.fetch(user);
@@ -779,8 +1232,10 @@ describe("General event test", () => {
       );
 
       const amount = participationAmount
-        .div(fetchedOptionAccount.vaultBalance)
-        .mul(availableForWinners);
+        .mul(availableForWinners)
+        .div(fetchedOptionAccount.vaultBalance);
+
+      expect(quote.payout.eq(amount)).toBeTruthy();
 
       expect(
         fetchedUserAccount.stake.eq(fetchedUserBefore.stake.add(amount))
@@ -797,13 +1252,15 @@ describe("General event test", () => {
 
       const [participant] = findParticipantAddress(eventId, bob.publicKey);
       const [eventOption] = findEventOptionAddress(eventId, 0);
+      const [portfolio] = findPortfolioAddress(bob.publicKey, 0);
+      const [treasury] = findTreasuryAddress();
 
       const adminBalanceBefore = await provider.connection.getBalance(
         adminUser
       );
       const userBalanceBefore = await provider.connection.getBalance(user);
-      const contractAdminBalanceBefore = await provider.connection.getBalance(
-        authority.publicKey
+      const treasuryBalanceBefore = await provider.connection.getBalance(
+        treasury
       );
       const fetchedUserBefore = await program.account.user.fetch(user);
       const fetchedAdminUserBefore = await program.account.user.fetch(
@@ -816,8 +1273,9 @@ describe("General event test", () => {
           .claimEventReward(eventId)
           .accounts({
             sender: bob.publicKey,
-            contractAdmin: authority.publicKey,
             option: eventOption,
+            optionsTable: null,
+            portfolio,
           })
           .signers([bob])
           .rpc();
@@ -830,8 +1288,8 @@ describe("General event test", () => {
 
       const fetchedAdminUserAfter = await program.account.user.fetch(adminUser);
       const adminBalanceAfter = await provider.connection.getBalance(adminUser);
-      const contractAdminBalanceAfter = await provider.connection.getBalance(
-        authority.publicKey
+      const treasuryBalanceAfter = await provider.connection.getBalance(
+        treasury
       );
 
       // Reporter released:
@@ -851,7 +1309,7 @@ describe("General event test", () => {
 
       expect(adminBalanceAfter).toEqual(adminBalanceBefore);
 
-      expect(contractAdminBalanceAfter).toEqual(contractAdminBalanceBefore);
+      expect(treasuryBalanceAfter).toEqual(treasuryBalanceBefore);
 
       // Fetching participation:
       const fetchedParticipationAccount =
@@ -860,6 +1318,17 @@ describe("General event test", () => {
       expect(fetchedParticipationAccount.isClaimed).toBeTruthy();
       expect(fetchedParticipationAccount.appealed).toBeFalsy();
 
+      // Fetching portfolio:
+      const fetchedPortfolioAccount = await program.account.portfolio.fetch(
+        portfolio
+      );
+
+      expect(
+        fetchedPortfolioAccount.entries.find((entry) =>
+          entry.eventId.eq(eventId)
+        )
+      ).toBeUndefined();
+
       // Fetching user:
       const userBalanceAfter = await provider.connection.getBalance(user);
       const fetchedUserAccount = await program.account.user.fetch(user);
@@ -868,8 +1337,8 @@ describe("General event test", () => {
       );
 
       const amount = participationAmount
-        .div(fetchedOptionAccount.vaultBalance)
-        .mul(availableForWinners);
+        .mul(availableForWinners)
+        .div(fetchedOptionAccount.vaultBalance);
 
       expect(
         fetchedUserAccount.stake.eq(fetchedUserBefore.stake.add(amount))
@@ -879,6 +1348,352 @@ describe("General event test", () => {
     });
   });
 
+  describe("settle_batch", () => {
+    const participants = () => [alice, bob, carol];
+
+    const settlementAccounts = () =>
+      participants().flatMap((participant) =>
+        [
+          findParticipantAddress(eventId, participant.publicKey)[0],
+          findUserAddress(participant.publicKey)[0],
+          findPortfolioAddress(participant.publicKey, 0)[0],
+        ].map((pubkey) => ({ pubkey, isWritable: true, isSigner: false }))
+      );
+
+    const settleBatch = () =>
+      program.methods
+        .settleBatch(eventId)
+        .accounts({
+          sender: eve.publicKey,
+          option: findEventOptionAddress(eventId, 0)[0],
+          optionsTable: null,
+        })
+        .remainingAccounts(settlementAccounts())
+        .signers([eve])
+        .rpc();
+
+    beforeAll(async () => {
+      const now = new BN(Math.round(new Date().getTime()) / 1000);
+      await createNewEvent(now.addn(2), now.addn(5), another_authority);
+
+      await createOption(another_authority);
+      await createOption(another_authority);
+      await publishEvent(another_authority);
+
+      await sleep(2000);
+
+      await participate(alice, 0);
+      await participate(bob, 1);
+      await participate(carol, 0);
+
+      await sleep(2000);
+      await completeEvent(0, another_authority);
+      await sleep(2000);
+    });
+
+    it("success", async () => {
+      const [event] = findEventAddress(eventId);
+      const users = participants().map(
+        (participant) => findUserAddress(participant.publicKey)[0]
+      );
+
+      const fetchedUsersBefore = await Promise.all(
+        users.map((user) => program.account.user.fetch(user))
+      );
+
+      try {
+        await settleBatch();
+      } catch (error) {
+        throw new Error(error);
+      }
+
+      const fetchedEventAccount = await program.account.event.fetch(event);
+      const fetchedOptionAccount = await program.account.eventOption.fetch(
+        findEventOptionAddress(eventId, 0)[0]
+      );
+
+      expect(fetchedEventAccount.stake.eq(new BN(0))).toBeTruthy();
+
+      const availableForWinners = fetchedEventAccount.totalAmount
+        .sub(fetchedEventAccount.totalAmount.mul(orgReward).divn(100))
+        .sub(platformFee);
+      const payout = participationAmount
+        .mul(availableForWinners)
+        .div(fetchedOptionAccount.vaultBalance);
+
+      for (const [index, participant] of participants().entries()) {
+        const [participation] = findParticipantAddress(
+          eventId,
+          participant.publicKey
+        );
+        const fetchedParticipationAccount =
+          await program.account.participation.fetch(participation);
+
+        expect(fetchedParticipationAccount.isClaimed).toBeTruthy();
+
+        const fetchedPortfolioAccount = await program.account.portfolio.fetch(
+          findPortfolioAddress(participant.publicKey, 0)[0]
+        );
+
+        expect(
+          fetchedPortfolioAccount.entries.find((entry) =>
+            entry.eventId.eq(eventId)
+          )
+        ).toBeUndefined();
+
+        const fetchedUserAccount = await program.account.user.fetch(
+          users[index]
+        );
+        const expectedPayout =
+          fetchedParticipationAccount.option === 0 ? payout : new BN(0);
+
+        expect(
+          fetchedUserAccount.stake.eq(
+            fetchedUsersBefore[index].stake.add(expectedPayout)
+          )
+        ).toBeTruthy();
+      }
+    });
+
+    it("success - settled positions are skipped", async () => {
+      const users = participants().map(
+        (participant) => findUserAddress(participant.publicKey)[0]
+      );
+      const balancesBefore = await Promise.all(
+        users.map((user) => provider.connection.getBalance(user))
+      );
+
+      try {
+        await settleBatch();
+      } catch (error) {
+        throw new Error(error);
+      }
+
+      const balancesAfter = await Promise.all(
+        users.map((user) => provider.connection.getBalance(user))
+      );
+
+      expect(balancesAfter).toEqual(balancesBefore);
+    });
+
+    it("fail - claim after settlement", async () => {
+      await expectThrowError(
+        () =>
+          program.methods
+            .claimEventReward(eventId)
+            .accounts({
+              sender: alice.publicKey,
+              option: findEventOptionAddress(eventId, 0)[0],
+              optionsTable: null,
+              portfolio: findPortfolioAddress(alice.publicKey, 0)[0],
+            })
+            .signers([alice])
+            .rpc(),
+        programError("AlreadyClaimed")
+      );
+    });
+
+    it("fail - incomplete accounts", async () => {
+      await expectThrowError(
+        () =>
+          program.methods
+            .settleBatch(eventId)
+            .accounts({
+              sender: eve.publicKey,
+              option: findEventOptionAddress(eventId, 0)[0],
+              optionsTable: null,
+            })
+            .remainingAccounts(settlementAccounts().slice(1))
+            .signers([eve])
+            .rpc(),
+        programError("InvalidSettlementAccounts")
+      );
+    });
+  });
+
+  describe("claim_many", () => {
+    const eventIds: BN[] = [];
+
+    const claimAccounts = (options: number[]) =>
+      eventIds.flatMap((id, index) => [
+        {
+          pubkey: findEventAddress(id)[0],
+          isWritable: true,
+          isSigner: false,
+        },
+        {
+          pubkey: findEventOptionAddress(id, options[index])[0],
+          isWritable: false,
+          isSigner: false,
+        },
+        {
+          pubkey: findParticipantAddress(id, alice.publicKey)[0],
+          isWritable: true,
+          isSigner: false,
+        },
+      ]);
+
+    const claimMany = (options = [0, 1]) =>
+      program.methods
+        .claimMany(eventIds.length)
+        .accounts({
+          sender: alice.publicKey,
+        })
+        .remainingAccounts([
+          ...claimAccounts(options),
+          {
+            pubkey: findPortfolioAddress(alice.publicKey, 0)[0],
+            isWritable: true,
+            isSigner: false,
+          },
+        ])
+        .signers([alice]);
+
+    beforeAll(async () => {
+      // Alice wins the first event and loses the second one
+      for (const option of [0, 1]) {
+        const now = new BN(Math.round(new Date().getTime()) / 1000);
+        await createNewEvent(now.addn(2), now.addn(5), another_authority);
+
+        await createOption(another_authority);
+        await createOption(another_authority);
+        await publishEvent(another_authority);
+
+        await sleep(2000);
+
+        await participate(alice, option);
+        await participate(bob, 1 - option);
+
+        await sleep(2000);
+        await completeEvent(0, another_authority);
+
+        eventIds.push(eventId);
+      }
+
+      await sleep(2000);
+    });
+
+    it("success", async () => {
+      const [user] = findUserAddress(alice.publicKey);
+      const fetchedUserBefore = await program.account.user.fetch(user);
+
+      const results = await claimMany().view();
+
+      expect(results.map((result) => result.status)).toEqual([
+        { claimed: {} },
+        { claimed: {} },
+      ]);
+      expect(results[0].eventId.eq(eventIds[0])).toBeTruthy();
+      expect(results[0].amount.gtn(0)).toBeTruthy();
+      expect(results[1].amount.eqn(0)).toBeTruthy();
+
+      try {
+        await claimMany().rpc();
+      } catch (error) {
+        throw new Error(error);
+      }
+
+      const fetchedUserAccount = await program.account.user.fetch(user);
+
+      expect(
+        fetchedUserAccount.stake.eq(
+          fetchedUserBefore.stake.add(results[0].amount)
+        )
+      ).toBeTruthy();
+
+      const fetchedPortfolioAccount = await program.account.portfolio.fetch(
+        findPortfolioAddress(alice.publicKey, 0)[0]
+      );
+
+      for (const id of eventIds) {
+        const fetchedParticipationAccount =
+          await program.account.participation.fetch(
+            findParticipantAddress(id, alice.publicKey)[0]
+          );
+
+        expect(fetchedParticipationAccount.isClaimed).toBeTruthy();
+        expect(
+          fetchedPortfolioAccount.entries.find((entry) => entry.eventId.eq(id))
+        ).toBeUndefined();
+
+        // Only the organizer releases the stake along with its own claims
+        const fetchedEventAccount = await program.account.event.fetch(
+          findEventAddress(id)[0]
+        );
+
+        expect(fetchedEventAccount.stake.gtn(0)).toBeTruthy();
+      }
+    });
+
+    it("success - claimed entries are skipped", async () => {
+      const [user] = findUserAddress(alice.publicKey);
+      const balanceBefore = await provider.connection.getBalance(user);
+
+      const results = await claimMany().view();
+
+      expect(results.map((result) => result.status)).toEqual([
+        { alreadyClaimed: {} },
+        { alreadyClaimed: {} },
+      ]);
+
+      try {
+        await claimMany().rpc();
+      } catch (error) {
+        throw new Error(error);
+      }
+
+      expect(await provider.connection.getBalance(user)).toEqual(
+        balanceBefore
+      );
+    });
+
+    it("success - organizer stake released without positions", async () => {
+      for (const id of eventIds) {
+        try {
+          await program.methods
+            .settleBatch(id)
+            .accounts({
+              sender: eve.publicKey,
+              option: findEventOptionAddress(id, 0)[0],
+              optionsTable: null,
+            })
+            .signers([eve])
+            .rpc();
+        } catch (error) {
+          throw new Error(error);
+        }
+
+        const fetchedEventAccount = await program.account.event.fetch(
+          findEventAddress(id)[0]
+        );
+
+        expect(fetchedEventAccount.stake.eq(new BN(0))).toBeTruthy();
+      }
+    });
+
+    it("fail - option of another position", async () => {
+      await expectThrowError(
+        () => claimMany([1, 1]).rpc(),
+        programError("InvalidClaimAccounts")
+      );
+    });
+
+    it("fail - claim count exceeding accounts", async () => {
+      await expectThrowError(
+        () =>
+          program.methods
+            .claimMany(eventIds.length + 1)
+            .accounts({
+              sender: alice.publicKey,
+            })
+            .remainingAccounts(claimAccounts([0, 1]))
+            .signers([alice])
+            .rpc(),
+        programError("InvalidClaimAccounts")
+      );
+    });
+  });
+
   describe("recharge", () => {
     beforeAll(async () => {
       const now = new BN(Math.round(new Date().getTime()) / 1000);
@@ -886,6 +1701,7 @@ describe("General event test", () => {
 
       await createOption(another_authority);
       await createOption(another_authority);
+      await publishEvent(another_authority);
 
       await sleep(2000);
 
@@ -898,6 +1714,7 @@ describe("General event test", () => {
     it("success", async () => {
       const [user] = findUserAddress(alice.publicKey);
       const [participant] = findParticipantAddress(eventId, alice.publicKey);
+      const [portfolio] = findPortfolioAddress(alice.publicKey, 0);
 
       const userBalanceBefore = await provider.connection.getBalance(user);
       const fetchedUserBefore = await program.account.user.fetch(user);
@@ -908,6 +1725,7 @@ describe("General event test", () => {
           .recharge(eventId)
           .accounts({
             sender: alice.publicKey,
+            portfolio,
           })
           .signers([alice])
           .rpc();
@@ -937,6 +1755,116 @@ describe("General event test", () => {
       );
     });
   });
+
+  describe("event_authority_transfer", () => {
+    beforeAll(async () => {
+      await createPublishedEvent();
+    });
+
+    it("fail - accept without proposal", async () => {
+      await expectThrowError(
+        () =>
+          program.methods
+            .acceptEventAuthority(eventId)
+            .accounts({
+              sender: another_authority.publicKey,
+            })
+            .signers([another_authority])
+            .rpc(),
+        programError("AuthorityMismatch")
+      );
+    });
+
+    it("success", async () => {
+      const [event] = findEventAddress(eventId);
+      const [previousUser] = findUserAddress(authority.publicKey);
+      const [user] = findUserAddress(another_authority.publicKey);
+
+      const fetchedPreviousUserBefore = await program.account.user.fetch(
+        previousUser
+      );
+      const fetchedUserBefore = await program.account.user.fetch(user);
+
+      // Propose:
+      await program.methods
+        .proposeEventAuthority(eventId, another_authority.publicKey)
+        .accounts({
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      // Accept:
+      await program.methods
+        .acceptEventAuthority(eventId)
+        .accounts({
+          sender: another_authority.publicKey,
+        })
+        .signers([another_authority])
+        .rpc();
+
+      // Fetching event:
+      const fetchedEventAccount = await program.account.event.fetch(event);
+
+      expect(fetchedEventAccount.authority).toEqual(
+        another_authority.publicKey
+      );
+      // The default key stands for no pending authority
+      expect(fetchedEventAccount.pendingAuthority).toEqual(
+        web3.PublicKey.default
+      );
+
+      // Fetching users:
+      const fetchedPreviousUserAfter = await program.account.user.fetch(
+        previousUser
+      );
+      const fetchedUserAfter = await program.account.user.fetch(user);
+
+      expect(
+        fetchedPreviousUserAfter.lockedStake.eq(
+          fetchedPreviousUserBefore.lockedStake.sub(eventPrice)
+        )
+      ).toBeTruthy();
+      expect(
+        fetchedUserAfter.lockedStake.eq(
+          fetchedUserBefore.lockedStake.add(eventPrice)
+        )
+      ).toBeTruthy();
+    });
+
+    it("fail - accept on a canceled event", async () => {
+      await createPublishedEvent();
+
+      const [event] = findEventAddress(eventId);
+
+      await program.methods
+        .proposeEventAuthority(eventId, another_authority.publicKey)
+        .accounts({
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      await closeEvent();
+
+      // Canceling releases the stake
+      const fetchedEventAccount = await program.account.event.fetch(event);
+
+      expect(fetchedEventAccount.stake.isZero()).toBeTruthy();
+
+      await expectThrowError(
+        () =>
+          program.methods
+            .acceptEventAuthority(eventId)
+            .accounts({
+              sender: another_authority.publicKey,
+            })
+            .signers([another_authority])
+            .rpc(),
+        programError("CanceledEvent")
+      );
+    });
+  });
 });
 
 async function createNewEvent(
@@ -980,6 +1908,33 @@ async function createNewEvent(
   }
 }
 
+async function createPublishedEvent(
+  startDate?: BN,
+  endDate?: BN,
+  owner?: web3.Keypair
+) {
+  await createNewEvent(startDate, endDate, owner);
+  await createOption(owner);
+  await createOption(owner);
+  await publishEvent(owner);
+}
+
+async function publishEvent(owner?: web3.Keypair) {
+  const signer = owner ? owner : authority;
+
+  try {
+    await program.methods
+      .publishEvent(eventId)
+      .accounts({
+        authority: signer.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+  } catch (error) {
+    throw new Error(error);
+  }
+}
+
 async function createNewUser(owner: web3.Keypair) {
   const [user] = findUserAddress(authority.publicKey);
   const name = Array.from(bufferFromString("User name", 32));
@@ -1001,6 +1956,14 @@ async function createNewUser(owner: web3.Keypair) {
       })
       .signers([owner])
       .rpc();
+
+    await program.methods
+      .createPortfolioPage(0)
+      .accounts({
+        sender: owner.publicKey,
+      })
+      .signers([owner])
+      .rpc();
   } catch (error) {
     throw new Error(error);
   }
@@ -1025,6 +1988,7 @@ async function createOption(owner?: web3.Keypair) {
       .accounts({
         authority: signer.publicKey,
         option: eventOption,
+        organizer: null,
       })
       .signers([signer])
       .rpc();
@@ -1035,6 +1999,7 @@ async function createOption(owner?: web3.Keypair) {
 
 async function participate(patricipant: web3.Keypair, index: number) {
   const [eventOption] = findEventOptionAddress(eventId, index);
+  const [portfolio] = findPortfolioAddress(patricipant.publicKey, 0);
 
   try {
     // Create event option:
@@ -1043,6 +2008,8 @@ async function participate(patricipant: web3.Keypair, index: number) {
       .accounts({
         sender: patricipant.publicKey,
         option: eventOption,
+        optionsTable: null,
+        portfolio,
       })
       .signers([patricipant])
       .rpc();
@@ -1060,6 +2027,7 @@ async function completeEvent(index: number, owner?: web3.Keypair) {
       .completeEvent(eventId, index)
       .accounts({
         authority: signer.publicKey,
+        organizer: null,
       })
       .signers([signer])
       .rpc();
@@ -1077,7 +2045,7 @@ async function closeEvent(owner?: web3.Keypair) {
       .cancelEvent(eventId)
       .accounts({
         sender: signer.publicKey,
-        contractAdmin: authority.publicKey,
+        moderator: null,
       })
       .signers([signer])
       .rpc();
@@ -0,0 +1,344 @@
+import {
+  web3,
+  AnchorProvider,
+  setProvider,
+  Program,
+  workspace,
+  BN,
+} from "@coral-xyz/anchor";
+import { v4 as uuidv4 } from "uuid";
+
+import { expectThrowError } from "./util/console";
+import { programError } from "./util/error";
+
+import { Predictory } from "../target/types/predictory";
+
+import {
+  findContractStateAddress,
+  findEventOptionAddress,
+  findPortfolioAddress,
+  findProgramDataAddress,
+  findProposalAddress,
+  findUserAddress,
+} from "./util/entity";
+import {
+  airdrop,
+  bufferFromString,
+  ignoreIfExist,
+  ONE_SOL,
+  sleep,
+  uuidToBn,
+  waitUntil,
+} from "./util/setup";
+
+describe("Governance tests", () => {
+  const provider = AnchorProvider.env();
+  setProvider(provider);
+
+  const program = workspace.Predictory as Program<Predictory>;
+
+  const proposer = web3.Keypair.generate();
+  const voter = web3.Keypair.generate();
+  const newcomer = web3.Keypair.generate();
+  const organizer = web3.Keypair.generate();
+
+  const platformFee = ONE_SOL.muln(33).divn(1000);
+  const eventPrice = ONE_SOL.muln(33).divn(1000);
+  const orgReward = new BN(10);
+  const multiplier = new BN(5);
+
+  let proposalId: BN;
+
+  beforeAll(async () => {
+    const [programData] = findProgramDataAddress();
+
+    await ignoreIfExist(() =>
+      program.methods
+        .initializeContractState(
+          provider.publicKey,
+          multiplier,
+          eventPrice,
+          platformFee,
+          orgReward
+        )
+        .accounts({
+          authority: provider.publicKey,
+          programData,
+        })
+        .rpc()
+    );
+
+    for (const [keypair, name] of [
+      [proposer, "Proposer"],
+      [voter, "Voter"],
+      [newcomer, "Newcomer"],
+      [organizer, "Organizer"],
+    ] as const) {
+      await airdrop(provider.connection, keypair.publicKey);
+
+      await program.methods
+        .createUser(Array.from(bufferFromString(name, 32)))
+        .accounts({
+          sender: keypair.publicKey,
+        })
+        .signers([keypair])
+        .rpc();
+
+      await program.methods
+        .transferStake(ONE_SOL.muln(3))
+        .accounts({ sender: keypair.publicKey })
+        .signers([keypair])
+        .rpc();
+    }
+
+    // Trust is only earned by settling positions, so the proposer and the voter bet on an event first
+    const eventId = uuidToBn(uuidv4());
+    const now = Math.round(new Date().getTime() / 1000);
+    const endDate = now + 6;
+
+    await program.methods
+      .createEvent(eventId, {
+        name: Array.from(bufferFromString("Governance event", 32)),
+        isPrivate: false,
+        description: Array.from(bufferFromString("Description", 256)),
+        startDate: new BN(now + 3),
+        endDate: new BN(endDate),
+        participationDeadline: null,
+      })
+      .accounts({ authority: organizer.publicKey })
+      .signers([organizer])
+      .rpc();
+
+    for (const [index, description] of ["Yes", "No"].entries()) {
+      await program.methods
+        .createEventOption(
+          eventId,
+          index,
+          Array.from(bufferFromString(description, 256))
+        )
+        .accounts({
+          authority: organizer.publicKey,
+          option: findEventOptionAddress(eventId, index)[0],
+          organizer: null,
+        })
+        .signers([organizer])
+        .rpc();
+    }
+
+    await program.methods
+      .publishEvent(eventId)
+      .accounts({ authority: organizer.publicKey })
+      .signers([organizer])
+      .rpc();
+
+    for (const keypair of [proposer, voter]) {
+      await program.methods
+        .createPortfolioPage(0)
+        .accounts({ sender: keypair.publicKey })
+        .signers([keypair])
+        .rpc();
+    }
+
+    await waitUntil(now + 4);
+
+    for (const keypair of [proposer, voter]) {
+      await program.methods
+        .vote(eventId, 0, ONE_SOL.muln(2))
+        .accounts({
+          sender: keypair.publicKey,
+          option: findEventOptionAddress(eventId, 0)[0],
+          optionsTable: null,
+          portfolio: findPortfolioAddress(keypair.publicKey, 0)[0],
+        })
+        .signers([keypair])
+        .rpc();
+    }
+
+    await waitUntil(endDate + 1);
+
+    await program.methods
+      .completeEvent(eventId, 0)
+      .accounts({
+        authority: organizer.publicKey,
+        organizer: null,
+      })
+      .signers([organizer])
+      .rpc();
+
+    // Appellation window of the test build
+    await waitUntil(Math.round(new Date().getTime() / 1000) + 2);
+
+    for (const keypair of [proposer, voter]) {
+      await program.methods
+        .claimEventReward(eventId)
+        .accounts({
+          sender: keypair.publicKey,
+          option: findEventOptionAddress(eventId, 0)[0],
+          optionsTable: null,
+          portfolio: findPortfolioAddress(keypair.publicKey, 0)[0],
+        })
+        .signers([keypair])
+        .rpc();
+    }
+  });
+
+  describe("create_proposal", () => {
+    it("fail - no earned trust", async () => {
+      await expectThrowError(
+        () =>
+          program.methods
+            .createProposal({ orgReward: {} }, new BN(10))
+            .accounts({
+              sender: newcomer.publicKey,
+            })
+            .signers([newcomer])
+            .rpc(),
+        programError("TrustTooLow")
+      );
+    });
+
+    it("fail - parameter out of bounds", async () => {
+      await expectThrowError(
+        () =>
+          program.methods
+            .createProposal({ orgReward: {} }, new BN(101))
+            .accounts({
+              sender: proposer.publicKey,
+            })
+            .signers([proposer])
+            .rpc(),
+        programError("ParamOutOfBounds")
+      );
+    });
+
+    it("success", async () => {
+      const [state] = findContractStateAddress();
+      const fetchedStateAccount = await program.account.state.fetch(state);
+
+      proposalId = fetchedStateAccount.proposalCount;
+
+      await program.methods
+        .createProposal({ orgReward: {} }, fetchedStateAccount.orgReward)
+        .accounts({
+          sender: proposer.publicKey,
+        })
+        .signers([proposer])
+        .rpc();
+
+      const [proposal] = findProposalAddress(proposalId);
+      const fetchedProposalAccount = await program.account.proposal.fetch(
+        proposal
+      );
+
+      expect(fetchedProposalAccount.id.eq(proposalId)).toBeTruthy();
+      expect(fetchedProposalAccount.proposer).toEqual(proposer.publicKey);
+      expect(fetchedProposalAccount.executed).toBeFalsy();
+    });
+  });
+
+  describe("vote_proposal", () => {
+    it("fail - no earned trust", async () => {
+      await expectThrowError(
+        () =>
+          program.methods
+            .voteProposal(proposalId, true)
+            .accounts({
+              sender: newcomer.publicKey,
+            })
+            .signers([newcomer])
+            .rpc(),
+        programError("TrustTooLow")
+      );
+    });
+
+    it("success", async () => {
+      for (const keypair of [proposer, voter]) {
+        await program.methods
+          .voteProposal(proposalId, true)
+          .accounts({
+            sender: keypair.publicKey,
+          })
+          .signers([keypair])
+          .rpc();
+      }
+
+      const [proposal] = findProposalAddress(proposalId);
+      const fetchedProposalAccount = await program.account.proposal.fetch(
+        proposal
+      );
+
+      // Only the trust earned above the initial level counts
+      let earnedTrust = 0;
+      for (const keypair of [proposer, voter]) {
+        const fetchedUserAccount = await program.account.user.fetch(
+          findUserAddress(keypair.publicKey)[0]
+        );
+
+        earnedTrust += fetchedUserAccount.trustLvl.toNumber() - 5;
+      }
+
+      expect(earnedTrust).toBeGreaterThanOrEqual(10);
+      expect(fetchedProposalAccount.votesFor.toNumber()).toEqual(earnedTrust);
+      expect(fetchedProposalAccount.votesAgainst.toNumber()).toEqual(0);
+    });
+
+    it("fail - already voted", async () => {
+      await expectThrowError(() =>
+        program.methods
+          .voteProposal(proposalId, false)
+          .accounts({
+            sender: voter.publicKey,
+          })
+          .signers([voter])
+          .rpc()
+      );
+    });
+  });
+
+  describe("execute_proposal", () => {
+    it("fail - voting is active", async () => {
+      await expectThrowError(
+        () =>
+          program.methods
+            .executeProposal(proposalId)
+            .accounts({
+              sender: voter.publicKey,
+            })
+            .signers([voter])
+            .rpc(),
+        programError("ProposalVotingActive")
+      );
+    });
+
+    it("success", async () => {
+      await sleep(3000);
+
+      await program.methods
+        .executeProposal(proposalId)
+        .accounts({
+          sender: voter.publicKey,
+        })
+        .signers([voter])
+        .rpc();
+
+      const [proposal] = findProposalAddress(proposalId);
+      const fetchedProposalAccount = await program.account.proposal.fetch(
+        proposal
+      );
+
+      expect(fetchedProposalAccount.executed).toBeTruthy();
+
+      await expectThrowError(
+        () =>
+          program.methods
+            .executeProposal(proposalId)
+            .accounts({
+              sender: voter.publicKey,
+            })
+            .signers([voter])
+            .rpc(),
+        programError("ProposalAlreadyExecuted")
+      );
+    });
+  });
+});
@@ -0,0 +1,336 @@
+import {
+  web3,
+  AnchorProvider,
+  setProvider,
+  Program,
+  workspace,
+  BN,
+} from "@coral-xyz/anchor";
+import { v4 as uuidv4 } from "uuid";
+
+import { expectThrowError } from "./util/console";
+import { programError } from "./util/error";
+
+import { Predictory } from "../target/types/predictory";
+
+import {
+  findEventAddress,
+  findEventMetaAddress,
+  findEventOptionAddress,
+  findOrganizerAddress,
+  findProgramDataAddress,
+} from "./util/entity";
+import {
+  airdrop,
+  bufferFromString,
+  ignoreIfExist,
+  ONE_SOL,
+  uuidToBn,
+} from "./util/setup";
+
+const EDIT_META = 1 << 0;
+const MANAGE_OPTIONS = 1 << 1;
+const RESOLVE = 1 << 2;
+
+describe("Organizer tests", () => {
+  const provider = AnchorProvider.env();
+  setProvider(provider);
+
+  const program = workspace.Predictory as Program<Predictory>;
+
+  const owner = web3.Keypair.generate();
+  const member = web3.Keypair.generate();
+
+  const eventId = uuidToBn(uuidv4());
+
+  const platformFee = ONE_SOL.muln(33).divn(1000);
+  const eventPrice = ONE_SOL.muln(33).divn(1000);
+  const orgReward = new BN(10);
+  const multiplier = new BN(5);
+
+  beforeAll(async () => {
+    await airdrop(provider.connection, owner.publicKey);
+    await airdrop(provider.connection, member.publicKey);
+
+    const [programData] = findProgramDataAddress();
+
+    await ignoreIfExist(() =>
+      program.methods
+        .initializeContractState(
+          provider.publicKey,
+          multiplier,
+          eventPrice,
+          platformFee,
+          orgReward
+        )
+        .accounts({
+          authority: provider.publicKey,
+          programData,
+        })
+        .rpc()
+    );
+
+    await program.methods
+      .createUser(Array.from(bufferFromString("Owner", 32)))
+      .accounts({
+        sender: owner.publicKey,
+      })
+      .signers([owner])
+      .rpc();
+
+    await program.methods
+      .transferStake(ONE_SOL)
+      .accounts({
+        sender: owner.publicKey,
+      })
+      .signers([owner])
+      .rpc();
+
+    const now = new BN(Math.round(new Date().getTime()) / 1000);
+
+    await program.methods
+      .createEvent(eventId, {
+        name: Array.from(bufferFromString("Organizer event", 32)),
+        isPrivate: false,
+        description: Array.from(bufferFromString("Description", 256)),
+        startDate: now.addn(500),
+        endDate: now.addn(1000),
+        participationDeadline: null,
+      })
+      .accounts({
+        authority: owner.publicKey,
+      })
+      .signers([owner])
+      .rpc();
+  });
+
+  describe("add_event_organizer", () => {
+    it("fail - authority mismatch", async () => {
+      await expectThrowError(
+        () =>
+          program.methods
+            .addEventOrganizer(eventId, member.publicKey, EDIT_META, 10)
+            .accounts({
+              authority: member.publicKey,
+            })
+            .signers([member])
+            .rpc(),
+        programError("AuthorityMismatch")
+      );
+    });
+
+    it("fail - reward share above 100%", async () => {
+      await expectThrowError(
+        () =>
+          program.methods
+            .addEventOrganizer(eventId, member.publicKey, EDIT_META, 101)
+            .accounts({
+              authority: owner.publicKey,
+            })
+            .signers([owner])
+            .rpc(),
+        programError("InvalidRewardShare")
+      );
+    });
+
+    it("success", async () => {
+      const [event] = findEventAddress(eventId);
+      const [organizer] = findOrganizerAddress(eventId, member.publicKey);
+
+      await program.methods
+        .addEventOrganizer(eventId, member.publicKey, EDIT_META, 10)
+        .accounts({
+          authority: owner.publicKey,
+        })
+        .signers([owner])
+        .rpc();
+
+      const fetchedOrganizerAccount =
+        await program.account.eventOrganizer.fetch(organizer);
+
+      expect(fetchedOrganizerAccount.eventId.eq(eventId)).toBeTruthy();
+      expect(fetchedOrganizerAccount.member).toEqual(member.publicKey);
+      expect(fetchedOrganizerAccount.permissions).toEqual(EDIT_META);
+      expect(fetchedOrganizerAccount.rewardShare).toEqual(10);
+
+      const fetchedEventAccount = await program.account.event.fetch(event);
+
+      expect(fetchedEventAccount.organizerShares).toEqual(10);
+      expect(fetchedEventAccount.organizerCount).toEqual(1);
+    });
+  });
+
+  describe("delegated permissions", () => {
+    it("success - co-organizer edits metadata", async () => {
+      const [eventMeta] = findEventMetaAddress(eventId);
+      const [organizer] = findOrganizerAddress(eventId, member.publicKey);
+      const newName = Array.from(bufferFromString("Renamed event", 32));
+
+      await program.methods
+        .updateEventName(eventId, newName)
+        .accounts({
+          authority: member.publicKey,
+          organizer,
+        })
+        .signers([member])
+        .rpc();
+
+      const fetchedEventMetaAccount = await program.account.eventMeta.fetch(
+        eventMeta
+      );
+
+      expect(fetchedEventMetaAccount.name).toEqual(newName);
+    });
+
+    it("fail - co-organizer without option permission", async () => {
+      const [organizer] = findOrganizerAddress(eventId, member.publicKey);
+      const [option] = findEventOptionAddress(eventId, 0);
+
+      await expectThrowError(
+        () =>
+          program.methods
+            .createEventOption(
+              eventId,
+              0,
+              Array.from(bufferFromString("Option", 256))
+            )
+            .accounts({
+              authority: member.publicKey,
+              option,
+              organizer,
+            })
+            .signers([member])
+            .rpc(),
+        programError("AuthorityMismatch")
+      );
+    });
+
+    it("success - co-organizer manages options after update", async () => {
+      const [event] = findEventAddress(eventId);
+      const [organizer] = findOrganizerAddress(eventId, member.publicKey);
+      const [option] = findEventOptionAddress(eventId, 0);
+
+      await program.methods
+        .updateEventOrganizer(
+          eventId,
+          member.publicKey,
+          EDIT_META | MANAGE_OPTIONS | RESOLVE,
+          20
+        )
+        .accounts({
+          authority: owner.publicKey,
+        })
+        .signers([owner])
+        .rpc();
+
+      await program.methods
+        .createEventOption(
+          eventId,
+          0,
+          Array.from(bufferFromString("Option", 256))
+        )
+        .accounts({
+          authority: member.publicKey,
+          option,
+          organizer,
+        })
+        .signers([member])
+        .rpc();
+
+      const fetchedEventAccount = await program.account.event.fetch(event);
+
+      expect(fetchedEventAccount.optionCount).toEqual(1);
+      expect(fetchedEventAccount.organizerShares).toEqual(20);
+    });
+  });
+
+  describe("remove_event_organizer", () => {
+    it("success", async () => {
+      const [event] = findEventAddress(eventId);
+      const [organizer] = findOrganizerAddress(eventId, member.publicKey);
+
+      await program.methods
+        .removeEventOrganizer(eventId, member.publicKey)
+        .accounts({
+          authority: owner.publicKey,
+        })
+        .signers([owner])
+        .rpc();
+
+      const organizerAccount = await provider.connection.getAccountInfo(
+        organizer
+      );
+      expect(organizerAccount).toBeNull();
+
+      const fetchedEventAccount = await program.account.event.fetch(event);
+
+      expect(fetchedEventAccount.organizerShares).toEqual(0);
+      expect(fetchedEventAccount.organizerCount).toEqual(0);
+    });
+  });
+
+  describe("delete_event", () => {
+    const draftId = uuidToBn(uuidv4());
+    const [organizer] = findOrganizerAddress(draftId, member.publicKey);
+
+    const deleteEvent = (organizers: web3.PublicKey[]) =>
+      program.methods
+        .deleteEvent(draftId)
+        .accounts({
+          authority: owner.publicKey,
+          optionsTable: null,
+        })
+        .remainingAccounts(
+          organizers.map((pubkey) => ({
+            pubkey,
+            isSigner: false,
+            isWritable: true,
+          }))
+        )
+        .signers([owner])
+        .rpc();
+
+    beforeAll(async () => {
+      const now = new BN(Math.round(new Date().getTime()) / 1000);
+
+      await program.methods
+        .createEvent(draftId, {
+          name: Array.from(bufferFromString("Draft event", 32)),
+          isPrivate: false,
+          description: Array.from(bufferFromString("Description", 256)),
+          startDate: now.addn(500),
+          endDate: now.addn(1000),
+          participationDeadline: null,
+        })
+        .accounts({
+          authority: owner.publicKey,
+        })
+        .signers([owner])
+        .rpc();
+
+      await program.methods
+        .addEventOrganizer(draftId, member.publicKey, EDIT_META, 0)
+        .accounts({
+          authority: owner.publicKey,
+        })
+        .signers([owner])
+        .rpc();
+    });
+
+    it("fail - co-organizer accounts left open", async () => {
+      await expectThrowError(
+        () => deleteEvent([]),
+        programError("EventHasOrganizers")
+      );
+    });
+
+    it("success - co-organizer accounts are closed", async () => {
+      await deleteEvent([organizer]);
+
+      expect(
+        await provider.connection.getAccountInfo(findEventAddress(draftId)[0])
+      ).toBeNull();
+      expect(await provider.connection.getAccountInfo(organizer)).toBeNull();
+    });
+  });
+});
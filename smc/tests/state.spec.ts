@@ -16,6 +16,8 @@ import { Predictory } from "../target/types/predictory";
 import {
   findContractStateAddress,
   findProgramDataAddress,
+  findRoleAddress,
+  findTreasuryAddress,
 } from "./util/entity";
 import { airdrop, ONE_SOL } from "./util/setup";
 
@@ -35,6 +37,11 @@ describe("State tests", () => {
   const orgReward = new BN(10);
   const multiplier = new BN(5);
 
+  const FEE_MANAGER = 0;
+  const GUARDIAN = 3;
+
+  const [feeManagerRole] = findRoleAddress(FEE_MANAGER, authority.publicKey);
+
   beforeAll(async () => {
     testMint = new TestToken(provider);
     await testMint.mint(1_000_000_000);
@@ -121,12 +128,60 @@ describe("State tests", () => {
     });
   });
 
+  describe("grant_role", () => {
+    it("fail - authority mismatch", async () => {
+      await expectThrowError(
+        () =>
+          program.methods
+            .grantRole({ feeManager: {} }, another_authority.publicKey)
+            .accounts({
+              authority: another_authority.publicKey,
+            })
+            .signers([another_authority])
+            .rpc(),
+        programError("AuthorityMismatch")
+      );
+    });
+
+    it("success", async () => {
+      const [role] = findRoleAddress(FEE_MANAGER, authority.publicKey);
+
+      await program.methods
+        .grantRole({ feeManager: {} }, authority.publicKey)
+        .accounts({
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      const fetchedRoleAccount = await program.account.roleMember.fetch(role);
+
+      expect(fetchedRoleAccount.member).toEqual(authority.publicKey);
+      expect(fetchedRoleAccount.role).toEqual({ feeManager: {} });
+    });
+
+    it("fail - param change without role", async () => {
+      const [role] = findRoleAddress(FEE_MANAGER, another_authority.publicKey);
+
+      await expectThrowError(() =>
+        program.methods
+          .queueParamChange({ multiplier: {} }, multiplier)
+          .accounts({
+            authority: another_authority.publicKey,
+            role,
+          })
+          .signers([another_authority])
+          .rpc()
+      );
+    });
+  });
+
   describe("update_state", () => {
     it("fail - authority mismatch", async () => {
       await expectThrowError(
         () =>
           program.methods
-            .setContractAuthority(another_authority.publicKey)
+            .proposeAuthority(another_authority.publicKey)
             .accounts({
               authority: another_authority.publicKey,
             })
@@ -136,13 +191,75 @@ describe("State tests", () => {
       );
     });
 
+    it("fail - parameter out of bounds", async () => {
+      await expectThrowError(
+        () =>
+          program.methods
+            .queueParamChange({ orgReward: {} }, new BN(101))
+            .accounts({
+              authority: authority.publicKey,
+              role: feeManagerRole,
+            })
+            .signers([authority])
+            .rpc(),
+        programError("ParamOutOfBounds")
+      );
+    });
+
+    it("success - cancel parameter change", async () => {
+      const [state] = findContractStateAddress();
+
+      await program.methods
+        .queueParamChange({ multiplier: {} }, multiplier.addn(1))
+        .accounts({
+          authority: authority.publicKey,
+          role: feeManagerRole,
+        })
+        .signers([authority])
+        .rpc();
+
+      await program.methods
+        .cancelParamChange({ multiplier: {} })
+        .accounts({
+          authority: authority.publicKey,
+          role: feeManagerRole,
+        })
+        .signers([authority])
+        .rpc();
+
+      await expectThrowError(
+        () =>
+          program.methods
+            .applyParamChange({ multiplier: {} })
+            .accounts({
+              sender: authority.publicKey,
+            })
+            .signers([authority])
+            .rpc(),
+        programError("NoPendingChange")
+      );
+
+      const fetchedStateAccount = await program.account.state.fetch(state);
+
+      expect(fetchedStateAccount.multiplier.eq(multiplier)).toBeTruthy();
+    });
+
     it("success - update contract multiplier", async () => {
       const [state] = findContractStateAddress();
 
       await program.methods
-        .setContractMultiplier(multiplier)
+        .queueParamChange({ multiplier: {} }, multiplier)
         .accounts({
           authority: authority.publicKey,
+          role: feeManagerRole,
+        })
+        .signers([authority])
+        .rpc();
+
+      await program.methods
+        .applyParamChange({ multiplier: {} })
+        .accounts({
+          sender: authority.publicKey,
         })
         .signers([authority])
         .rpc();
@@ -156,9 +273,18 @@ describe("State tests", () => {
       const [state] = findContractStateAddress();
 
       await program.methods
-        .setContractFee(platformFee)
+        .queueParamChange({ platformFee: {} }, platformFee)
         .accounts({
           authority: authority.publicKey,
+          role: feeManagerRole,
+        })
+        .signers([authority])
+        .rpc();
+
+      await program.methods
+        .applyParamChange({ platformFee: {} })
+        .accounts({
+          sender: authority.publicKey,
         })
         .signers([authority])
         .rpc();
@@ -172,9 +298,18 @@ describe("State tests", () => {
       const [state] = findContractStateAddress();
 
       await program.methods
-        .setOrgReward(orgReward)
+        .queueParamChange({ orgReward: {} }, orgReward)
         .accounts({
           authority: authority.publicKey,
+          role: feeManagerRole,
+        })
+        .signers([authority])
+        .rpc();
+
+      await program.methods
+        .applyParamChange({ orgReward: {} })
+        .accounts({
+          sender: authority.publicKey,
         })
         .signers([authority])
         .rpc();
@@ -188,9 +323,18 @@ describe("State tests", () => {
       const [state] = findContractStateAddress();
 
       await program.methods
-        .setEventPrice(eventPrice)
+        .queueParamChange({ eventPrice: {} }, eventPrice)
         .accounts({
           authority: authority.publicKey,
+          role: feeManagerRole,
+        })
+        .signers([authority])
+        .rpc();
+
+      await program.methods
+        .applyParamChange({ eventPrice: {} })
+        .accounts({
+          sender: authority.publicKey,
         })
         .signers([authority])
         .rpc();
@@ -200,20 +344,361 @@ describe("State tests", () => {
       expect(fetchedStateAccount.eventPrice.eq(eventPrice)).toBeTruthy();
     });
 
+    it("success - cancel authority transfer", async () => {
+      const [state] = findContractStateAddress();
+
+      await program.methods
+        .proposeAuthority(another_authority.publicKey)
+        .accounts({
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      await program.methods
+        .cancelAuthorityTransfer()
+        .accounts({
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      const fetchedStateAccount = await program.account.state.fetch(state);
+
+      expect(fetchedStateAccount.pendingAuthority).toBeNull();
+
+      await expectThrowError(
+        () =>
+          program.methods
+            .acceptAuthority()
+            .accounts({
+              sender: another_authority.publicKey,
+              role: findRoleAddress(GUARDIAN, another_authority.publicKey)[0],
+            })
+            .signers([another_authority])
+            .rpc(),
+        programError("AuthorityMismatch")
+      );
+    });
+
+    it("fail - accept before timelock", async () => {
+      await program.methods
+        .setAuthorityTimelock(new BN(1000))
+        .accounts({
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      await program.methods
+        .proposeAuthority(another_authority.publicKey)
+        .accounts({
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      await expectThrowError(
+        () =>
+          program.methods
+            .acceptAuthority()
+            .accounts({
+              sender: another_authority.publicKey,
+              role: findRoleAddress(GUARDIAN, another_authority.publicKey)[0],
+            })
+            .signers([another_authority])
+            .rpc(),
+        programError("AuthorityTimelocked")
+      );
+
+      await program.methods
+        .setAuthorityTimelock(new BN(0))
+        .accounts({
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+    });
+
     it("success - update contract authority", async () => {
       const [state] = findContractStateAddress();
 
       await program.methods
-        .setContractAuthority(provider.publicKey)
+        .proposeAuthority(provider.publicKey)
         .accounts({
           authority: authority.publicKey,
         })
         .signers([authority])
         .rpc();
 
+      await program.methods
+        .acceptAuthority()
+        .accounts({
+          sender: provider.publicKey,
+        })
+        .rpc();
+
       const fetchedStateAccount = await program.account.state.fetch(state);
 
       expect(fetchedStateAccount.authority).toEqual(provider.publicKey);
+      expect(fetchedStateAccount.pendingAuthority).toBeNull();
+    });
+  });
+
+  describe("migrate_state", () => {
+    it("fail - already migrated", async () => {
+      await expectThrowError(
+        () =>
+          program.methods
+            .migrateState()
+            .accounts({
+              payer: authority.publicKey,
+            })
+            .signers([authority])
+            .rpc(),
+        programError("AccountAlreadyMigrated")
+      );
+    });
+  });
+
+  describe("treasury", () => {
+    it("fail - initialize treasury authority mismatch", async () => {
+      await expectThrowError(
+        () =>
+          program.methods
+            .initializeTreasury()
+            .accounts({
+              authority: another_authority.publicKey,
+            })
+            .signers([another_authority])
+            .rpc(),
+        programError("AuthorityMismatch")
+      );
+    });
+
+    it("fail - treasury already exists", async () => {
+      await expectThrowError(
+        () =>
+          program.methods
+            .initializeTreasury()
+            .accounts({
+              authority: provider.publicKey,
+            })
+            .rpc(),
+        /custom program error: 0x0/
+      );
+    });
+
+    it("fail - split does not add up", async () => {
+      await expectThrowError(
+        () =>
+          program.methods
+            .setTreasurySplit([
+              { wallet: authority.publicKey, shareBps: 5000 },
+              { wallet: another_authority.publicKey, shareBps: 4000 },
+            ])
+            .accounts({
+              authority: authority.publicKey,
+              role: feeManagerRole,
+            })
+            .signers([authority])
+            .rpc(),
+        programError("InvalidTreasurySplit")
+      );
+    });
+
+    it("success - distribute", async () => {
+      const [treasury] = findTreasuryAddress();
+      const opsWallet = web3.Keypair.generate();
+      const insuranceWallet = web3.Keypair.generate();
+
+      await program.methods
+        .setTreasurySplit([
+          { wallet: opsWallet.publicKey, shareBps: 7000 },
+          { wallet: insuranceWallet.publicKey, shareBps: 3000 },
+        ])
+        .accounts({
+          authority: authority.publicKey,
+          role: feeManagerRole,
+        })
+        .signers([authority])
+        .rpc();
+
+      await airdrop(provider.connection, treasury);
+
+      const treasuryInfo = await provider.connection.getAccountInfo(treasury);
+      const rent =
+        await provider.connection.getMinimumBalanceForRentExemption(
+          treasuryInfo.data.length
+        );
+      const available = treasuryInfo.lamports - rent;
+
+      await program.methods
+        .distributeTreasury()
+        .accounts({
+          sender: authority.publicKey,
+        })
+        .remainingAccounts([
+          { pubkey: opsWallet.publicKey, isWritable: true, isSigner: false },
+          {
+            pubkey: insuranceWallet.publicKey,
+            isWritable: true,
+            isSigner: false,
+          },
+        ])
+        .signers([authority])
+        .rpc();
+
+      expect(
+        await provider.connection.getBalance(opsWallet.publicKey)
+      ).toEqual(Math.floor((available * 7000) / 10000));
+      expect(
+        await provider.connection.getBalance(insuranceWallet.publicKey)
+      ).toEqual(Math.floor((available * 3000) / 10000));
+
+      await expectThrowError(
+        () =>
+          program.methods
+            .distributeTreasury()
+            .accounts({
+              sender: authority.publicKey,
+            })
+            .remainingAccounts([
+              {
+                pubkey: insuranceWallet.publicKey,
+                isWritable: true,
+                isSigner: false,
+              },
+            ])
+            .signers([authority])
+            .rpc(),
+        programError("RecipientMismatch")
+      );
+    });
+  });
+
+  describe("pause", () => {
+    const PAUSE_VOTING = 1 << 1;
+    const PAUSE_WITHDRAWALS = 1 << 3;
+
+    it("success - grant guardian roles", async () => {
+      const [role] = findRoleAddress(GUARDIAN, another_authority.publicKey);
+
+      await program.methods
+        .grantRole({ guardian: {} }, another_authority.publicKey)
+        .accounts({
+          authority: provider.publicKey,
+        })
+        .rpc();
+
+      await program.methods
+        .grantRole({ guardian: {} }, provider.publicKey)
+        .accounts({
+          authority: provider.publicKey,
+        })
+        .rpc();
+
+      const fetchedRoleAccount = await program.account.roleMember.fetch(role);
+
+      expect(fetchedRoleAccount.member).toEqual(another_authority.publicKey);
+    });
+
+    it("fail - missing guardian role", async () => {
+      await expectThrowError(() =>
+        program.methods
+          .setPaused(PAUSE_VOTING)
+          .accounts({
+            sender: authority.publicKey,
+            role: findRoleAddress(GUARDIAN, authority.publicKey)[0],
+          })
+          .signers([authority])
+          .rpc()
+      );
+    });
+
+    it("fail - neither authority nor guardian", async () => {
+      await expectThrowError(
+        () =>
+          program.methods
+            .setPaused(PAUSE_VOTING)
+            .accounts({
+              sender: authority.publicKey,
+              role: null,
+            })
+            .signers([authority])
+            .rpc(),
+        programError("AuthorityMismatch")
+      );
+    });
+
+    it("success - contract authority pauses without a role", async () => {
+      const [state] = findContractStateAddress();
+
+      await program.methods
+        .setPaused(PAUSE_VOTING)
+        .accounts({
+          sender: provider.publicKey,
+          role: null,
+        })
+        .rpc();
+
+      expect((await program.account.state.fetch(state)).paused).toEqual(
+        PAUSE_VOTING
+      );
+
+      await program.methods
+        .setPaused(0)
+        .accounts({
+          sender: provider.publicKey,
+          role: null,
+        })
+        .rpc();
+    });
+
+    it("fail - invalid flags", async () => {
+      await expectThrowError(
+        () =>
+          program.methods
+            .setPaused(1 << 7)
+            .accounts({
+              sender: another_authority.publicKey,
+              role: findRoleAddress(GUARDIAN, another_authority.publicKey)[0],
+            })
+            .signers([another_authority])
+            .rpc(),
+        programError("InvalidPauseFlags")
+      );
+    });
+
+    it("success - guardians pause and unpause", async () => {
+      const [state] = findContractStateAddress();
+
+      await program.methods
+        .setPaused(PAUSE_VOTING | PAUSE_WITHDRAWALS)
+        .accounts({
+          sender: another_authority.publicKey,
+          role: findRoleAddress(GUARDIAN, another_authority.publicKey)[0],
+        })
+        .signers([another_authority])
+        .rpc();
+
+      let fetchedStateAccount = await program.account.state.fetch(state);
+
+      expect(fetchedStateAccount.paused).toEqual(
+        PAUSE_VOTING | PAUSE_WITHDRAWALS
+      );
+
+      await program.methods
+        .setPaused(0)
+        .accounts({
+          sender: provider.publicKey,
+          role: findRoleAddress(GUARDIAN, provider.publicKey)[0],
+        })
+        .rpc();
+
+      fetchedStateAccount = await program.account.state.fetch(state);
+
+      expect(fetchedStateAccount.paused).toEqual(0);
     });
   });
 });
@@ -0,0 +1,299 @@
+import {
+  web3,
+  AnchorProvider,
+  setProvider,
+  Program,
+  workspace,
+  BN,
+} from "@coral-xyz/anchor";
+import { v4 as uuidv4 } from "uuid";
+
+import { Predictory } from "../target/types/predictory";
+
+import { expectThrowError } from "./util/console";
+import { programError } from "./util/error";
+import {
+  findEventAddress,
+  findEventOptionAddress,
+  findEventOptionMetaAddress,
+  findOptionsTableAddress,
+  findParticipantAddress,
+  findPortfolioAddress,
+  findProgramDataAddress,
+  findUserAddress,
+} from "./util/entity";
+import {
+  airdrop,
+  bufferFromString,
+  ignoreIfExist,
+  ONE_SOL,
+  uuidToBn,
+  waitUntil,
+} from "./util/setup";
+
+describe("Options table tests", () => {
+  const provider = AnchorProvider.env();
+  setProvider(provider);
+
+  const program = workspace.Predictory as Program<Predictory>;
+
+  const organizer = web3.Keypair.generate();
+  const alice = web3.Keypair.generate();
+  const bob = web3.Keypair.generate();
+
+  const eventId = uuidToBn(uuidv4());
+  const now = Math.round(new Date().getTime() / 1000);
+  const startDate = now + 4;
+  const endDate = now + 10;
+
+  const [event] = findEventAddress(eventId);
+  const [optionsTable] = findOptionsTableAddress(eventId);
+
+  const amount = ONE_SOL.divn(10);
+
+  async function createUser(owner: web3.Keypair) {
+    await program.methods
+      .createUser(Array.from(bufferFromString("Table", 32)))
+      .accounts({ sender: owner.publicKey })
+      .signers([owner])
+      .rpc();
+
+    await program.methods
+      .transferStake(ONE_SOL)
+      .accounts({ sender: owner.publicKey })
+      .signers([owner])
+      .rpc();
+
+    await program.methods
+      .createPortfolioPage(0)
+      .accounts({ sender: owner.publicKey })
+      .signers([owner])
+      .rpc();
+  }
+
+  async function createEvent(id: BN) {
+    await program.methods
+      .createEvent(id, {
+        name: Array.from(bufferFromString("Table event", 32)),
+        isPrivate: false,
+        description: Array.from(bufferFromString("Description", 256)),
+        startDate: new BN(startDate),
+        endDate: new BN(endDate),
+        participationDeadline: null,
+      })
+      .accounts({ authority: organizer.publicKey })
+      .signers([organizer])
+      .rpc();
+  }
+
+  function vote(participant: web3.Keypair, index: number) {
+    return program.methods
+      .vote(eventId, index, amount)
+      .accounts({
+        sender: participant.publicKey,
+        option: null,
+        optionsTable,
+        portfolio: findPortfolioAddress(participant.publicKey, 0)[0],
+      })
+      .signers([participant])
+      .rpc();
+  }
+
+  beforeAll(async () => {
+    await airdrop(provider.connection, organizer.publicKey);
+    await airdrop(provider.connection, alice.publicKey);
+    await airdrop(provider.connection, bob.publicKey);
+
+    const [programData] = findProgramDataAddress();
+
+    await ignoreIfExist(() =>
+      program.methods
+        .initializeContractState(
+          provider.publicKey,
+          new BN(5),
+          ONE_SOL.muln(33).divn(1000),
+          ONE_SOL.muln(33).divn(1000),
+          new BN(10)
+        )
+        .accounts({
+          authority: provider.publicKey,
+          programData,
+        })
+        .rpc()
+    );
+
+    await createUser(organizer);
+    await createUser(alice);
+    await createUser(bob);
+
+    await createEvent(eventId);
+  });
+
+  it("fail - table after the first option", async () => {
+    const otherEventId = uuidToBn(uuidv4());
+
+    await createEvent(otherEventId);
+
+    await program.methods
+      .createEventOption(
+        otherEventId,
+        0,
+        Array.from(bufferFromString("Option", 256))
+      )
+      .accounts({
+        authority: organizer.publicKey,
+        option: findEventOptionAddress(otherEventId, 0)[0],
+        organizer: null,
+      })
+      .signers([organizer])
+      .rpc();
+
+    await expectThrowError(
+      () =>
+        program.methods
+          .createOptionsTable(otherEventId)
+          .accounts({
+            authority: organizer.publicKey,
+            organizer: null,
+          })
+          .signers([organizer])
+          .rpc(),
+      programError("InvalidOptionPool")
+    );
+  });
+
+  it("success - create table", async () => {
+    await program.methods
+      .createOptionsTable(eventId)
+      .accounts({
+        authority: organizer.publicKey,
+        organizer: null,
+      })
+      .signers([organizer])
+      .rpc();
+
+    const fetchedEventAccount = await program.account.event.fetch(event);
+    const fetchedTableAccount = await program.account.optionsTable.fetch(
+      optionsTable
+    );
+
+    expect(fetchedEventAccount.optionsTable).toBeTruthy();
+    expect(new BN(fetchedTableAccount.eventId, "le").eq(eventId)).toBeTruthy();
+    expect(fetchedTableAccount.votes.every((votes) => votes.isZero())).toBe(
+      true
+    );
+  });
+
+  it("success - options only get their text", async () => {
+    const descriptions = ["Yes", "No", "Maybe"];
+
+    await program.methods
+      .createEventOptions(eventId, descriptions)
+      .accounts({
+        authority: organizer.publicKey,
+        organizer: null,
+      })
+      .remainingAccounts(
+        descriptions.map((_, index) => ({
+          pubkey: findEventOptionMetaAddress(eventId, index)[0],
+          isSigner: false,
+          isWritable: true,
+        }))
+      )
+      .signers([organizer])
+      .rpc();
+
+    const fetchedEventAccount = await program.account.event.fetch(event);
+
+    expect(fetchedEventAccount.optionCount).toEqual(3);
+    expect(
+      await provider.connection.getAccountInfo(
+        findEventOptionAddress(eventId, 0)[0]
+      )
+    ).toBeNull();
+
+    await program.methods
+      .publishEvent(eventId)
+      .accounts({ authority: organizer.publicKey })
+      .signers([organizer])
+      .rpc();
+
+    await waitUntil(startDate + 1);
+  });
+
+  it("success - votes land in the table", async () => {
+    await vote(alice, 1);
+    await vote(bob, 2);
+
+    const fetchedTableAccount = await program.account.optionsTable.fetch(
+      optionsTable
+    );
+
+    expect(fetchedTableAccount.votes[0].isZero()).toBeTruthy();
+    expect(fetchedTableAccount.votes[1].eqn(1)).toBeTruthy();
+    expect(fetchedTableAccount.vaultBalances[1].eq(amount)).toBeTruthy();
+    expect(fetchedTableAccount.vaultBalances[2].eq(amount)).toBeTruthy();
+
+    const odds = await program.methods
+      .eventOdds(eventId)
+      .accounts({})
+      .remainingAccounts([
+        { pubkey: optionsTable, isSigner: false, isWritable: false },
+      ])
+      .view();
+
+    expect(odds.options.length).toEqual(3);
+    expect(odds.options[1].vaultBalance.eq(amount)).toBeTruthy();
+  });
+
+  it("fail - option index out of the event", async () => {
+    const carol = web3.Keypair.generate();
+
+    await airdrop(provider.connection, carol.publicKey);
+    await createUser(carol);
+
+    await expectThrowError(
+      () => vote(carol, 3),
+      programError("InvalidIndex")
+    );
+  });
+
+  it("success - claim reads the table", async () => {
+    await waitUntil(endDate + 1);
+
+    await program.methods
+      .completeEvent(eventId, 1)
+      .accounts({
+        authority: organizer.publicKey,
+        organizer: null,
+      })
+      .signers([organizer])
+      .rpc();
+
+    // Appellation window of the test build
+    await waitUntil(Math.round(new Date().getTime() / 1000) + 2);
+
+    const [user] = findUserAddress(alice.publicKey);
+    const stakeBefore = (await program.account.user.fetch(user)).stake;
+
+    await program.methods
+      .claimEventReward(eventId)
+      .accounts({
+        sender: alice.publicKey,
+        option: null,
+        optionsTable,
+        portfolio: findPortfolioAddress(alice.publicKey, 0)[0],
+      })
+      .signers([alice])
+      .rpc();
+
+    const fetchedParticipationAccount =
+      await program.account.participation.fetch(
+        findParticipantAddress(eventId, alice.publicKey)[0]
+      );
+    const fetchedUserAccount = await program.account.user.fetch(user);
+
+    expect(fetchedParticipationAccount.isClaimed).toBeTruthy();
+    expect(fetchedUserAccount.stake.gt(stakeBefore.add(amount))).toBeTruthy();
+  });
+});
@@ -140,6 +140,40 @@ describe("User tests", () => {
 
       expect(fetchedUserAccount.stake.eq(amount)).toBeTruthy();
     });
+
+    it("success - emits stake deposited event", async () => {
+      const received = new Promise<any>((resolve) => {
+        const listener = program.addEventListener(
+          "stakeDeposited",
+          async (event) => {
+            await program.removeEventListener(listener);
+            resolve(event);
+          }
+        );
+      });
+
+      await program.methods
+        .transferStake(amount)
+        .accounts({
+          sender: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      const event = await received;
+
+      expect(event.user).toEqual(authority.publicKey);
+      expect(event.amount.eq(amount)).toBeTruthy();
+      expect(event.stake.eq(amount.muln(2))).toBeTruthy();
+
+      await program.methods
+        .withdrawStake(amount)
+        .accounts({
+          sender: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+    });
   });
 
   describe("withdraw_stake", () => {
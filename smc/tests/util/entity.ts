@@ -51,6 +51,55 @@ export function findEventOptionAddress(
   );
 }
 
+export function findEventOptionMetaAddress(
+  eventId: BN,
+  index: number
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [
+      bufferFromString("option_meta"),
+      eventId.toBuffer("le", 16),
+      Buffer.from([index]),
+    ],
+    TEST_PROGRAM_ID
+  );
+}
+
+export function findOptionsTableAddress(eventId: BN): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [bufferFromString("options_table"), eventId.toBuffer("le", 16)],
+    TEST_PROGRAM_ID
+  );
+}
+
+export function findAttestationAddress(eventId: BN): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [bufferFromString("attestation"), eventId.toBuffer("le", 16)],
+    TEST_PROGRAM_ID
+  );
+}
+
+export function findAppealAddress(eventId: BN): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [bufferFromString("appeal"), eventId.toBuffer("le", 16)],
+    TEST_PROGRAM_ID
+  );
+}
+
+export function findCallbackAddress(eventId: BN): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [bufferFromString("callback"), eventId.toBuffer("le", 16)],
+    TEST_PROGRAM_ID
+  );
+}
+
+export function findCallbackAuthorityAddress(eventId: BN): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [bufferFromString("callback_authority"), eventId.toBuffer("le", 16)],
+    TEST_PROGRAM_ID
+  );
+}
+
 export function findParticipantAddress(
   eventId: BN,
   sender: PublicKey
@@ -64,3 +113,54 @@ export function findParticipantAddress(
     TEST_PROGRAM_ID
   );
 }
+
+export function findPortfolioAddress(
+  owner: PublicKey,
+  page: number
+): [PublicKey, number] {
+  const pageBuffer = Buffer.alloc(2);
+  pageBuffer.writeUInt16LE(page);
+
+  return PublicKey.findProgramAddressSync(
+    [bufferFromString("portfolio"), owner.toBytes(), pageBuffer],
+    TEST_PROGRAM_ID
+  );
+}
+
+export function findOrganizerAddress(
+  eventId: BN,
+  member: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [
+      bufferFromString("organizer"),
+      eventId.toBuffer("le", 16),
+      member.toBytes(),
+    ],
+    TEST_PROGRAM_ID
+  );
+}
+
+export function findRoleAddress(
+  role: number,
+  member: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [bufferFromString("role"), Buffer.from([role]), member.toBytes()],
+    TEST_PROGRAM_ID
+  );
+}
+
+export function findProposalAddress(proposalId: BN): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [bufferFromString("proposal"), proposalId.toBuffer("le", 8)],
+    TEST_PROGRAM_ID
+  );
+}
+
+export function findTreasuryAddress(): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [bufferFromString("treasury")],
+    TEST_PROGRAM_ID
+  );
+}